@@ -169,7 +169,18 @@ pub async fn run(args: &MainArgs) -> Result<()> {
                     &PrivateKeySigner::random(),
                     boundless_client.deployment.boundless_market_address,
                     &db_url,
-                    IndexerServiceConfig { interval: Duration::from_secs(2), retries: 5 },
+                    IndexerServiceConfig {
+                        interval: Duration::from_secs(2),
+                        retries: 5,
+                        vacuum_interval: Duration::ZERO,
+                        sqlite_full_vacuum: false,
+                        chain_id: None,
+                        skip_address_validation: false,
+                        instance_id: "bench".to_string(),
+                        lock_ttl: Duration::from_secs(300),
+                        lock_policy: Default::default(),
+                        disable_writer_lock: false,
+                    },
                 )
                 .await?;
 
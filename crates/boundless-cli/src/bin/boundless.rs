@@ -43,7 +43,6 @@ this version. Full signer support is available in the SDK."#;
 
 use std::{
     any::Any,
-    borrow::Cow,
     fs::File,
     io::BufReader,
     path::{Path, PathBuf},
@@ -66,7 +65,7 @@ use boundless_cli::{
     commands::zkc::ZKCCommands, config::ProverConfig, convert_timestamp, DefaultProver,
     OrderFulfilled,
 };
-use clap::{Args, CommandFactory, Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 use clap_complete::aot::Shell;
 use risc0_aggregation::SetInclusionReceiptVerifierParameters;
 use risc0_ethereum_contracts::{set_verifier::SetVerifierService, IRiscZeroVerifier};
@@ -79,17 +78,23 @@ use shadow_rs::shadow;
 use tracing_subscriber::{fmt, prelude::*, EnvFilter};
 use url::Url;
 
-use boundless_cli::{commands::povw::PovwCommands, config::GlobalConfig};
+use boundless_cli::{
+    commands::{
+        doctor::Doctor,
+        market::{MarketCommands, MarketSlash, SubmitOfferArgs},
+        povw::PovwCommands,
+    },
+    config::GlobalConfig,
+    watch::{parse_watch_interval, Watch, WatchField},
+};
 use boundless_market::{
     contracts::{
         boundless_market::{BoundlessMarketService, FulfillmentTx, UnlockedRequest},
-        FulfillmentData, Offer, Predicate, ProofRequest, RequestInputType, Selector,
+        FulfillmentData, Offer, Predicate, ProofRequest, RequestInputType,
     },
     input::GuestEnv,
-    request_builder::{OfferParams, RequirementParams},
-    selector::ProofType,
     storage::{fetch_url, StorageProvider, StorageProviderConfig},
-    Client, Deployment, StandardClient,
+    Client, Deployment,
 };
 
 shadow!(build);
@@ -112,6 +117,10 @@ enum Command {
     #[command(subcommand)]
     Ops(Box<OpsCommands>),
 
+    /// Requestor commands for the Boundless Market
+    #[command(subcommand)]
+    Market(Box<MarketCommands>),
+
     #[command(subcommand)]
     Povw(Box<PovwCommands>),
 
@@ -121,17 +130,17 @@ enum Command {
     /// Display configuration and environment variables
     Config {},
 
+    /// Check the configuration and environment, and suggest fixes for any problems found
+    Doctor(Doctor),
+
     /// Print shell completions (e.g. for bash or zsh) to stdout.
     Completions { shell: Shell },
 }
 
 #[derive(Subcommand, Clone, Debug)]
 enum OpsCommands {
-    /// Slash a prover for a given request
-    Slash {
-        /// The proof request identifier
-        request_id: U256,
-    },
+    /// Slash a prover for a given request, same as `market slash`
+    Slash(MarketSlash),
 }
 
 #[derive(Subcommand, Clone, Debug)]
@@ -153,6 +162,10 @@ enum AccountCommands {
         /// Address to check the balance of;
         /// if not provided, defaults to the wallet address
         address: Option<Address>,
+
+        /// Refresh the balance every given number of seconds, until interrupted
+        #[clap(long, value_name = "SECONDS", value_parser = parse_watch_interval)]
+        watch: Option<Duration>,
     },
     /// Deposit collateral funds into the market
     DepositCollateral {
@@ -169,6 +182,10 @@ enum AccountCommands {
         /// Address to check the balance of;
         /// if not provided, defaults to the wallet address
         address: Option<Address>,
+
+        /// Refresh the balance every given number of seconds, until interrupted
+        #[clap(long, value_name = "SECONDS", value_parser = parse_watch_interval)]
+        watch: Option<Duration>,
     },
 }
 
@@ -206,6 +223,10 @@ enum RequestCommands {
 
         /// The time at which the request expires, in seconds since the UNIX epoch
         expires_at: Option<u64>,
+
+        /// Refresh the status every given number of seconds, until interrupted
+        #[clap(long, value_name = "SECONDS", value_parser = parse_watch_interval)]
+        watch: Option<Duration>,
     },
 
     /// Get the journal and seal for a given request
@@ -308,81 +329,6 @@ enum ProvingCommands {
     },
 }
 
-#[derive(Args, Clone, Debug)]
-struct SubmitOfferArgs {
-    /// Optional identifier for the request
-    id: Option<u32>,
-
-    #[clap(flatten)]
-    program: SubmitOfferProgram,
-
-    /// Wait until the request is fulfilled
-    #[clap(short, long, default_value = "false")]
-    wait: bool,
-
-    /// Submit the request offchain via the provided order stream service url
-    #[clap(short, long)]
-    offchain: bool,
-
-    /// Use risc0_zkvm::serde to encode the input as a `Vec<u8>`
-    #[clap(long)]
-    encode_input: bool,
-
-    #[clap(flatten)]
-    input: SubmitOfferInput,
-
-    #[clap(flatten)]
-    requirements: SubmitOfferRequirements,
-
-    #[clap(flatten, next_help_heading = "Offer")]
-    offer_params: OfferParams,
-
-    /// Configuration for the StorageProvider to use for uploading programs and inputs.
-    #[clap(flatten, next_help_heading = "Storage Provider")]
-    storage_config: StorageProviderConfig,
-}
-
-#[derive(Args, Clone, Debug)]
-#[group(required = true, multiple = false)]
-struct SubmitOfferInput {
-    /// Input for the guest, given as a string.
-    #[clap(long)]
-    input: Option<String>,
-    /// Input for the guest, given as a path to a file.
-    #[clap(long)]
-    input_file: Option<PathBuf>,
-}
-
-#[derive(Args, Clone, Debug)]
-#[group(required = true, multiple = false)]
-struct SubmitOfferProgram {
-    /// Program binary to use as the guest image, given as a path.
-    ///
-    /// The program will be uploaded to a public URL using the configured storage provider before
-    /// the proof request is sent.
-    #[clap(short = 'p', long = "program")]
-    path: Option<PathBuf>,
-    /// Program binary to use as a guest image, given as a public URL.
-    ///
-    /// This option accepts a pre-uploaded program. If also using small inputs, a storage provider
-    /// is not required when using a pre-uploaded program.
-    #[clap(long = "program-url")]
-    url: Option<Url>,
-}
-
-#[derive(Args, Clone, Debug)]
-struct SubmitOfferRequirements {
-    /// Address of the callback to use in the requirements.
-    #[clap(long, requires = "callback_gas_limit")]
-    callback_address: Option<Address>,
-    /// Gas limit of the callback to use in the requirements.
-    #[clap(long, requires = "callback_address")]
-    callback_gas_limit: Option<u64>,
-    /// Request a groth16 proof (i.e., a Groth16).
-    #[clap(long, default_value = "any")]
-    proof_type: ProofType,
-}
-
 #[derive(Parser, Debug)]
 #[clap(author, long_version = build::CLAP_LONG_VERSION, about = "CLI for Boundless", long_about = CLI_LONG_ABOUT)]
 struct MainArgs {
@@ -431,9 +377,11 @@ pub(crate) async fn run(args: &MainArgs) -> Result<()> {
         Command::Request(request_cmd) => handle_request_command(request_cmd, &args.config).await,
         Command::Proving(proving_cmd) => handle_proving_command(proving_cmd, &args.config).await,
         Command::Ops(operation_cmd) => handle_ops_command(operation_cmd, &args.config).await,
+        Command::Market(market_cmd) => market_cmd.run(&args.config).await,
         Command::Povw(povw_cmd) => povw_cmd.run(&args.config).await,
         Command::Zkc(zkc_cmd) => zkc_cmd.run(&args.config).await,
         Command::Config {} => handle_config_command(&args.config).await,
+        Command::Doctor(doctor_cmd) => doctor_cmd.run(&args.config).await,
         Command::Completions { shell } => generate_shell_completions(shell),
     }
 }
@@ -445,14 +393,8 @@ fn generate_shell_completions(shell: &Shell) -> Result<()> {
 
 /// Handle ops-related commands
 async fn handle_ops_command(cmd: &OpsCommands, config: &GlobalConfig) -> Result<()> {
-    let client = config.build_client_with_signer().await?;
     match cmd {
-        OpsCommands::Slash { request_id } => {
-            tracing::info!("Slashing prover for request 0x{:x}", request_id);
-            client.boundless_market.slash(*request_id).await?;
-            tracing::info!("Successfully slashed prover for request 0x{:x}", request_id);
-            Ok(())
-        }
+        OpsCommands::Slash(slash) => slash.run(config).await,
     }
 }
 
@@ -472,6 +414,15 @@ async fn parse_collateral_amount(
     Ok((parsed_amount, formatted_amount, symbol))
 }
 
+/// Fetch the balance of the given address, for the watch mode of the balance command
+async fn balance_fields(
+    client: &Client<impl Provider, impl Any, impl Any, impl Any>,
+    addr: Address,
+) -> Result<Vec<WatchField>> {
+    let balance = client.boundless_market.balance_of(addr).await?;
+    Ok(vec![WatchField::new("Balance", format!("{} ETH", format_ether(balance)))])
+}
+
 /// Handle account-related commands
 async fn handle_account_command(cmd: &AccountCommands, config: &GlobalConfig) -> Result<()> {
     match cmd {
@@ -489,12 +440,17 @@ async fn handle_account_command(cmd: &AccountCommands, config: &GlobalConfig) ->
             tracing::info!("Successfully withdrew {} ETH from the market", format_ether(*amount));
             Ok(())
         }
-        AccountCommands::Balance { address } => {
+        AccountCommands::Balance { address, watch } => {
             let client = config.build_client().await?;
             let addr = address.unwrap_or(client.boundless_market.caller());
             if addr == Address::ZERO {
                 bail!("No address specified for balance query. Please provide an address or a private key.")
             }
+            if let Some(interval) = watch {
+                return Watch::new(format!("Balance of {addr}"), *interval)
+                    .run(|| balance_fields(&client, addr))
+                    .await;
+            }
             tracing::info!("Checking balance for address {}", addr);
             let balance = client.boundless_market.balance_of(addr).await?;
             tracing::info!("Balance for address {}: {} ETH", addr, format_ether(balance));
@@ -562,7 +518,7 @@ async fn handle_account_command(cmd: &AccountCommands, config: &GlobalConfig) ->
             tracing::info!("Successfully withdrew {formatted_amount} {symbol} from collateral");
             Ok(())
         }
-        AccountCommands::CollateralBalance { address } => {
+        AccountCommands::CollateralBalance { address, watch } => {
             let client = config.build_client().await?;
             let symbol = client.boundless_market.collateral_token_symbol().await?;
             let decimals = client.boundless_market.collateral_token_decimals().await?;
@@ -570,6 +526,20 @@ async fn handle_account_command(cmd: &AccountCommands, config: &GlobalConfig) ->
             if addr == Address::ZERO {
                 bail!("No address specified for collateral balance query. Please provide an address or a private key.")
             }
+            if let Some(interval) = watch {
+                let (client, symbol) = (&client, &symbol);
+                return Watch::new(format!("Collateral balance of {addr}"), *interval)
+                    .run(move || async move {
+                        let balance = client.boundless_market.balance_of_collateral(addr).await?;
+                        let balance = format_units(balance, decimals)
+                            .map_err(|e| anyhow!("Failed to format collateral balance: {}", e))?;
+                        Ok(vec![WatchField::new(
+                            "Collateral balance",
+                            format!("{balance} {symbol}"),
+                        )])
+                    })
+                    .await;
+            }
             tracing::info!("Checking collateral balance for address {}", addr);
             let balance = client.boundless_market.balance_of_collateral(addr).await?;
             let balance = format_units(balance, decimals)
@@ -583,16 +553,7 @@ async fn handle_account_command(cmd: &AccountCommands, config: &GlobalConfig) ->
 /// Handle request-related commands
 async fn handle_request_command(cmd: &RequestCommands, config: &GlobalConfig) -> Result<()> {
     match cmd {
-        RequestCommands::SubmitOffer(offer_args) => {
-            let client = config
-                .client_builder_with_signer()?
-                .with_storage_provider_config(&offer_args.storage_config)?
-                .build()
-                .await
-                .context("Failed to build Boundless Client")?;
-            tracing::info!("Submitting new proof request with offer");
-            submit_offer(client, offer_args).await
-        }
+        RequestCommands::SubmitOffer(offer_args) => offer_args.run(config).await,
         RequestCommands::Submit {
             yaml_request,
             wait,
@@ -615,8 +576,18 @@ async fn handle_request_command(cmd: &RequestCommands, config: &GlobalConfig) ->
             )
             .await
         }
-        RequestCommands::Status { request_id, expires_at } => {
+        RequestCommands::Status { request_id, expires_at, watch } => {
             let client = config.build_client().await?;
+            if let Some(interval) = watch {
+                let client = &client;
+                return Watch::new(format!("Request 0x{request_id:x}"), *interval)
+                    .run(move || async move {
+                        let status =
+                            client.boundless_market.get_status(*request_id, *expires_at).await?;
+                        Ok(vec![WatchField::new("Status", format!("{status:?}"))])
+                    })
+                    .await;
+            }
             tracing::info!("Checking status for request 0x{:x}", request_id);
             let status = client.boundless_market.get_status(*request_id, *expires_at).await?;
             tracing::info!("Request 0x{:x} status: {:?}", request_id, status);
@@ -1049,98 +1020,6 @@ async fn create_pg_pool() -> Result<sqlx::PgPool, sqlx::Error> {
     sqlx::PgPool::connect(&connection_string).await
 }
 
-/// Submit an offer and create a proof request
-async fn submit_offer(client: StandardClient, args: &SubmitOfferArgs) -> Result<()> {
-    let request = client.new_request();
-
-    // Resolve the program from command line arguments.
-    let request = match (args.program.path.clone(), args.program.url.clone()) {
-        (Some(path), None) => {
-            if client.storage_provider.is_none() {
-                bail!("A storage provider is required to upload programs.\nPlease provide a storage provider (see --help for options) or upload your program and set --program-url.")
-            }
-            let program: Cow<'static, [u8]> = std::fs::read(&path)
-                .context(format!("Failed to read program file at {:?}", args.program))?
-                .into();
-            request.with_program(program)
-        }
-        (None, Some(url)) => request.with_program_url(url).map_err(|e| match e {}).unwrap(),
-        _ => bail!("Exactly one of program path and program-url args must be provided"),
-    };
-
-    // Process input based on provided arguments
-    let stdin: Vec<u8> = match (&args.input.input, &args.input.input_file) {
-        (Some(input), None) => input.as_bytes().to_vec(),
-        (None, Some(input_file)) => std::fs::read(input_file)
-            .context(format!("Failed to read input file at {input_file:?}"))?,
-        _ => bail!("Exactly one of input or input-file args must be provided"),
-    };
-
-    // Prepare the input environment
-    let env = if args.encode_input {
-        GuestEnv::builder().write(&stdin)?
-    } else {
-        GuestEnv::builder().write_slice(&stdin)
-    };
-    let request = request.with_env(env);
-
-    // Configure callback if provided
-    let mut requirements = RequirementParams::builder();
-    if let Some(address) = args.requirements.callback_address {
-        requirements.callback_address(address);
-        if let Some(gas_limit) = args.requirements.callback_gas_limit {
-            requirements.callback_gas_limit(gas_limit);
-        }
-    }
-    match args.requirements.proof_type {
-        // TODO(risc0-ethereum/#597): This needs to be kept up to date with releases of
-        // risc0-ethereum. Add a Selector::inclusion_latest() function to risc0-ethereum and use it
-        // here.
-        ProofType::Inclusion => requirements.selector(Selector::set_inclusion_latest() as u32),
-        ProofType::Groth16 => requirements.selector(Selector::groth16_latest() as u32),
-        ProofType::Any => &mut requirements,
-        ty => bail!("unsupported proof type provided in proof-type flag: {:?}", ty),
-    };
-    let request = request.with_requirements(requirements);
-
-    let request = client.build_request(request).await.context("failed to build proof request")?;
-    tracing::debug!("Request details: {}", serde_yaml::to_string(&request)?);
-
-    // Submit the request
-    let (request_id, expires_at) = if args.offchain {
-        tracing::info!("Submitting request offchain");
-        client.submit_request_offchain(&request).await?
-    } else {
-        tracing::info!("Submitting request onchain");
-        client.submit_request_onchain(&request).await?
-    };
-
-    tracing::info!(
-        "Submitted request 0x{request_id:x}, bidding starts at {}",
-        convert_timestamp(request.offer.rampUpStart)
-    );
-
-    // Wait for fulfillment if requested
-    if args.wait {
-        tracing::info!("Waiting for request fulfillment...");
-        let fulfillment = client
-            .boundless_market
-            .wait_for_request_fulfillment(request_id, Duration::from_secs(5), expires_at)
-            .await?;
-        let fulfillment_data = fulfillment.data()?;
-        let seal = fulfillment.seal;
-
-        tracing::info!("Request fulfilled!");
-        tracing::info!(
-            "Fulfillment Data: {} - Seal: {}",
-            serde_json::to_string_pretty(&fulfillment_data)?,
-            serde_json::to_string_pretty(&seal)?
-        );
-    }
-
-    Ok(())
-}
-
 struct SubmitOptions {
     wait: bool,
     offchain: bool,
@@ -1303,6 +1182,30 @@ async fn handle_config_command(config: &GlobalConfig) -> Result<()> {
         println!("Transaction Timeout: <not set>");
     }
     println!("Log Level: {:?}", config.log_level);
+    if let Some(ref preset) = config.deployment_preset {
+        println!("Deployment Preset: {} (chain ID {})", preset.name, preset.chain_id);
+        match preset.market {
+            Some(ref market) => {
+                println!("  Boundless Market Address: {}", market.boundless_market_address)
+            }
+            None => println!("  Boundless Market Address: <not deployed>"),
+        }
+        match preset.povw {
+            Some(ref povw) => {
+                println!("  PoVW Accounting Address: {}", povw.povw_accounting_address);
+                println!("  PoVW Mint Address: {}", povw.povw_mint_address);
+            }
+            None => println!("  PoVW Addresses: <not deployed>"),
+        }
+        match preset.zkc {
+            Some(ref zkc) => {
+                println!("  ZKC Address: {}", zkc.zkc_address);
+                println!("  veZKC Address: {}", zkc.vezkc_address);
+                println!("  Staking Rewards Address: {}", zkc.staking_rewards_address);
+            }
+            None => println!("  ZKC Addresses: <not deployed>"),
+        }
+    }
     if let Some(ref deployment) = config.deployment {
         println!("Using custom Boundless deployment");
         println!("Chain ID: {:?}", deployment.chain_id);
@@ -1330,7 +1233,7 @@ async fn handle_config_command(config: &GlobalConfig) -> Result<()> {
     };
 
     let Some(deployment) =
-        config.deployment.clone().or_else(|| Deployment::from_chain_id(chain_id))
+        config.market_deployment().or_else(|| Deployment::from_chain_id(chain_id))
     else {
         println!("❌ No Boundless deployment config provided for unknown chain ID: {chain_id}");
         return Ok(());
@@ -1423,12 +1326,17 @@ mod tests {
         primitives::{aliases::U96, utils::format_units, Bytes},
         providers::WalletProvider,
     };
+    use boundless_cli::{
+        commands::market::{SubmitOfferInput, SubmitOfferProgram, SubmitOfferRequirements},
+        watch::CHANGE_MARKER,
+    };
     use boundless_market::{
         contracts::{
             hit_points::default_allowance, Predicate, RequestId, RequestInput, RequestStatus,
-            Requirements,
+            Requirements, Selector,
         },
-        selector::is_groth16_selector,
+        request_builder::OfferParams,
+        selector::{is_groth16_selector, ProofType},
     };
     use boundless_test_utils::{
         guests::{ECHO_ID, ECHO_PATH},
@@ -1443,6 +1351,120 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn deployment_preset_resolves_all_subsystems() {
+        use boundless_cli::config::DeploymentPreset;
+        use boundless_market::deployments::NamedChain;
+
+        let sepolia = NamedChain::Sepolia as u64;
+        let config = GlobalConfig {
+            rpc_url: None,
+            private_key: None,
+            deployment_preset: Some("sepolia".parse().unwrap()),
+            deployment: None,
+            tx_timeout: None,
+            log_level: LevelFilter::INFO,
+        };
+
+        let market = config.market_deployment().unwrap();
+        let povw = config.povw_deployment(None, sepolia).unwrap();
+        let zkc = config.zkc_deployment(None, sepolia).unwrap();
+        assert_eq!(market.chain_id, Some(sepolia));
+        assert_eq!(povw.chain_id, Some(sepolia));
+        assert_eq!(zkc.chain_id, Some(sepolia));
+        assert_eq!(
+            market.boundless_market_address,
+            Deployment::from_chain(NamedChain::Sepolia).unwrap().boundless_market_address
+        );
+
+        // A preset for a different chain than the RPC endpoint is rejected.
+        let err = config.zkc_deployment(None, NamedChain::Mainnet as u64).unwrap_err();
+        assert!(err.to_string().contains("deployment preset sepolia is for chain ID"));
+
+        // Explicitly provided addresses take precedence over the preset.
+        let explicit = boundless_zkc::deployments::Deployment::builder()
+            .zkc_address(Address::ZERO)
+            .vezkc_address(Address::ZERO)
+            .staking_rewards_address(Address::ZERO)
+            .build()
+            .unwrap();
+        let zkc = config.zkc_deployment(Some(&explicit), sepolia).unwrap();
+        assert_eq!(zkc.zkc_address, Address::ZERO);
+
+        assert!("unknown".parse::<DeploymentPreset>().is_err());
+        let mainnet = DeploymentPreset::from_name("mainnet").unwrap();
+        assert!(mainnet.market.is_none());
+        assert!(mainnet.povw.is_some() && mainnet.zkc.is_some());
+    }
+
+    #[test]
+    fn deployment_preset_local_and_file() {
+        use boundless_cli::config::DeploymentPreset;
+        use boundless_market::deployments::NamedChain;
+
+        let dir = tempfile::tempdir().unwrap();
+
+        // The local preset is read from the file written by `just localnet`.
+        let env_file = dir.path().join(".env.localnet");
+        std::fs::write(&env_file, "export BOUNDLESS_MARKET_ADDRESS=\n").unwrap();
+        let err = DeploymentPreset::local(&env_file).unwrap_err();
+        assert!(err.to_string().contains("BOUNDLESS_MARKET_ADDRESS is not set"));
+        std::fs::write(
+            &env_file,
+            format!(
+                "# Contracts\nexport ORDER_STREAM_URL=\"http://localhost:8585\"\n\
+                 export VERIFIER_ADDRESS={}\nexport SET_VERIFIER_ADDRESS={}\n\
+                 export BOUNDLESS_MARKET_ADDRESS={}\nexport HIT_POINTS_ADDRESS=\n",
+                Address::repeat_byte(1),
+                Address::repeat_byte(2),
+                Address::repeat_byte(3)
+            ),
+        )
+        .unwrap();
+        let local = DeploymentPreset::local(&env_file).unwrap();
+        assert_eq!(local.name, DeploymentPreset::LOCAL);
+        assert_eq!(local.chain_id, NamedChain::AnvilHardhat as u64);
+        let market = local.market.unwrap();
+        assert_eq!(market.verifier_router_address, Some(Address::repeat_byte(1)));
+        assert_eq!(market.set_verifier_address, Address::repeat_byte(2));
+        assert_eq!(market.boundless_market_address, Address::repeat_byte(3));
+        assert_eq!(market.collateral_token_address, None);
+        assert_eq!(market.order_stream_url.as_deref(), Some("http://localhost:8585"));
+        assert!(local.povw.is_none() && local.zkc.is_none());
+
+        // Other presets are loaded from a YAML file, given as the path to `--deployment`.
+        let preset_file = dir.path().join("staging.yaml");
+        std::fs::write(
+            &preset_file,
+            format!(
+                "chain_id: 11155111\nzkc:\n  zkc_address: \"{}\"\n  vezkc_address: \"{}\"\n  \
+                 staking_rewards_address: \"{}\"\n",
+                Address::repeat_byte(4),
+                Address::repeat_byte(5),
+                Address::repeat_byte(6)
+            ),
+        )
+        .unwrap();
+        let config = GlobalConfig {
+            rpc_url: None,
+            private_key: None,
+            deployment_preset: Some(preset_file.to_str().unwrap().parse().unwrap()),
+            deployment: None,
+            tx_timeout: None,
+            log_level: LevelFilter::INFO,
+        };
+        let preset = config.deployment_preset.as_ref().unwrap();
+        assert_eq!(preset.name, "staging");
+        assert!(preset.market.is_none() && preset.povw.is_none());
+        let zkc = config.zkc_deployment(None, NamedChain::Sepolia as u64).unwrap();
+        assert_eq!(zkc.chain_id, Some(NamedChain::Sepolia as u64));
+        assert_eq!(zkc.staking_rewards_address, Address::repeat_byte(6));
+
+        // Unknown fields in a preset file are rejected.
+        std::fs::write(&preset_file, "chain_id: 1\nmarkets: {}\n").unwrap();
+        assert!(DeploymentPreset::from_file(&preset_file).is_err());
+    }
+
     // generate a test request
     fn generate_request(id: u32, addr: &Address) -> ProofRequest {
         ProofRequest::new(
@@ -1490,6 +1512,7 @@ mod tests {
         let config = GlobalConfig {
             rpc_url: Some(anvil.endpoint_url()),
             private_key: Some(private_key),
+            deployment_preset: None,
             deployment: Some(ctx.deployment.clone()),
             tx_timeout: None,
             log_level: LevelFilter::INFO,
@@ -1565,6 +1588,7 @@ mod tests {
 
         args.command = Command::Account(Box::new(AccountCommands::Balance {
             address: Some(ctx.customer_signer.address()),
+            watch: None,
         }));
         run(&args).await.unwrap();
         assert!(logs_contain(&format!(
@@ -1594,6 +1618,39 @@ mod tests {
         assert_eq!(balance, U256::from(0));
     }
 
+    #[tokio::test]
+    async fn test_watch_balance() {
+        let (ctx, _anvil, config) = setup_test_env(AccountOwner::Customer).await;
+        let client = config.build_client().await.unwrap();
+        let addr = ctx.customer_signer.address();
+        let initial = ctx.customer_market.balance_of(addr).await.unwrap();
+        let amount = parse_ether("1").unwrap();
+
+        // Deposit between the two refreshes of the watch.
+        let mut out = Vec::new();
+        let watch = Watch::new("Balance", Duration::from_secs(2))
+            .with_refreshes(2)
+            .run_with_output(&mut out, false, || balance_fields(&client, addr));
+        let deposit = async {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+            ctx.customer_market.deposit(amount).await
+        };
+        let (watched, deposited) = tokio::join!(watch, deposit);
+        watched.unwrap();
+        deposited.unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        let refreshes = out.split("\n\n").collect::<Vec<_>>();
+        assert_eq!(refreshes.len(), 2, "{out}");
+        assert!(!refreshes[0].contains(CHANGE_MARKER), "{out}");
+        let change = format!(
+            "{} ETH {CHANGE_MARKER} (was {} ETH)",
+            format_ether(initial + amount),
+            format_ether(initial)
+        );
+        assert!(refreshes[1].contains(&change), "{out}");
+    }
+
     #[tokio::test]
     #[traced_test]
     async fn test_fail_deposit_withdraw() {
@@ -1642,6 +1699,7 @@ mod tests {
 
         args.command = Command::Account(Box::new(AccountCommands::CollateralBalance {
             address: Some(ctx.prover_signer.address()),
+            watch: None,
         }));
         run(&args).await.unwrap();
         assert!(logs_contain(&format!(
@@ -1792,7 +1850,11 @@ mod tests {
                         input: Some(hex::encode([0x41, 0x41, 0x41, 0x41])),
                         input_file: None,
                     },
-                    program: SubmitOfferProgram { path: Some(PathBuf::from(ECHO_PATH)), url: None },
+                    program: SubmitOfferProgram {
+                        path: Some(PathBuf::from(ECHO_PATH)),
+                        url: None,
+                        image_id: None,
+                    },
                     requirements: SubmitOfferRequirements {
                         callback_address: None,
                         callback_gas_limit: None,
@@ -1829,6 +1891,7 @@ mod tests {
             command: Command::Request(Box::new(RequestCommands::Status {
                 request_id: request.id,
                 expires_at: None,
+                watch: None,
             })),
         };
 
@@ -1876,6 +1939,7 @@ mod tests {
             command: Command::Request(Box::new(RequestCommands::Status {
                 request_id: request.id,
                 expires_at: None,
+                watch: None,
             })),
         };
         run(&status_args).await.unwrap();
@@ -1895,12 +1959,14 @@ mod tests {
         }
 
         // test the Slash command
-        run(&MainArgs {
-            config,
-            command: Command::Ops(Box::new(OpsCommands::Slash { request_id: request.id })),
-        })
-        .await
+        let slash_args = MainArgs::try_parse_from([
+            "boundless".to_string(),
+            "ops".to_string(),
+            "slash".to_string(),
+            format!("0x{:x}", request.id),
+        ])
         .unwrap();
+        run(&MainArgs { config, command: slash_args.command }).await.unwrap();
         assert!(logs_contain(&format!(
             "Successfully slashed prover for request 0x{:x}",
             request.id
@@ -1958,6 +2024,7 @@ mod tests {
         let prover_config = GlobalConfig {
             rpc_url: Some(anvil.endpoint_url()),
             private_key: Some(ctx.prover_signer.clone()),
+            deployment_preset: None,
             deployment: Some(ctx.deployment),
             tx_timeout: None,
             log_level: LevelFilter::INFO,
@@ -1982,6 +2049,7 @@ mod tests {
             command: Command::Request(Box::new(RequestCommands::Status {
                 request_id,
                 expires_at: None,
+                watch: None,
             })),
         })
         .await
@@ -2015,6 +2083,7 @@ mod tests {
             command: Command::Request(Box::new(RequestCommands::Status {
                 request_id,
                 expires_at: None,
+                watch: None,
             })),
         })
         .await
@@ -2098,6 +2167,7 @@ mod tests {
                 command: Command::Request(Box::new(RequestCommands::Status {
                     request_id,
                     expires_at: None,
+                    watch: None,
                 })),
             })
             .await
@@ -2293,6 +2363,7 @@ mod tests {
         let prover_config = GlobalConfig {
             rpc_url: Some(anvil.endpoint_url()),
             private_key: Some(ctx.prover_signer.clone()),
+            deployment_preset: None,
             deployment: Some(ctx.deployment),
             tx_timeout: None,
             log_level: LevelFilter::INFO,
@@ -0,0 +1,542 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Diagnostics of the configuration and environment of the Boundless CLI.
+//!
+//! Each diagnostic is a [Check], run against a [DoctorEnv] resolved once from the global
+//! configuration. New checks are added by implementing [Check] and listing them in
+//! [Doctor::checks].
+
+use std::{
+    fmt,
+    time::{Duration, Instant, SystemTime},
+};
+
+use alloy::{
+    consensus::BlockHeader,
+    eips::BlockNumberOrTag,
+    primitives::{
+        utils::{format_ether, parse_ether},
+        Address, U256,
+    },
+    providers::{DynProvider, Provider, ProviderBuilder},
+};
+use anyhow::{bail, Context};
+use async_trait::async_trait;
+use boundless_market::Deployment;
+use clap::Args;
+
+use crate::{
+    commands::zkc::{get_current_epoch, get_epoch_end_time},
+    config::GlobalConfig,
+};
+
+/// Command to check the configuration and environment of the CLI.
+#[non_exhaustive]
+#[derive(Args, Clone, Debug)]
+pub struct Doctor {
+    /// Signer balance, in ether, below which a warning is reported.
+    #[clap(long, value_parser = parse_ether, default_value = "0.01")]
+    pub min_balance: U256,
+    /// RPC round-trip latency, in milliseconds, above which a warning is reported.
+    #[clap(long, default_value_t = 2000)]
+    pub max_rpc_latency_ms: u64,
+    /// Difference, in seconds, between the local clock and the latest block above which a
+    /// warning is reported.
+    #[clap(long, default_value_t = 300)]
+    pub max_clock_skew_secs: u64,
+}
+
+impl Doctor {
+    /// Run the [Doctor] command.
+    ///
+    /// Prints the report of all checks, and returns an error if any of them failed.
+    pub async fn run(&self, global_config: &GlobalConfig) -> anyhow::Result<()> {
+        let env = DoctorEnv::connect(global_config.clone()).await;
+        let report = run_checks(&env, &self.checks()).await;
+        println!("{report}");
+
+        let failures = report.count(CheckStatus::Fail);
+        if failures > 0 {
+            bail!("{failures} of {} checks failed", report.results.len());
+        }
+        Ok(())
+    }
+
+    /// The checks run by this command, in the order they are reported.
+    pub fn checks(&self) -> Vec<Box<dyn Check>> {
+        vec![
+            Box::new(RpcCheck { max_latency: Duration::from_millis(self.max_rpc_latency_ms) }),
+            Box::new(ChainIdCheck),
+            Box::new(ContractCodeCheck),
+            Box::new(SignerBalanceCheck { min_balance: self.min_balance }),
+            Box::new(ClockSkewCheck { max_skew_secs: self.max_clock_skew_secs }),
+            Box::new(EpochClockCheck),
+        ]
+    }
+}
+
+/// A single diagnostic of the environment.
+#[async_trait]
+pub trait Check: Send + Sync {
+    /// Short name of the check, shown in the report.
+    fn name(&self) -> &'static str;
+
+    /// Run the check against the environment.
+    async fn run(&self, env: &DoctorEnv) -> CheckOutcome;
+}
+
+/// Connection to the RPC endpoint, established before running the checks.
+pub struct RpcConnection {
+    /// Provider connected to the RPC endpoint.
+    pub provider: DynProvider,
+    /// Chain ID reported by the RPC endpoint.
+    pub chain_id: u64,
+    /// Round-trip time of the chain ID request.
+    pub latency: Duration,
+}
+
+/// Environment the checks run against.
+pub struct DoctorEnv {
+    /// Global configuration of the CLI.
+    pub config: GlobalConfig,
+    /// Connection to the RPC endpoint, or the reason it could not be established.
+    pub rpc: Result<RpcConnection, String>,
+}
+
+impl DoctorEnv {
+    /// Resolve the environment from the global configuration, connecting to the RPC endpoint.
+    pub async fn connect(config: GlobalConfig) -> Self {
+        let rpc = match config.require_rpc_url() {
+            Ok(rpc_url) => {
+                let provider = ProviderBuilder::new().connect_http(rpc_url).erased();
+                let start = Instant::now();
+                match provider.get_chain_id().await {
+                    Ok(chain_id) => {
+                        Ok(RpcConnection { provider, chain_id, latency: start.elapsed() })
+                    }
+                    Err(err) => Err(format!("failed to get chain ID: {err}")),
+                }
+            }
+            Err(err) => Err(err.to_string()),
+        };
+        Self { config, rpc }
+    }
+
+    /// Access the RPC connection, or the outcome of a check that was skipped without it.
+    fn connection(&self) -> Result<&RpcConnection, CheckOutcome> {
+        self.rpc.as_ref().map_err(|_| CheckOutcome::skip("requires a reachable RPC endpoint"))
+    }
+}
+
+/// Status of a check, in increasing order of severity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CheckStatus {
+    /// The check passed.
+    Pass,
+    /// The check could not run, because it does not apply or a check it depends on failed.
+    Skip,
+    /// The check passed, but the environment may cause problems.
+    Warn,
+    /// The check failed.
+    Fail,
+}
+
+impl fmt::Display for CheckStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pass => write!(f, "PASS"),
+            Self::Skip => write!(f, "SKIP"),
+            Self::Warn => write!(f, "WARN"),
+            Self::Fail => write!(f, "FAIL"),
+        }
+    }
+}
+
+/// Outcome of running a check.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CheckOutcome {
+    /// Status of the check.
+    pub status: CheckStatus,
+    /// What the check found.
+    pub message: String,
+    /// How to fix the problem found by the check, for warnings and failures.
+    pub hint: Option<String>,
+}
+
+impl CheckOutcome {
+    /// Outcome of a check with the [CheckStatus::Pass] status.
+    pub fn pass(message: impl Into<String>) -> Self {
+        Self { status: CheckStatus::Pass, message: message.into(), hint: None }
+    }
+
+    /// Outcome of a check with the [CheckStatus::Skip] status.
+    pub fn skip(message: impl Into<String>) -> Self {
+        Self { status: CheckStatus::Skip, message: message.into(), hint: None }
+    }
+
+    /// Outcome of a check with the [CheckStatus::Warn] status.
+    pub fn warn(message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self { status: CheckStatus::Warn, message: message.into(), hint: Some(hint.into()) }
+    }
+
+    /// Outcome of a check with the [CheckStatus::Fail] status.
+    pub fn fail(message: impl Into<String>, hint: impl Into<String>) -> Self {
+        Self { status: CheckStatus::Fail, message: message.into(), hint: Some(hint.into()) }
+    }
+}
+
+/// Report of the outcome of each check, in the order they were run.
+#[derive(Clone, Debug, Default)]
+pub struct DoctorReport {
+    /// Name and outcome of each check.
+    pub results: Vec<(&'static str, CheckOutcome)>,
+}
+
+impl DoctorReport {
+    /// Number of checks with the given status.
+    pub fn count(&self, status: CheckStatus) -> usize {
+        self.results.iter().filter(|(_, outcome)| outcome.status == status).count()
+    }
+}
+
+impl fmt::Display for DoctorReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (name, outcome) in &self.results {
+            writeln!(f, "[{}] {name}: {}", outcome.status, outcome.message)?;
+            if let Some(hint) = &outcome.hint {
+                writeln!(f, "       hint: {hint}")?;
+            }
+        }
+        write!(
+            f,
+            "\n{} passed, {} warnings, {} failed, {} skipped",
+            self.count(CheckStatus::Pass),
+            self.count(CheckStatus::Warn),
+            self.count(CheckStatus::Fail),
+            self.count(CheckStatus::Skip)
+        )
+    }
+}
+
+/// Run the given checks in order against the environment.
+pub async fn run_checks(env: &DoctorEnv, checks: &[Box<dyn Check>]) -> DoctorReport {
+    let mut report = DoctorReport::default();
+    for check in checks {
+        let outcome = check.run(env).await;
+        tracing::debug!("Check {} finished with status {}", check.name(), outcome.status);
+        report.results.push((check.name(), outcome));
+    }
+    report
+}
+
+/// Checks that the RPC endpoint is reachable and responsive.
+struct RpcCheck {
+    max_latency: Duration,
+}
+
+#[async_trait]
+impl Check for RpcCheck {
+    fn name(&self) -> &'static str {
+        "rpc"
+    }
+
+    async fn run(&self, env: &DoctorEnv) -> CheckOutcome {
+        let conn = match &env.rpc {
+            Ok(conn) => conn,
+            Err(err) => {
+                return CheckOutcome::fail(
+                    format!("RPC endpoint is unreachable: {err}"),
+                    "check --rpc-url or the RPC_URL env var, and that the endpoint is up",
+                )
+            }
+        };
+        let message =
+            format!("connected to chain ID {} in {}ms", conn.chain_id, conn.latency.as_millis());
+        if conn.latency > self.max_latency {
+            return CheckOutcome::warn(
+                message,
+                "the RPC endpoint is slow; transactions may time out, consider another provider",
+            );
+        }
+        CheckOutcome::pass(message)
+    }
+}
+
+/// Checks that the chain of the RPC endpoint matches the configured deployment.
+struct ChainIdCheck;
+
+#[async_trait]
+impl Check for ChainIdCheck {
+    fn name(&self) -> &'static str {
+        "chain id"
+    }
+
+    async fn run(&self, env: &DoctorEnv) -> CheckOutcome {
+        let chain_id = match env.connection() {
+            Ok(conn) => conn.chain_id,
+            Err(outcome) => return outcome,
+        };
+        if let Some(preset) = &env.config.deployment_preset {
+            if preset.chain_id != chain_id {
+                return CheckOutcome::fail(
+                    format!(
+                        "deployment preset {} is for chain ID {}, but the RPC endpoint is on chain ID {chain_id}",
+                        preset.name, preset.chain_id
+                    ),
+                    "use an RPC endpoint for the chain of the preset, or the preset of the RPC endpoint's chain",
+                );
+            }
+        }
+        if let Some(expected) = env.config.deployment.as_ref().and_then(|d| d.chain_id) {
+            if expected != chain_id {
+                return CheckOutcome::fail(
+                    format!(
+                        "deployment is configured for chain ID {expected}, but the RPC endpoint is on chain ID {chain_id}"
+                    ),
+                    "check --chain-id or the CHAIN_ID env var",
+                );
+            }
+        }
+        if env.config.market_deployment().is_none() && Deployment::from_chain_id(chain_id).is_none()
+        {
+            return CheckOutcome::fail(
+                format!("no known Boundless deployment for chain ID {chain_id}"),
+                "set --deployment, or provide the deployment addresses explicitly",
+            );
+        }
+        CheckOutcome::pass(format!("chain ID {chain_id} matches the deployment"))
+    }
+}
+
+/// Checks that there is contract code at every configured contract address.
+struct ContractCodeCheck;
+
+impl ContractCodeCheck {
+    /// Contracts of the market, PoVW, and ZKC deployments resolved for the chain.
+    fn contracts(env: &DoctorEnv, chain_id: u64) -> Vec<(&'static str, Address)> {
+        let mut contracts = Vec::new();
+        if let Some(market) =
+            env.config.market_deployment().or_else(|| Deployment::from_chain_id(chain_id))
+        {
+            contracts.push(("BoundlessMarket", market.boundless_market_address));
+            contracts.push(("SetVerifier", market.set_verifier_address));
+            if let Some(address) = market.verifier_router_address {
+                contracts.push(("VerifierRouter", address));
+            }
+            if let Some(address) = market.collateral_token_address {
+                contracts.push(("CollateralToken", address));
+            }
+        }
+        if let Ok(povw) = env.config.povw_deployment(None, chain_id) {
+            contracts.push(("PovwAccounting", povw.povw_accounting_address));
+            contracts.push(("PovwMint", povw.povw_mint_address));
+        }
+        if let Ok(zkc) = env.config.zkc_deployment(None, chain_id) {
+            contracts.push(("ZKC", zkc.zkc_address));
+            contracts.push(("veZKC", zkc.vezkc_address));
+            contracts.push(("StakingRewards", zkc.staking_rewards_address));
+        }
+        contracts
+    }
+}
+
+#[async_trait]
+impl Check for ContractCodeCheck {
+    fn name(&self) -> &'static str {
+        "contract code"
+    }
+
+    async fn run(&self, env: &DoctorEnv) -> CheckOutcome {
+        let conn = match env.connection() {
+            Ok(conn) => conn,
+            Err(outcome) => return outcome,
+        };
+        let contracts = Self::contracts(env, conn.chain_id);
+        if contracts.is_empty() {
+            return CheckOutcome::skip("no contract addresses configured");
+        }
+
+        let mut missing = Vec::new();
+        for (name, address) in &contracts {
+            match conn.provider.get_code_at(*address).await {
+                Ok(code) if !code.is_empty() => {}
+                Ok(_) => missing.push(format!("{name} ({address})")),
+                Err(err) => {
+                    return CheckOutcome::fail(
+                        format!("failed to get code of {name} at {address}: {err}"),
+                        "check that the RPC endpoint supports eth_getCode",
+                    )
+                }
+            }
+        }
+        if !missing.is_empty() {
+            return CheckOutcome::fail(
+                format!("no contract code at {}", missing.join(", ")),
+                "check the configured contract addresses, and that they are for the chain of the RPC endpoint",
+            );
+        }
+        CheckOutcome::pass(format!("found code at all {} contract addresses", contracts.len()))
+    }
+}
+
+/// Checks that the signer has funds to pay for gas.
+struct SignerBalanceCheck {
+    min_balance: U256,
+}
+
+#[async_trait]
+impl Check for SignerBalanceCheck {
+    fn name(&self) -> &'static str {
+        "signer balance"
+    }
+
+    async fn run(&self, env: &DoctorEnv) -> CheckOutcome {
+        let Some(signer) = &env.config.private_key else {
+            return CheckOutcome::skip("no private key configured");
+        };
+        let conn = match env.connection() {
+            Ok(conn) => conn,
+            Err(outcome) => return outcome,
+        };
+        let address = signer.address();
+        let balance = match conn.provider.get_balance(address).await {
+            Ok(balance) => balance,
+            Err(err) => {
+                return CheckOutcome::fail(
+                    format!("failed to get the balance of {address}: {err}"),
+                    "check that the RPC endpoint is healthy",
+                )
+            }
+        };
+
+        let message = format!("signer {address} holds {} ETH", format_ether(balance));
+        let hint = format!(
+            "fund {address} with at least {} ETH to pay for transactions",
+            format_ether(self.min_balance)
+        );
+        if balance.is_zero() {
+            CheckOutcome::fail(message, hint)
+        } else if balance < self.min_balance {
+            CheckOutcome::warn(message, hint)
+        } else {
+            CheckOutcome::pass(message)
+        }
+    }
+}
+
+/// Checks that the local clock agrees with the timestamp of the latest block.
+struct ClockSkewCheck {
+    max_skew_secs: u64,
+}
+
+#[async_trait]
+impl Check for ClockSkewCheck {
+    fn name(&self) -> &'static str {
+        "clock skew"
+    }
+
+    async fn run(&self, env: &DoctorEnv) -> CheckOutcome {
+        let conn = match env.connection() {
+            Ok(conn) => conn,
+            Err(outcome) => return outcome,
+        };
+        let block_timestamp = match latest_block_timestamp(&conn.provider).await {
+            Ok(timestamp) => timestamp,
+            Err(err) => {
+                return CheckOutcome::fail(
+                    format!("{err:#}"),
+                    "check that the RPC endpoint is healthy",
+                )
+            }
+        };
+        let now = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .expect("Time went backwards")
+            .as_secs();
+
+        let skew = now.abs_diff(block_timestamp);
+        let message = format!("local clock is {skew}s from the latest block");
+        if skew > self.max_skew_secs {
+            return CheckOutcome::warn(
+                message,
+                "sync the local clock with NTP, or check that the RPC endpoint is not lagging behind the chain",
+            );
+        }
+        CheckOutcome::pass(message)
+    }
+}
+
+/// Checks that the current ZKC epoch is consistent with the time of the latest block.
+struct EpochClockCheck;
+
+#[async_trait]
+impl Check for EpochClockCheck {
+    fn name(&self) -> &'static str {
+        "epoch clock"
+    }
+
+    async fn run(&self, env: &DoctorEnv) -> CheckOutcome {
+        let conn = match env.connection() {
+            Ok(conn) => conn,
+            Err(outcome) => return outcome,
+        };
+        let Ok(zkc) = env.config.zkc_deployment(None, conn.chain_id) else {
+            return CheckOutcome::skip(format!("no ZKC deployment for chain ID {}", conn.chain_id));
+        };
+
+        let result = async {
+            let epoch =
+                u32::try_from(get_current_epoch(conn.provider.clone(), zkc.zkc_address).await?)?;
+            let end_time = u64::try_from(
+                get_epoch_end_time(conn.provider.clone(), zkc.zkc_address, epoch).await?,
+            )?;
+            let block_timestamp = latest_block_timestamp(&conn.provider).await?;
+            anyhow::Ok((epoch, end_time, block_timestamp))
+        }
+        .await;
+        let (epoch, end_time, block_timestamp) = match result {
+            Ok(result) => result,
+            Err(err) => {
+                return CheckOutcome::fail(
+                    format!("failed to get the current epoch: {err:#}"),
+                    "check the ZKC address of the deployment",
+                )
+            }
+        };
+
+        if end_time < block_timestamp {
+            return CheckOutcome::fail(
+                format!(
+                    "epoch {epoch} ended at {end_time}, before the latest block at {block_timestamp}"
+                ),
+                "check the ZKC address of the deployment, and that the RPC endpoint is in sync",
+            );
+        }
+        CheckOutcome::pass(format!(
+            "epoch {epoch} ends in {}s",
+            end_time.saturating_sub(block_timestamp)
+        ))
+    }
+}
+
+async fn latest_block_timestamp(provider: &impl Provider) -> anyhow::Result<u64> {
+    Ok(provider
+        .get_block_by_number(BlockNumberOrTag::Latest)
+        .await
+        .context("failed to get the latest block")?
+        .context("latest block not found")?
+        .header
+        .timestamp())
+}
@@ -0,0 +1,46 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Commands of the Boundless CLI for interacting with the Boundless Market.
+
+mod slash;
+mod submit_request;
+
+pub use slash::{slash_eligibility, MarketSlash, SlashEligibility, SlashEstimate};
+pub use submit_request::{
+    submit_offer, SubmitOfferArgs, SubmitOfferInput, SubmitOfferProgram, SubmitOfferRequirements,
+};
+
+use clap::Subcommand;
+
+use crate::config::GlobalConfig;
+
+/// Commands for interacting with the Boundless Market.
+#[derive(Subcommand, Clone, Debug)]
+pub enum MarketCommands {
+    /// Build, sign, and submit a proof request from a program and input.
+    SubmitRequest(Box<SubmitOfferArgs>),
+    /// Slash the prover of a locked request that expired, for one request or a batch.
+    Slash(MarketSlash),
+}
+
+impl MarketCommands {
+    /// Run the command.
+    pub async fn run(&self, global_config: &GlobalConfig) -> anyhow::Result<()> {
+        match self {
+            Self::SubmitRequest(cmd) => cmd.run(global_config).await,
+            Self::Slash(cmd) => cmd.run(global_config).await,
+        }
+    }
+}
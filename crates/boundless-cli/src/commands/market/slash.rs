@@ -0,0 +1,244 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{fmt, path::PathBuf, str::FromStr};
+
+use alloy::{
+    primitives::{utils::format_units, Address, U256},
+    providers::Provider,
+};
+use anyhow::{bail, Context};
+use boundless_market::contracts::{
+    boundless_market::BoundlessMarketService, IBoundlessMarket::IBoundlessMarketErrors, TxnErr,
+};
+use clap::Args;
+
+use crate::{config::GlobalConfig, convert_timestamp};
+
+/// Slash the prover of a locked request that expired without being fulfilled by the locker.
+///
+/// Half of the lock collateral is burned. The other half goes to the prover that fulfilled the
+/// request after the lock deadline, or to the market treasury if the request expired unfulfilled,
+/// in which case the requestor is refunded.
+#[non_exhaustive]
+#[derive(Args, Clone, Debug)]
+pub struct MarketSlash {
+    /// The proof request identifier.
+    #[clap(required_unless_present = "from_file", conflicts_with = "from_file")]
+    pub request_id: Option<U256>,
+
+    /// File listing the identifiers of the requests to slash, one per line.
+    ///
+    /// Blank lines and lines starting with `#` are ignored.
+    #[clap(long)]
+    pub from_file: Option<PathBuf>,
+
+    /// Only report whether each request can be slashed, without sending any transactions.
+    #[clap(long)]
+    pub dry_run: bool,
+}
+
+/// Whether a request can be slashed, as determined by simulating the slash transaction.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SlashEligibility {
+    /// The request can be slashed.
+    Eligible(SlashEstimate),
+    /// The request was never locked.
+    NotLocked,
+    /// The request has already been slashed.
+    AlreadySlashed,
+    /// The locking prover fulfilled the request before the lock deadline.
+    Fulfilled,
+    /// The request has not expired yet, and can only be slashed after the given deadline.
+    NotExpired {
+        /// UNIX timestamp of the request deadline.
+        deadline: u64,
+    },
+}
+
+impl fmt::Display for SlashEligibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Eligible(_) => write!(f, "eligible"),
+            Self::NotLocked => write!(f, "not eligible, the request is not locked"),
+            Self::AlreadySlashed => write!(f, "not eligible, the request is already slashed"),
+            Self::Fulfilled => {
+                write!(f, "not eligible, the request was fulfilled before the lock deadline")
+            }
+            Self::NotExpired { deadline } => {
+                write!(
+                    f,
+                    "not eligible, the request does not expire until {}",
+                    convert_timestamp(*deadline)
+                )
+            }
+        }
+    }
+}
+
+/// Expected outcome of slashing a request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlashEstimate {
+    /// Prover that locked the request and is slashed.
+    pub locker: Address,
+    /// Collateral burned.
+    pub burned: U256,
+    /// Collateral transferred to the recipient.
+    pub transferred: U256,
+    /// Whether the request was fulfilled after the lock deadline, in which case the fulfilling
+    /// prover receives the transferred collateral rather than the market treasury.
+    pub fulfilled_after_lock_deadline: bool,
+}
+
+impl MarketSlash {
+    /// Run the [MarketSlash] command.
+    pub async fn run(&self, global_config: &GlobalConfig) -> anyhow::Result<()> {
+        let request_ids = self.request_ids()?;
+
+        if self.dry_run {
+            let client = global_config.build_client().await?;
+            let market = &client.boundless_market;
+            let (decimals, symbol) = collateral_token(market).await?;
+            for request_id in request_ids {
+                match slash_eligibility(market, request_id).await {
+                    Ok(eligibility) => {
+                        println!("0x{request_id:x}: {eligibility}");
+                        if let SlashEligibility::Eligible(estimate) = eligibility {
+                            print_estimate(&estimate, decimals, &symbol)?;
+                        }
+                    }
+                    Err(err) => println!("0x{request_id:x}: failed to check eligibility: {err:#}"),
+                }
+            }
+            return Ok(());
+        }
+
+        let client = global_config.build_client_with_signer().await?;
+        let market = &client.boundless_market;
+        let (decimals, symbol) = collateral_token(market).await?;
+        let mut failed = 0;
+        for &request_id in &request_ids {
+            let eligibility = match slash_eligibility(market, request_id).await {
+                Ok(eligibility) => eligibility,
+                Err(err) => {
+                    println!("0x{request_id:x}: failed to check eligibility: {err:#}");
+                    failed += 1;
+                    continue;
+                }
+            };
+            if !matches!(eligibility, SlashEligibility::Eligible(_)) {
+                println!("0x{request_id:x}: {eligibility}");
+                failed += 1;
+                continue;
+            }
+
+            tracing::info!("Slashing prover for request 0x{:x}", request_id);
+            match market.slash(request_id).await {
+                Ok(slashed) => {
+                    tracing::info!("Successfully slashed prover for request 0x{:x}", request_id);
+                    println!(
+                        "0x{request_id:x}: slashed, burned {} {symbol} and transferred {} {symbol} to {}",
+                        format_units(slashed.collateralBurned, decimals)?,
+                        format_units(slashed.collateralTransferred, decimals)?,
+                        slashed.collateralRecipient
+                    );
+                }
+                Err(err) => {
+                    println!("0x{request_id:x}: failed to slash: {err}");
+                    failed += 1;
+                }
+            }
+        }
+
+        if failed > 0 {
+            bail!("{failed} of {} requests were not slashed", request_ids.len());
+        }
+        Ok(())
+    }
+
+    /// Request IDs given on the command line or listed in the file.
+    fn request_ids(&self) -> anyhow::Result<Vec<U256>> {
+        let Some(path) = &self.from_file else {
+            return Ok(self.request_id.into_iter().collect());
+        };
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read request IDs from {}", path.display()))?;
+        contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| U256::from_str(line).with_context(|| format!("Invalid request ID: {line}")))
+            .collect()
+    }
+}
+
+/// Check whether a request can be slashed by simulating the slash transaction.
+pub async fn slash_eligibility<P: Provider>(
+    market: &BoundlessMarketService<P>,
+    request_id: U256,
+) -> anyhow::Result<SlashEligibility> {
+    let simulation = market.instance().slash(request_id).from(market.caller()).call().await;
+    if let Err(err) = simulation {
+        return match TxnErr::from(err) {
+            TxnErr::BoundlessMarketErr(IBoundlessMarketErrors::RequestIsNotLocked(_)) => {
+                Ok(SlashEligibility::NotLocked)
+            }
+            TxnErr::BoundlessMarketErr(IBoundlessMarketErrors::RequestIsSlashed(_)) => {
+                Ok(SlashEligibility::AlreadySlashed)
+            }
+            TxnErr::BoundlessMarketErr(IBoundlessMarketErrors::RequestIsFulfilled(_)) => {
+                Ok(SlashEligibility::Fulfilled)
+            }
+            TxnErr::BoundlessMarketErr(IBoundlessMarketErrors::RequestIsNotExpired(err)) => {
+                Ok(SlashEligibility::NotExpired { deadline: err.deadline })
+            }
+            err => Err(err).context("Failed to simulate slash transaction"),
+        };
+    }
+
+    let (request, locker) = market
+        .get_locked_request(request_id)
+        .await
+        .context("Failed to find the lock of the request")?;
+    let transferred = request.offer.collateral_reward_if_locked_and_not_fulfilled();
+    let fulfilled_after_lock_deadline = market.is_fulfilled(request_id).await?;
+    Ok(SlashEligibility::Eligible(SlashEstimate {
+        locker,
+        burned: request.offer.lockCollateral - transferred,
+        transferred,
+        fulfilled_after_lock_deadline,
+    }))
+}
+
+async fn collateral_token<P: Provider>(
+    market: &BoundlessMarketService<P>,
+) -> anyhow::Result<(u8, String)> {
+    let decimals = market.collateral_token_decimals().await?;
+    let symbol = market.collateral_token_symbol().await?;
+    Ok((decimals, symbol))
+}
+
+fn print_estimate(estimate: &SlashEstimate, decimals: u8, symbol: &str) -> anyhow::Result<()> {
+    let recipient = match estimate.fulfilled_after_lock_deadline {
+        true => "the fulfilling prover",
+        false => "the market treasury, refunding the requestor",
+    };
+    println!(
+        "  prover {} would lose {} {symbol} burned and {} {symbol} transferred to {recipient}",
+        estimate.locker,
+        format_units(estimate.burned, decimals)?,
+        format_units(estimate.transferred, decimals)?,
+    );
+    Ok(())
+}
@@ -0,0 +1,236 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::{borrow::Cow, path::PathBuf, time::Duration};
+
+use alloy::primitives::{Address, B256};
+use anyhow::{bail, Context, Result};
+use boundless_market::{
+    contracts::Selector,
+    input::GuestEnv,
+    request_builder::{OfferParams, RequirementParams},
+    selector::ProofType,
+    storage::StorageProviderConfig,
+    StandardClient,
+};
+use clap::Args;
+use risc0_zkvm::sha::Digest;
+use url::Url;
+
+use crate::{config::GlobalConfig, convert_timestamp};
+
+/// Build, sign, and submit a proof request to the Boundless Market.
+///
+/// Used by both `market submit-request` and `request submit-offer`. The program can be given
+/// either as a local ELF, which is uploaded using the configured storage provider, or as a
+/// pre-uploaded program URL. When using a program URL, the image ID can be provided to skip
+/// computing it from the downloaded program.
+#[derive(Args, Clone, Debug)]
+pub struct SubmitOfferArgs {
+    /// Optional identifier for the request
+    pub id: Option<u32>,
+
+    /// Program to use as the guest image.
+    #[clap(flatten)]
+    pub program: SubmitOfferProgram,
+
+    /// Wait until the request is fulfilled
+    #[clap(short, long, default_value = "false")]
+    pub wait: bool,
+
+    /// Submit the request offchain via the provided order stream service url
+    #[clap(short, long)]
+    pub offchain: bool,
+
+    /// Use risc0_zkvm::serde to encode the input as a `Vec<u8>`
+    #[clap(long)]
+    pub encode_input: bool,
+
+    /// Input for the guest.
+    #[clap(flatten)]
+    pub input: SubmitOfferInput,
+
+    /// Requirements of the request.
+    #[clap(flatten)]
+    pub requirements: SubmitOfferRequirements,
+
+    /// Parameters of the offer.
+    #[clap(flatten, next_help_heading = "Offer")]
+    pub offer_params: OfferParams,
+
+    /// Configuration for the StorageProvider to use for uploading programs and inputs.
+    #[clap(flatten, next_help_heading = "Storage Provider")]
+    pub storage_config: StorageProviderConfig,
+}
+
+/// Input for the guest of a [SubmitOfferArgs] request.
+#[derive(Args, Clone, Debug)]
+#[group(required = true, multiple = false)]
+pub struct SubmitOfferInput {
+    /// Input for the guest, given as a string.
+    #[clap(long)]
+    pub input: Option<String>,
+    /// Input for the guest, given as a path to a file.
+    #[clap(long)]
+    pub input_file: Option<PathBuf>,
+}
+
+/// Program of a [SubmitOfferArgs] request.
+#[derive(Args, Clone, Debug)]
+#[group(required = true, multiple = false)]
+pub struct SubmitOfferProgram {
+    /// Program binary to use as the guest image, given as a path.
+    ///
+    /// The program will be uploaded to a public URL using the configured storage provider before
+    /// the proof request is sent.
+    #[clap(short = 'p', long = "program")]
+    pub path: Option<PathBuf>,
+    /// Program binary to use as a guest image, given as a public URL.
+    ///
+    /// This option accepts a pre-uploaded program. If also using small inputs, a storage provider
+    /// is not required when using a pre-uploaded program.
+    #[clap(long = "program-url")]
+    pub url: Option<Url>,
+    /// Image ID of the program at the given program URL.
+    ///
+    /// When not provided, the image ID is computed from the downloaded program.
+    #[clap(long, requires = "url")]
+    pub image_id: Option<B256>,
+}
+
+/// Requirements of a [SubmitOfferArgs] request.
+#[derive(Args, Clone, Debug)]
+pub struct SubmitOfferRequirements {
+    /// Address of the callback to use in the requirements.
+    #[clap(long, requires = "callback_gas_limit")]
+    pub callback_address: Option<Address>,
+    /// Gas limit of the callback to use in the requirements.
+    #[clap(long, requires = "callback_address")]
+    pub callback_gas_limit: Option<u64>,
+    /// Request a groth16 proof (i.e., a Groth16).
+    #[clap(long, default_value = "any")]
+    pub proof_type: ProofType,
+}
+
+impl SubmitOfferArgs {
+    /// Run the [SubmitOfferArgs] command.
+    pub async fn run(&self, global_config: &GlobalConfig) -> Result<()> {
+        let client = global_config
+            .client_builder_with_signer()?
+            .with_storage_provider_config(&self.storage_config)?
+            .build()
+            .await
+            .context("Failed to build Boundless Client")?;
+        tracing::info!("Submitting new proof request with offer");
+        submit_offer(client, self).await
+    }
+}
+
+/// Submit an offer and create a proof request
+pub async fn submit_offer(client: StandardClient, args: &SubmitOfferArgs) -> Result<()> {
+    let request = client.new_request();
+
+    // Resolve the program from command line arguments.
+    let request = match (args.program.path.clone(), args.program.url.clone()) {
+        (Some(path), None) => {
+            if client.storage_provider.is_none() {
+                bail!("A storage provider is required to upload programs.\nPlease provide a storage provider (see --help for options) or upload your program and set --program-url.")
+            }
+            let program: Cow<'static, [u8]> = std::fs::read(&path)
+                .context(format!("Failed to read program file at {:?}", args.program))?
+                .into();
+            request.with_program(program)
+        }
+        (None, Some(url)) => request.with_program_url(url).map_err(|e| match e {}).unwrap(),
+        _ => bail!("Exactly one of program path and program-url args must be provided"),
+    };
+    let request = match args.program.image_id {
+        Some(image_id) => request.with_image_id(Digest::from(<[u8; 32]>::from(image_id))),
+        None => request,
+    };
+
+    // Process input based on provided arguments
+    let stdin: Vec<u8> = match (&args.input.input, &args.input.input_file) {
+        (Some(input), None) => input.as_bytes().to_vec(),
+        (None, Some(input_file)) => std::fs::read(input_file)
+            .context(format!("Failed to read input file at {input_file:?}"))?,
+        _ => bail!("Exactly one of input or input-file args must be provided"),
+    };
+
+    // Prepare the input environment
+    let env = if args.encode_input {
+        GuestEnv::builder().write(&stdin)?
+    } else {
+        GuestEnv::builder().write_slice(&stdin)
+    };
+    let request = request.with_env(env);
+
+    // Configure callback if provided
+    let mut requirements = RequirementParams::builder();
+    if let Some(address) = args.requirements.callback_address {
+        requirements.callback_address(address);
+        if let Some(gas_limit) = args.requirements.callback_gas_limit {
+            requirements.callback_gas_limit(gas_limit);
+        }
+    }
+    match args.requirements.proof_type {
+        // TODO(risc0-ethereum/#597): This needs to be kept up to date with releases of
+        // risc0-ethereum. Add a Selector::inclusion_latest() function to risc0-ethereum and use it
+        // here.
+        ProofType::Inclusion => requirements.selector(Selector::set_inclusion_latest() as u32),
+        ProofType::Groth16 => requirements.selector(Selector::groth16_latest() as u32),
+        ProofType::Any => &mut requirements,
+        ty => bail!("unsupported proof type provided in proof-type flag: {:?}", ty),
+    };
+    let request = request.with_requirements(requirements).with_offer(args.offer_params.clone());
+
+    let request = client.build_request(request).await.context("failed to build proof request")?;
+    tracing::debug!("Request details: {}", serde_yaml::to_string(&request)?);
+
+    // Submit the request
+    let (request_id, expires_at) = if args.offchain {
+        tracing::info!("Submitting request offchain");
+        client.submit_request_offchain(&request).await?
+    } else {
+        tracing::info!("Submitting request onchain");
+        client.submit_request_onchain(&request).await?
+    };
+
+    tracing::info!(
+        "Submitted request 0x{request_id:x}, bidding starts at {}",
+        convert_timestamp(request.offer.rampUpStart)
+    );
+    println!("Submitted request 0x{request_id:x}");
+    println!("Check the status of the request with: boundless request status 0x{request_id:x}");
+
+    // Wait for fulfillment if requested
+    if args.wait {
+        tracing::info!("Waiting for request fulfillment...");
+        let fulfillment = client
+            .boundless_market
+            .wait_for_request_fulfillment(request_id, Duration::from_secs(5), expires_at)
+            .await?;
+        let fulfillment_data = fulfillment.data()?;
+        let seal = fulfillment.seal;
+
+        tracing::info!("Request fulfilled!");
+        tracing::info!(
+            "Fulfillment Data: {} - Seal: {}",
+            serde_json::to_string_pretty(&fulfillment_data)?,
+            serde_json::to_string_pretty(&seal)?
+        );
+    }
+
+    Ok(())
+}
@@ -17,5 +17,7 @@
 // TODO(victor): Move the main command groups (e.g. prove, request, account) to modules under this
 // one.
 
+pub mod doctor;
+pub mod market;
 pub mod povw;
 pub mod zkc;
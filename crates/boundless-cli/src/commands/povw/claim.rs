@@ -13,32 +13,33 @@
 // limitations under the License.
 
 use std::{
-    collections::{BTreeMap, BTreeSet, HashMap},
-    time::{Duration, SystemTime, UNIX_EPOCH},
+    io::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
 };
 
 use alloy::{
-    contract::Event,
-    primitives::{Address, U256},
+    primitives::{utils::format_ether, B256, U256},
     providers::{Provider, ProviderBuilder},
-    rpc::types::{Filter, Log},
-    sol_types::SolEvent,
 };
-use anyhow::{bail, ensure, Context};
+use anyhow::{bail, Context};
+use atomicwrites::{AtomicFile, OverwriteBehavior};
 use boundless_povw::{
+    claim::{
+        claim_rewards, report_claim, ClaimOutcome, ClaimParams, ClaimProgress, GasEstimate,
+        MintGasModel,
+    },
     deployments::Deployment,
-    log_updater::IPovwAccounting::{self, EpochFinalized, IPovwAccountingInstance, WorkLogUpdated},
-    mint_calculator::{prover::MintCalculatorProver, IPovwMint, CHAIN_SPECS},
 };
+use boundless_zkc::units::{format_zkc, ZKC_DECIMALS};
 use clap::Args;
 use risc0_povw::PovwLogId;
-use risc0_zkvm::{default_prover, Digest, ProverOpts};
+use risc0_zkvm::default_prover;
+use serde::{Deserialize, Serialize};
 use url::Url;
 
 use crate::config::{GlobalConfig, ProverConfig};
 
-const HOUR: Duration = Duration::from_secs(60 * 60);
-
 // TODO: Figure out what rewards the user is eligible for and warn them if they are receiving less
 // than their cycles could get them.
 
@@ -46,17 +47,37 @@ const HOUR: Duration = Duration::from_secs(60 * 60);
 #[non_exhaustive]
 #[derive(Args, Clone, Debug)]
 pub struct PovwClaim {
-    // TODO(povw): Support providing multiple log IDs.
     /// Work log ID for the reward claim.
     ///
     /// State for submitted updates is retrieved from the chain using the ID. Note that initiating
     /// the claim can be done for any log ID and does not require authorization.
-    /// Work log ID for the reward claim.
+    #[arg(short, long, required_unless_present = "work_logs_file")]
+    pub log_id: Option<PovwLogId>,
+
+    /// Path to a JSON file with a list of work log IDs to claim rewards for.
     ///
-    /// State for submitted updates is retrieved from the chain using the ID. Note that initiating
-    /// the claim can be done for any log ID and does not require authorization.
-    #[arg(short, long)]
-    pub log_id: PovwLogId,
+    /// The work logs are claimed in groups of up to --group-size, in the order of the list, with
+    /// one mint proof and transaction per group. Work logs without updates in finalized epochs are
+    /// skipped. Completed claims are recorded in the progress file, so that running the command
+    /// again after a failure resumes with the first work log that was not claimed. The progress
+    /// file is removed once a batch claim completes.
+    #[arg(long, conflicts_with = "log_id")]
+    pub work_logs_file: Option<PathBuf>,
+
+    /// Maximum number of work logs claimed with a single mint in a batch claim.
+    #[arg(
+        long,
+        default_value_t = 8,
+        requires = "work_logs_file",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    pub group_size: u32,
+
+    /// Path to the progress file for a batch claim with --work-logs-file.
+    ///
+    /// Defaults to the path of the work logs file with a `.progress.json` extension.
+    #[arg(long, requires = "work_logs_file")]
+    pub progress_file: Option<PathBuf>,
 
     // TODO: Deprecate and/or remove this when history support works without the Beacon API.
     /// URL for an Ethereum Beacon chain (i.e. consensus chain) API.
@@ -85,6 +106,14 @@ pub struct PovwClaim {
     #[clap(long, default_value_t = 10000)]
     pub event_query_chunk_size: u64,
 
+    /// Only report the planned mints, with their journal size and estimated gas, without proving
+    /// or sending them.
+    ///
+    /// In dev mode, the mint is executed and its gas estimated with `eth_estimateGas`, which
+    /// requires a deployment accepting dev-mode seals. Otherwise, a static gas model is used.
+    #[clap(long, conflicts_with = "progress_file")]
+    pub report_only: bool,
+
     #[clap(flatten, next_help_heading = "Prover")]
     prover_config: ProverConfig,
 }
@@ -96,6 +125,9 @@ impl PovwClaim {
             tracing::warn!("No Beacon API URL provided; claiming rewards may fail the multi-block continuity check.");
             tracing::warn!("You can provide it using the --beacon-api-url flag.");
         }
+        if self.report_only {
+            return self.run_report(global_config).await;
+        }
         let tx_signer = global_config.require_private_key()?;
         let rpc_url = global_config.require_rpc_url()?;
 
@@ -107,408 +139,350 @@ impl PovwClaim {
             .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
 
         let chain_id = provider.get_chain_id().await.context("Failed to query the chain ID")?;
-        let chain_spec = CHAIN_SPECS.get(&chain_id).with_context(|| {
-            format!("No known Steel chain specification for chain ID {chain_id}")
-        })?;
-        let deployment = self
-            .deployment
-            .clone()
-            .or_else(|| Deployment::from_chain_id(chain_id))
-            .context(
-            "could not determine deployment from chain ID; please specify deployment explicitly",
-        )?;
-
-        // Determine the limits on the blocks that will be searched for events.
-        let latest_block_number =
-            provider.get_block_number().await.context("Failed to query the block number")?;
-        let search_limit_time = SystemTime::now()
-            .checked_sub(self.days * 24 * HOUR)
-            .context("Invalid number of days")?;
-        let lower_limit_block_number = block_number_near_timestamp(
-            &provider,
-            latest_block_number,
-            search_limit_time,
-            Some(HOUR),
-        )
-        .await
-        .context("Failed to determine the block number for the event search limit")?;
-        tracing::debug!("Event search will use a lower limit of block {lower_limit_block_number}");
-
-        let povw_accounting =
-            IPovwAccounting::new(deployment.povw_accounting_address, provider.clone());
-        let povw_mint = IPovwMint::new(deployment.povw_mint_address, provider.clone());
-
-        // Determine the commit range for which we can mint. This is the difference between the
-        // recoreded work log commit on the accounting contract and on the mint contract.
-        let initial_commit = Digest::from(
-            *povw_mint.workLogCommit(self.log_id.into()).call().await.with_context(|| {
-                format!(
-                    "Failed to call IPovwMint.workLogCommit on {}",
-                    deployment.povw_mint_address
-                )
-            })?,
-        );
-        let final_commit = Digest::from(
-            *povw_accounting.workLogCommit(self.log_id.into()).call().await.with_context(|| {
-                format!(
-                    "Failed to call IPovwAccounting.workLogCommit on {}",
-                    deployment.povw_accounting_address
-                )
-            })?,
-        );
-        tracing::debug!(%initial_commit, %final_commit, "Commit range for mint");
+        let deployment = global_config.povw_deployment(self.deployment.as_ref(), chain_id)?;
+
+        self.prover_config.configure_proving_backend_with_health_check().await?;
 
-        if initial_commit == final_commit {
-            tracing::info!("All rewards for submitted work log updates have been claimed");
-            return Ok(());
+        if let Some(work_logs_file) = &self.work_logs_file {
+            return self.run_batch(provider, deployment, global_config, work_logs_file).await;
         }
+        let log_id = self.log_id.context("A work log ID is required")?;
+        let outcome =
+            self.claim(provider, deployment, global_config, vec![log_id], log_progress).await?;
 
-        // Search for the WorkLogUpdated events, and the the EpochFinalized events.
-        tracing::info!("Searching for work log update events in the past {} days", self.days);
-        let update_events = search_work_log_updated(
-            &povw_accounting,
-            self.log_id,
-            initial_commit,
-            final_commit,
-            latest_block_number,
-            lower_limit_block_number,
-            self.event_query_chunk_size,
-        )
-        .await
-        .context("Search for work log update events failed")?;
-        tracing::info!("Found {} work log update events", update_events.len());
-
-        // Check to see what the current pending epoch is on the PoVW accounting contract. Filter
-        // out update events with an epoch that has not finalized (with a warning).
-        let pending_epoch = povw_accounting
-            .pendingEpoch()
-            .call()
-            .await
-            .context("Failed to check the pending epoch")?
-            .number;
-        let finalized_update_events = update_events
-            .into_iter()
-            .filter(|(event, _)| {
-                if event.epochNumber >= pending_epoch {
-                    tracing::warn!(
-                        "Skipping update in epoch {}, which has not been finalized",
-                        event.epochNumber
+        match outcome {
+            ClaimOutcome::AlreadyClaimed => {
+                tracing::info!("All rewards for submitted work log updates have been claimed");
+            }
+            ClaimOutcome::Claimed { tx_hash, mints, epochs } => {
+                tracing::info!(%tx_hash, "Reward claim completed");
+                let epochs = epochs.iter().map(|epoch| epoch.to_string()).collect::<Vec<_>>();
+                tracing::info!("Claimed rewards for epochs: {}", epochs.join(", "));
+                for mint in mints {
+                    tracing::info!(
+                        "Minted rewards: {} ZKC to {}",
+                        format_zkc(mint.value, ZKC_DECIMALS),
+                        mint.recipient
                     );
-                    false
-                } else {
-                    true
                 }
-            })
-            .collect::<Vec<_>>();
-
-        // NOTE: At least one epoch must be skipped to reach this error.
-        if finalized_update_events.is_empty() {
-            bail!("No update events found for finalized epochs; no rewards to claim")
+            }
         }
+        Ok(())
+    }
 
-        // We can refine the range we search for EpochFinalized events using the first event.
-        let lower_limit_block_number = finalized_update_events
-            .first()
-            .map(|(_, block_numer)| *block_numer)
-            .unwrap_or(lower_limit_block_number);
-        let epochs = finalized_update_events
-            .iter()
-            .map(|(event, _)| event.epochNumber)
-            .collect::<BTreeSet<_>>();
-
-        ensure!(!epochs.is_empty(), "List of epochs for claim is empty");
-        let first_epoch = epochs.iter().next().unwrap();
-        let last_epoch = epochs.iter().last().unwrap();
-        if first_epoch == last_epoch {
-            tracing::info!("Searching for epoch finalization event for epoch {first_epoch}");
-        } else {
-            tracing::info!("Searching for epoch finalization events, from epoch {first_epoch} to epoch {last_epoch}");
-        }
-        let epoch_events = search_epoch_finalized(
-            &povw_accounting,
-            epochs,
-            latest_block_number,
-            lower_limit_block_number,
-            self.event_query_chunk_size,
-        )
-        .await
-        .context("Search for epoch finalized events failed")?;
-        tracing::info!("Found {} epoch finalization events", epoch_events.len());
-
-        let event_block_numbers = BTreeSet::from_iter(
-            finalized_update_events
-                .iter()
-                .map(|(_, block_number)| *block_number)
-                .chain(epoch_events.keys().copied()),
-        );
+    /// Report the planned mint for each work log or group of work logs, without proving or
+    /// sending it.
+    async fn run_report(&self, global_config: &GlobalConfig) -> anyhow::Result<()> {
+        let rpc_url = global_config.require_rpc_url()?;
 
-        self.prover_config.configure_proving_backend_with_health_check().await?;
-        let mint_calculator_prover = MintCalculatorProver::builder()
-            .prover(default_prover())
-            .provider(provider.clone())
-            .beacon_api(self.beacon_api_url.clone())
-            .povw_accounting_address(deployment.povw_accounting_address)
-            .zkc_address(deployment.zkc_address)
-            .zkc_rewards_address(deployment.vezkc_address)
-            .chain_spec(chain_spec)
-            .prover_opts(ProverOpts::groth16())
-            .build()?;
-
-        tracing::info!("Building input data for Mint Calculator guest");
-        let mint_input = mint_calculator_prover
-            .build_input(event_block_numbers, [self.log_id])
+        // Connect to the chain. No wallet is needed, since nothing is sent.
+        let provider = ProviderBuilder::new()
+            .connect(rpc_url.as_str())
             .await
-            .context("Failed to build input for Mint Calculator Guest")?;
+            .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
 
-        tracing::info!("Proving Mint Calculator guest");
-        let mint_prove_info = mint_calculator_prover
-            .prove_mint(&mint_input)
-            .await
-            .context("Failed to prove Mint Calculator guest")?;
+        let chain_id = provider.get_chain_id().await.context("Failed to query the chain ID")?;
+        let deployment = global_config.povw_deployment(self.deployment.as_ref(), chain_id)?;
+        let gas_price = provider.get_gas_price().await.context("Failed to query the gas price")?;
 
-        tracing::info!("Sending reward claim transaction");
-        let tx_result = povw_mint
-            .mint_with_receipt(&mint_prove_info.receipt)
-            .context("Failed to construct reward claim transaction")?
-            .send()
-            .await
-            .context("Failed to send reward claim transaction")?;
-        let tx_hash = tx_result.tx_hash();
-        tracing::info!(%tx_hash, "Sent transaction for reward claim");
-
-        let timeout = global_config.tx_timeout.or(tx_result.timeout());
-        tracing::debug!(?timeout, %tx_hash, "Waiting for transaction receipt");
-        let tx_receipt = tx_result
-            .with_timeout(timeout)
-            .get_receipt()
-            .await
-            .context("Failed to receive receipt reward claim transaction")?;
+        let groups = match &self.work_logs_file {
+            Some(work_logs_file) => self.group(&load_work_logs(work_logs_file)?),
+            None => vec![vec![self.log_id.context("A work log ID is required")?]],
+        };
 
-        ensure!(
-            tx_receipt.status(),
-            "Reward claim transaction failed: tx_hash = {}",
-            tx_receipt.transaction_hash
+        println!(
+            "{:<56}  {:>7}  {:>5}  {:>13}  {:>10}  {:>14}  estimated cost (ETH)",
+            "work logs", "updates", "mints", "journal bytes", "input (KB)", "gas"
+        );
+        for log_ids in groups {
+            let key = group_key(&log_keys(&log_ids));
+            let params = self.claim_params(deployment.clone(), global_config, log_ids);
+            let report = report_claim(
+                provider.clone(),
+                default_prover(),
+                &params,
+                &MintGasModel::DEFAULT,
+                log_progress,
+            )
+            .await
+            .with_context(|| format!("Failed to plan the claim for work log {key}"))?;
+            let Some(report) = report else {
+                println!("{key:<56}  all rewards claimed");
+                continue;
+            };
+            let gas = match report.gas {
+                GasEstimate::Simulated(gas) => format!("{gas}"),
+                GasEstimate::Modeled(gas) => format!("~{gas}"),
+            };
+            let cost = U256::from(report.gas.gas()) * U256::from(gas_price);
+            println!(
+                "{key:<56}  {:>7}  {:>5}  {:>13}  {:>10}  {gas:>14}  {}",
+                report.updates,
+                report.mints,
+                report.journal_size,
+                report.input_size / 1024,
+                format_ether(cost)
+            );
+        }
+        tracing::info!(
+            "Gas marked with ~ is estimated with a static model; costs use the current gas price"
         );
-
-        // TODO(povw): Display some info, like how much of a reward was created.
-        tracing::info!("Reward claim completed");
         Ok(())
     }
-}
-
-async fn block_number_near_timestamp(
-    provider: impl Provider,
-    latest_block_number: u64,
-    timestamp: SystemTime,
-    approx: Option<Duration>,
-) -> anyhow::Result<u64> {
-    tracing::debug!("Search for block with timestamp less than {timestamp:?}");
-
-    // Phase 1: Linear search backwards in chunks until we find a block <= target_timestamp
-    const LINEAR_SEARCH_CHUNK_SIZE: u64 = 100000;
-    let mut probe = latest_block_number;
-    loop {
-        let block = provider
-            .get_block_by_number(probe.into())
-            .await
-            .context("Failed to get block {probe}")?
-            .context("Block {probe} not found")?;
 
-        let block_timestamp = UNIX_EPOCH + Duration::from_secs(block.header.timestamp);
-        tracing::debug!("Linear search at block {probe}, timestamp {block_timestamp:?}");
-        if block_timestamp <= timestamp {
-            break;
+    fn claim_params(
+        &self,
+        deployment: Deployment,
+        global_config: &GlobalConfig,
+        log_ids: Vec<PovwLogId>,
+    ) -> ClaimParams {
+        ClaimParams {
+            beacon_api_url: self.beacon_api_url.clone(),
+            days: self.days,
+            event_query_chunk_size: self.event_query_chunk_size,
+            tx_timeout: global_config.tx_timeout,
+            ..ClaimParams::new_group(log_ids, deployment)
         }
+    }
 
-        probe = probe.saturating_sub(LINEAR_SEARCH_CHUNK_SIZE);
-        if probe == 0 {
-            // We've reached the block 0. This the closest possible block to the timestamp.
-            return Ok(0);
-        }
+    async fn claim(
+        &self,
+        provider: impl Provider + Clone + 'static,
+        deployment: Deployment,
+        global_config: &GlobalConfig,
+        log_ids: Vec<PovwLogId>,
+        progress: impl FnMut(ClaimProgress),
+    ) -> anyhow::Result<ClaimOutcome> {
+        let params = self.claim_params(deployment, global_config, log_ids);
+        claim_rewards(provider, default_prover(), &params, progress).await
     }
 
-    // Phase 2: binary search between [low, high]
-    // NOTE: If the latest block is less than the target timestamp, the binary search will not run.
-    let mut high = u64::min(probe + LINEAR_SEARCH_CHUNK_SIZE, latest_block_number);
-    let mut low = probe;
-    while low < high {
-        let mid = (low + high).div_ceil(2);
-        let block = provider
-            .get_block_by_number(mid.into())
-            .await
-            .context("Failed to get block {mid}")?
-            .context("Block {mid} not found")?;
+    /// Split the given work logs into groups of up to the group size, in order, claimed with one
+    /// mint each.
+    fn group(&self, log_ids: &[PovwLogId]) -> Vec<Vec<PovwLogId>> {
+        log_ids.chunks(self.group_size as usize).map(<[_]>::to_vec).collect()
+    }
 
-        let block_timestamp = UNIX_EPOCH + Duration::from_secs(block.header.timestamp);
-        tracing::debug!("Binary search at block {mid}, timestamp {block_timestamp:?}");
-        if block_timestamp <= timestamp {
-            low = mid; // candidate, move up
+    /// Claim the rewards for the work logs in the work logs file, in groups, skipping the work
+    /// logs recorded as claimed in the progress file. The progress file is removed once all
+    /// groups have been claimed, so that a later batch claim starts from scratch.
+    async fn run_batch(
+        &self,
+        provider: impl Provider + Clone + 'static,
+        deployment: Deployment,
+        global_config: &GlobalConfig,
+        work_logs_file: &Path,
+    ) -> anyhow::Result<()> {
+        let log_ids = load_work_logs(work_logs_file)?;
+        let progress_path = self
+            .progress_file
+            .clone()
+            .unwrap_or_else(|| work_logs_file.with_extension("progress.json"));
+        let mut progress = BatchClaimProgress::load(&progress_path)?;
 
-            // If an approximation factor is provided, see if we are close enough.
-            if let Some(approx) = approx {
-                if block_timestamp >= timestamp.checked_sub(approx).unwrap_or(UNIX_EPOCH) {
+        let remaining = log_ids
+            .iter()
+            .copied()
+            .filter(|log_id| {
+                let claimed = progress.is_claimed(*log_id);
+                if claimed {
+                    tracing::info!("Skipping work log {log_id:#x}, claimed in a previous run");
+                }
+                !claimed
+            })
+            .collect::<Vec<_>>();
+
+        let mut failure = None;
+        let mut skipped = Vec::new();
+        for group in self.group(&remaining) {
+            let key = group_key(&log_keys(&group));
+            tracing::info!("Claiming rewards for work log {key}");
+            let mut group_skipped = Vec::new();
+            let outcome = self
+                .claim(provider.clone(), deployment.clone(), global_config, group.clone(), |p| {
+                    if let ClaimProgress::SkippedUnfinalizedLog { log_id } = p {
+                        group_skipped.push(log_id);
+                    }
+                    log_progress(p)
+                })
+                .await;
+            // Work logs skipped for having no finalized updates are left to a later batch claim.
+            let log_ids = log_keys(
+                &group.into_iter().filter(|id| !group_skipped.contains(id)).collect::<Vec<_>>(),
+            );
+            skipped.extend(group_skipped);
+            let record = match outcome {
+                Ok(ClaimOutcome::AlreadyClaimed) => {
+                    tracing::info!("All rewards for work log {key} have been claimed");
+                    ClaimRecord { log_ids, tx_hash: None, minted: U256::ZERO }
+                }
+                Ok(ClaimOutcome::Claimed { tx_hash, mints, .. }) => {
+                    tracing::info!(%tx_hash, "Reward claim completed for work log {key}");
+                    let minted = mints.iter().map(|mint| mint.value).sum();
+                    ClaimRecord { log_ids, tx_hash: Some(tx_hash), minted }
+                }
+                Err(err) => {
+                    failure = Some(err.context(format!("Failed to claim work log {key}")));
                     break;
                 }
+            };
+            if !record.log_ids.is_empty() {
+                progress.claims.push(record);
+                progress.save(&progress_path)?;
             }
-        } else {
-            high = mid - 1;
         }
-    }
 
-    Ok(low)
-}
-
-/// Search for work log updated events required for the mint operation.
-///
-/// This function pregressively searches backwards in chunks, start at the upoer limit block, until
-/// it finds all the events needed or hits the lower limit block. It returns the sorted list of
-/// found [WorkLogUpdated] events along with the block number at which they were emitted.
-async fn search_work_log_updated(
-    povw_accounting: &IPovwAccountingInstance<impl Provider>,
-    log_id: PovwLogId,
-    initial_commit: Digest,
-    final_commit: Digest,
-    upper_limit_block_number: u64,
-    lower_limit_block_number: u64,
-    chunk_size: u64,
-) -> anyhow::Result<Vec<(WorkLogUpdated, u64)>> {
-    let mut events = HashMap::<Digest, (WorkLogUpdated, u64)>::new();
-    let search_predicate = |query_logs: &[(WorkLogUpdated, Log)]| {
-        for (event, log) in query_logs {
-            let commit = Digest::from(*event.initialCommit);
-            let block_number =
-                log.block_number.context("Log from range does not have block number")?;
-            events.insert(commit, (event.clone(), block_number));
-            tracing::debug!(block_number, ?event, "Found WorkLogUpdated event");
+        println!("{:<56}  {:>24}  transaction", "work logs", "minted (ZKC)");
+        for record in &progress.claims {
+            println!(
+                "{:<56}  {:>24}  {}",
+                group_key(&record.log_ids),
+                format_zkc(record.minted, ZKC_DECIMALS),
+                record.tx_hash.map(|hash| hash.to_string()).unwrap_or_else(|| "-".into())
+            );
         }
-        let halt = events.contains_key(&initial_commit);
-        if halt {
-            tracing::debug!(%initial_commit, "Reached initial commit");
+        for log_id in log_ids.iter().filter(|log_id| !progress.is_claimed(**log_id)) {
+            let status = if skipped.contains(log_id) { "not finalized" } else { "not claimed" };
+            println!("{:<56}  {:>24}  -", format!("{log_id:#x}"), status);
         }
-        Ok(!halt)
-    };
-
-    // Set up the event filter for the specified block range
-    let filter = Filter::new()
-        .address(*povw_accounting.address())
-        .event_signature(WorkLogUpdated::SIGNATURE_HASH)
-        .topic1(Address::from(log_id));
-
-    tracing::debug!(%initial_commit, %final_commit, %upper_limit_block_number, %lower_limit_block_number, "Searching for WorkLogUpdated events");
-    search_events(
-        povw_accounting.provider(),
-        filter,
-        lower_limit_block_number,
-        upper_limit_block_number,
-        chunk_size,
-        search_predicate,
-    )
-    .await
-    .context("Failed to search for WorkLogUpdated events")?;
-
-    // Reconstruct the chain of WorkLogUpdated from initial_commit to final_commit.
-    let mut commit = initial_commit;
-    let mut sorted_events = Vec::new();
-    while commit != final_commit {
-        match events.remove(&commit) {
-            Some((event, block_number)) => {
-                sorted_events.push((event.clone(), block_number));
-                commit = Digest::from(*event.updatedCommit);
-            }
-            None => bail!("Missing WorkLogUpdated event in chain with initial commit {commit}; did not reach final commit {final_commit}")
+
+        if let Some(err) = failure {
+            tracing::error!(
+                "Batch claim stopped; run the command again to resume from the progress file {}",
+                progress_path.display()
+            );
+            return Err(err);
+        }
+
+        // The claim is complete. Later updates to the same work logs must be claimed again, so
+        // the progress must not carry over to the next batch claim.
+        if progress_path.exists() {
+            std::fs::remove_file(&progress_path).with_context(|| {
+                format!("Failed to remove progress file {}", progress_path.display())
+            })?;
+            tracing::info!(
+                "Batch claim completed; removed progress file {}",
+                progress_path.display()
+            );
         }
+        Ok(())
     }
+}
 
-    Ok(sorted_events)
+/// Progress of a batch claim, stored as a JSON file between executions of the claim command.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+struct BatchClaimProgress {
+    /// Claims completed, in the order they were made.
+    claims: Vec<ClaimRecord>,
 }
 
-async fn search_epoch_finalized(
-    povw_accounting: &IPovwAccountingInstance<impl Provider>,
-    mut epochs: BTreeSet<U256>,
-    upper_limit_block_number: u64,
-    lower_limit_block_number: u64,
-    chunk_size: u64,
-) -> anyhow::Result<BTreeMap<u64, EpochFinalized>> {
-    tracing::debug!(?epochs, "Searching for EpochFinalized events");
-
-    let mut events = BTreeMap::<u64, EpochFinalized>::new();
-    let search_predicate = |query_logs: &[(EpochFinalized, Log)]| {
-        for (event, log) in query_logs {
-            // Remove the epoch from the set we are searching for.
-            if epochs.remove(&event.epoch) {
-                let block_number =
-                    log.block_number.context("Log from range does not have block number")?;
-                events.insert(block_number, event.clone());
-                tracing::debug!(block_number, ?event, "Found EpochFinalized event");
-            }
+/// Record of a completed claim for a group of work logs.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ClaimRecord {
+    /// IDs of the work logs claimed together, with a single mint.
+    log_ids: Vec<String>,
+    /// Hash of the reward claim transaction, if any rewards were left to claim.
+    tx_hash: Option<B256>,
+    /// Total value minted by the claim.
+    minted: U256,
+}
+
+impl BatchClaimProgress {
+    /// Whether the given work log was claimed by one of the recorded claims.
+    fn is_claimed(&self, log_id: PovwLogId) -> bool {
+        let key = format!("{log_id:#x}");
+        self.claims.iter().any(|record| record.log_ids.contains(&key))
+    }
+
+    /// Load the progress from the given path, starting from scratch if the file does not exist.
+    fn load(path: &Path) -> anyhow::Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
         }
-        let halt = epochs.is_empty();
-        if halt {
-            tracing::debug!("Found all epoch finalized events");
+        let data = std::fs::read(path)
+            .with_context(|| format!("Failed to read progress file {}", path.display()))?;
+        serde_json::from_slice(&data)
+            .with_context(|| format!("Failed to decode progress file {}", path.display()))
+    }
+
+    /// Save the progress to the given path. Uses AtomicFile to avoid corruption on a crash.
+    fn save(&self, path: &Path) -> anyhow::Result<()> {
+        let data = serde_json::to_vec_pretty(self).context("Failed to serialize progress")?;
+        AtomicFile::new(path, OverwriteBehavior::AllowOverwrite)
+            .write(|f| f.write_all(&data))
+            .with_context(|| format!("Failed to write progress file {}", path.display()))
+    }
+}
+
+/// Load the list of work log IDs from a JSON file, such as `["0x1234...", "0xabcd..."]`.
+fn load_work_logs(path: &Path) -> anyhow::Result<Vec<PovwLogId>> {
+    let data = std::fs::read(path)
+        .with_context(|| format!("Failed to read work logs file {}", path.display()))?;
+    let entries: Vec<String> = serde_json::from_slice(&data).with_context(|| {
+        format!("Failed to decode work logs file {}; expected a list of IDs", path.display())
+    })?;
+    let mut log_ids = Vec::with_capacity(entries.len());
+    for entry in entries {
+        let log_id = PovwLogId::from_str(&entry)
+            .map_err(|err| anyhow::anyhow!("Invalid work log ID {entry}: {err}"))?;
+        if log_ids.contains(&log_id) {
+            bail!("Work log ID {entry} is listed more than once");
         }
-        Ok(!halt)
-    };
-
-    // Set up the event filter for the specified block range
-    let filter = Filter::new()
-        .address(*povw_accounting.address())
-        .event_signature(EpochFinalized::SIGNATURE_HASH);
-
-    search_events(
-        povw_accounting.provider(),
-        filter,
-        lower_limit_block_number,
-        upper_limit_block_number,
-        chunk_size,
-        search_predicate,
-    )
-    .await
-    .context("Failed to search for EpochFinalized events")?;
-
-    Ok(events)
+        log_ids.push(log_id);
+    }
+    Ok(log_ids)
+}
+
+/// Describe a group of work logs in logs and tables, by its first work log ID and size.
+fn group_key(log_ids: &[String]) -> String {
+    match log_ids {
+        [log_id] => log_id.clone(),
+        [first, rest @ ..] => format!("{first} and {} more", rest.len()),
+        [] => "none".into(),
+    }
 }
 
-async fn search_events<P: Provider + Clone, E: SolEvent>(
-    provider: P,
-    filter: Filter,
-    lower_limit_block_number: u64,
-    upper_limit_block_number: u64,
-    chunk_size: u64,
-    mut f: impl FnMut(&[(E, Log)]) -> anyhow::Result<bool>,
-) -> anyhow::Result<()> {
-    let mut upper_block = upper_limit_block_number;
-    loop {
-        // The scan has reach block 0. This can only really happen in tests.
-        if upper_block == 0 {
-            tracing::warn!("Scan for events reached block 0");
-            break;
+/// Format the given work log IDs as they are recorded in the progress file.
+fn log_keys(log_ids: &[PovwLogId]) -> Vec<String> {
+    log_ids.iter().map(|log_id| format!("{log_id:#x}")).collect()
+}
+
+fn log_progress(progress: ClaimProgress) {
+    match progress {
+        ClaimProgress::SearchingUpdates { days } => {
+            tracing::info!("Searching for work log update events in the past {days} days");
         }
-        if upper_block < lower_limit_block_number {
-            bail!("Search reached lower limit block number {lower_limit_block_number}");
+        ClaimProgress::FoundUpdates { count } => {
+            tracing::info!("Found {count} work log update events");
         }
-
-        // Calculate the block range to query: from lower_block to upper_block. Range is
-        // inclusive of both lower and upper block.
-        let lower_block =
-            u64::max(upper_block.saturating_sub(chunk_size) + 1, lower_limit_block_number);
-
-        // Set up the event filter for the specified block range
-        tracing::debug!(range = ?(lower_block, upper_block), "Querying for events");
-        let query_logs = Event::new(
-            provider.clone(),
-            filter.clone().from_block(lower_block).to_block(upper_block),
-        )
-        .query()
-        .await
-        .with_context(|| format!("Query for events in the range {lower_block} to {upper_block}"))?;
-
-        // Check the predicate to see if the search should continue.
-        if !f(&query_logs)? {
-            break;
+        ClaimProgress::SkippedUnfinalizedEpoch { epoch } => {
+            tracing::warn!("Skipping update in epoch {epoch}, which has not been finalized");
         }
-
-        // Move the window down and continue.
-        upper_block = lower_block.saturating_sub(1);
+        ClaimProgress::SkippedUnfinalizedLog { log_id } => {
+            tracing::warn!(
+                "Skipping work log {log_id:#x}, which has no updates in finalized epochs"
+            );
+        }
+        ClaimProgress::SearchingEpochFinalizations { first, last } => {
+            if first == last {
+                tracing::info!("Searching for epoch finalization event for epoch {first}");
+            } else {
+                tracing::info!(
+                    "Searching for epoch finalization events, from epoch {first} to epoch {last}"
+                );
+            }
+        }
+        ClaimProgress::FoundEpochFinalizations { count } => {
+            tracing::info!("Found {count} epoch finalization events");
+        }
+        ClaimProgress::BuildingInput => {
+            tracing::info!("Building input data for Mint Calculator guest");
+        }
+        ClaimProgress::Proving => tracing::info!("Proving Mint Calculator guest"),
+        ClaimProgress::SendingTransaction => tracing::info!("Sending reward claim transaction"),
+        ClaimProgress::TransactionSent { tx_hash } => {
+            tracing::info!(%tx_hash, "Sent transaction for reward claim");
+        }
+        _ => {}
     }
-    Ok(())
 }
@@ -0,0 +1,298 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::{Path, PathBuf};
+
+use alloy::{
+    primitives::{Address, Bytes, Signature, B256, U256},
+    providers::{Provider, ProviderBuilder},
+    rpc::types::TransactionRequest,
+    signers::{local::PrivateKeySigner, SignerSync},
+    sol_types::SolValue,
+};
+use anyhow::{bail, ensure, Context};
+use boundless_povw::{deployments::Deployment, log_updater::IPovwAccounting};
+use clap::Args;
+use risc0_povw::PovwLogId;
+use serde::{Deserialize, Serialize};
+
+use super::{PovwSubmit, State};
+use crate::config::{GlobalConfig, ProverConfig};
+
+alloy::sol! {
+    /// Linkage from a retired work log to the work log that continues it.
+    #[derive(Debug, PartialEq, Eq, Serialize, Deserialize)]
+    struct WorkLogMigration {
+        /// Work log ID of the retired work log.
+        address oldLogId;
+        /// Work log ID of the work log that continues it.
+        address newLogId;
+        /// Chain ID of the PoVW accounting contract.
+        uint256 chainId;
+        /// Address of the PoVW accounting contract.
+        address povwAccounting;
+        /// Final commit of the retired work log, as posted onchain.
+        bytes32 finalCommit;
+    }
+}
+
+/// A [WorkLogMigration] signed by the key of the retired work log.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
+pub struct MigrationAttestation {
+    /// The migration being attested to.
+    pub migration: WorkLogMigration,
+    /// EIP-191 signature over the ABI encoding of the migration, by the old work log key.
+    pub signature: Signature,
+}
+
+impl MigrationAttestation {
+    /// Sign the given migration with the key of the retired work log.
+    pub fn sign(migration: WorkLogMigration, signer: &PrivateKeySigner) -> anyhow::Result<Self> {
+        ensure!(
+            signer.address() == migration.oldLogId,
+            "Signer does not match the old log ID: signer: {}, old log ID: {}",
+            signer.address(),
+            migration.oldLogId
+        );
+        let signature = signer
+            .sign_message_sync(&migration.abi_encode())
+            .context("Failed to sign work log migration")?;
+        Ok(Self { migration, signature })
+    }
+
+    /// Verify that the attestation is signed by the key of the retired work log.
+    pub fn verify(&self) -> anyhow::Result<()> {
+        let signer = self
+            .signature
+            .recover_address_from_msg(self.migration.abi_encode())
+            .context("Failed to recover signer from migration attestation")?;
+        ensure!(
+            signer == self.migration.oldLogId,
+            "Migration attestation is not signed by the old work log key: signer: {}, old log ID: {}",
+            signer,
+            self.migration.oldLogId
+        );
+        Ok(())
+    }
+
+    /// Encode the attestation as calldata, for publishing onchain.
+    ///
+    /// The encoding is the ABI encoding of the migration followed by the 65-byte signature.
+    pub fn to_calldata(&self) -> Bytes {
+        let mut calldata = self.migration.abi_encode();
+        calldata.extend_from_slice(&self.signature.as_bytes());
+        calldata.into()
+    }
+
+    /// Load a migration attestation from the given path.
+    pub async fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let data = tokio::fs::read(path)
+            .await
+            .with_context(|| format!("Failed to read migration attestation: {}", path.display()))?;
+        serde_json::from_slice(&data).with_context(|| {
+            format!("Failed to decode migration attestation from file: {}", path.display())
+        })
+    }
+
+    /// Save the migration attestation to the given path.
+    pub async fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        tokio::fs::write(path, serde_json::to_vec_pretty(self)?)
+            .await
+            .with_context(|| format!("Failed to write migration attestation to {}", path.display()))
+    }
+}
+
+/// Migrate to a new work log, e.g. when rotating the work log signing key.
+///
+/// This command posts any prepared updates for the current work log so that its final state is
+/// onchain, initializes the state for the new work log, and writes an attestation, signed by the
+/// key of the old work log, that links the old work log to the new one.
+#[non_exhaustive]
+#[derive(Args, Clone, Debug)]
+pub struct PovwMigrateLog {
+    /// State of the work log to migrate from.
+    #[arg(short, long, env = "POVW_STATE_PATH")]
+    pub state: PathBuf,
+
+    /// Work log identifier of the new work log.
+    #[arg(long)]
+    pub new_log_id: PovwLogId,
+
+    /// Path to create the state file for the new work log at.
+    #[arg(long)]
+    pub new_state: PathBuf,
+
+    /// Path to write the migration attestation to.
+    ///
+    /// Defaults to the new state path, with the extension `migration.json`.
+    #[arg(long)]
+    pub attestation: Option<PathBuf>,
+
+    /// Publish the migration attestation onchain, as calldata of a transaction to the old work
+    /// log ID address.
+    #[arg(long)]
+    pub publish: bool,
+
+    /// Private key used to sign work log updates for the old work log. Should have an address
+    /// equal to the old work log ID.
+    ///
+    /// If this option is not set, the value of the private key from global config will be used.
+    #[clap(long, env = "POVW_PRIVATE_KEY", hide_env_values = true)]
+    pub povw_private_key: Option<PrivateKeySigner>,
+
+    /// The address to assign any PoVW rewards from the final update to. If not provided, defaults
+    /// to the old work log ID.
+    #[clap(short, long, env = "POVW_VALUE_RECIPIENT")]
+    pub value_recipient: Option<Address>,
+
+    /// Deployment configuration for the PoVW and ZKC contracts.
+    #[clap(flatten, next_help_heading = "Deployment")]
+    pub deployment: Option<Deployment>,
+
+    #[clap(flatten, next_help_heading = "Prover")]
+    prover_config: ProverConfig,
+}
+
+impl PovwMigrateLog {
+    /// Run the [PovwMigrateLog] command.
+    pub async fn run(&self, global_config: &GlobalConfig) -> anyhow::Result<()> {
+        let tx_signer = global_config.require_private_key()?;
+        let work_log_signer = self.povw_private_key.as_ref().unwrap_or(&tx_signer);
+        let rpc_url = global_config.require_rpc_url()?;
+
+        let attestation_path = self
+            .attestation
+            .clone()
+            .unwrap_or_else(|| self.new_state.with_extension("migration.json"));
+        if self.new_state.exists() {
+            bail!("File already exists at the new state path; refusing to overwrite");
+        }
+        if attestation_path.exists() {
+            bail!("File already exists at the attestation path; refusing to overwrite");
+        }
+
+        let old_state = State::load(&self.state)
+            .await
+            .with_context(|| format!("Failed to load state from {}", self.state.display()))?;
+        ensure!(
+            Address::from(old_state.log_id) != Address::from(self.new_log_id),
+            "New log ID is the same as the current log ID: {:x}",
+            self.new_log_id
+        );
+        ensure!(
+            Address::from(old_state.log_id) == work_log_signer.address(),
+            "Signer does not match the state log ID: signer: {}, state: {}",
+            work_log_signer.address(),
+            old_state.log_id
+        );
+
+        let provider = ProviderBuilder::new()
+            .wallet(tx_signer.clone())
+            .connect(rpc_url.as_str())
+            .await
+            .with_context(|| format!("Failed to connect provider to {rpc_url}"))?;
+        let chain_id = provider
+            .get_chain_id()
+            .await
+            .with_context(|| format!("Failed to get chain ID from {rpc_url}"))?;
+        let deployment = global_config.povw_deployment(self.deployment.as_ref(), chain_id)?;
+
+        // Post the final update(s) for the old work log.
+        if old_state.log_builder_receipts.is_empty() {
+            tracing::warn!(
+                "Work log {:x} has no prepared updates; skipping final update",
+                old_state.log_id
+            );
+        } else {
+            tracing::info!("Posting final update for work log {:x}", old_state.log_id);
+            let submit = PovwSubmit {
+                state: self.state.clone(),
+                povw_private_key: Some(work_log_signer.clone()),
+                value_recipient: self.value_recipient,
+                force: false,
+                deployment: Some(deployment.clone()),
+                prover_config: self.prover_config.clone(),
+            };
+            submit.run(global_config).await.context("Failed to post final work log update")?;
+        }
+
+        // Confirm the onchain work log matches the final local state before retiring it.
+        let povw_accounting =
+            IPovwAccounting::new(deployment.povw_accounting_address, provider.clone());
+        let onchain_commit =
+            povw_accounting.workLogCommit(old_state.log_id.into()).call().await.with_context(
+                || format!("Failed to get work log commit for {:x}", old_state.log_id),
+            )?;
+        let final_commit = B256::from(bytemuck::cast::<_, [u8; 32]>(old_state.work_log.commit()));
+        ensure!(
+            onchain_commit == final_commit,
+            "Onchain commit for work log {:x} does not match the final commit in state: onchain: {}, state: {}",
+            old_state.log_id,
+            onchain_commit,
+            final_commit
+        );
+
+        // Initialize the new work log.
+        tracing::info!("Initializing a new work log with ID {:x}", self.new_log_id);
+        State::new(self.new_log_id).save(&self.new_state).context("Failed to save new state")?;
+
+        // Link the old work log to the new one.
+        let migration = WorkLogMigration {
+            oldLogId: old_state.log_id.into(),
+            newLogId: self.new_log_id.into(),
+            chainId: U256::from(chain_id),
+            povwAccounting: deployment.povw_accounting_address,
+            finalCommit: final_commit,
+        };
+        let attestation = MigrationAttestation::sign(migration, work_log_signer)?;
+        attestation.save(&attestation_path).await?;
+        tracing::info!("Saved migration attestation to {}", attestation_path.display());
+
+        if self.publish {
+            let tx = TransactionRequest::default()
+                .to(old_state.log_id.into())
+                .input(attestation.to_calldata().into());
+            let pending_tx = provider
+                .send_transaction(tx)
+                .await
+                .context("Failed to send migration attestation transaction")?;
+            let tx_hash = *pending_tx.tx_hash();
+            tracing::info!(%tx_hash, "Sent transaction for migration attestation");
+
+            let timeout = global_config.tx_timeout.or(pending_tx.timeout());
+            let tx_receipt = pending_tx
+                .with_timeout(timeout)
+                .get_receipt()
+                .await
+                .context("Failed to receive receipt for migration attestation transaction")?;
+            ensure!(
+                tx_receipt.status(),
+                "Migration attestation transaction failed: tx_hash = {}",
+                tx_receipt.transaction_hash
+            );
+            tracing::info!("Published migration attestation in tx {tx_hash}");
+        }
+
+        tracing::info!(
+            "Migrated work log {:x} to {:x}; new state saved to {}",
+            old_state.log_id,
+            self.new_log_id,
+            self.new_state.display()
+        );
+        Ok(())
+    }
+}
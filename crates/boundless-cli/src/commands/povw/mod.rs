@@ -15,14 +15,16 @@
 //! Commands of the Boundless CLI for Proof of Verifiable Work (PoVW) operations.
 
 mod claim;
+mod migrate_log;
 mod prepare;
 mod state;
 mod submit;
 
 pub use claim::PovwClaim;
+pub use migrate_log::{MigrationAttestation, PovwMigrateLog, WorkLogMigration};
 pub use prepare::PovwPrepare;
-pub use state::State;
-pub use submit::PovwSubmit;
+pub use state::{ConsumedNonces, NonceRange, State, SubmissionStatus};
+pub use submit::{submission_status, PovwSubmit};
 
 use clap::Subcommand;
 use risc0_zkvm::{GenericReceipt, ReceiptClaim, WorkClaim};
@@ -41,6 +43,8 @@ pub enum PovwCommands {
     Submit(PovwSubmit),
     /// Claim ZKC rewards associated with submitted work log updates in past epochs.
     Claim(PovwClaim),
+    /// Migrate to a new work log, linking it to the current one with a signed attestation.
+    MigrateLog(PovwMigrateLog),
 }
 
 impl PovwCommands {
@@ -50,6 +54,7 @@ impl PovwCommands {
             Self::Prepare(cmd) => cmd.run().await,
             Self::Submit(cmd) => cmd.run(global_config).await,
             Self::Claim(cmd) => cmd.run(global_config).await,
+            Self::MigrateLog(cmd) => cmd.run(global_config).await,
         }
     }
 }
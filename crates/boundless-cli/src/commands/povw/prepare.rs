@@ -23,7 +23,7 @@ use url::Url;
 
 use crate::config::ProverConfig;
 
-use super::{State, WorkReceipt};
+use super::{NonceRange, State, WorkReceipt};
 
 /// Compress a directory of work receipts into a work log update.
 #[non_exhaustive]
@@ -66,6 +66,13 @@ pub struct PovwPrepare {
     #[arg(long)]
     allow_partial_update: bool,
 
+    /// Skip checking the work receipts against the nonce ranges consumed by prior updates.
+    ///
+    /// Intended for recovery, when the registry of consumed nonces in the state file is known to
+    /// be out of date.
+    #[arg(long)]
+    force: bool,
+
     #[clap(flatten, next_help_heading = "Prover")]
     prover_config: ProverConfig,
 }
@@ -134,6 +141,14 @@ impl PovwPrepare {
         }
         tracing::info!("Loaded {} work receipts", work_receipts.len());
 
+        // Check for nonce overlaps before proving, as the update would otherwise fail.
+        let nonce_ranges = work_receipts.iter().map(nonce_range).collect::<Result<Vec<_>>>()?;
+        if self.force {
+            tracing::warn!("Skipping nonce overlap check");
+        } else {
+            state.check_nonce_ranges(&nonce_ranges)?;
+        }
+
         // Set up the work log update prover
         self.prover_config.configure_proving_backend_with_health_check().await?;
         let prover_builder = WorkLogUpdateProver::builder()
@@ -170,6 +185,7 @@ impl PovwPrepare {
         state
             .update_work_log(prover.work_log, prove_info.receipt)
             .context("Failed to update state")?
+            .record_consumed_nonces(nonce_ranges)?
             .save(&self.state)
             .context("Failed to save state")?;
 
@@ -256,6 +272,29 @@ fn check_work_receipt<T: Borrow<WorkReceipt>>(
     Ok(work_receipt)
 }
 
+/// Get the range of nonces consumed by a work receipt.
+fn nonce_range(work_receipt: &WorkReceipt) -> anyhow::Result<NonceRange> {
+    let work = work_receipt
+        .claim()
+        .as_value()
+        .context("Loaded receipt has a pruned claim")?
+        .work
+        .as_value()
+        .context("Loaded receipt has a pruned work claim")?
+        .clone();
+    ensure!(
+        work.nonce_min.job == work.nonce_max.job,
+        "Receipt has nonces spanning multiple jobs: {} to {}",
+        work.nonce_min.job,
+        work.nonce_max.job
+    );
+    Ok(NonceRange {
+        job: work.nonce_min.job,
+        segment_min: work.nonce_min.segment,
+        segment_max: work.nonce_max.segment,
+    })
+}
+
 // TODO: Create a common crate that Bento, test-utils and the CLI can all use.
 /// Work receipt info matching Bento API format
 /// Copied from bento/crates/api/src/lib.rs
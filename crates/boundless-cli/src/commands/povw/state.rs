@@ -14,7 +14,7 @@
 
 //! Commands of the Boundless CLI for Proof of Verifiable Work (PoVW) operations.
 
-use std::{collections::HashMap, io::Write, path::Path, time::SystemTime};
+use std::{collections::HashMap, fmt, io::Write, ops::Range, path::Path, time::SystemTime};
 
 use alloy::{primitives::B256, rpc::types::TransactionReceipt};
 use anyhow::{bail, ensure, Context, Result};
@@ -51,10 +51,72 @@ pub struct State {
     /// A map of the transaction hashes to related state. Used to determine which blocks have
     /// update events for the claim rewards operation.
     pub update_transactions: HashMap<B256, UpdateTransactionState>,
+    /// Registry of the nonce ranges consumed by each update in `log_builder_receipts`. Used to
+    /// detect work receipts included in more than one update before proving or submitting.
+    pub consumed_nonces: Vec<ConsumedNonces>,
     /// Time at which this state was last updated.
     pub updated_at: SystemTime,
 }
 
+/// Range of nonces consumed by a work receipt, within a single job of a work log.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NonceRange {
+    /// Job number of the nonces.
+    pub job: u64,
+    /// First segment index in the range.
+    pub segment_min: u32,
+    /// Last segment index in the range, inclusive.
+    pub segment_max: u32,
+}
+
+impl NonceRange {
+    /// Whether this range shares any nonce with the other range.
+    pub fn overlaps(&self, other: &NonceRange) -> bool {
+        self.job == other.job
+            && self.segment_min <= other.segment_max
+            && other.segment_min <= self.segment_max
+    }
+}
+
+impl fmt::Display for NonceRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "job {} segments {}..={}", self.job, self.segment_min, self.segment_max)
+    }
+}
+
+/// Nonce range consumed by an update to the work log.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConsumedNonces {
+    /// Range of nonces consumed.
+    pub range: NonceRange,
+    /// Index of the update in `log_builder_receipts` that consumed the range.
+    pub update: usize,
+}
+
+/// Encoding of the [State] before the nonce registry was added.
+#[derive(Deserialize)]
+struct StateV1 {
+    log_id: PovwLogId,
+    work_log: WorkLog,
+    log_builder_receipts: Vec<Receipt>,
+    update_transactions: HashMap<B256, UpdateTransactionState>,
+    updated_at: SystemTime,
+}
+
+impl From<StateV1> for State {
+    fn from(state: StateV1) -> Self {
+        // Nonces consumed by updates prepared before the registry existed are not known.
+        Self {
+            log_id: state.log_id,
+            work_log: state.work_log,
+            log_builder_receipts: state.log_builder_receipts,
+            update_transactions: state.update_transactions,
+            consumed_nonces: Vec::new(),
+            updated_at: state.updated_at,
+        }
+    }
+}
+
 /// State of a log update transaction sent to the chain.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 #[non_exhaustive]
@@ -65,12 +127,31 @@ pub struct UpdateTransactionState {
     update_event: Option<WorkLogUpdated>,
 }
 
+/// Status of the updates in a [State] relative to the work log commit onchain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum SubmissionStatus {
+    /// The onchain work log already includes every update in the state.
+    AlreadySubmitted,
+    /// The updates starting at the given index in `log_builder_receipts` are not yet onchain.
+    Pending {
+        /// Index of the first update to submit.
+        first_update: usize,
+    },
+    /// The onchain commit matches no update in the state, so none of the updates can be applied.
+    Diverged {
+        /// Work log commit currently onchain.
+        onchain_commit: B256,
+    },
+}
+
 /// A one-byte version number tacked on to the front of the encoded state for cross-version compat.
 #[repr(u8)]
 #[non_exhaustive]
 #[derive(Copy, Clone, Debug, TryFromPrimitive)]
 enum StateVersion {
     V1,
+    V2,
 }
 
 impl State {
@@ -81,13 +162,14 @@ impl State {
             work_log: WorkLog::EMPTY,
             log_builder_receipts: Vec::new(),
             update_transactions: HashMap::new(),
+            consumed_nonces: Vec::new(),
             updated_at: SystemTime::now(),
         }
     }
 
     /// Encode this state into a buffer of bytes.
     pub fn encode(&self) -> anyhow::Result<Vec<u8>> {
-        let mut buffer = vec![StateVersion::V1 as u8];
+        let mut buffer = vec![StateVersion::V2 as u8];
         buffer.extend_from_slice(&bincode::serialize(self)?);
         Ok(buffer)
     }
@@ -100,7 +182,10 @@ impl State {
         }
         let (&[version], buffer) = buffer.split_at(1) else { unreachable!("can't touch this") };
         match version.try_into() {
-            Ok(StateVersion::V1) => {
+            Ok(StateVersion::V1) => bincode::deserialize::<StateV1>(buffer)
+                .map(Into::into)
+                .context("failed to deserialize state"),
+            Ok(StateVersion::V2) => {
                 bincode::deserialize(buffer).context("failed to deserialize state")
             }
             Err(_) => bail!("unknown state version number: {version}"),
@@ -152,6 +237,88 @@ impl State {
         Ok(self)
     }
 
+    /// Determine which updates in this state remain to be submitted, given the onchain commit.
+    pub fn submission_status(&self, onchain_commit: B256) -> anyhow::Result<SubmissionStatus> {
+        let Some(latest_receipt) = self.log_builder_receipts.last() else {
+            bail!("Loaded state has no log builder receipts")
+        };
+        let latest_journal = LogBuilderJournal::decode(&latest_receipt.journal.bytes)
+            .context("Failed to decode journal from latest receipt")?;
+        if bytemuck::cast::<_, [u8; 32]>(latest_journal.updated_commit) == *onchain_commit {
+            return Ok(SubmissionStatus::AlreadySubmitted);
+        }
+
+        // Find the latest receipt with an initial commit equal to the commit onchain. All updates
+        // from that point on should be submitted.
+        for (i, receipt) in self.log_builder_receipts.iter().enumerate().rev() {
+            let journal = LogBuilderJournal::decode(&receipt.journal.bytes).with_context(|| {
+                format!("Failed to decode journal from receipt in state at index {i}")
+            })?;
+            if bytemuck::cast::<_, [u8; 32]>(journal.initial_commit) == *onchain_commit {
+                return Ok(SubmissionStatus::Pending { first_update: i });
+            }
+        }
+        Ok(SubmissionStatus::Diverged { onchain_commit })
+    }
+
+    /// Record the nonce ranges consumed by the latest update to the work log.
+    pub fn record_consumed_nonces(
+        &mut self,
+        ranges: impl IntoIterator<Item = NonceRange>,
+    ) -> anyhow::Result<&mut Self> {
+        let update = self
+            .log_builder_receipts
+            .len()
+            .checked_sub(1)
+            .context("Cannot record consumed nonces for a state with no updates")?;
+        self.consumed_nonces
+            .extend(ranges.into_iter().map(|range| ConsumedNonces { range, update }));
+        self.updated_at = SystemTime::now();
+        Ok(self)
+    }
+
+    /// Check that the given nonce ranges, to be included in a new update, do not overlap with each
+    /// other or with any range consumed by a prior update.
+    pub fn check_nonce_ranges(&self, ranges: &[NonceRange]) -> anyhow::Result<()> {
+        let mut overlaps = Vec::new();
+        for (i, range) in ranges.iter().enumerate() {
+            for consumed in self.consumed_nonces.iter().filter(|c| c.range.overlaps(range)) {
+                overlaps.push(format!(
+                    "{range} overlaps {} consumed by update {}",
+                    consumed.range, consumed.update
+                ));
+            }
+            for other in ranges[..i].iter().filter(|other| other.overlaps(range)) {
+                overlaps.push(format!("{range} overlaps {other} in the same update"));
+            }
+        }
+        ensure_no_overlaps(overlaps)
+    }
+
+    /// Check that the nonce ranges consumed by the given updates do not overlap with ranges
+    /// consumed by any other update in this state.
+    pub fn check_consumed_nonces(&self, updates: Range<usize>) -> anyhow::Result<()> {
+        let mut overlaps = Vec::new();
+        for (i, consumed) in self.consumed_nonces.iter().enumerate() {
+            if !updates.contains(&consumed.update) {
+                continue;
+            }
+            for (j, other) in self.consumed_nonces.iter().enumerate() {
+                // Report each pair within the given updates once.
+                if i == j || (updates.contains(&other.update) && j < i) {
+                    continue;
+                }
+                if consumed.range.overlaps(&other.range) {
+                    overlaps.push(format!(
+                        "{} in update {} overlaps {} consumed by update {}",
+                        consumed.range, consumed.update, other.range, other.update
+                    ));
+                }
+            }
+        }
+        ensure_no_overlaps(overlaps)
+    }
+
     /// Add a pending transaction hash for a log update transaction.
     pub fn add_pending_update_tx(&mut self, tx_hash: B256) -> anyhow::Result<&mut Self> {
         self.update_transactions
@@ -302,3 +469,13 @@ impl State {
         Ok(())
     }
 }
+
+fn ensure_no_overlaps(overlaps: Vec<String>) -> anyhow::Result<()> {
+    if overlaps.is_empty() {
+        return Ok(());
+    }
+    bail!(
+        "Work receipts consume overlapping nonce ranges; use --force to skip this check:\n  {}",
+        overlaps.join("\n  ")
+    )
+}
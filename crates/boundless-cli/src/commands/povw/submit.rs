@@ -25,10 +25,9 @@ use boundless_povw::{
     log_updater::{prover::LogUpdaterProver, IPovwAccounting},
 };
 use clap::Args;
-use risc0_povw::guest::Journal as LogBuilderJournal;
 use risc0_zkvm::{default_prover, ProverOpts};
 
-use super::State;
+use super::{State, SubmissionStatus};
 use crate::config::{GlobalConfig, ProverConfig};
 
 /// Submit a work log update to the PoVW accounting contract.
@@ -52,12 +51,19 @@ pub struct PovwSubmit {
     #[clap(short, long, env = "POVW_VALUE_RECIPIENT")]
     pub value_recipient: Option<Address>,
 
+    /// Skip checking the updates against the nonce ranges consumed by prior updates.
+    ///
+    /// Intended for recovery, when the registry of consumed nonces in the state file is known to
+    /// be out of date.
+    #[clap(long)]
+    pub force: bool,
+
     /// Deployment configuration for the PoVW and ZKC contracts.
     #[clap(flatten, next_help_heading = "Deployment")]
     pub deployment: Option<Deployment>,
 
     #[clap(flatten, next_help_heading = "Prover")]
-    prover_config: ProverConfig,
+    pub(super) prover_config: ProverConfig,
 }
 
 impl PovwSubmit {
@@ -91,64 +97,44 @@ impl PovwSubmit {
             .get_chain_id()
             .await
             .with_context(|| format!("Failed to get chain ID from {rpc_url}"))?;
-        let deployment = self
-            .deployment
-            .clone()
-            .or_else(|| Deployment::from_chain_id(chain_id))
-            .context(
-            "could not determine deployment from chain ID; please specify deployment explicitly",
-        )?;
+        let deployment = global_config.povw_deployment(self.deployment.as_ref(), chain_id)?;
         let povw_accounting =
             IPovwAccounting::new(deployment.povw_accounting_address, provider.clone());
 
-        // Get the current work log commit, to determine which update(s) should be applied.
-        let onchain_commit =
-            povw_accounting.workLogCommit(state.log_id.into()).call().await.with_context(|| {
-                format!(
-                    "Failed to get work log commit for {:x} from {:x}",
-                    state.log_id, deployment.povw_accounting_address
-                )
-            })?;
-
-        // Check if the latest log builder receipt has an updated_commit value equal to what is
-        // onchain. If so, the onchain work log is already up to date.
-        let Some(latest_receipt) = state.log_builder_receipts.last() else {
-            bail!("Loaded state has no log builder receipts")
+        // Compare the state to the onchain work log, to determine which update(s) should be applied.
+        let matching_receipt_index = match submission_status(
+            provider.clone(),
+            deployment.povw_accounting_address,
+            &state,
+        )
+        .await?
+        {
+            SubmissionStatus::AlreadySubmitted => {
+                tracing::info!("Work log update already submitted; the onchain PoVW accounting contract is up to date with the latest commit in state");
+                return Ok(());
+            }
+            SubmissionStatus::Pending { first_update } => first_update,
+            SubmissionStatus::Diverged { onchain_commit } => bail!(
+                "Onchain work log commit {onchain_commit} does not match any update in {}. The \
+                 work log onchain has diverged from this state, which happens when the state file \
+                 was restored from an older copy or the work log was updated using another state \
+                 file. Use the latest state file for work log {:x}.",
+                self.state.display(),
+                state.log_id
+            ),
         };
-        let latest_receipt_journal = LogBuilderJournal::decode(&latest_receipt.journal.bytes)
-            .context("Failed to decode journal from latest receipt")?;
-        if bytemuck::cast::<_, [u8; 32]>(latest_receipt_journal.updated_commit) == *onchain_commit {
-            tracing::info!("Onchain PoVW accounting contract is already up to date with the latest commit in state");
-            return Ok(());
-        }
-
-        // Find the index of the receipt in the state that has an initial commit equal to the
-        // commit current onchain. We will send all updates after that point.
-        let matching_receipt_index = state
-            .log_builder_receipts
-            .iter()
-            .enumerate()
-            .rev()
-            .map(|(i, receipt)| {
-                let journal =
-                    LogBuilderJournal::decode(&receipt.journal.bytes).with_context(|| {
-                        format!("Failed to decode journal from receipt in state at index {i}")
-                    })?;
-                anyhow::Ok(
-                    (bytemuck::cast::<_, [u8; 32]>(journal.initial_commit) == *onchain_commit)
-                        .then_some(i),
-                )
-            })
-            .find_map(|x| x.transpose())
-            .with_context(|| {
-                format!("Failed to find receipt with initial commit matching {onchain_commit}")
-            })??;
 
         // Iterate over all the log builder receipts that should be sent to the chain.
         // NOTE: In most cases, this will be one receipt. It may be more if the prover previously
         // built a work log update but it failed to send (e.g. network instability or high gas
         // fees caused the transaction not to go through).
-        let receipts_for_update = state.log_builder_receipts[matching_receipt_index..].to_vec();
+        let pending_updates = matching_receipt_index..state.log_builder_receipts.len();
+        if self.force {
+            tracing::warn!("Skipping nonce overlap check");
+        } else {
+            state.check_consumed_nonces(pending_updates.clone())?;
+        }
+        let receipts_for_update = state.log_builder_receipts[pending_updates].to_vec();
         if receipts_for_update.len() > 1 {
             tracing::info!(
                 "Updating onchain work log {:x} with {} update receipts",
@@ -235,3 +221,22 @@ impl PovwSubmit {
         Ok(())
     }
 }
+
+/// Compare the updates in a work log state against the work log commit onchain.
+///
+/// Used to avoid submitting updates that are already onchain, or that can no longer be applied.
+pub async fn submission_status(
+    provider: impl Provider,
+    povw_accounting_address: Address,
+    state: &State,
+) -> anyhow::Result<SubmissionStatus> {
+    let povw_accounting = IPovwAccounting::new(povw_accounting_address, provider);
+    let onchain_commit =
+        povw_accounting.workLogCommit(state.log_id.into()).call().await.with_context(|| {
+            format!(
+                "Failed to get work log commit for {:x} from {:x}",
+                state.log_id, povw_accounting_address
+            )
+        })?;
+    state.submission_status(onchain_commit)
+}
@@ -0,0 +1,230 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use alloy::{
+    primitives::{utils::format_units, Address, U256},
+    providers::{Provider, ProviderBuilder},
+};
+use anyhow::{ensure, Context};
+use boundless_market::contracts::token::IERC20;
+use boundless_zkc::{
+    deployments::Deployment,
+    units::{format_zkc, ZKC_DECIMALS},
+};
+use clap::Args;
+
+use crate::config::GlobalConfig;
+
+/// Command to audit the allowances an account has granted on the ZKC and collateral tokens.
+#[non_exhaustive]
+#[derive(Args, Clone, Debug)]
+pub struct ZkcAuditAllowances {
+    /// Address of the token owner to audit.
+    pub owner: Address,
+    /// Additional spender to check, on top of the known Boundless contracts.
+    ///
+    /// Can be given more than once.
+    #[clap(long = "spender")]
+    pub spenders: Vec<Address>,
+    /// Spender to revoke the allowances of, by setting them to zero.
+    ///
+    /// Can be given more than once. Requires the private key of the owner.
+    #[clap(long = "revoke")]
+    pub revoke: Vec<Address>,
+    /// Configuration for the ZKC deployment to use.
+    #[clap(flatten, next_help_heading = "ZKC Deployment")]
+    pub deployment: Option<Deployment>,
+}
+
+/// Allowance granted by the owner to a spender on a token.
+#[derive(Clone, Debug)]
+pub struct Allowance {
+    /// Name of the token.
+    pub token_name: &'static str,
+    /// Address of the token contract.
+    pub token: Address,
+    /// Number of decimals of the token.
+    pub decimals: u8,
+    /// Name of the spender, if it is a known contract.
+    pub spender_name: Option<&'static str>,
+    /// Address of the spender.
+    pub spender: Address,
+    /// Current allowance.
+    pub amount: U256,
+}
+
+impl Allowance {
+    /// Whether the allowance is unlimited, as set by approving the maximum value. Allowances of
+    /// at least half the maximum value are treated as unlimited, as they can be partially spent
+    /// by token implementations that decrease unlimited allowances.
+    pub fn is_unlimited(&self) -> bool {
+        self.amount >= U256::MAX >> 1
+    }
+}
+
+impl ZkcAuditAllowances {
+    /// Run the [ZkcAuditAllowances] command.
+    pub async fn run(&self, global_config: &GlobalConfig) -> anyhow::Result<()> {
+        let rpc_url = global_config.require_rpc_url()?;
+
+        // Connect to the chain.
+        let provider = ProviderBuilder::new()
+            .connect(rpc_url.as_str())
+            .await
+            .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
+        let chain_id = provider.get_chain_id().await?;
+        let deployment = global_config.zkc_deployment(self.deployment.as_ref(), chain_id)?;
+
+        // Collect the tokens and known spenders from the deployments available on this chain.
+        let mut tokens = vec![("ZKC", deployment.zkc_address)];
+        let mut spenders = vec![
+            (Some("veZKC staking"), deployment.vezkc_address),
+            (Some("staking rewards"), deployment.staking_rewards_address),
+        ];
+        let market_deployment = global_config
+            .market_deployment()
+            .or_else(|| boundless_market::Deployment::from_chain_id(chain_id));
+        if let Some(market) = market_deployment {
+            spenders.push((Some("Boundless market"), market.boundless_market_address));
+            if let Some(collateral) = market.collateral_token_address {
+                if collateral != deployment.zkc_address {
+                    tokens.push(("collateral", collateral));
+                }
+            }
+        }
+        if let Ok(povw) = global_config.povw_deployment(None, chain_id) {
+            spenders.push((Some("PoVW mint"), povw.povw_mint_address));
+            spenders.push((Some("PoVW accounting"), povw.povw_accounting_address));
+        }
+        for spender in self.spenders.iter().chain(&self.revoke) {
+            if !spenders.iter().any(|(_, addr)| addr == spender) {
+                spenders.push((None, *spender));
+            }
+        }
+
+        let allowances = allowances(provider, &tokens, self.owner, &spenders).await?;
+        println!("{:<10}  {:<44}  {:<18}  {:>30}", "token", "spender", "name", "allowance");
+        for allowance in &allowances {
+            let amount = if allowance.is_unlimited() {
+                "unlimited".to_string()
+            } else if allowance.token == deployment.zkc_address {
+                format_zkc(allowance.amount, ZKC_DECIMALS)
+            } else {
+                format_units(allowance.amount, allowance.decimals)?
+            };
+            println!(
+                "{:<10}  {:<44}  {:<18}  {:>30}",
+                allowance.token_name,
+                allowance.spender.to_string(),
+                allowance.spender_name.unwrap_or("-"),
+                amount
+            );
+        }
+        let unlimited = allowances.iter().filter(|a| a.is_unlimited()).count();
+        if unlimited > 0 {
+            tracing::warn!("Found {unlimited} unlimited allowances granted by {}", self.owner);
+        }
+
+        if self.revoke.is_empty() {
+            return Ok(());
+        }
+        let to_revoke = allowances
+            .iter()
+            .filter(|a| self.revoke.contains(&a.spender) && !a.amount.is_zero())
+            .collect::<Vec<_>>();
+        if to_revoke.is_empty() {
+            tracing::info!("No allowances to revoke");
+            return Ok(());
+        }
+
+        let tx_signer = global_config.require_private_key()?;
+        ensure!(
+            tx_signer.address() == self.owner,
+            "Revoking allowances requires the private key of the owner {}",
+            self.owner
+        );
+        let provider = ProviderBuilder::new()
+            .wallet(tx_signer.clone())
+            .connect(rpc_url.as_str())
+            .await
+            .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
+
+        for allowance in to_revoke {
+            let token = IERC20::new(allowance.token, provider.clone());
+            let tx_result = token
+                .approve(allowance.spender, U256::ZERO)
+                .send()
+                .await
+                .context("Failed to send approve transaction")?;
+            let tx_hash = tx_result.tx_hash();
+            tracing::info!(
+                %tx_hash,
+                "Sent transaction to revoke the {} allowance of {}",
+                allowance.token_name,
+                allowance.spender
+            );
+
+            let timeout = global_config.tx_timeout.or(tx_result.timeout());
+            tracing::debug!(?timeout, %tx_hash, "Waiting for transaction receipt");
+            let tx_receipt = tx_result
+                .with_timeout(timeout)
+                .get_receipt()
+                .await
+                .context("Failed to receive receipt for approve transaction")?;
+            ensure!(
+                tx_receipt.status(),
+                "Approve transaction failed: tx_hash = {}",
+                tx_receipt.transaction_hash
+            );
+            tracing::info!(
+                "Revoked the {} allowance of {}",
+                allowance.token_name,
+                allowance.spender
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Get the allowances granted by the owner to each of the spenders, on each of the tokens.
+pub async fn allowances(
+    provider: impl Provider + Clone,
+    tokens: &[(&'static str, Address)],
+    owner: Address,
+    spenders: &[(Option<&'static str>, Address)],
+) -> anyhow::Result<Vec<Allowance>> {
+    let mut allowances = Vec::with_capacity(tokens.len() * spenders.len());
+    for &(token_name, token) in tokens {
+        let contract = IERC20::new(token, provider.clone());
+        let decimals = contract
+            .decimals()
+            .call()
+            .await
+            .with_context(|| format!("Failed to get {token_name} decimals at {token}"))?;
+        for &(spender_name, spender) in spenders {
+            let amount = contract.allowance(owner, spender).call().await.with_context(|| {
+                format!("Failed to get {token_name} allowance of {spender} at {token}")
+            })?;
+            allowances.push(Allowance {
+                token_name,
+                token,
+                decimals,
+                spender_name,
+                spender,
+                amount,
+            });
+        }
+    }
+    Ok(allowances)
+}
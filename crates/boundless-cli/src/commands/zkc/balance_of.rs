@@ -13,12 +13,15 @@
 // limitations under the License.
 
 use alloy::{
-    primitives::{utils::format_ether, Address, U256},
+    primitives::{Address, U256},
     providers::{Provider, ProviderBuilder},
 };
 use anyhow::Context;
 use boundless_market::contracts::token::IERC20;
-use boundless_zkc::deployments::Deployment;
+use boundless_zkc::{
+    deployments::Deployment,
+    units::{format_zkc, ZKC_DECIMALS},
+};
 use clap::Args;
 
 use crate::config::GlobalConfig;
@@ -44,11 +47,10 @@ impl ZkcBalance {
             .await
             .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
         let chain_id = provider.get_chain_id().await?;
-        let deployment = self.deployment.clone().or_else(|| Deployment::from_chain_id(chain_id))
-            .context("could not determine ZKC deployment from chain ID; please specify deployment explicitly")?;
+        let deployment = global_config.zkc_deployment(self.deployment.as_ref(), chain_id)?;
 
         let balance = balance_of(provider, deployment.zkc_address, self.account).await?;
-        tracing::info!("Balance: {} ZKC", format_ether(balance));
+        tracing::info!("Balance: {} ZKC", format_zkc(balance, ZKC_DECIMALS));
 
         Ok(())
     }
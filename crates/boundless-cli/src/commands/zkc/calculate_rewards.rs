@@ -13,11 +13,15 @@
 // limitations under the License.
 
 use alloy::{
-    primitives::{utils::format_ether, Address, U256},
+    primitives::{Address, U256},
     providers::{Provider, ProviderBuilder},
 };
 use anyhow::Context;
-use boundless_zkc::{contracts::IStakingRewards, deployments::Deployment};
+use boundless_zkc::{
+    contracts::IStakingRewards,
+    deployments::Deployment,
+    units::{format_zkc, ZKC_DECIMALS},
+};
 use clap::Args;
 
 use crate::config::GlobalConfig;
@@ -44,12 +48,11 @@ impl ZkcCalculateRewards {
             .await
             .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
         let chain_id = provider.get_chain_id().await?;
-        let deployment = self.deployment.clone().or_else(|| Deployment::from_chain_id(chain_id))
-            .context("could not determine ZKC deployment from chain ID; please specify deployment explicitly")?;
+        let deployment = global_config.zkc_deployment(self.deployment.as_ref(), chain_id)?;
 
         let total =
             calculate_rewards(provider, deployment.staking_rewards_address, self.account).await?;
-        tracing::info!("Unclaimed rewards: {} ZKC", format_ether(total));
+        tracing::info!("Unclaimed rewards: {} ZKC", format_zkc(total, ZKC_DECIMALS));
 
         Ok(())
     }
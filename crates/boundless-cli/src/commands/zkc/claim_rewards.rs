@@ -13,18 +13,20 @@
 // limitations under the License.
 
 use alloy::{
-    primitives::{utils::format_ether, Address, U256},
+    primitives::{Address, U256},
     providers::{Provider, ProviderBuilder},
     sol_types::SolCall,
 };
-use anyhow::{ensure, Context};
+use anyhow::{bail, ensure, Context};
+use boundless_market::contracts::token::IERC20;
 use boundless_zkc::{
     contracts::{extract_tx_logs, IStakingRewards, IZKC},
     deployments::Deployment,
+    units::{format_zkc, ZKC_DECIMALS},
 };
 use clap::Args;
 
-use crate::config::GlobalConfig;
+use crate::{commands::zkc::estimate_and_preview, config::GlobalConfig};
 
 /// Command to claim rewards for ZKC.
 #[non_exhaustive]
@@ -38,6 +40,12 @@ pub struct ZkcClaimRewards {
     /// Only valid when used with `--calldata`.
     #[clap(long, requires = "calldata")]
     pub from: Option<Address>,
+    /// Address to send the claimed rewards to, e.g. a cold wallet.
+    ///
+    /// The staking rewards contract always pays out to the claiming account, so the rewards are
+    /// claimed to the signer and then transferred to this address in a second transaction.
+    #[clap(long)]
+    pub payout_to: Option<Address>,
     /// Configuration for the ZKC deployment to use.
     #[clap(flatten, next_help_heading = "ZKC Deployment")]
     pub deployment: Option<Deployment>,
@@ -54,16 +62,21 @@ impl ZkcClaimRewards {
             .await
             .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
         let chain_id = provider.get_chain_id().await?;
-        let deployment = self.deployment.clone().or_else(|| Deployment::from_chain_id(chain_id))
-            .context("could not determine ZKC deployment from chain ID; please specify deployment explicitly")?;
+        let deployment = global_config.zkc_deployment(self.deployment.as_ref(), chain_id)?;
 
         let account = match &self.from {
             Some(addr) => *addr,
             None => global_config.require_private_key()?.address(),
         };
 
+        if self.payout_to == Some(Address::ZERO) {
+            bail!("Refusing to pay out rewards to the zero address");
+        }
+        // Paying out to the claiming account is the same as a plain claim.
+        let payout_to = self.payout_to.filter(|addr| *addr != account);
+
         if self.calldata {
-            return print_calldata(provider, deployment, account).await;
+            return print_calldata(provider, deployment, account, payout_to).await;
         }
 
         let tx_signer = global_config.require_private_key()?;
@@ -73,13 +86,35 @@ impl ZkcClaimRewards {
             .await
             .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
         let chain_id = provider.get_chain_id().await?;
-        let deployment = self.deployment.clone().or_else(|| Deployment::from_chain_id(chain_id))
-            .context("could not determine ZKC deployment from chain ID; please specify deployment explicitly")?;
+        let deployment = global_config.zkc_deployment(self.deployment.as_ref(), chain_id)?;
 
-        let total =
-            claim_rewards(provider, deployment.staking_rewards_address, account, global_config)
+        match payout_to {
+            Some(recipient) => {
+                let total = claim_rewards_to(
+                    provider,
+                    deployment.staking_rewards_address,
+                    deployment.zkc_address,
+                    account,
+                    recipient,
+                    global_config,
+                )
+                .await?;
+                tracing::info!(
+                    "Paid out rewards: {} ZKC to {recipient}",
+                    format_zkc(total, ZKC_DECIMALS)
+                );
+            }
+            None => {
+                let total = claim_rewards(
+                    provider,
+                    deployment.staking_rewards_address,
+                    account,
+                    global_config,
+                )
                 .await?;
-        tracing::info!("Claimed rewards: {} ZKC", format_ether(total));
+                tracing::info!("Claimed rewards: {} ZKC", format_zkc(total, ZKC_DECIMALS));
+            }
+        }
 
         Ok(())
     }
@@ -89,6 +124,7 @@ async fn print_calldata(
     provider: impl Provider + Clone,
     deployment: Deployment,
     from: Address,
+    payout_to: Option<Address>,
 ) -> anyhow::Result<()> {
     let staking = IStakingRewards::new(deployment.staking_rewards_address, provider);
     let current_epoch: u32 = staking.getCurrentEpoch().call().await?.try_into()?;
@@ -108,6 +144,16 @@ async fn print_calldata(
     println!("From: {}", from);
     println!("Calldata: 0x{}", hex::encode(claim_call.abi_encode()));
     println!("=====================================");
+
+    if let Some(recipient) = payout_to {
+        let total = unclaimed_rewards.iter().sum::<U256>();
+        let transfer_call = IERC20::transferCall { to: recipient, value: total };
+        println!("=========== Transfer Call ===========");
+        println!("Contract: {}", deployment.zkc_address);
+        println!("From: {}", from);
+        println!("Calldata: 0x{}", hex::encode(transfer_call.abi_encode()));
+        println!("=====================================");
+    }
     Ok(())
 }
 
@@ -118,7 +164,7 @@ pub async fn claim_rewards(
     account: Address,
     global_config: &GlobalConfig,
 ) -> anyhow::Result<U256> {
-    let staking = IStakingRewards::new(staking_rewards_address, provider);
+    let staking = IStakingRewards::new(staking_rewards_address, &provider);
     let current_epoch: u32 = staking.getCurrentEpoch().call().await?.try_into()?;
     let epochs: Vec<U256> = (0..current_epoch).map(U256::from).collect();
     let unclaimed_rewards = staking.calculateUnclaimedRewards(account, epochs).call().await?;
@@ -129,11 +175,9 @@ pub async fn claim_rewards(
         }
     }
     ensure!(!unclaimed_epochs.is_empty(), "No unclaimed rewards for account {}", account);
-    let tx_result = staking
-        .claimRewards(unclaimed_epochs)
-        .send()
-        .await
-        .context("Failed to send claimRewards transaction")?;
+    let call = staking.claimRewards(unclaimed_epochs).from(account);
+    tracing::info!("{}", estimate_and_preview::<IZKC::IZKCErrors, _, _>(&provider, &call).await?);
+    let tx_result = call.send().await.context("Failed to send claimRewards transaction")?;
 
     let tx_hash = tx_result.tx_hash();
     tracing::info!(%tx_hash, "Sent transaction for claimRewards");
@@ -157,3 +201,66 @@ pub async fn claim_rewards(
 
     Ok(total)
 }
+
+/// Claim rewards for a specified address, and transfer them to a payout address.
+///
+/// The claim and the transfer are sent as separate transactions. If the transfer fails, the claimed
+/// rewards remain with the claiming account and the returned error describes how to recover them.
+pub async fn claim_rewards_to(
+    provider: impl Provider + Clone,
+    staking_rewards_address: Address,
+    zkc_address: Address,
+    account: Address,
+    payout_to: Address,
+    global_config: &GlobalConfig,
+) -> anyhow::Result<U256> {
+    ensure!(payout_to != Address::ZERO, "Refusing to pay out rewards to the zero address");
+
+    let total =
+        claim_rewards(provider.clone(), staking_rewards_address, account, global_config).await?;
+    tracing::info!("Claimed rewards: {} ZKC to {account}", format_zkc(total, ZKC_DECIMALS));
+
+    transfer_rewards(provider, zkc_address, payout_to, total, global_config).await.with_context(
+        || {
+            format!(
+                "Claimed {} ZKC to {account}, but failed to transfer it to {payout_to}. \
+                 The rewards remain in {account}; to recover them, transfer {total} wei of ZKC \
+                 (token {zkc_address}) from {account} to {payout_to}",
+                format_zkc(total, ZKC_DECIMALS)
+            )
+        },
+    )?;
+
+    Ok(total)
+}
+
+async fn transfer_rewards(
+    provider: impl Provider,
+    zkc_address: Address,
+    to: Address,
+    amount: U256,
+    global_config: &GlobalConfig,
+) -> anyhow::Result<()> {
+    let zkc = IERC20::new(zkc_address, provider);
+    let tx_result =
+        zkc.transfer(to, amount).send().await.context("Failed to send transfer transaction")?;
+
+    let tx_hash = tx_result.tx_hash();
+    tracing::info!(%tx_hash, "Sent transaction for transfer");
+
+    let timeout = global_config.tx_timeout.or(tx_result.timeout());
+    tracing::debug!(?timeout, %tx_hash, "Waiting for transaction receipt");
+    let tx_receipt = tx_result
+        .with_timeout(timeout)
+        .get_receipt()
+        .await
+        .context("Failed to receive receipt transfer transaction")?;
+
+    ensure!(
+        tx_receipt.status(),
+        "transfer transaction failed: tx_hash = {}",
+        tx_receipt.transaction_hash
+    );
+
+    Ok(())
+}
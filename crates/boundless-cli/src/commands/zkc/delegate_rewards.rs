@@ -12,26 +12,57 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::{
+    path::{Path, PathBuf},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
 use alloy::{
-    primitives::Address,
+    primitives::{Address, U256},
     providers::{Provider, ProviderBuilder},
     sol_types::SolCall,
 };
 use anyhow::{ensure, Context};
-use boundless_zkc::{contracts::IRewards, deployments::Deployment};
+use boundless_zkc::{
+    contracts::{IRewards, IVotes},
+    delegation::SignedRewardDelegation,
+    deployments::Deployment,
+};
 use clap::Args;
 
-use crate::config::GlobalConfig;
+use crate::{commands::zkc::estimate_and_preview, config::GlobalConfig};
 
 /// Command to delegate rewards for ZKC.
 #[non_exhaustive]
 #[derive(Args, Clone, Debug)]
 pub struct ZkcDelegateRewards {
     /// Address to delegate rewards to.
-    pub to: Address,
+    #[clap(required_unless_present = "submit")]
+    pub to: Option<Address>,
     /// Whether to only print the calldata without sending the transaction.
-    #[clap(long)]
+    #[clap(long, conflicts_with_all = ["sign_only", "submit"])]
     pub calldata: bool,
+    /// Sign the delegation and write it to the `--out` file, without sending a transaction.
+    ///
+    /// Any account can then submit the signed delegation with `--submit`, paying for gas. Without
+    /// an RPC URL, the delegation is signed offline, which requires `--nonce` and the ZKC
+    /// deployment, including its chain ID.
+    #[clap(long, requires = "out", conflicts_with = "submit")]
+    pub sign_only: bool,
+    /// File to write the signed delegation to.
+    #[clap(long, requires = "sign_only")]
+    pub out: Option<PathBuf>,
+    /// veZKC signature nonce of the delegating account.
+    ///
+    /// Fetched from the chain if not specified.
+    #[clap(long, requires = "sign_only")]
+    pub nonce: Option<U256>,
+    /// Time in seconds for which the signed delegation can be submitted.
+    #[clap(long, default_value_t = 86400)]
+    pub expiry: u64,
+    /// Submit a delegation signed with `--sign-only`, read from the given file.
+    #[clap(long, value_name = "FILE")]
+    pub submit: Option<PathBuf>,
     /// Configuration for the ZKC deployment to use.
     #[clap(flatten, next_help_heading = "ZKC Deployment")]
     pub deployment: Option<Deployment>,
@@ -40,6 +71,15 @@ pub struct ZkcDelegateRewards {
 impl ZkcDelegateRewards {
     /// Run the [DelegateRewards] command.
     pub async fn run(&self, global_config: &GlobalConfig) -> anyhow::Result<()> {
+        if let Some(path) = &self.submit {
+            return submit(global_config, path).await;
+        }
+        let to = self.to.context("the address to delegate rewards to is required")?;
+        if self.sign_only {
+            let out = self.out.as_deref().context("--out is required with --sign-only")?;
+            return self.sign_only(global_config, to, out).await;
+        }
+
         let rpc_url = global_config.require_rpc_url()?;
 
         // Connect to the chain.
@@ -48,11 +88,10 @@ impl ZkcDelegateRewards {
             .await
             .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
         let chain_id = provider.get_chain_id().await?;
-        let deployment = self.deployment.clone().or_else(|| Deployment::from_chain_id(chain_id))
-            .context("could not determine ZKC deployment from chain ID; please specify deployment explicitly")?;
+        let deployment = global_config.zkc_deployment(self.deployment.as_ref(), chain_id)?;
 
         if self.calldata {
-            print_calldata(&deployment, self.to);
+            print_calldata(&deployment, to);
             return Ok(());
         }
 
@@ -65,11 +104,12 @@ impl ZkcDelegateRewards {
 
         let rewards = IRewards::new(deployment.vezkc_address, provider.clone());
 
-        let tx_result = rewards
-            .delegateRewards(self.to)
-            .send()
-            .await
-            .context("Failed to send delegateRewards transaction")?;
+        let call = rewards.delegateRewards(to).from(tx_signer.address());
+        tracing::info!(
+            "{}",
+            estimate_and_preview::<IRewards::IRewardsErrors, _, _>(&provider, &call).await?
+        );
+        let tx_result = call.send().await.context("Failed to send delegateRewards transaction")?;
         let tx_hash = tx_result.tx_hash();
         tracing::info!(%tx_hash, "Sent transaction for delegating rewards");
 
@@ -91,6 +131,139 @@ impl ZkcDelegateRewards {
         tracing::info!("Delegating rewards completed");
         Ok(())
     }
+
+    /// Sign the delegation with the configured private key and write it to the given file.
+    async fn sign_only(
+        &self,
+        global_config: &GlobalConfig,
+        delegatee: Address,
+        out: &Path,
+    ) -> anyhow::Result<()> {
+        let signer = global_config.require_private_key()?;
+        let (deployment, chain_id, nonce) = match &global_config.rpc_url {
+            Some(rpc_url) => {
+                let provider = ProviderBuilder::new()
+                    .connect(rpc_url.as_str())
+                    .await
+                    .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
+                let chain_id = provider.get_chain_id().await?;
+                let deployment =
+                    global_config.zkc_deployment(self.deployment.as_ref(), chain_id)?;
+                let nonce = match self.nonce {
+                    Some(nonce) => nonce,
+                    None => IVotes::new(deployment.vezkc_address, provider)
+                        .nonces(signer.address())
+                        .call()
+                        .await
+                        .context("Failed to get the delegation nonce")?,
+                };
+                (deployment, chain_id, nonce)
+            }
+            None => {
+                let deployment = self
+                    .deployment
+                    .clone()
+                    .context("the ZKC deployment is required to sign without an RPC URL")?;
+                let chain_id = deployment
+                    .chain_id
+                    .context("--chain-id is required to sign without an RPC URL")?;
+                let nonce = self.nonce.context("--nonce is required to sign without an RPC URL")?;
+                (deployment, chain_id, nonce)
+            }
+        };
+
+        let expiry = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + self.expiry;
+        let signed = SignedRewardDelegation::sign(
+            &signer,
+            delegatee,
+            nonce,
+            expiry,
+            deployment.vezkc_address,
+            chain_id,
+        )
+        .await?;
+        std::fs::write(out, serde_json::to_string_pretty(&signed)?)
+            .with_context(|| format!("Failed to write signed delegation to {}", out.display()))?;
+
+        tracing::info!(
+            "Signed delegation of rewards of {} to {} with nonce {}, valid until {}",
+            signer.address(),
+            delegatee,
+            nonce,
+            expiry
+        );
+        tracing::info!("Signed delegation written to {}", out.display());
+        Ok(())
+    }
+}
+
+/// Submit a signed delegation read from the given file, paying for gas with the configured
+/// private key.
+async fn submit(global_config: &GlobalConfig, path: &Path) -> anyhow::Result<()> {
+    let signed: SignedRewardDelegation =
+        serde_json::from_str(&std::fs::read_to_string(path).with_context(|| {
+            format!("Failed to read signed delegation from {}", path.display())
+        })?)
+        .with_context(|| format!("Failed to parse signed delegation from {}", path.display()))?;
+    signed.verify().context("Invalid signed delegation")?;
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    ensure!(signed.expiry >= now, "Signed delegation expired at {}", signed.expiry);
+
+    let rpc_url = global_config.require_rpc_url()?;
+    let tx_signer = global_config.require_private_key()?;
+    let provider = ProviderBuilder::new()
+        .wallet(tx_signer.clone())
+        .connect(rpc_url.as_str())
+        .await
+        .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
+    let chain_id = provider.get_chain_id().await?;
+    ensure!(
+        chain_id == signed.chain_id,
+        "Signed delegation is for chain ID {}, but the RPC URL is for chain ID {chain_id}",
+        signed.chain_id
+    );
+
+    let nonce = IVotes::new(signed.vezkc_address, provider.clone())
+        .nonces(signed.delegator)
+        .call()
+        .await
+        .context("Failed to get the delegation nonce")?;
+    ensure!(
+        nonce == signed.nonce,
+        "Signed delegation has nonce {}, but the next nonce of {} is {nonce}",
+        signed.nonce,
+        signed.delegator
+    );
+
+    let rewards = IRewards::new(signed.vezkc_address, provider.clone());
+    let IRewards::delegateRewardsBySigCall { delegatee, nonce, expiry, v, r, s } =
+        signed.to_call()?;
+    let call =
+        rewards.delegateRewardsBySig(delegatee, nonce, expiry, v, r, s).from(tx_signer.address());
+    tracing::info!(
+        "{}",
+        estimate_and_preview::<IRewards::IRewardsErrors, _, _>(&provider, &call).await?
+    );
+    let tx_result = call.send().await.context("Failed to send delegateRewardsBySig transaction")?;
+    let tx_hash = tx_result.tx_hash();
+    tracing::info!(%tx_hash, "Sent transaction for delegating rewards by signature");
+
+    let timeout = global_config.tx_timeout.or(tx_result.timeout());
+    tracing::debug!(?timeout, %tx_hash, "Waiting for transaction receipt");
+    let tx_receipt = tx_result
+        .with_timeout(timeout)
+        .get_receipt()
+        .await
+        .context("Failed to receive receipt delegating rewards transaction")?;
+
+    ensure!(
+        tx_receipt.status(),
+        "Delegating rewards transaction failed: tx_hash = {}",
+        tx_receipt.transaction_hash
+    );
+
+    tracing::info!("Delegating rewards of {} to {delegatee} completed", signed.delegator);
+    Ok(())
 }
 
 fn print_calldata(deployment: &Deployment, delegatee: Address) {
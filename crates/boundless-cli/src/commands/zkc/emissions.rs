@@ -0,0 +1,108 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::ops::Range;
+
+use alloy::providers::{Provider, ProviderBuilder};
+use anyhow::{ensure, Context};
+use boundless_zkc::{
+    deployments::Deployment,
+    emissions::{fetch_emissions, verify_against_chain},
+    units::{format_zkc, ZKC_DECIMALS},
+};
+use clap::Args;
+
+use crate::config::GlobalConfig;
+
+/// Command to print the ZKC emissions schedule.
+#[non_exhaustive]
+#[derive(Args, Clone, Debug)]
+pub struct ZkcEmissions {
+    /// Range of epochs to print, e.g. `0..100`. The end of the range is exclusive.
+    #[clap(long, value_parser = parse_epoch_range)]
+    pub range: Range<u64>,
+    /// Whether to check the emissions reported by the contract against the ZKC supply schedule.
+    ///
+    /// Exits with an error if any mismatch is found.
+    #[clap(long)]
+    pub verify: bool,
+    /// Difference allowed between the reported and scheduled emissions with --verify, in basis
+    /// points of the scheduled emissions, to absorb rounding.
+    #[clap(long, default_value_t = 1, requires = "verify")]
+    pub tolerance_bps: u64,
+    /// Configuration for the ZKC deployment to use.
+    #[clap(flatten, next_help_heading = "ZKC Deployment")]
+    pub deployment: Option<Deployment>,
+}
+
+impl ZkcEmissions {
+    /// Run the [ZkcEmissions] command.
+    pub async fn run(&self, global_config: &GlobalConfig) -> anyhow::Result<()> {
+        let rpc_url = global_config.require_rpc_url()?;
+
+        // Connect to the chain.
+        let provider = ProviderBuilder::new()
+            .connect(rpc_url.as_str())
+            .await
+            .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
+        let chain_id = provider.get_chain_id().await?;
+        let deployment = global_config.zkc_deployment(self.deployment.as_ref(), chain_id)?;
+
+        let (table, mismatches) = if self.verify {
+            verify_against_chain(
+                provider,
+                deployment.zkc_address,
+                self.range.clone(),
+                self.tolerance_bps,
+            )
+            .await?
+        } else {
+            (fetch_emissions(provider, deployment.zkc_address, self.range.clone()).await?, vec![])
+        };
+
+        println!("{:>8}  {:>24}  {:>24}  {:>24}", "epoch", "total", "povw", "staking");
+        for row in &table {
+            println!(
+                "{:>8}  {:>24}  {:>24}  {:>24}",
+                row.epoch,
+                format_zkc(row.total, ZKC_DECIMALS),
+                format_zkc(row.povw, ZKC_DECIMALS),
+                format_zkc(row.staking, ZKC_DECIMALS)
+            );
+        }
+
+        for mismatch in &mismatches {
+            tracing::error!("{mismatch}");
+        }
+        ensure!(
+            mismatches.is_empty(),
+            "found {} mismatches between the emissions schedule and the contract",
+            mismatches.len()
+        );
+        if self.verify {
+            tracing::info!("Emissions for {} epochs match the supply schedule", table.len());
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse a range of epochs in the form `start..end`.
+fn parse_epoch_range(arg: &str) -> anyhow::Result<Range<u64>> {
+    let (start, end) = arg.split_once("..").context("expected a range such as 0..100")?;
+    let start: u64 = start.trim().parse().context("invalid start epoch")?;
+    let end: u64 = end.trim().parse().context("invalid end epoch")?;
+    ensure!(start < end, "range of epochs must not be empty");
+    Ok(start..end)
+}
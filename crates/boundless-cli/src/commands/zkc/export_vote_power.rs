@@ -0,0 +1,102 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::path::PathBuf;
+
+use alloy::{
+    primitives::U256,
+    providers::{Provider, ProviderBuilder},
+};
+use anyhow::{ensure, Context};
+use boundless_zkc::{
+    deployments::Deployment,
+    units::{format_zkc, ZKC_DECIMALS},
+    vote_power::{deployment_block, vote_power_snapshot},
+};
+use clap::Args;
+
+use crate::config::GlobalConfig;
+
+/// Command to export the veZKC vote power of every address at a block.
+#[non_exhaustive]
+#[derive(Args, Clone, Debug)]
+pub struct ZkcExportVotePower {
+    /// Block at which to compute the vote power.
+    #[clap(long)]
+    pub block: u64,
+    /// First block to scan for vote power changes.
+    ///
+    /// Must be no later than the block the veZKC contract was deployed in. Defaults to that block,
+    /// found by searching for the first block at which the veZKC contract has code.
+    #[clap(long)]
+    pub from_block: Option<u64>,
+    /// Path of the JSON file to write the export to.
+    #[clap(long)]
+    pub out: PathBuf,
+    /// Configuration for the ZKC deployment to use.
+    #[clap(flatten, next_help_heading = "ZKC Deployment")]
+    pub deployment: Option<Deployment>,
+}
+
+impl ZkcExportVotePower {
+    /// Run the [ZkcExportVotePower] command.
+    pub async fn run(&self, global_config: &GlobalConfig) -> anyhow::Result<()> {
+        let rpc_url = global_config.require_rpc_url()?;
+
+        // Connect to the chain.
+        let provider = ProviderBuilder::new()
+            .connect(rpc_url.as_str())
+            .await
+            .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
+        let chain_id = provider.get_chain_id().await?;
+        let deployment = global_config.zkc_deployment(self.deployment.as_ref(), chain_id)?;
+
+        let latest_block = provider.get_block_number().await?;
+        ensure!(
+            self.block <= latest_block,
+            "block {} is after the latest block {latest_block}",
+            self.block
+        );
+
+        let from_block = match self.from_block {
+            Some(from_block) => from_block,
+            None => {
+                let from_block = deployment_block(&provider, deployment.vezkc_address, self.block)
+                    .await
+                    .context("failed to find the veZKC deployment block")?;
+                tracing::debug!("Found veZKC deployment at block {from_block}");
+                from_block
+            }
+        };
+
+        let snapshot =
+            vote_power_snapshot(provider, deployment.vezkc_address, from_block, self.block).await?;
+
+        let json = serde_json::to_string_pretty(&snapshot)?;
+        std::fs::write(&self.out, json)
+            .with_context(|| format!("failed to write export to {}", self.out.display()))?;
+
+        let total: U256 = snapshot.powers.iter().map(|entry| entry.power).sum();
+        tracing::info!(
+            "Exported vote power of {} addresses at block {} to {}",
+            snapshot.powers.len(),
+            snapshot.block,
+            self.out.display()
+        );
+        tracing::info!("Total vote power: {}", format_zkc(total, ZKC_DECIMALS));
+        tracing::info!("Merkle root: {}", snapshot.merkle_root);
+
+        Ok(())
+    }
+}
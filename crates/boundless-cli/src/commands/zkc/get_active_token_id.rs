@@ -47,8 +47,7 @@ impl ZkcGetActiveTokenId {
             .await
             .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
         let chain_id = provider.get_chain_id().await?;
-        let deployment = self.deployment.clone().or_else(|| Deployment::from_chain_id(chain_id))
-            .context("could not determine ZKC deployment from chain ID; please specify deployment explicitly")?;
+        let deployment = global_config.zkc_deployment(self.deployment.as_ref(), chain_id)?;
 
         let token_id =
             get_active_token_id(provider, deployment.vezkc_address, self.account).await?;
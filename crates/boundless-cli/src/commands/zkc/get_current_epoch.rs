@@ -42,8 +42,7 @@ impl ZkcGetCurrentEpoch {
             .await
             .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
         let chain_id = provider.get_chain_id().await?;
-        let deployment = self.deployment.clone().or_else(|| Deployment::from_chain_id(chain_id))
-            .context("could not determine ZKC deployment from chain ID; please specify deployment explicitly")?;
+        let deployment = global_config.zkc_deployment(self.deployment.as_ref(), chain_id)?;
 
         let current_epoch = get_current_epoch(provider, deployment.zkc_address).await?;
         tracing::info!("Current epoch: {}", u32::try_from(current_epoch)?);
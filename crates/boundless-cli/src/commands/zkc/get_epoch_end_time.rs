@@ -45,8 +45,7 @@ impl ZkcGetEpochEndTime {
             .await
             .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
         let chain_id = provider.get_chain_id().await?;
-        let deployment = self.deployment.clone().or_else(|| Deployment::from_chain_id(chain_id))
-            .context("could not determine ZKC deployment from chain ID; please specify deployment explicitly")?;
+        let deployment = global_config.zkc_deployment(self.deployment.as_ref(), chain_id)?;
 
         let end_time = get_epoch_end_time(provider, deployment.zkc_address, self.epoch).await?;
         let datetime = DateTime::from_timestamp(u64::try_from(end_time)? as i64, 0)
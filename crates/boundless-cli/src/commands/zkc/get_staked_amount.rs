@@ -13,13 +13,14 @@
 // limitations under the License.
 
 use alloy::{
-    primitives::{utils::format_ether, Address, U256},
+    primitives::{Address, U256},
     providers::{Provider, ProviderBuilder},
 };
 use anyhow::Context;
 use boundless_zkc::{
     contracts::{DecodeRevert, IStaking},
     deployments::Deployment,
+    units::{format_zkc, ZKC_DECIMALS},
 };
 use chrono::DateTime;
 use clap::Args;
@@ -48,14 +49,13 @@ impl ZkcGetStakedAmount {
             .await
             .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
         let chain_id = provider.get_chain_id().await?;
-        let deployment = self.deployment.clone().or_else(|| Deployment::from_chain_id(chain_id))
-            .context("could not determine ZKC deployment from chain ID; please specify deployment explicitly")?;
+        let deployment = global_config.zkc_deployment(self.deployment.as_ref(), chain_id)?;
 
         let (amount, withdrawable_at) =
             get_staked_amount(provider, deployment.vezkc_address, self.account).await?;
 
         let withdrawable_at: u64 = withdrawable_at.try_into()?;
-        tracing::info!("Staked amount: {} ZKC", format_ether(amount));
+        tracing::info!("Staked amount: {} ZKC", format_zkc(amount, ZKC_DECIMALS));
         if withdrawable_at == 0 {
             tracing::info!("Not withdrawable");
         } else {
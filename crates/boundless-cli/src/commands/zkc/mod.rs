@@ -14,29 +14,39 @@
 
 //! Commands of the Boundless CLI for ZKC operations.
 
+mod audit_allowances;
 mod balance_of;
 mod calculate_rewards;
 mod claim_rewards;
 mod delegate_rewards;
+mod emissions;
+mod export_vote_power;
 mod get_active_token_id;
 mod get_current_epoch;
 mod get_epoch_end_time;
 mod get_rewards_delegates;
 mod get_staked_amount;
 mod stake;
+mod tx_cost;
 mod unstake;
+mod withdrawal_status;
 
+pub use audit_allowances::{allowances, Allowance, ZkcAuditAllowances};
 pub use balance_of::{balance_of, ZkcBalance};
 pub use calculate_rewards::{calculate_rewards, ZkcCalculateRewards};
-pub use claim_rewards::{claim_rewards, ZkcClaimRewards};
+pub use claim_rewards::{claim_rewards, claim_rewards_to, ZkcClaimRewards};
 pub use delegate_rewards::ZkcDelegateRewards;
+pub use emissions::ZkcEmissions;
+pub use export_vote_power::ZkcExportVotePower;
 pub use get_active_token_id::{get_active_token_id, ZkcGetActiveTokenId};
 pub use get_current_epoch::{get_current_epoch, ZkcGetCurrentEpoch};
 pub use get_epoch_end_time::{get_epoch_end_time, ZkcGetEpochEndTime};
 pub use get_rewards_delegates::{get_rewards_delegates, ZkcGetRewardsDelegates};
 pub use get_staked_amount::{get_staked_amount, ZkcGetStakedAmount};
 pub use stake::ZkcStake;
+pub use tx_cost::{estimate_and_preview, TxCostPreview};
 pub use unstake::ZkcUnstake;
+pub use withdrawal_status::{withdrawal_status, WithdrawalStatus, ZkcWithdrawalStatus};
 
 use clap::Subcommand;
 
@@ -67,6 +77,14 @@ pub enum ZKCCommands {
     ClaimRewards(ZkcClaimRewards),
     /// Get rewards delegates for a specified address.
     GetRewardsDelegates(ZkcGetRewardsDelegates),
+    /// Export the vote power of every address at a block, for off-chain voting.
+    ExportVotePower(ZkcExportVotePower),
+    /// Print the emissions schedule for a range of epochs.
+    Emissions(ZkcEmissions),
+    /// Audit the allowances granted on the ZKC and collateral tokens, and optionally revoke them.
+    AuditAllowances(ZkcAuditAllowances),
+    /// Report whether the withdrawal of an address is pending, ready, or overdue.
+    WithdrawalStatus(ZkcWithdrawalStatus),
 }
 
 impl ZKCCommands {
@@ -84,6 +102,10 @@ impl ZKCCommands {
             Self::CalculateRewards(cmd) => cmd.run(global_config).await,
             Self::ClaimRewards(cmd) => cmd.run(global_config).await,
             Self::GetRewardsDelegates(cmd) => cmd.run(global_config).await,
+            Self::ExportVotePower(cmd) => cmd.run(global_config).await,
+            Self::Emissions(cmd) => cmd.run(global_config).await,
+            Self::AuditAllowances(cmd) => cmd.run(global_config).await,
+            Self::WithdrawalStatus(cmd) => cmd.run(global_config).await,
         }
     }
 }
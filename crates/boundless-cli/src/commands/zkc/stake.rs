@@ -15,12 +15,10 @@
 use std::io::{self, Write};
 
 use alloy::{
+    contract::{CallBuilder, CallDecoder},
     eips::BlockId,
     network::Ethereum,
-    primitives::{
-        utils::{format_ether, parse_units},
-        Address, B256, U256,
-    },
+    primitives::{Address, B256, U256},
     providers::{PendingTransactionBuilder, Provider, ProviderBuilder},
     signers::Signer,
     sol_types::SolCall,
@@ -30,10 +28,14 @@ use boundless_market::contracts::token::{IERC20Permit, Permit, IERC20};
 use boundless_zkc::{
     contracts::{extract_tx_log, DecodeRevert, IStaking},
     deployments::Deployment,
+    units::{format_zkc, parse_zkc, ZKC_DECIMALS},
 };
 use clap::Args;
 
-use crate::{commands::zkc::get_active_token_id, config::GlobalConfig};
+use crate::{
+    commands::zkc::{estimate_and_preview, get_active_token_id},
+    config::GlobalConfig,
+};
 
 /// Command to stake ZKC.
 #[non_exhaustive]
@@ -75,8 +77,7 @@ impl ZkcStake {
             .await
             .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
         let chain_id = provider.get_chain_id().await?;
-        let deployment = self.deployment.clone().or_else(|| Deployment::from_chain_id(chain_id))
-            .context("could not determine ZKC deployment from chain ID; please specify deployment explicitly")?;
+        let deployment = global_config.zkc_deployment(self.deployment.as_ref(), chain_id)?;
 
         let account = match &self.from {
             Some(addr) => *addr,
@@ -87,9 +88,7 @@ impl ZkcStake {
             get_active_token_id(provider.clone(), deployment.vezkc_address, account).await?;
         let add = !token_id.is_zero();
 
-        let parsed_amount = parse_units(&self.amount, 18)
-            .map_err(|e| anyhow!("Failed to parse ZKC amount: {}", e))?
-            .into();
+        let parsed_amount = parse_zkc(&self.amount).context("Failed to parse ZKC amount")?;
         if parsed_amount == U256::from(0) {
             bail!("Amount is below the denomination minimum: {}", self.amount);
         }
@@ -105,22 +104,6 @@ impl ZkcStake {
             .await
             .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
 
-        if !add {
-            println!(
-                "You're creating a new ZKC stake position. This will lock {} ZKC for 30 days.",
-                format_ether(parsed_amount)
-            );
-            print!("Type 'yes' to confirm and continue: ");
-            io::stdout().flush().ok();
-            let mut input = String::new();
-            io::stdin()
-                .read_line(&mut input)
-                .map_err(|e| anyhow!("failed to read confirmation: {}", e))?;
-            if input.trim().to_lowercase() != "yes" {
-                bail!("Stake cancelled by user");
-            }
-        }
-
         let pending_tx = match self.no_permit {
             false => {
                 self.stake_with_permit(
@@ -134,7 +117,7 @@ impl ZkcStake {
                 .await?
             }
             true => self
-                .stake(provider, deployment, parsed_amount, add)
+                .stake(provider, deployment, parsed_amount, tx_signer.address(), add)
                 .await
                 .context("Sending stake transaction failed")?,
         };
@@ -170,8 +153,8 @@ impl ZkcStake {
                 };
             tracing::info!(
                 "Staking completed: token_id = {token_id}, owner = {owner}, amount added = {} ZKC, new total = {} ZKC",
-                format_ether(amount_added),
-                format_ether(new_total)
+                format_zkc(amount_added, ZKC_DECIMALS),
+                format_zkc(new_total, ZKC_DECIMALS)
             );
         } else {
             let (token_id, owner, amount) =
@@ -183,7 +166,7 @@ impl ZkcStake {
                 };
             tracing::info!(
                 "Staking completed: token_id = {token_id}, owner = {owner}, amount = {} ZKC",
-                format_ether(amount)
+                format_zkc(amount, ZKC_DECIMALS)
             );
         }
         Ok(())
@@ -211,22 +194,60 @@ impl ZkcStake {
         Ok(())
     }
 
+    /// Preview the cost of the staking call, and get explicit confirmation if it creates a new
+    /// stake position.
+    async fn confirm<P: Provider, D: CallDecoder>(
+        &self,
+        provider: &impl Provider,
+        call: &CallBuilder<P, D>,
+        value: U256,
+        add: bool,
+    ) -> anyhow::Result<()> {
+        let preview =
+            estimate_and_preview::<IStaking::IStakingErrors, _, _>(provider, call).await?;
+        if add {
+            println!("{preview}");
+            return Ok(());
+        }
+
+        println!(
+            "You're creating a new ZKC stake position. This will lock {} ZKC for 30 days.",
+            format_zkc(value, ZKC_DECIMALS)
+        );
+        println!("{preview}");
+        print!("Type 'yes' to confirm and continue: ");
+        io::stdout().flush().ok();
+        let mut input = String::new();
+        io::stdin()
+            .read_line(&mut input)
+            .map_err(|e| anyhow!("failed to read confirmation: {}", e))?;
+        if input.trim().to_lowercase() != "yes" {
+            bail!("Stake cancelled by user");
+        }
+        Ok(())
+    }
+
     async fn stake(
         &self,
         provider: impl Provider + Clone,
         deployment: Deployment,
         value: U256,
+        account: Address,
         add: bool,
     ) -> Result<PendingTransactionBuilder<Ethereum>, anyhow::Error> {
-        let staking = IStaking::new(deployment.vezkc_address, provider);
+        let staking = IStaking::new(deployment.vezkc_address, provider.clone());
         let send_result = match add {
             false => {
+                let call = staking.stake(value).from(account);
+                self.confirm(&provider, &call, value, add).await?;
                 tracing::trace!("Calling stake({})", value);
-                staking.stake(value).send().await
+                call.send().await
             }
             true => {
+                let call = staking.addToStake(value).from(account);
+                self.confirm(&provider, &call, value, add).await?;
                 tracing::trace!("Calling addToStake({})", value);
-                staking.addToStake(value).send().await
+                call.send().await
             }
         };
         send_result
@@ -266,15 +287,19 @@ impl ZkcStake {
         let s = B256::from_slice(&sig[32..64]);
         let v: u8 = sig[64];
 
-        let staking = IStaking::new(deployment.vezkc_address, provider);
+        let staking = IStaking::new(deployment.vezkc_address, provider.clone());
         let send_result = match add {
             false => {
+                let call = staking.stakeWithPermit(value, deadline, v, r, s).from(owner);
+                self.confirm(&provider, &call, value, add).await?;
                 tracing::trace!("Calling stakeWithPermit({})", value);
-                staking.stakeWithPermit(value, deadline, v, r, s).send().await
+                call.send().await
             }
             true => {
+                let call = staking.addToStakeWithPermit(value, deadline, v, r, s).from(owner);
+                self.confirm(&provider, &call, value, add).await?;
                 tracing::trace!("Calling addToStakeWithPermit({})", value);
-                staking.addToStakeWithPermit(value, deadline, v, r, s).send().await
+                call.send().await
             }
         };
         send_result
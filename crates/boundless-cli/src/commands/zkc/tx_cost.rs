@@ -0,0 +1,75 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{self, Debug};
+
+use alloy::{
+    contract::{CallBuilder, CallDecoder},
+    primitives::{
+        utils::{format_ether, format_units},
+        U256,
+    },
+    providers::Provider,
+    sol_types::SolInterface,
+};
+use anyhow::Context;
+use boundless_zkc::contracts::DecodeRevert;
+
+/// Expected cost of a transaction, previewed before sending it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TxCostPreview {
+    /// Gas the transaction is estimated to use.
+    pub gas: u64,
+    /// Max fee per gas the transaction is sent with, in wei.
+    pub max_fee_per_gas: u128,
+}
+
+impl TxCostPreview {
+    /// Max cost of the transaction in wei, paying the max fee for all the estimated gas.
+    pub fn max_cost(&self) -> U256 {
+        U256::from(self.gas) * U256::from(self.max_fee_per_gas)
+    }
+}
+
+impl fmt::Display for TxCostPreview {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let max_fee_gwei = format_units(self.max_fee_per_gas, "gwei").map_err(|_| fmt::Error)?;
+        write!(
+            f,
+            "Estimated cost: up to {} ETH ({} gas at up to {max_fee_gwei} gwei)",
+            format_ether(self.max_cost()),
+            self.gas
+        )
+    }
+}
+
+/// Estimate the gas used by a contract call and fetch the current fees, to preview the cost of
+/// sending it.
+///
+/// Fails if the call reverts, with the revert decoded as an error of the contract interface `E`
+/// when possible.
+pub async fn estimate_and_preview<E, P, D>(
+    provider: &impl Provider,
+    call: &CallBuilder<P, D>,
+) -> anyhow::Result<TxCostPreview>
+where
+    E: SolInterface + Debug,
+    P: Provider,
+    D: CallDecoder,
+{
+    let gas =
+        call.estimate_gas().await.maybe_decode_revert::<E>().context("Failed to estimate gas")?;
+    let fees = provider.estimate_eip1559_fees().await.context("Failed to fetch current fees")?;
+    Ok(TxCostPreview { gas, max_fee_per_gas: fees.max_fee_per_gas })
+}
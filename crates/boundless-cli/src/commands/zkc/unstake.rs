@@ -17,21 +17,22 @@ use std::io::{self, Write};
 use alloy::{
     consensus::BlockHeader,
     eips::BlockNumberOrTag,
-    network::Ethereum,
-    primitives::{utils::format_ether, Address, U256},
-    providers::{PendingTransactionBuilder, Provider, ProviderBuilder},
+    primitives::{Address, U256},
+    providers::{Provider, ProviderBuilder},
     sol_types::SolCall,
 };
 use anyhow::{ensure, Context};
 use boundless_zkc::{
     contracts::{DecodeRevert, IStaking},
     deployments::Deployment,
+    units::{format_zkc, ZKC_DECIMALS},
+    unstake::preview_unstake,
 };
 use chrono::DateTime;
 use clap::Args;
 
 use crate::{
-    commands::zkc::{get_active_token_id, get_staked_amount},
+    commands::zkc::{estimate_and_preview, get_active_token_id, get_staked_amount},
     config::GlobalConfig,
 };
 
@@ -40,12 +41,15 @@ use crate::{
 #[derive(Args, Clone, Debug)]
 pub struct ZkcUnstake {
     /// Whether to only print the calldata without sending the transaction.
-    #[clap(long)]
+    #[clap(long, group = "read_only")]
     pub calldata: bool,
+    /// Whether to only preview the rewards forgone by unstaking without sending a transaction.
+    #[clap(long, group = "read_only")]
+    pub preview: bool,
     /// The account address to unstake from.
     ///
-    /// Only valid when used with `--calldata`.
-    #[clap(long, requires = "calldata")]
+    /// Only valid when used with `--calldata` or `--preview`.
+    #[clap(long, requires = "read_only")]
     pub from: Option<Address>,
     /// Configuration for the ZKC deployment to use.
     #[clap(flatten, next_help_heading = "ZKC Deployment")]
@@ -63,8 +67,7 @@ impl ZkcUnstake {
             .await
             .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
         let chain_id = provider.get_chain_id().await?;
-        let deployment = self.deployment.clone().or_else(|| Deployment::from_chain_id(chain_id))
-            .context("could not determine ZKC deployment from chain ID; please specify deployment explicitly")?;
+        let deployment = global_config.zkc_deployment(self.deployment.as_ref(), chain_id)?;
 
         let account = match &self.from {
             Some(addr) => *addr,
@@ -87,6 +90,11 @@ impl ZkcUnstake {
         if self.calldata {
             return self.print_calldata(provider, deployment, withdrawable_at).await;
         }
+        if self.preview {
+            return self
+                .print_preview(provider, deployment, account, amount, withdrawable_at)
+                .await;
+        }
 
         let tx_signer = global_config.require_private_key()?;
         let provider = ProviderBuilder::new()
@@ -95,19 +103,24 @@ impl ZkcUnstake {
             .await
             .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
         let chain_id = provider.get_chain_id().await?;
-        let deployment = self.deployment.clone().or_else(|| Deployment::from_chain_id(chain_id))
-            .context("could not determine ZKC deployment from chain ID; please specify deployment explicitly")?;
+        let deployment = global_config.zkc_deployment(self.deployment.as_ref(), chain_id)?;
+        let staking = IStaking::new(deployment.vezkc_address, provider.clone());
 
         let send_result = if withdrawable_at.is_zero() {
+            let call = staking.initiateUnstake().from(tx_signer.address());
+            let preview =
+                estimate_and_preview::<IStaking::IStakingErrors, _, _>(&provider, &call).await?;
+
             // Explain what initiating an unstake does and get explicit confirmation.
             println!(
                 "You're about to initiate unstaking of your active ZKC position ({} ZKC).",
-                format_ether(amount)
+                format_zkc(amount, ZKC_DECIMALS)
             );
             println!(
                 "- This starts a 30-day cooldown. After it ends, you can complete the unstake process and withdraw your tokens."
             );
             println!("- Your staking position will close immediately: you'll lose rewards and voting power and stop earning rewards until you open a new position.");
+            println!("{preview}");
             print!("Type 'yes' to confirm and continue: ");
             io::stdout().flush().ok();
             let mut input = String::new();
@@ -117,7 +130,7 @@ impl ZkcUnstake {
             if input.trim().to_lowercase() != "yes" {
                 anyhow::bail!("Unstake cancelled by user");
             }
-            self.initiate_unstake(provider.clone(), deployment).await
+            call.send().await
         } else {
             let block_timestamp = get_block_timestamp(provider.clone()).await?;
             let withdrawable_at = u64::try_from(withdrawable_at)?;
@@ -129,7 +142,12 @@ impl ZkcUnstake {
                     datetime.format("%Y-%m-%d %H:%M:%S")
                 );
             }
-            self.complete_unstake(provider.clone(), deployment).await
+            let call = staking.completeUnstake().from(tx_signer.address());
+            println!(
+                "{}",
+                estimate_and_preview::<IStaking::IStakingErrors, _, _>(&provider, &call).await?
+            );
+            call.send().await
         };
         let pending_tx = send_result.maybe_decode_revert::<IStaking::IStakingErrors>()?;
 
@@ -188,22 +206,59 @@ impl ZkcUnstake {
         Ok(())
     }
 
-    async fn initiate_unstake(
+    async fn print_preview(
         &self,
         provider: impl Provider + Clone,
         deployment: Deployment,
-    ) -> alloy::contract::Result<PendingTransactionBuilder<Ethereum>, alloy::contract::Error> {
-        let staking = IStaking::new(deployment.vezkc_address, provider);
-        staking.initiateUnstake().send().await
-    }
+        account: Address,
+        amount: U256,
+        withdrawable_at: U256,
+    ) -> anyhow::Result<()> {
+        if !withdrawable_at.is_zero() {
+            let datetime = DateTime::from_timestamp(u64::try_from(withdrawable_at)? as i64, 0)
+                .context("failed to create DateTime")?;
+            println!(
+                "Unstaking already initiated. Withdrawal period ends at UTC: {}",
+                datetime.format("%Y-%m-%d %H:%M:%S")
+            );
+            return Ok(());
+        }
 
-    async fn complete_unstake(
-        &self,
-        provider: impl Provider + Clone,
-        deployment: Deployment,
-    ) -> alloy::contract::Result<PendingTransactionBuilder<Ethereum>, alloy::contract::Error> {
-        let staking = IStaking::new(deployment.vezkc_address, provider);
-        staking.completeUnstake().send().await
+        let block_timestamp = get_block_timestamp(provider.clone()).await?;
+        let preview = preview_unstake(
+            provider,
+            deployment.zkc_address,
+            deployment.vezkc_address,
+            account,
+            block_timestamp,
+        )
+        .await?;
+        let cooldown_end = DateTime::from_timestamp(preview.cooldown_end as i64, 0)
+            .context("failed to create DateTime")?;
+
+        println!("========= Unstake Preview =========");
+        println!("Staked amount: {} ZKC", format_zkc(amount, ZKC_DECIMALS));
+        println!("Withdrawal period ends at UTC: {}", cooldown_end.format("%Y-%m-%d %H:%M:%S"));
+        println!(
+            "Reward power: {} of {}",
+            format_zkc(preview.reward_power, ZKC_DECIMALS),
+            format_zkc(preview.total_reward_power, ZKC_DECIMALS)
+        );
+        for epoch in &preview.epochs {
+            println!(
+                "Epoch {}: {} ZKC of {} ZKC emissions",
+                epoch.epoch,
+                format_zkc(epoch.rewards, ZKC_DECIMALS),
+                format_zkc(epoch.emissions, ZKC_DECIMALS)
+            );
+        }
+        println!(
+            "Estimated rewards forgone: {} ZKC",
+            format_zkc(preview.forgone_rewards(), ZKC_DECIMALS)
+        );
+        println!("Penalties: none; reward and voting power are lost as soon as unstaking starts");
+        println!("===================================");
+        Ok(())
     }
 }
 
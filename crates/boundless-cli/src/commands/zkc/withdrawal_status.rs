@@ -0,0 +1,163 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+
+use alloy::{
+    consensus::BlockHeader,
+    eips::BlockNumberOrTag,
+    primitives::{Address, U256},
+    providers::{Provider, ProviderBuilder},
+};
+use anyhow::Context;
+use boundless_zkc::{
+    deployments::Deployment,
+    units::{format_zkc, ZKC_DECIMALS},
+};
+use clap::Args;
+
+use crate::{commands::zkc::get_staked_amount, config::GlobalConfig, convert_timestamp};
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+
+/// Command to report the status of the ZKC withdrawal of an address.
+#[non_exhaustive]
+#[derive(Args, Clone, Debug)]
+pub struct ZkcWithdrawalStatus {
+    /// Address to report the withdrawal status of.
+    pub account: Address,
+    /// Number of days a withdrawal can stay ready without being completed before it is reported
+    /// as overdue.
+    #[clap(long, default_value_t = 7)]
+    pub overdue_days: u64,
+    /// Configuration for the ZKC deployment to use.
+    #[clap(flatten, next_help_heading = "ZKC Deployment")]
+    pub deployment: Option<Deployment>,
+}
+
+/// Status of the withdrawal of a staking position.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WithdrawalStatus {
+    /// No unstake was initiated.
+    NotInitiated,
+    /// The withdrawal period has not ended yet.
+    Pending {
+        /// UNIX timestamp at which the withdrawal period ends.
+        withdrawable_at: u64,
+    },
+    /// The withdrawal period has ended, and the unstake can be completed.
+    Ready {
+        /// UNIX timestamp at which the withdrawal period ended.
+        withdrawable_at: u64,
+    },
+    /// The withdrawal period ended longer ago than the overdue threshold, and the unstake was
+    /// still not completed.
+    Overdue {
+        /// UNIX timestamp at which the withdrawal period ended.
+        withdrawable_at: u64,
+        /// Number of full days since the withdrawal period ended.
+        days_ready: u64,
+    },
+}
+
+impl WithdrawalStatus {
+    /// Classify a withdrawal given its withdrawable timestamp, as reported by the staking
+    /// contract, the current block timestamp, and the overdue threshold in seconds.
+    pub fn new(withdrawable_at: u64, now: u64, overdue_after_secs: u64) -> Self {
+        if withdrawable_at == 0 {
+            return Self::NotInitiated;
+        }
+        if now < withdrawable_at {
+            return Self::Pending { withdrawable_at };
+        }
+        let ready_for = now - withdrawable_at;
+        if ready_for > overdue_after_secs {
+            Self::Overdue { withdrawable_at, days_ready: ready_for / SECONDS_PER_DAY }
+        } else {
+            Self::Ready { withdrawable_at }
+        }
+    }
+}
+
+impl fmt::Display for WithdrawalStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotInitiated => write!(f, "no withdrawal initiated"),
+            Self::Pending { withdrawable_at } => {
+                write!(f, "pending, withdrawable from {}", convert_timestamp(*withdrawable_at))
+            }
+            Self::Ready { withdrawable_at } => {
+                write!(
+                    f,
+                    "ready, withdrawable since {}; run `boundless zkc unstake` to complete it",
+                    convert_timestamp(*withdrawable_at)
+                )
+            }
+            Self::Overdue { withdrawable_at, days_ready } => {
+                write!(
+                    f,
+                    "overdue, withdrawable since {} ({days_ready} days) but never completed; run `boundless zkc unstake` to complete it",
+                    convert_timestamp(*withdrawable_at)
+                )
+            }
+        }
+    }
+}
+
+impl ZkcWithdrawalStatus {
+    /// Run the [ZkcWithdrawalStatus] command.
+    pub async fn run(&self, global_config: &GlobalConfig) -> anyhow::Result<()> {
+        let rpc_url = global_config.require_rpc_url()?;
+
+        // Connect to the chain.
+        let provider = ProviderBuilder::new()
+            .connect(rpc_url.as_str())
+            .await
+            .with_context(|| format!("failed to connect provider to {rpc_url}"))?;
+        let chain_id = provider.get_chain_id().await?;
+        let deployment = global_config.zkc_deployment(self.deployment.as_ref(), chain_id)?;
+
+        let (amount, status) = withdrawal_status(
+            provider,
+            deployment.vezkc_address,
+            self.account,
+            self.overdue_days.saturating_mul(SECONDS_PER_DAY),
+        )
+        .await?;
+
+        tracing::info!("Staked amount: {} ZKC", format_zkc(amount, ZKC_DECIMALS));
+        tracing::info!("Withdrawal status: {status}");
+        Ok(())
+    }
+}
+
+/// Get the staked amount and the withdrawal status of the given account, as of the latest block.
+pub async fn withdrawal_status(
+    provider: impl Provider + Clone,
+    staking_address: Address,
+    account: Address,
+    overdue_after_secs: u64,
+) -> anyhow::Result<(U256, WithdrawalStatus)> {
+    let (amount, withdrawable_at) =
+        get_staked_amount(provider.clone(), staking_address, account).await?;
+    let now = provider
+        .get_block_by_number(BlockNumberOrTag::Latest)
+        .await
+        .context("failed to get block")?
+        .context("failed to get block")?
+        .header
+        .timestamp();
+    let status = WithdrawalStatus::new(u64::try_from(withdrawable_at)?, now, overdue_after_secs);
+    Ok((amount, status))
+}
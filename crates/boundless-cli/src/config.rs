@@ -14,17 +14,19 @@
 
 //! Common configuration options for commands in the Boundless CLI.
 
-use std::{num::ParseIntError, time::Duration};
+use std::{collections::HashMap, num::ParseIntError, path::Path, str::FromStr, time::Duration};
 
-use alloy::{providers::DynProvider, signers::local::PrivateKeySigner};
-use anyhow::{Context, Result};
+use alloy::{primitives::Address, providers::DynProvider, signers::local::PrivateKeySigner};
+use anyhow::{bail, Context, Result};
 use clap::Args;
 use risc0_zkvm::ProverOpts;
+use serde::Deserialize;
 use tracing::level_filters::LevelFilter;
 use url::Url;
 
 use boundless_market::{
-    client::ClientBuilder, request_builder::StandardRequestBuilder, Client, Deployment, NotProvided,
+    client::ClientBuilder, deployments::NamedChain, request_builder::StandardRequestBuilder,
+    Client, Deployment, NotProvided,
 };
 
 /// Common configuration options for all commands
@@ -46,6 +48,14 @@ pub struct GlobalConfig {
     #[clap(long, env = "LOG_LEVEL", global = true, default_value = "info")]
     pub log_level: LevelFilter,
 
+    /// Named deployment preset to use for the market, PoVW, and ZKC contracts.
+    ///
+    /// One of: mainnet, sepolia, base, base-sepolia, or local for the Anvil network started with
+    /// `just localnet`. Can also be the path to a YAML file defining a preset. Addresses provided
+    /// explicitly take precedence over the preset.
+    #[clap(long = "deployment", env = "BOUNDLESS_DEPLOYMENT", global = true)]
+    pub deployment_preset: Option<DeploymentPreset>,
+
     /// Configuration for the Boundless deployment to use.
     #[clap(flatten, next_help_heading = "Boundless Deployment")]
     pub deployment: Option<Deployment>,
@@ -76,10 +86,78 @@ impl GlobalConfig {
     pub fn client_builder(&self) -> Result<ClientBuilder> {
         Ok(Client::builder()
             .with_rpc_url(self.require_rpc_url()?)
-            .with_deployment(self.deployment.clone())
+            .with_deployment(self.market_deployment())
             .with_timeout(self.tx_timeout))
     }
 
+    /// The Boundless market [Deployment] given explicitly or by the deployment preset, if any.
+    ///
+    /// When neither is set, the deployment is determined from the chain ID by the client.
+    pub fn market_deployment(&self) -> Option<Deployment> {
+        self.deployment
+            .clone()
+            .or_else(|| self.deployment_preset.as_ref().and_then(|preset| preset.market.clone()))
+    }
+
+    /// Resolve the PoVW deployment to use on the given chain.
+    ///
+    /// Uses the explicitly provided deployment if set, then the deployment preset, then the
+    /// known deployment for the chain ID.
+    pub fn povw_deployment(
+        &self,
+        explicit: Option<&boundless_povw::deployments::Deployment>,
+        chain_id: u64,
+    ) -> Result<boundless_povw::deployments::Deployment> {
+        if let Some(deployment) = explicit {
+            return Ok(deployment.clone());
+        }
+        match self.preset_for_chain(chain_id)? {
+            Some(preset) => preset.povw.clone().with_context(|| {
+                format!("deployment preset {} has no PoVW deployment", preset.name)
+            }),
+            None => boundless_povw::deployments::Deployment::from_chain_id(chain_id).context(
+                "could not determine deployment from chain ID; please specify deployment explicitly",
+            ),
+        }
+    }
+
+    /// Resolve the ZKC deployment to use on the given chain.
+    ///
+    /// Uses the explicitly provided deployment if set, then the deployment preset, then the
+    /// known deployment for the chain ID.
+    pub fn zkc_deployment(
+        &self,
+        explicit: Option<&boundless_zkc::deployments::Deployment>,
+        chain_id: u64,
+    ) -> Result<boundless_zkc::deployments::Deployment> {
+        if let Some(deployment) = explicit {
+            return Ok(deployment.clone());
+        }
+        match self.preset_for_chain(chain_id)? {
+            Some(preset) => preset.zkc.clone().with_context(|| {
+                format!("deployment preset {} has no ZKC deployment", preset.name)
+            }),
+            None => boundless_zkc::deployments::Deployment::from_chain_id(chain_id).context(
+                "could not determine ZKC deployment from chain ID; please specify deployment explicitly",
+            ),
+        }
+    }
+
+    /// Access [Self::deployment_preset], checking that it matches the connected chain.
+    fn preset_for_chain(&self, chain_id: u64) -> Result<Option<&DeploymentPreset>> {
+        let Some(preset) = self.deployment_preset.as_ref() else {
+            return Ok(None);
+        };
+        if preset.chain_id != chain_id {
+            bail!(
+                "deployment preset {} is for chain ID {}, but the RPC URL is for chain ID {chain_id}",
+                preset.name,
+                preset.chain_id
+            );
+        }
+        Ok(Some(preset))
+    }
+
     /// Create a parially initialzed [ClientBuilder] from the options in this struct.
     ///
     /// Requures [Self::rpc_url] and [Self::private_key] to be set.
@@ -122,6 +200,258 @@ impl GlobalConfig {
     }
 }
 
+/// A named preset of the Boundless market, PoVW, and ZKC deployments on a single chain.
+///
+/// Presets for public networks are built from the deployments known to each of the market, PoVW,
+/// and ZKC crates, so that all three resolve addresses for the same chain. A subsystem is `None`
+/// when it is not deployed on the chain of the preset.
+///
+/// The `local` preset is read from the `.env.localnet` file written by `just localnet`, and other
+/// presets can be loaded from a YAML file such as the following, where each subsystem is optional.
+///
+/// ```yaml
+/// name: staging
+/// chain_id: 11155111
+/// market:
+///   boundless_market_address: "0x..."
+///   set_verifier_address: "0x..."
+///   verifier_router_address: "0x..."
+///   collateral_token_address: "0x..."
+///   order_stream_url: "https://..."
+/// povw:
+///   povw_accounting_address: "0x..."
+///   povw_mint_address: "0x..."
+///   zkc_address: "0x..."
+///   vezkc_address: "0x..."
+/// zkc:
+///   zkc_address: "0x..."
+///   vezkc_address: "0x..."
+///   staking_rewards_address: "0x..."
+/// ```
+#[derive(Clone, Debug)]
+pub struct DeploymentPreset {
+    /// Name of the preset, as given to `--deployment`, or as defined in the preset file.
+    pub name: String,
+    /// EIP-155 chain ID of the network.
+    pub chain_id: u64,
+    /// Boundless market deployment.
+    pub market: Option<Deployment>,
+    /// PoVW deployment.
+    pub povw: Option<boundless_povw::deployments::Deployment>,
+    /// ZKC deployment.
+    pub zkc: Option<boundless_zkc::deployments::Deployment>,
+}
+
+impl DeploymentPreset {
+    /// Names of the presets for public networks, along with the chain for each.
+    pub const PRESETS: [(&str, NamedChain); 4] = [
+        ("mainnet", NamedChain::Mainnet),
+        ("sepolia", NamedChain::Sepolia),
+        ("base", NamedChain::Base),
+        ("base-sepolia", NamedChain::BaseSepolia),
+    ];
+
+    /// Name of the preset for the local Anvil network started with `just localnet`.
+    pub const LOCAL: &str = "local";
+
+    /// Path of the file to which `just localnet` writes the addresses of the local network.
+    pub const LOCALNET_ENV_FILE: &str = ".env.localnet";
+
+    /// Lookup the preset of a public network by name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let (name, chain) = Self::PRESETS.into_iter().find(|(preset, _)| *preset == name)?;
+        Some(Self {
+            name: name.to_string(),
+            chain_id: chain as u64,
+            market: Deployment::from_chain(chain),
+            povw: boundless_povw::deployments::Deployment::from_chain(chain),
+            zkc: boundless_zkc::deployments::Deployment::from_chain(chain),
+        })
+    }
+
+    /// Load the preset of the local Anvil network from the environment file written by
+    /// `just localnet`.
+    ///
+    /// Only the market is deployed on the local network, so the PoVW and ZKC deployments are
+    /// `None`.
+    pub fn local(env_file: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(env_file).with_context(|| {
+            format!(
+                "failed to read {}; start the local network with `just localnet` first",
+                env_file.display()
+            )
+        })?;
+        let vars = contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.starts_with('#'))
+            .filter_map(|line| line.trim_start_matches("export ").split_once('='))
+            .map(|(key, value)| (key.trim(), value.trim().trim_matches('"')))
+            .filter(|(_, value)| !value.is_empty())
+            .collect::<HashMap<_, _>>();
+        let address = |key: &str| -> Result<Option<Address>> {
+            vars.get(key)
+                .map(|value| value.parse::<Address>())
+                .transpose()
+                .with_context(|| format!("invalid {key} in {}", env_file.display()))
+        };
+        let require = |key: &str| -> Result<Address> {
+            address(key)?.with_context(|| {
+                format!(
+                    "{key} is not set in {}; start the local network with `just localnet` first",
+                    env_file.display()
+                )
+            })
+        };
+
+        let chain_id = NamedChain::AnvilHardhat as u64;
+        let mut market = Deployment::builder();
+        market
+            .chain_id(chain_id)
+            .boundless_market_address(require("BOUNDLESS_MARKET_ADDRESS")?)
+            .set_verifier_address(require("SET_VERIFIER_ADDRESS")?);
+        if let Some(verifier) = address("VERIFIER_ADDRESS")? {
+            market.verifier_router_address(verifier);
+        }
+        if let Some(collateral) = address("HIT_POINTS_ADDRESS")? {
+            market.collateral_token_address(collateral);
+        }
+        if let Some(order_stream_url) = vars.get("ORDER_STREAM_URL") {
+            market.order_stream_url(order_stream_url.to_string());
+        }
+        Ok(Self {
+            name: Self::LOCAL.to_string(),
+            chain_id,
+            market: Some(market.build()?),
+            povw: None,
+            zkc: None,
+        })
+    }
+
+    /// Load a preset from a YAML file. The name of the preset defaults to the file name, without
+    /// its extension.
+    pub fn from_file(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read deployment preset {}", path.display()))?;
+        let file: PresetFile = serde_yaml::from_str(&contents)
+            .with_context(|| format!("failed to parse deployment preset {}", path.display()))?;
+        let name = match file.name {
+            Some(name) => name,
+            None => path
+                .file_stem()
+                .context("deployment preset file has no name")?
+                .to_string_lossy()
+                .into_owned(),
+        };
+
+        let market = match file.market {
+            Some(market) => {
+                let mut builder = Deployment::builder();
+                builder
+                    .chain_id(file.chain_id)
+                    .boundless_market_address(market.boundless_market_address)
+                    .set_verifier_address(market.set_verifier_address);
+                if let Some(verifier) = market.verifier_router_address {
+                    builder.verifier_router_address(verifier);
+                }
+                if let Some(collateral) = market.collateral_token_address {
+                    builder.collateral_token_address(collateral);
+                }
+                if let Some(order_stream_url) = market.order_stream_url {
+                    builder.order_stream_url(order_stream_url);
+                }
+                Some(builder.build()?)
+            }
+            None => None,
+        };
+        let povw = match file.povw {
+            Some(povw) => Some(
+                boundless_povw::deployments::Deployment::builder()
+                    .chain_id(file.chain_id)
+                    .povw_accounting_address(povw.povw_accounting_address)
+                    .povw_mint_address(povw.povw_mint_address)
+                    .zkc_address(povw.zkc_address)
+                    .vezkc_address(povw.vezkc_address)
+                    .build()?,
+            ),
+            None => None,
+        };
+        let zkc = match file.zkc {
+            Some(zkc) => Some(
+                boundless_zkc::deployments::Deployment::builder()
+                    .chain_id(file.chain_id)
+                    .zkc_address(zkc.zkc_address)
+                    .vezkc_address(zkc.vezkc_address)
+                    .staking_rewards_address(zkc.staking_rewards_address)
+                    .build()?,
+            ),
+            None => None,
+        };
+        Ok(Self { name, chain_id: file.chain_id, market, povw, zkc })
+    }
+}
+
+impl FromStr for DeploymentPreset {
+    type Err = anyhow::Error;
+
+    fn from_str(name: &str) -> Result<Self> {
+        if let Some(preset) = Self::from_name(name) {
+            return Ok(preset);
+        }
+        if name == Self::LOCAL {
+            return Self::local(Path::new(Self::LOCALNET_ENV_FILE));
+        }
+        let path = Path::new(name);
+        if path.is_file() {
+            return Self::from_file(path);
+        }
+        let names = Self::PRESETS.map(|(name, _)| name);
+        bail!(
+            "unknown deployment preset {name}; expected one of {}, {}, or the path to a preset file",
+            names.join(", "),
+            Self::LOCAL
+        )
+    }
+}
+
+/// Contents of a deployment preset file, see [DeploymentPreset].
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PresetFile {
+    name: Option<String>,
+    chain_id: u64,
+    market: Option<MarketPreset>,
+    povw: Option<PovwPreset>,
+    zkc: Option<ZkcPreset>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct MarketPreset {
+    boundless_market_address: Address,
+    set_verifier_address: Address,
+    verifier_router_address: Option<Address>,
+    collateral_token_address: Option<Address>,
+    order_stream_url: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PovwPreset {
+    povw_accounting_address: Address,
+    povw_mint_address: Address,
+    zkc_address: Address,
+    vezkc_address: Address,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ZkcPreset {
+    zkc_address: Address,
+    vezkc_address: Address,
+    staking_rewards_address: Address,
+}
+
 const DEFAULT_BENTO_API_URL: &str = "http://localhost:8081";
 
 /// Configuration options for commands that utilize proving.
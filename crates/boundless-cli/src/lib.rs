@@ -20,6 +20,7 @@
 
 pub mod commands;
 pub mod config;
+pub mod watch;
 
 use alloy::{
     primitives::{Address, Bytes},
@@ -0,0 +1,315 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Watch mode for status commands, refreshing their output at a fixed interval.
+//!
+//! On a terminal, each refresh clears the screen and redraws the values in place, and the screen
+//! is redrawn when the terminal is resized. Otherwise, each refresh is printed in full after the
+//! previous one. In both cases, values that changed since the previous refresh are marked, and a
+//! failed refresh shows the last values under a stale banner rather than stopping the watch.
+
+use std::{
+    future::Future,
+    io::{IsTerminal, Write},
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+
+/// Marker printed after a value that changed since the previous refresh.
+pub const CHANGE_MARKER: &str = "*";
+
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+const BOLD_YELLOW: &str = "\x1b[1;33m";
+const RESET: &str = "\x1b[0m";
+
+/// Parse a watch interval given in seconds.
+pub fn parse_watch_interval(arg: &str) -> Result<Duration, String> {
+    let secs: u64 = arg.parse().map_err(|err| format!("invalid interval {arg}: {err}"))?;
+    if secs == 0 {
+        return Err("interval must be at least one second".into());
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+/// A labeled value shown by a watched command.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WatchField {
+    /// Label of the value.
+    pub label: String,
+    /// Value, formatted for display.
+    pub value: String,
+}
+
+impl WatchField {
+    /// Create a field with the given label and value.
+    pub fn new(label: impl Into<String>, value: impl Into<String>) -> Self {
+        Self { label: label.into(), value: value.into() }
+    }
+}
+
+/// Repeatedly fetches and displays a set of [WatchField] values.
+#[derive(Clone, Debug)]
+pub struct Watch {
+    title: String,
+    interval: Duration,
+    refreshes: Option<usize>,
+}
+
+/// Values shown by the last refresh, kept to mark changes and to redraw them.
+#[derive(Default)]
+struct WatchState {
+    fields: Vec<WatchField>,
+    previous: Vec<WatchField>,
+    updated_at: Option<DateTime<Local>>,
+    error: Option<(DateTime<Local>, String)>,
+}
+
+impl Watch {
+    /// Create a watch with the given title, refreshing at the given interval until interrupted.
+    pub fn new(title: impl Into<String>, interval: Duration) -> Self {
+        Self { title: title.into(), interval, refreshes: None }
+    }
+
+    /// Stop after the given number of refreshes.
+    pub fn with_refreshes(self, refreshes: usize) -> Self {
+        Self { refreshes: Some(refreshes), ..self }
+    }
+
+    /// Run the watch on stdout, redrawing in place if stdout is a terminal.
+    pub async fn run<F, Fut>(&self, fetch: F) -> Result<()>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<Vec<WatchField>>>,
+    {
+        let stdout = std::io::stdout();
+        let is_terminal = stdout.is_terminal();
+        self.run_with_output(stdout, is_terminal, fetch).await
+    }
+
+    /// Run the watch on the given output. If `is_terminal` is true, each refresh clears the
+    /// screen and redraws the values, and changed values are highlighted.
+    pub async fn run_with_output<W, F, Fut>(
+        &self,
+        mut out: W,
+        is_terminal: bool,
+        mut fetch: F,
+    ) -> Result<()>
+    where
+        W: Write,
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<Vec<WatchField>>>,
+    {
+        let mut resized = resize_signal(is_terminal)?;
+        let mut state = WatchState::default();
+        let mut refresh = 0;
+        loop {
+            match fetch().await {
+                Ok(fields) => {
+                    state.previous = std::mem::replace(&mut state.fields, fields);
+                    state.updated_at = Some(Local::now());
+                    state.error = None;
+                }
+                Err(err) => {
+                    // Keep the previous values, so they are shown as stale rather than changed.
+                    state.previous = state.fields.clone();
+                    state.error = Some((Local::now(), format!("{err:#}")));
+                }
+            }
+            self.draw(&mut out, is_terminal, &state)?;
+
+            refresh += 1;
+            if self.refreshes.is_some_and(|refreshes| refresh >= refreshes) {
+                return Ok(());
+            }
+
+            let sleep = tokio::time::sleep(self.interval);
+            tokio::pin!(sleep);
+            loop {
+                tokio::select! {
+                    _ = &mut sleep => break,
+                    Some(()) = next_resize(&mut resized) => self.draw(&mut out, is_terminal, &state)?,
+                }
+            }
+        }
+    }
+
+    fn draw(&self, out: &mut impl Write, is_terminal: bool, state: &WatchState) -> Result<()> {
+        let width = if is_terminal { terminal_width() } else { None };
+        let mut lines = Vec::new();
+        let updated_at = match state.updated_at {
+            Some(time) => time.format("%Y-%m-%d %H:%M:%S").to_string(),
+            None => "never".into(),
+        };
+        lines.push(format!(
+            "{} (every {}s, updated {updated_at})",
+            self.title,
+            self.interval.as_secs()
+        ));
+        if let Some((time, err)) = &state.error {
+            lines.push(format!(
+                "STALE: refresh at {} failed: {err}",
+                time.format("%Y-%m-%d %H:%M:%S")
+            ));
+        }
+
+        let label_width = state.fields.iter().map(|field| field.label.len()).max().unwrap_or(0);
+        for field in &state.fields {
+            let previous = state.previous.iter().find(|prev| prev.label == field.label);
+            let changed = state.error.is_none()
+                && previous.is_some_and(|previous| previous.value != field.value);
+            let mut line = format!("{:<label_width$}  {}", field.label, field.value);
+            if changed {
+                line.push_str(&format!(" {CHANGE_MARKER} (was {})", previous.unwrap().value));
+            }
+            if let Some(width) = width {
+                line = line.chars().take(width).collect();
+            }
+            if changed && is_terminal {
+                line = format!("{BOLD_YELLOW}{line}{RESET}");
+            }
+            lines.push(line);
+        }
+
+        if is_terminal {
+            write!(out, "{CLEAR_SCREEN}")?;
+        } else {
+            writeln!(out)?;
+        }
+        for line in lines {
+            writeln!(out, "{line}")?;
+        }
+        out.flush().context("Failed to write watch output")
+    }
+}
+
+#[cfg(unix)]
+type ResizeSignal = Option<tokio::signal::unix::Signal>;
+#[cfg(not(unix))]
+type ResizeSignal = Option<()>;
+
+/// Listen for terminal resizes, if the output is a terminal.
+fn resize_signal(is_terminal: bool) -> Result<ResizeSignal> {
+    #[cfg(unix)]
+    if is_terminal {
+        use tokio::signal::unix::{signal, SignalKind};
+        return Ok(Some(
+            signal(SignalKind::window_change()).context("Failed to listen for terminal resizes")?,
+        ));
+    }
+    let _ = is_terminal;
+    Ok(None)
+}
+
+/// Wait for the next terminal resize. Never completes when resizes are not listened for.
+async fn next_resize(resized: &mut ResizeSignal) -> Option<()> {
+    match resized {
+        #[cfg(unix)]
+        Some(signal) => signal.recv().await,
+        #[cfg(not(unix))]
+        Some(()) => std::future::pending().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Width of the terminal attached to stdout, in columns.
+fn terminal_width() -> Option<usize> {
+    #[cfg(unix)]
+    {
+        // SAFETY: winsize is plain data, and TIOCGWINSZ only writes a winsize to the pointer.
+        let (res, size) = unsafe {
+            let mut size: libc::winsize = std::mem::zeroed();
+            (libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size), size)
+        };
+        if res == 0 && size.ws_col > 0 {
+            return Some(size.ws_col as usize);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use anyhow::anyhow;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn marks_changed_values() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut out = Vec::new();
+        Watch::new("Balance", Duration::from_millis(10))
+            .with_refreshes(2)
+            .run_with_output(&mut out, false, || {
+                let call = calls.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    Ok(vec![
+                        WatchField::new("Balance", format!("{} ETH", call + 1)),
+                        WatchField::new("Address", "0x01"),
+                    ])
+                }
+            })
+            .await
+            .unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        let refreshes = out.split("\n\n").collect::<Vec<_>>();
+        assert_eq!(refreshes.len(), 2, "{out}");
+        assert!(!refreshes[0].contains(CHANGE_MARKER), "{out}");
+        assert!(refreshes[1].contains(&format!("2 ETH {CHANGE_MARKER} (was 1 ETH)")), "{out}");
+        assert!(!refreshes[1].contains(&format!("0x01 {CHANGE_MARKER}")), "{out}");
+        assert!(!out.contains(CLEAR_SCREEN));
+    }
+
+    #[tokio::test]
+    async fn shows_stale_values_on_error() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut out = Vec::new();
+        Watch::new("Balance", Duration::from_millis(10))
+            .with_refreshes(3)
+            .run_with_output(&mut out, false, || {
+                let call = calls.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    match call {
+                        1 => Err(anyhow!("connection refused")),
+                        _ => Ok(vec![WatchField::new("Balance", format!("{call} ETH"))]),
+                    }
+                }
+            })
+            .await
+            .unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+        let refreshes = out.split("\n\n").collect::<Vec<_>>();
+        assert_eq!(refreshes.len(), 3, "{out}");
+        assert!(refreshes[1].contains("STALE: refresh at"), "{out}");
+        assert!(refreshes[1].contains("connection refused"), "{out}");
+        assert!(refreshes[1].contains("Balance  0 ETH"), "{out}");
+        assert!(!refreshes[2].contains("STALE"), "{out}");
+        assert!(refreshes[2].contains(&format!("2 ETH {CHANGE_MARKER} (was 0 ETH)")), "{out}");
+    }
+
+    #[test]
+    fn parses_interval() {
+        assert_eq!(parse_watch_interval("5").unwrap(), Duration::from_secs(5));
+        assert!(parse_watch_interval("0").is_err());
+        assert!(parse_watch_interval("soon").is_err());
+    }
+}
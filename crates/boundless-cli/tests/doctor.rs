@@ -0,0 +1,61 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration tests for the doctor command.
+
+use alloy::{node_bindings::Anvil, primitives::Address};
+use assert_cmd::Command;
+use predicates::str::contains;
+
+#[test]
+fn test_doctor_unreachable_rpc() {
+    let mut cmd = Command::cargo_bin("boundless").unwrap();
+
+    cmd.args(["doctor"])
+        .env("NO_COLOR", "1")
+        .env("RPC_URL", "http://127.0.0.1:1")
+        .assert()
+        .failure()
+        .stdout(contains("[FAIL] rpc: RPC endpoint is unreachable"))
+        .stdout(contains("hint: check --rpc-url or the RPC_URL env var"))
+        .stdout(contains("[SKIP] chain id: requires a reachable RPC endpoint"))
+        .stdout(contains("[SKIP] contract code: requires a reachable RPC endpoint"))
+        .stdout(contains("1 failed"));
+}
+
+#[test]
+fn test_doctor_missing_contract_code() {
+    let anvil = Anvil::new().spawn();
+    let market_address = Address::repeat_byte(0x11);
+    let mut cmd = Command::cargo_bin("boundless").unwrap();
+
+    // No contracts are deployed on the fresh Anvil instance.
+    cmd.args(["doctor"])
+        .env("NO_COLOR", "1")
+        .env("RPC_URL", anvil.endpoint_url().as_str())
+        .env("PRIVATE_KEY", format!("0x{}", hex::encode(anvil.keys()[0].to_bytes())))
+        .env("CHAIN_ID", anvil.chain_id().to_string())
+        .env("BOUNDLESS_MARKET_ADDRESS", format!("{market_address:#x}"))
+        .env("SET_VERIFIER_ADDRESS", format!("{:#x}", Address::repeat_byte(0x22)))
+        .assert()
+        .failure()
+        .stdout(contains(format!("[PASS] rpc: connected to chain ID {}", anvil.chain_id())))
+        .stdout(contains(format!("[PASS] chain id: chain ID {}", anvil.chain_id())))
+        .stdout(contains(format!(
+            "[FAIL] contract code: no contract code at BoundlessMarket ({market_address})"
+        )))
+        .stdout(contains("hint: check the configured contract addresses"))
+        .stdout(contains("[PASS] signer balance"))
+        .stdout(contains("1 failed"));
+}
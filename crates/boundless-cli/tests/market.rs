@@ -0,0 +1,293 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Integration tests for market-related CLI commands.
+
+use alloy::{
+    node_bindings::{Anvil, AnvilInstance},
+    primitives::{utils::parse_ether, Bytes, U256},
+    providers::{ext::AnvilApi, Provider},
+    signers::local::PrivateKeySigner,
+    sol_types::eip712_domain,
+};
+use assert_cmd::Command;
+use boundless_market::{
+    contracts::{
+        boundless_market::FulfillmentTx, hit_points::default_allowance, AssessorReceipt,
+        FulfillmentDataType, Offer, Predicate, ProofRequest, RequestId, RequestStatus,
+        Requirements,
+    },
+    input::GuestEnv,
+};
+use boundless_test_utils::{
+    guests::{ECHO_ID, ECHO_PATH},
+    market::{create_test_ctx, mock_singleton, TestCtx},
+};
+use predicates::str::contains;
+use risc0_zkvm::sha::Digest;
+
+// NOTE: Tests in this file print the CLI output. Run `cargo test -- --nocapture --test-threads=1` to see it.
+
+/// Create a command for the `boundless` binary, configured to use the test deployment.
+fn boundless_cmd<P: Provider>(
+    ctx: &TestCtx<P>,
+    anvil: &AnvilInstance,
+    signer: &PrivateKeySigner,
+) -> Command {
+    let mut cmd = Command::cargo_bin("boundless").unwrap();
+    cmd.env("RPC_URL", anvil.endpoint_url().as_str())
+        .env("PRIVATE_KEY", format!("0x{}", hex::encode(signer.to_bytes())))
+        .env("CHAIN_ID", anvil.chain_id().to_string())
+        .env("BOUNDLESS_MARKET_ADDRESS", format!("{:#x}", ctx.deployment.boundless_market_address))
+        .env("SET_VERIFIER_ADDRESS", format!("{:#x}", ctx.deployment.set_verifier_address))
+        .env("VERIFIER_ADDRESS", format!("{:#x}", ctx.deployment.verifier_router_address.unwrap()))
+        .env("NO_COLOR", "1")
+        .env("RUST_LOG", "boundless_cli=debug,info")
+        .env("RISC0_DEV_MODE", "1");
+    cmd
+}
+
+#[test]
+fn test_submit_request_help() {
+    let mut cmd = Command::cargo_bin("boundless").unwrap();
+
+    cmd.args(["market", "submit-request", "--help"])
+        .env("NO_COLOR", "1")
+        .assert()
+        .success()
+        .stdout(contains("Usage:"))
+        .stdout(contains("--program-url"))
+        .stdout(contains("--offchain"));
+}
+
+#[tokio::test]
+#[ignore = "Generates a proof. Slow without RISC0_DEV_MODE=1"]
+async fn test_submit_request_and_fulfill() -> anyhow::Result<()> {
+    let anvil = Anvil::new().spawn();
+    let ctx = create_test_ctx(&anvil).await?;
+    ctx.customer_market.deposit(parse_ether("1")?).await?;
+    ctx.prover_market
+        .deposit_collateral_with_permit(default_allowance(), &ctx.prover_signer)
+        .await?;
+
+    // Submit the request as the customer, uploading the echo program with the file storage provider.
+    let output = boundless_cmd(&ctx, &anvil, &ctx.customer_signer)
+        .args([
+            "market",
+            "submit-request",
+            "--program",
+            ECHO_PATH,
+            "--input",
+            "hello",
+            "--min-price",
+            "0",
+            "--max-price",
+            "1000000",
+            "--lock-collateral",
+            "0",
+            "--ramp-up-period",
+            "1",
+            "--lock-timeout",
+            "600",
+            "--timeout",
+            "1200",
+            "--storage-provider",
+            "file",
+        ])
+        .assert()
+        .success()
+        .stdout(contains("boundless request status"))
+        .get_output()
+        .stdout
+        .clone();
+    let stdout = String::from_utf8(output)?;
+    let request_id = stdout
+        .lines()
+        .find_map(|line| line.strip_prefix("Submitted request "))
+        .expect("request ID is printed")
+        .trim()
+        .to_string();
+    let status = ctx
+        .customer_market
+        .get_status(U256::from_str_radix(request_id.trim_start_matches("0x"), 16)?, None)
+        .await?;
+    assert_eq!(status, RequestStatus::Unknown);
+
+    // Lock and fulfill the request as the prover.
+    boundless_cmd(&ctx, &anvil, &ctx.prover_signer)
+        .args(["proving", "lock", "--request-id", &request_id])
+        .assert()
+        .success();
+    boundless_cmd(&ctx, &anvil, &ctx.prover_signer)
+        .args(["proving", "fulfill", "--request-ids", &request_id, "--use-default-prover"])
+        .assert()
+        .success();
+
+    boundless_cmd(&ctx, &anvil, &ctx.customer_signer)
+        .args(["request", "status", &request_id])
+        .assert()
+        .success()
+        .stdout(contains("status: Fulfilled"));
+
+    Ok(())
+}
+
+fn now_timestamp() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::SystemTime::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Submit and lock a request from the customer, returning the locked request.
+async fn submit_and_lock_request<P: Provider>(ctx: &TestCtx<P>) -> anyhow::Result<ProofRequest> {
+    ctx.customer_market.deposit(parse_ether("1")?).await?;
+    ctx.prover_market
+        .deposit_collateral_with_permit(default_allowance(), &ctx.prover_signer)
+        .await?;
+
+    let request = ProofRequest::new(
+        RequestId::new(
+            ctx.customer_signer.address(),
+            ctx.customer_market.index_from_nonce().await?,
+        ),
+        Requirements::new(Predicate::prefix_match(Digest::from(ECHO_ID), Bytes::default())),
+        format!("file://{ECHO_PATH}"),
+        GuestEnv::builder().build_inline()?,
+        Offer {
+            minPrice: U256::from(20000000000000u64),
+            maxPrice: U256::from(40000000000000u64),
+            rampUpStart: now_timestamp(),
+            timeout: 100,
+            lockTimeout: 100,
+            rampUpPeriod: 1,
+            lockCollateral: U256::from(10),
+        },
+    );
+    ctx.customer_market.submit_request(&request, &ctx.customer_signer).await?;
+    let client_sig = request
+        .sign_request(
+            &ctx.customer_signer,
+            ctx.deployment.boundless_market_address,
+            ctx.customer_market.get_chain_id().await?,
+        )
+        .await?;
+    ctx.prover_market.lock_request(&request, client_sig.as_bytes().to_vec(), None).await?;
+    Ok(request)
+}
+
+#[tokio::test]
+async fn test_slash() -> anyhow::Result<()> {
+    let anvil = Anvil::new().spawn();
+    let ctx = create_test_ctx(&anvil).await?;
+    let request = submit_and_lock_request(&ctx).await?;
+    let request_id = format!("0x{:x}", request.id);
+
+    // The request cannot be slashed before it expires.
+    boundless_cmd(&ctx, &anvil, &ctx.customer_signer)
+        .args(["market", "slash", &request_id, "--dry-run"])
+        .assert()
+        .success()
+        .stdout(contains("not eligible, the request does not expire until"));
+
+    // Move past the request deadline without fulfilling the request.
+    ctx.customer_provider.anvil_increase_time(200).await?;
+    ctx.customer_provider.anvil_mine(Some(1), None).await?;
+
+    boundless_cmd(&ctx, &anvil, &ctx.customer_signer)
+        .args(["market", "slash", &request_id, "--dry-run"])
+        .assert()
+        .success()
+        .stdout(contains(format!("{request_id}: eligible")))
+        .stdout(contains(format!("prover {}", ctx.prover_signer.address())))
+        .stdout(contains("the market treasury"));
+    assert!(!ctx.customer_market.is_slashed(request.id).await?);
+
+    boundless_cmd(&ctx, &anvil, &ctx.customer_signer)
+        .args(["market", "slash", &request_id])
+        .assert()
+        .success()
+        .stdout(contains(format!("{request_id}: slashed")));
+    assert!(ctx.customer_market.is_slashed(request.id).await?);
+
+    // Slashing again is reported as ineligible.
+    boundless_cmd(&ctx, &anvil, &ctx.customer_signer)
+        .args(["market", "slash", &request_id])
+        .assert()
+        .failure()
+        .stdout(contains("the request is already slashed"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_slash_fulfilled_request() -> anyhow::Result<()> {
+    let anvil = Anvil::new().spawn();
+    let ctx = create_test_ctx(&anvil).await?;
+    let request = submit_and_lock_request(&ctx).await?;
+    let request_id = format!("0x{:x}", request.id);
+
+    // Fulfill the request with a mock proof before the lock deadline.
+    let domain = eip712_domain! {
+        name: "IBoundlessMarket",
+        version: "1",
+        chain_id: anvil.chain_id(),
+        verifying_contract: ctx.deployment.boundless_market_address,
+    };
+    let (root, set_verifier_seal, fulfillment, assessor_seal) = mock_singleton(
+        &request,
+        domain,
+        ctx.prover_signer.address(),
+        FulfillmentDataType::ImageIdAndJournal,
+    );
+    ctx.set_verifier.submit_merkle_root(root, set_verifier_seal).await?;
+    let assessor_receipt = AssessorReceipt {
+        seal: assessor_seal,
+        selectors: vec![],
+        prover: ctx.prover_signer.address(),
+        callbacks: vec![],
+    };
+    ctx.prover_market.fulfill(FulfillmentTx::new(vec![fulfillment], assessor_receipt)).await?;
+
+    ctx.customer_provider.anvil_increase_time(200).await?;
+    ctx.customer_provider.anvil_mine(Some(1), None).await?;
+
+    // Batch the fulfilled request from a file, along with a request that was never locked.
+    let unknown_id = RequestId::new(ctx.customer_signer.address(), 1000);
+    let ids_file = tempfile::NamedTempFile::new()?;
+    std::fs::write(
+        ids_file.path(),
+        format!("# requests to slash\n{request_id}\n\n0x{:x}\n", U256::from(unknown_id)),
+    )?;
+
+    boundless_cmd(&ctx, &anvil, &ctx.customer_signer)
+        .args(["market", "slash", "--dry-run", "--from-file"])
+        .arg(ids_file.path())
+        .assert()
+        .success()
+        .stdout(contains(format!(
+            "{request_id}: not eligible, the request was fulfilled before the lock deadline"
+        )))
+        .stdout(contains("not eligible, the request is not locked"));
+
+    boundless_cmd(&ctx, &anvil, &ctx.customer_signer)
+        .args(["market", "slash", "--from-file"])
+        .arg(ids_file.path())
+        .assert()
+        .failure()
+        .stderr(contains("2 of 2 requests were not slashed"));
+    assert!(!ctx.customer_market.is_slashed(request.id).await?);
+
+    Ok(())
+}
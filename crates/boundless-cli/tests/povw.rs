@@ -18,11 +18,14 @@ use std::path::Path;
 
 use alloy::{providers::ext::AnvilApi, signers::local::PrivateKeySigner};
 use assert_cmd::Command;
-use boundless_cli::commands::povw::State;
-use boundless_test_utils::povw::{bento_mock::BentoMockServer, make_work_claim, test_ctx};
+use boundless_cli::commands::povw::{
+    submission_status, MigrationAttestation, State, SubmissionStatus,
+};
+use boundless_povw::log_updater::LogBuilderJournal;
+use boundless_test_utils::povw::{bento_mock::BentoMockServer, make_work_claim, test_ctx, TestCtx};
 use predicates::str::contains;
-use risc0_povw::PovwLogId;
-use risc0_zkvm::{FakeReceipt, GenericReceipt, ReceiptClaim, VerifierContext, WorkClaim};
+use risc0_povw::{guest::RISC0_POVW_LOG_BUILDER_ID, PovwLogId, WorkLog};
+use risc0_zkvm::{Digest, FakeReceipt, GenericReceipt, ReceiptClaim, VerifierContext, WorkClaim};
 use tempfile::TempDir;
 
 // NOTE: Tests in this file print the CLI output. Run `cargo test -- --nocapture --test-threads=1` to see it.
@@ -106,6 +109,47 @@ async fn prove_update_basic() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Test that prepare refuses work receipts with overlapping nonces before proving.
+#[tokio::test]
+async fn prove_update_overlapping_nonces() -> anyhow::Result<()> {
+    let temp_dir = TempDir::new()?;
+    let temp_path = temp_dir.path();
+
+    let signer = PrivateKeySigner::random();
+    let log_id: PovwLogId = signer.address().into();
+
+    // Include the same work receipt twice, as if copied under a different name.
+    let receipt_path = temp_path.join("receipt.bin");
+    make_fake_work_receipt_file(log_id, 1000, 10, &receipt_path)?;
+    let receipt_copy_path = temp_path.join("receipt_copy.bin");
+    std::fs::copy(&receipt_path, &receipt_copy_path)?;
+
+    let state_path = temp_path.join("state.bin");
+    let mut cmd = Command::cargo_bin("boundless")?;
+    cmd.args([
+        "povw",
+        "prepare",
+        "--new",
+        &format!("{:#x}", log_id),
+        "--state",
+        state_path.to_str().unwrap(),
+        receipt_path.to_str().unwrap(),
+        receipt_copy_path.to_str().unwrap(),
+    ])
+    .env("NO_COLOR", "1")
+    .env("RUST_LOG", "boundless_cli=debug,info")
+    .env("RISC0_DEV_MODE", "1")
+    .assert()
+    .failure()
+    .stderr(contains("overlapping nonce ranges"))
+    .stderr(contains("segments 0..=9 in the same update"));
+
+    // No update was proven, so no state was written.
+    assert!(!state_path.exists());
+
+    Ok(())
+}
+
 /// End-to-end test that proves a work log update and sends it to a local Anvil chain.
 #[tokio::test]
 async fn prove_and_send_update() -> anyhow::Result<()> {
@@ -183,6 +227,210 @@ async fn prove_and_send_update() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Test that submit detects updates that are already onchain, and states that diverged from the
+/// onchain work log, before proving.
+#[tokio::test]
+async fn submit_already_submitted_and_diverged() -> anyhow::Result<()> {
+    let ctx = test_ctx().await?;
+
+    let temp_dir = TempDir::new()?;
+    let temp_path = temp_dir.path();
+
+    // Use a random signer for the work log (with zero balance)
+    let work_log_signer = PrivateKeySigner::random();
+    let log_id: PovwLogId = work_log_signer.address().into();
+
+    // Use an Anvil-provided signer for transaction signing (with balance)
+    let tx_signer: PrivateKeySigner = ctx.anvil.lock().await.keys()[1].clone().into();
+
+    // Prepare two updates, keeping a copy of the state after the first one.
+    let state_path = temp_path.join("state.bin");
+    let stale_state_path = temp_path.join("stale_state.bin");
+    for (i, new_log) in [true, false].into_iter().enumerate() {
+        let receipt_path = temp_path.join(format!("receipt{i}.bin"));
+        make_fake_work_receipt_file(log_id, 1000, 10, &receipt_path)?;
+        let mut cmd = Command::cargo_bin("boundless")?;
+        cmd.args(["povw", "prepare", "--state", state_path.to_str().unwrap()]);
+        if new_log {
+            cmd.args(["--new", &format!("{:#x}", log_id)]);
+        }
+        cmd.arg(receipt_path.to_str().unwrap())
+            .env("NO_COLOR", "1")
+            .env("RUST_LOG", "boundless_cli=debug,info")
+            .env("RISC0_DEV_MODE", "1")
+            .assert()
+            .success();
+        if new_log {
+            std::fs::copy(&state_path, &stale_state_path)?;
+        }
+    }
+
+    let rpc_url = ctx.anvil.lock().await.endpoint_url().to_string();
+    let submit = |state_path: &Path| -> anyhow::Result<assert_cmd::assert::Assert> {
+        let mut cmd = Command::cargo_bin("boundless")?;
+        Ok(cmd
+            .args(["povw", "submit", "--state", state_path.to_str().unwrap()])
+            .env("NO_COLOR", "1")
+            .env("RUST_LOG", "boundless_cli=debug,info")
+            .env("POVW_ACCOUNTING_ADDRESS", format!("{:#x}", ctx.povw_accounting.address()))
+            .env("POVW_MINT_ADDRESS", format!("{:#x}", ctx.povw_mint.address()))
+            .env("ZKC_ADDRESS", format!("{:#x}", ctx.zkc.address()))
+            .env("VEZKC_ADDRESS", format!("{:#x}", ctx.zkc_rewards.address()))
+            .env("PRIVATE_KEY", format!("{:#x}", tx_signer.to_bytes()))
+            .env("RISC0_DEV_MODE", "1")
+            .env("RPC_URL", &rpc_url)
+            .env("POVW_PRIVATE_KEY", format!("{:#x}", work_log_signer.to_bytes()))
+            .assert())
+    };
+
+    // Submit both updates, then submit again, which should be a no-op.
+    submit(&state_path)?.success().stdout(contains("Work log update confirmed"));
+    submit(&state_path)?.success().stdout(contains("already submitted"));
+
+    // The stale copy of the state only knows about the first update, and cannot be submitted.
+    submit(&stale_state_path)?.failure().stderr(contains("diverged"));
+
+    // The same checks are available to library users.
+    let state = State::load(&state_path).await?;
+    let status =
+        submission_status(ctx.provider.clone(), *ctx.povw_accounting.address(), &state).await?;
+    assert_eq!(status, SubmissionStatus::AlreadySubmitted);
+    let stale_state = State::load(&stale_state_path).await?;
+    let status =
+        submission_status(ctx.provider.clone(), *ctx.povw_accounting.address(), &stale_state)
+            .await?;
+    assert!(matches!(status, SubmissionStatus::Diverged { .. }));
+
+    Ok(())
+}
+
+/// End-to-end test that migrates from one work log to another, posting the final update for the old
+/// work log and the first update for the new one.
+#[tokio::test]
+async fn migrate_log() -> anyhow::Result<()> {
+    // 1. Set up a local Anvil node with the required contracts
+    let ctx = test_ctx().await?;
+
+    let temp_dir = TempDir::new()?;
+    let temp_path = temp_dir.path();
+
+    // Old and new work log signers (with zero balance)
+    let old_signer = PrivateKeySigner::random();
+    let old_log_id: PovwLogId = old_signer.address().into();
+    let new_signer = PrivateKeySigner::random();
+    let new_log_id: PovwLogId = new_signer.address().into();
+
+    // Use an Anvil-provided signer for transaction signing (with balance)
+    let tx_signer: PrivateKeySigner = ctx.anvil.lock().await.keys()[1].clone().into();
+
+    // 2. Prepare an update for the old work log, which migrate-log will post as its final update
+    let receipt_path = temp_path.join("receipt_old.bin");
+    make_fake_work_receipt_file(old_log_id, 1000, 10, &receipt_path)?;
+    let old_state_path = temp_path.join("old_state.bin");
+    let mut cmd = Command::cargo_bin("boundless")?;
+    cmd.args([
+        "povw",
+        "prepare",
+        "--new",
+        &format!("{:#x}", old_log_id),
+        "--state",
+        old_state_path.to_str().unwrap(),
+        receipt_path.to_str().unwrap(),
+    ])
+    .env("NO_COLOR", "1")
+    .env("RUST_LOG", "boundless_cli=debug,info")
+    .env("RISC0_DEV_MODE", "1")
+    .assert()
+    .success();
+
+    // 3. Run the migration, publishing the attestation onchain
+    let new_state_path = temp_path.join("new_state.bin");
+    let attestation_path = temp_path.join("migration.json");
+    let mut cmd = Command::cargo_bin("boundless")?;
+    cmd.args([
+        "povw",
+        "migrate-log",
+        "--state",
+        old_state_path.to_str().unwrap(),
+        "--new-log-id",
+        &format!("{:#x}", new_log_id),
+        "--new-state",
+        new_state_path.to_str().unwrap(),
+        "--attestation",
+        attestation_path.to_str().unwrap(),
+        "--publish",
+    ])
+    .env("NO_COLOR", "1")
+    .env("RUST_LOG", "boundless_cli=debug,info")
+    .env("POVW_ACCOUNTING_ADDRESS", format!("{:#x}", ctx.povw_accounting.address()))
+    .env("POVW_MINT_ADDRESS", format!("{:#x}", ctx.povw_mint.address()))
+    .env("ZKC_ADDRESS", format!("{:#x}", ctx.zkc.address()))
+    .env("VEZKC_ADDRESS", format!("{:#x}", ctx.zkc_rewards.address()))
+    .env("PRIVATE_KEY", format!("{:#x}", tx_signer.to_bytes()))
+    .env("RISC0_DEV_MODE", "1")
+    .env("RPC_URL", ctx.anvil.lock().await.endpoint_url().as_str())
+    .env("POVW_PRIVATE_KEY", format!("{:#x}", old_signer.to_bytes()))
+    .assert()
+    .success()
+    .stdout(contains("Work log update confirmed"))
+    .stdout(contains("Published migration attestation"));
+
+    // The final update for the old work log is onchain
+    let old_state = State::load(&old_state_path).await?;
+    let onchain_commit = ctx.povw_accounting.workLogCommit(old_log_id.into()).call().await?;
+    assert_eq!(bytemuck::cast::<_, [u8; 32]>(old_state.work_log.commit()), *onchain_commit);
+
+    // The attestation links the old work log to the new one, and is signed by the old key
+    let attestation = MigrationAttestation::load(&attestation_path).await?;
+    attestation.verify()?;
+    assert_eq!(attestation.migration.oldLogId, old_signer.address());
+    assert_eq!(attestation.migration.newLogId, new_signer.address());
+    assert_eq!(attestation.migration.finalCommit, onchain_commit);
+
+    // 4. Prepare and submit the first update for the new work log
+    let new_state = State::load(&new_state_path).await?;
+    assert_eq!(new_state.log_id, new_log_id);
+    assert!(new_state.log_builder_receipts.is_empty());
+
+    let receipt_path = temp_path.join("receipt_new.bin");
+    make_fake_work_receipt_file(new_log_id, 2000, 10, &receipt_path)?;
+    let mut cmd = Command::cargo_bin("boundless")?;
+    cmd.args([
+        "povw",
+        "prepare",
+        "--state",
+        new_state_path.to_str().unwrap(),
+        receipt_path.to_str().unwrap(),
+    ])
+    .env("NO_COLOR", "1")
+    .env("RUST_LOG", "boundless_cli=debug,info")
+    .env("RISC0_DEV_MODE", "1")
+    .assert()
+    .success();
+
+    let mut cmd = Command::cargo_bin("boundless")?;
+    cmd.args(["povw", "submit", "--state", new_state_path.to_str().unwrap()])
+        .env("NO_COLOR", "1")
+        .env("RUST_LOG", "boundless_cli=debug,info")
+        .env("POVW_ACCOUNTING_ADDRESS", format!("{:#x}", ctx.povw_accounting.address()))
+        .env("POVW_MINT_ADDRESS", format!("{:#x}", ctx.povw_mint.address()))
+        .env("ZKC_ADDRESS", format!("{:#x}", ctx.zkc.address()))
+        .env("VEZKC_ADDRESS", format!("{:#x}", ctx.zkc_rewards.address()))
+        .env("PRIVATE_KEY", format!("{:#x}", tx_signer.to_bytes()))
+        .env("RISC0_DEV_MODE", "1")
+        .env("RPC_URL", ctx.anvil.lock().await.endpoint_url().as_str())
+        .env("POVW_PRIVATE_KEY", format!("{:#x}", new_signer.to_bytes()))
+        .assert()
+        .success()
+        .stdout(contains("Work log update confirmed"));
+
+    let new_state = State::load(&new_state_path).await?;
+    let onchain_commit = ctx.povw_accounting.workLogCommit(new_log_id.into()).call().await?;
+    assert_eq!(bytemuck::cast::<_, [u8; 32]>(new_state.work_log.commit()), *onchain_commit);
+
+    Ok(())
+}
+
 /// Test the claim command with multiple epochs of work log updates.
 #[tokio::test]
 async fn claim_reward_multi_epoch() -> anyhow::Result<()> {
@@ -477,6 +725,161 @@ async fn claim_on_partially_finalized_epochs() -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Test a batch claim over two work logs claimed one at a time, where the second claim fails, and
+/// that running the command again resumes with the second work log.
+#[tokio::test]
+async fn claim_batch_resumes_after_failure() -> anyhow::Result<()> {
+    let ctx = test_ctx().await?;
+    let temp_dir = TempDir::new()?;
+    let work_logs_path = temp_dir.path().join("work-logs.json");
+    let progress_path = temp_dir.path().join("work-logs.progress.json");
+    let signers = post_batch_claim_updates(&ctx, &work_logs_path).await?;
+    let log_id_strs: Vec<String> = signers.iter().map(|s| format!("{:#x}", s.address())).collect();
+
+    let result = batch_claim_cmd(&ctx, &work_logs_path, 1)
+        .await?
+        .assert()
+        .failure()
+        .stdout(contains(format!("Reward claim completed for work log {}", log_id_strs[0])));
+    println!("claim command output:\n{}", String::from_utf8_lossy(&result.get_output().stdout));
+    assert_eq!(claimed_groups(&progress_path)?, vec![vec![log_id_strs[0].clone()]]);
+    let first_balance = ctx.zkc.balanceOf(signers[0].address()).call().await?;
+    assert!(first_balance > alloy::primitives::U256::ZERO);
+
+    // Finalize the epoch of the second update and resume the batch.
+    ctx.finalize_epoch().await?;
+    ctx.provider.anvil_mine(Some(1), None).await?;
+
+    let result = batch_claim_cmd(&ctx, &work_logs_path, 1)
+        .await?
+        .assert()
+        .success()
+        .stdout(contains(format!(
+            "Skipping work log {}, claimed in a previous run",
+            log_id_strs[0]
+        )))
+        .stdout(contains(format!("Reward claim completed for work log {}", log_id_strs[1])));
+    println!("claim command output:\n{}", String::from_utf8_lossy(&result.get_output().stdout));
+    // The progress file is removed once the batch claim completes.
+    assert!(!progress_path.exists());
+    assert_eq!(ctx.zkc.balanceOf(signers[0].address()).call().await?, first_balance);
+    assert!(ctx.zkc.balanceOf(signers[1].address()).call().await? > alloy::primitives::U256::ZERO);
+    Ok(())
+}
+
+/// Test a batch claim over two work logs claimed with a single mint, where the second work log has
+/// no finalized updates and is skipped, and that a later batch claim claims it.
+#[tokio::test]
+async fn claim_batch_groups_work_logs() -> anyhow::Result<()> {
+    let ctx = test_ctx().await?;
+    let temp_dir = TempDir::new()?;
+    let work_logs_path = temp_dir.path().join("work-logs.json");
+    let progress_path = temp_dir.path().join("work-logs.progress.json");
+    let signers = post_batch_claim_updates(&ctx, &work_logs_path).await?;
+    let log_id_strs: Vec<String> = signers.iter().map(|s| format!("{:#x}", s.address())).collect();
+
+    let result = batch_claim_cmd(&ctx, &work_logs_path, 2)
+        .await?
+        .assert()
+        .success()
+        .stdout(contains(format!(
+            "Skipping work log {}, which has no updates in finalized epochs",
+            log_id_strs[1]
+        )))
+        .stdout(contains(format!(
+            "Reward claim completed for work log {} and 1 more",
+            log_id_strs[0]
+        )));
+    println!("claim command output:\n{}", String::from_utf8_lossy(&result.get_output().stdout));
+    assert!(!progress_path.exists());
+    let first_balance = ctx.zkc.balanceOf(signers[0].address()).call().await?;
+    assert!(first_balance > alloy::primitives::U256::ZERO);
+    assert_eq!(
+        ctx.zkc.balanceOf(signers[1].address()).call().await?,
+        alloy::primitives::U256::ZERO
+    );
+
+    // Finalize the epoch of the second update and run the batch claim again.
+    ctx.finalize_epoch().await?;
+    ctx.provider.anvil_mine(Some(1), None).await?;
+
+    let result = batch_claim_cmd(&ctx, &work_logs_path, 2).await?.assert().success().stdout(
+        contains(format!("Reward claim completed for work log {} and 1 more", log_id_strs[0])),
+    );
+    println!("claim command output:\n{}", String::from_utf8_lossy(&result.get_output().stdout));
+    assert!(!progress_path.exists());
+    assert_eq!(ctx.zkc.balanceOf(signers[0].address()).call().await?, first_balance);
+    assert!(ctx.zkc.balanceOf(signers[1].address()).call().await? > alloy::primitives::U256::ZERO);
+    Ok(())
+}
+
+/// Post updates to two new work logs, the first in a finalized epoch and the second in an epoch
+/// that is not finalized yet, and write their IDs to the given work logs file.
+async fn post_batch_claim_updates(
+    ctx: &TestCtx,
+    work_logs_path: &Path,
+) -> anyhow::Result<[PrivateKeySigner; 2]> {
+    let signers = [PrivateKeySigner::random(), PrivateKeySigner::random()];
+    let make_update = |signer: &PrivateKeySigner| {
+        LogBuilderJournal::builder()
+            .self_image_id(RISC0_POVW_LOG_BUILDER_ID)
+            .initial_commit(WorkLog::EMPTY.commit())
+            .updated_commit(Digest::new(rand::random()))
+            .update_value(25)
+            .work_log_id(signer.address())
+            .build()
+            .unwrap()
+    };
+
+    ctx.post_work_log_update(&signers[0], &make_update(&signers[0]), signers[0].address()).await?;
+    ctx.advance_epochs(alloy::primitives::U256::from(1)).await?;
+    ctx.finalize_epoch().await?;
+    ctx.post_work_log_update(&signers[1], &make_update(&signers[1]), signers[1].address()).await?;
+    ctx.advance_epochs(alloy::primitives::U256::from(1)).await?;
+    ctx.provider.anvil_mine(Some(1), None).await?;
+
+    let log_id_strs: Vec<String> = signers.iter().map(|s| format!("{:#x}", s.address())).collect();
+    std::fs::write(work_logs_path, serde_json::to_vec(&log_id_strs)?)?;
+    Ok(signers)
+}
+
+/// Build the command for a batch claim over the given work logs file.
+async fn batch_claim_cmd(
+    ctx: &TestCtx,
+    work_logs_path: &Path,
+    group_size: u32,
+) -> anyhow::Result<Command> {
+    let tx_signer: PrivateKeySigner = ctx.anvil.lock().await.keys()[1].clone().into();
+    let rpc_url = ctx.anvil.lock().await.endpoint_url();
+    let mut cmd = Command::cargo_bin("boundless")?;
+    cmd.args(["povw", "claim", "--work-logs-file", work_logs_path.to_str().unwrap()])
+        .args(["--group-size", &group_size.to_string()])
+        .env("NO_COLOR", "1")
+        .env("RUST_LOG", "boundless_cli=debug,info")
+        .env("POVW_ACCOUNTING_ADDRESS", format!("{:#x}", ctx.povw_accounting.address()))
+        .env("POVW_MINT_ADDRESS", format!("{:#x}", ctx.povw_mint.address()))
+        .env("ZKC_ADDRESS", format!("{:#x}", ctx.zkc.address()))
+        .env("VEZKC_ADDRESS", format!("{:#x}", ctx.zkc_rewards.address()))
+        .env("PRIVATE_KEY", format!("{:#x}", tx_signer.to_bytes()))
+        .env("RISC0_DEV_MODE", "1")
+        .env("RPC_URL", rpc_url.as_str());
+    Ok(cmd)
+}
+
+/// Read the groups of work logs recorded as claimed in the given progress file.
+fn claimed_groups(progress_path: &Path) -> anyhow::Result<Vec<Vec<String>>> {
+    if !progress_path.exists() {
+        return Ok(vec![]);
+    }
+    let progress: serde_json::Value = serde_json::from_slice(&std::fs::read(progress_path)?)?;
+    Ok(progress["claims"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|claim| serde_json::from_value(claim["log_ids"].clone()).unwrap())
+        .collect())
+}
+
 /// Make a fake work receipt with the given log ID and a random job number, encode it, and save it to a file.
 fn make_fake_work_receipt_file(
     log_id: PovwLogId,
@@ -15,11 +15,20 @@
 //! Integration tests for ZKC-related CLI commands.
 
 use alloy::{
-    primitives::{utils::format_ether, U256},
+    consensus::BlockHeader,
+    eips::BlockNumberOrTag,
+    primitives::{utils::format_ether, Address, U256},
+    providers::{ext::AnvilApi, Provider, ProviderBuilder},
     signers::local::PrivateKeySigner,
 };
 use assert_cmd::Command;
+use boundless_market::contracts::token::IERC20;
 use boundless_test_utils::zkc::test_ctx;
+use boundless_zkc::{
+    units::{format_zkc, ZKC_DECIMALS},
+    unstake::{preview_unstake, WITHDRAWAL_PERIOD_SECS},
+    vote_power::{merkle_root, VotePowerSnapshot},
+};
 use predicates::str::contains;
 
 #[tokio::test]
@@ -138,6 +147,151 @@ async fn test_stake_unstake() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_unstake_preview() -> anyhow::Result<()> {
+    // Set up a local Anvil node with the required contracts
+    let ctx = test_ctx().await?;
+
+    // Use an Anvil-provided signer for transaction signing (with balance)
+    let user: PrivateKeySigner = ctx.anvil.lock().await.keys()[1].clone().into();
+    let user_private_key = format!("0x{}", hex::encode(user.to_bytes()));
+
+    // Fund the user
+    let amount = U256::from(1_000_000_000);
+    let stake_amount = format_ether(U256::from(500_000_000));
+    ctx.zkc.initialMint(vec![user.address()], vec![amount]).send().await?.watch().await?;
+
+    // Run stake
+    let mut cmd = Command::cargo_bin("boundless")?;
+    cmd.args(["zkc", "stake", "--amount", &stake_amount])
+        .env("ZKC_ADDRESS", format!("{:#x}", ctx.deployment.zkc_address))
+        .env("VEZKC_ADDRESS", format!("{:#x}", ctx.deployment.vezkc_address))
+        .env("STAKING_REWARDS_ADDRESS", format!("{:#x}", ctx.deployment.staking_rewards_address))
+        .env("RPC_URL", ctx.anvil.lock().await.endpoint_url().as_str())
+        .env("PRIVATE_KEY", &user_private_key)
+        .env("NO_COLOR", "1")
+        .env("RUST_LOG", "boundless_cli=debug,info")
+        .write_stdin("yes\n")
+        .assert()
+        .success();
+
+    // Run unstake preview, which must not initiate the unstake
+    let mut cmd = Command::cargo_bin("boundless")?;
+    cmd.args(["zkc", "unstake", "--preview"])
+        .env("ZKC_ADDRESS", format!("{:#x}", ctx.deployment.zkc_address))
+        .env("VEZKC_ADDRESS", format!("{:#x}", ctx.deployment.vezkc_address))
+        .env("STAKING_REWARDS_ADDRESS", format!("{:#x}", ctx.deployment.staking_rewards_address))
+        .env("RPC_URL", ctx.anvil.lock().await.endpoint_url().as_str())
+        .env("PRIVATE_KEY", &user_private_key)
+        .env("NO_COLOR", "1")
+        .env("RUST_LOG", "boundless_cli=debug,info")
+        .assert()
+        .success()
+        .stdout(contains("Estimated rewards forgone"));
+    let staked = ctx.vezkc.getStakedAmountAndWithdrawalTime(user.address()).call().await?;
+    assert_eq!(staked.withdrawableAt, U256::ZERO);
+
+    let block = ctx.provider.get_block_by_number(BlockNumberOrTag::Latest).await?.unwrap();
+    let preview = preview_unstake(
+        ctx.provider.clone(),
+        ctx.deployment.zkc_address,
+        ctx.deployment.vezkc_address,
+        user.address(),
+        block.header.timestamp(),
+    )
+    .await?;
+    assert!(!preview.epochs.is_empty());
+
+    // Keep staking through the withdrawal period and compare the estimate to the actual accruals
+    ctx.provider.anvil_set_time(preview.cooldown_end + 1).await?;
+    ctx.provider.anvil_mine(Some(1), None).await?;
+    let epochs: Vec<U256> = preview.epochs.iter().map(|epoch| epoch.epoch).collect();
+    let accrued = ctx.staking_rewards.calculateRewards(user.address(), epochs).call().await?;
+    for (estimate, actual) in preview.epochs.iter().zip(accrued.iter()) {
+        assert_eq!(estimate.rewards, *actual, "epoch {}", estimate.epoch);
+    }
+    assert_eq!(preview.forgone_rewards(), accrued.iter().sum::<U256>());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_export_vote_power() -> anyhow::Result<()> {
+    // Set up a local Anvil node with the required contracts
+    let ctx = test_ctx().await?;
+    let rpc_url = ctx.anvil.lock().await.endpoint_url();
+
+    // Stake with two Anvil-provided signers
+    let users: Vec<PrivateKeySigner> =
+        ctx.anvil.lock().await.keys()[1..3].iter().map(|key| key.clone().into()).collect();
+    for (i, user) in users.iter().enumerate() {
+        let amount = U256::from(1_000_000_000);
+        let stake_amount = format_ether(U256::from(100_000_000 * (i + 1)));
+        ctx.zkc.initialMint(vec![user.address()], vec![amount]).send().await?.watch().await?;
+
+        let mut cmd = Command::cargo_bin("boundless")?;
+        cmd.args(["zkc", "stake", "--amount", &stake_amount])
+            .env("ZKC_ADDRESS", format!("{:#x}", ctx.deployment.zkc_address))
+            .env("VEZKC_ADDRESS", format!("{:#x}", ctx.deployment.vezkc_address))
+            .env(
+                "STAKING_REWARDS_ADDRESS",
+                format!("{:#x}", ctx.deployment.staking_rewards_address),
+            )
+            .env("RPC_URL", rpc_url.as_str())
+            .env("PRIVATE_KEY", format!("0x{}", hex::encode(user.to_bytes())))
+            .env("NO_COLOR", "1")
+            .env("RUST_LOG", "boundless_cli=debug,info")
+            .write_stdin("yes\n")
+            .assert()
+            .success();
+    }
+
+    // Run export at the latest block
+    let block = ctx.provider.get_block_by_number(BlockNumberOrTag::Latest).await?.unwrap();
+    let out = tempfile::NamedTempFile::new()?;
+    let mut cmd = Command::cargo_bin("boundless")?;
+    cmd.args([
+        "zkc",
+        "export-vote-power",
+        "--block",
+        &block.header.number().to_string(),
+        "--out",
+        out.path().to_str().unwrap(),
+    ])
+    .env("ZKC_ADDRESS", format!("{:#x}", ctx.deployment.zkc_address))
+    .env("VEZKC_ADDRESS", format!("{:#x}", ctx.deployment.vezkc_address))
+    .env("STAKING_REWARDS_ADDRESS", format!("{:#x}", ctx.deployment.staking_rewards_address))
+    .env("RPC_URL", rpc_url.as_str())
+    .env("NO_COLOR", "1")
+    .env("RUST_LOG", "boundless_cli=debug,info")
+    .assert()
+    .success()
+    .stdout(contains("Exported vote power of 2 addresses"));
+
+    let snapshot: VotePowerSnapshot = serde_json::from_slice(&std::fs::read(out.path())?)?;
+    assert_eq!(snapshot.block, block.header.number());
+    assert_eq!(snapshot.merkle_root, merkle_root(&snapshot.powers));
+    let mut exported: Vec<Address> = snapshot.powers.iter().map(|entry| entry.address).collect();
+    let mut expected: Vec<Address> = users.iter().map(|user| user.address()).collect();
+    exported.sort();
+    expected.sort();
+    assert_eq!(exported, expected);
+
+    // Compare the export against the past votes recorded by the contract
+    ctx.provider.anvil_increase_time(10).await?;
+    ctx.provider.anvil_mine(Some(1), None).await?;
+    for entry in &snapshot.powers {
+        let votes = ctx
+            .vezkc
+            .getPastVotes(entry.address, U256::from(block.header.timestamp()))
+            .call()
+            .await?;
+        assert_eq!(entry.power, votes, "address {}", entry.address);
+    }
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_delegate_rewards() -> anyhow::Result<()> {
     // Set up a local Anvil node with the required contracts
@@ -198,6 +352,101 @@ async fn test_delegate_rewards() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_delegate_rewards_by_sig() -> anyhow::Result<()> {
+    // Set up a local Anvil node with the required contracts
+    let ctx = test_ctx().await?;
+
+    // The delegator signs offline, and a different account submits the signed delegation.
+    let delegator: PrivateKeySigner = ctx.anvil.lock().await.keys()[1].clone().into();
+    let delegator_private_key = format!("0x{}", hex::encode(delegator.to_bytes()));
+    let submitter: PrivateKeySigner = ctx.anvil.lock().await.keys()[2].clone().into();
+    let submitter_private_key = format!("0x{}", hex::encode(submitter.to_bytes()));
+    let delegatee: PrivateKeySigner = ctx.anvil.lock().await.keys()[3].clone().into();
+    let chain_id = ctx.provider.get_chain_id().await?;
+    let signed_path = tempfile::NamedTempFile::new()?.into_temp_path();
+
+    // Fund and stake as the delegator
+    let amount = U256::from(1_000_000_000);
+    let stake_amount = format_ether(U256::from(500_000_000));
+    ctx.zkc.initialMint(vec![delegator.address()], vec![amount]).send().await?.watch().await?;
+    let mut cmd = Command::cargo_bin("boundless")?;
+    cmd.args(["zkc", "stake", "--amount", &stake_amount])
+        .env("ZKC_ADDRESS", format!("{:#x}", ctx.deployment.zkc_address))
+        .env("VEZKC_ADDRESS", format!("{:#x}", ctx.deployment.vezkc_address))
+        .env("STAKING_REWARDS_ADDRESS", format!("{:#x}", ctx.deployment.staking_rewards_address))
+        .env("RPC_URL", ctx.anvil.lock().await.endpoint_url().as_str())
+        .env("PRIVATE_KEY", &delegator_private_key)
+        .env("NO_COLOR", "1")
+        .env("RUST_LOG", "boundless_cli=debug,info")
+        .write_stdin("yes\n")
+        .assert()
+        .success();
+
+    // Sign the delegation without an RPC URL
+    let mut cmd = Command::cargo_bin("boundless")?;
+    cmd.args(["zkc", "delegate-rewards", &format!("{:#x}", delegatee.address())])
+        .args(["--sign-only", "--out", signed_path.to_str().unwrap(), "--nonce", "0"])
+        .env("CHAIN_ID", chain_id.to_string())
+        .env("ZKC_ADDRESS", format!("{:#x}", ctx.deployment.zkc_address))
+        .env("VEZKC_ADDRESS", format!("{:#x}", ctx.deployment.vezkc_address))
+        .env("STAKING_REWARDS_ADDRESS", format!("{:#x}", ctx.deployment.staking_rewards_address))
+        .env_remove("RPC_URL")
+        .env("PRIVATE_KEY", &delegator_private_key)
+        .env("NO_COLOR", "1")
+        .env("RUST_LOG", "boundless_cli=debug,info")
+        .assert()
+        .success()
+        .stdout(contains("Signed delegation written to"));
+
+    // Submit the signed delegation, paying for gas as the submitter
+    let mut cmd = Command::cargo_bin("boundless")?;
+    cmd.args(["zkc", "delegate-rewards", "--submit", signed_path.to_str().unwrap()])
+        .env("ZKC_ADDRESS", format!("{:#x}", ctx.deployment.zkc_address))
+        .env("VEZKC_ADDRESS", format!("{:#x}", ctx.deployment.vezkc_address))
+        .env("STAKING_REWARDS_ADDRESS", format!("{:#x}", ctx.deployment.staking_rewards_address))
+        .env("RPC_URL", ctx.anvil.lock().await.endpoint_url().as_str())
+        .env("PRIVATE_KEY", &submitter_private_key)
+        .env("NO_COLOR", "1")
+        .env("RUST_LOG", "boundless_cli=debug,info")
+        .assert()
+        .success()
+        .stdout(contains(format!(
+            "Delegating rewards of {} to {} completed",
+            delegator.address(),
+            delegatee.address()
+        )));
+
+    // The rewards of the delegator are delegated
+    let mut cmd = Command::cargo_bin("boundless")?;
+    cmd.args(["zkc", "get-rewards-delegates", &format!("{:#x}", delegator.address())])
+        .env("ZKC_ADDRESS", format!("{:#x}", ctx.deployment.zkc_address))
+        .env("VEZKC_ADDRESS", format!("{:#x}", ctx.deployment.vezkc_address))
+        .env("STAKING_REWARDS_ADDRESS", format!("{:#x}", ctx.deployment.staking_rewards_address))
+        .env("RPC_URL", ctx.anvil.lock().await.endpoint_url().as_str())
+        .env("NO_COLOR", "1")
+        .env("RUST_LOG", "boundless_cli=debug,info")
+        .assert()
+        .success()
+        .stdout(contains(format!("{:#x}", delegatee.address())));
+
+    // The nonce was consumed, so the signed delegation can't be submitted again
+    let mut cmd = Command::cargo_bin("boundless")?;
+    cmd.args(["zkc", "delegate-rewards", "--submit", signed_path.to_str().unwrap()])
+        .env("ZKC_ADDRESS", format!("{:#x}", ctx.deployment.zkc_address))
+        .env("VEZKC_ADDRESS", format!("{:#x}", ctx.deployment.vezkc_address))
+        .env("STAKING_REWARDS_ADDRESS", format!("{:#x}", ctx.deployment.staking_rewards_address))
+        .env("RPC_URL", ctx.anvil.lock().await.endpoint_url().as_str())
+        .env("PRIVATE_KEY", &submitter_private_key)
+        .env("NO_COLOR", "1")
+        .env("RUST_LOG", "boundless_cli=debug,info")
+        .assert()
+        .failure()
+        .stderr(contains("but the next nonce of"));
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_get_epoch_end_time() -> anyhow::Result<()> {
     // Set up a local Anvil node with the required contracts
@@ -237,3 +486,267 @@ async fn test_get_current_epoch() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn test_claim_rewards_payout_to() -> anyhow::Result<()> {
+    // Set up a local Anvil node with the required contracts
+    let ctx = test_ctx().await?;
+
+    // Use an Anvil-provided signer for transaction signing (with balance)
+    let user: PrivateKeySigner = ctx.anvil.lock().await.keys()[1].clone().into();
+    let user_private_key = format!("0x{}", hex::encode(user.to_bytes()));
+    let cold_wallet: PrivateKeySigner = ctx.anvil.lock().await.keys()[2].clone().into();
+
+    // Fund the user
+    let amount = U256::from(1_000_000_000);
+    let stake_amount = format_ether(U256::from(500_000_000));
+    ctx.zkc.initialMint(vec![user.address()], vec![amount]).send().await?.watch().await?;
+
+    // Run stake
+    let mut cmd = Command::cargo_bin("boundless")?;
+    cmd.args(["zkc", "stake", "--amount", &stake_amount])
+        .env("ZKC_ADDRESS", format!("{:#x}", ctx.deployment.zkc_address))
+        .env("VEZKC_ADDRESS", format!("{:#x}", ctx.deployment.vezkc_address))
+        .env("STAKING_REWARDS_ADDRESS", format!("{:#x}", ctx.deployment.staking_rewards_address))
+        .env("RPC_URL", ctx.anvil.lock().await.endpoint_url().as_str())
+        .env("PRIVATE_KEY", &user_private_key)
+        .env("NO_COLOR", "1")
+        .env("RUST_LOG", "boundless_cli=debug,info")
+        .write_stdin("yes\n")
+        .assert()
+        .success();
+
+    // End the current epoch so that its staking rewards become claimable
+    let epoch = ctx.zkc.getCurrentEpoch().call().await?;
+    let epoch_end = ctx.zkc.getEpochEndTime(epoch).call().await?;
+    ctx.provider.anvil_set_time(epoch_end.to::<u64>() + 1).await?;
+    ctx.provider.anvil_mine(Some(1), None).await?;
+
+    let user_balance_before = ctx.zkc.balanceOf(user.address()).call().await?;
+
+    // Claim rewards, paying out to the cold wallet
+    let mut cmd = Command::cargo_bin("boundless")?;
+    cmd.args(["zkc", "claim-rewards", "--payout-to", &format!("{:#x}", cold_wallet.address())])
+        .env("ZKC_ADDRESS", format!("{:#x}", ctx.deployment.zkc_address))
+        .env("VEZKC_ADDRESS", format!("{:#x}", ctx.deployment.vezkc_address))
+        .env("STAKING_REWARDS_ADDRESS", format!("{:#x}", ctx.deployment.staking_rewards_address))
+        .env("RPC_URL", ctx.anvil.lock().await.endpoint_url().as_str())
+        .env("PRIVATE_KEY", &user_private_key)
+        .env("NO_COLOR", "1")
+        .env("RUST_LOG", "boundless_cli=debug,info")
+        .assert()
+        .success()
+        .stdout(contains("Paid out rewards"));
+
+    // The rewards landed in the cold wallet, and nothing was left behind in the signer
+    let cold_wallet_balance = ctx.zkc.balanceOf(cold_wallet.address()).call().await?;
+    assert!(cold_wallet_balance > U256::ZERO);
+    assert_eq!(ctx.zkc.balanceOf(user.address()).call().await?, user_balance_before);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_claim_rewards_payout_to_zero_address() -> anyhow::Result<()> {
+    // Set up a local Anvil node with the required contracts
+    let ctx = test_ctx().await?;
+
+    let user: PrivateKeySigner = ctx.anvil.lock().await.keys()[1].clone().into();
+    let user_private_key = format!("0x{}", hex::encode(user.to_bytes()));
+
+    let mut cmd = Command::cargo_bin("boundless")?;
+    cmd.args(["zkc", "claim-rewards", "--payout-to", &format!("{:#x}", Address::ZERO)])
+        .env("ZKC_ADDRESS", format!("{:#x}", ctx.deployment.zkc_address))
+        .env("VEZKC_ADDRESS", format!("{:#x}", ctx.deployment.vezkc_address))
+        .env("STAKING_REWARDS_ADDRESS", format!("{:#x}", ctx.deployment.staking_rewards_address))
+        .env("RPC_URL", ctx.anvil.lock().await.endpoint_url().as_str())
+        .env("PRIVATE_KEY", &user_private_key)
+        .env("NO_COLOR", "1")
+        .env("RUST_LOG", "boundless_cli=debug,info")
+        .assert()
+        .failure()
+        .stderr(contains("zero address"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_audit_allowances() -> anyhow::Result<()> {
+    // Set up a local Anvil node with the required contracts
+    let ctx = test_ctx().await?;
+    let rpc_url = ctx.anvil.lock().await.endpoint_url();
+
+    // Use an Anvil-provided signer for transaction signing (with balance)
+    let user: PrivateKeySigner = ctx.anvil.lock().await.keys()[1].clone().into();
+    let user_private_key = format!("0x{}", hex::encode(user.to_bytes()));
+    let other_spender = Address::repeat_byte(0x42);
+
+    // Grant an unlimited allowance to the staking contract and a limited one to another spender
+    let user_provider = ProviderBuilder::new().wallet(user.clone()).connect_http(rpc_url.clone());
+    let zkc = IERC20::new(ctx.deployment.zkc_address, user_provider);
+    zkc.approve(ctx.deployment.vezkc_address, U256::MAX).send().await?.watch().await?;
+    zkc.approve(other_spender, U256::from(100)).send().await?.watch().await?;
+
+    let audit_cmd = |extra_args: &[String]| -> anyhow::Result<Command> {
+        let mut cmd = Command::cargo_bin("boundless")?;
+        cmd.args(["zkc", "audit-allowances", &format!("{:#x}", user.address())])
+            .args(extra_args)
+            .env("ZKC_ADDRESS", format!("{:#x}", ctx.deployment.zkc_address))
+            .env("VEZKC_ADDRESS", format!("{:#x}", ctx.deployment.vezkc_address))
+            .env(
+                "STAKING_REWARDS_ADDRESS",
+                format!("{:#x}", ctx.deployment.staking_rewards_address),
+            )
+            .env("RPC_URL", rpc_url.as_str())
+            .env("PRIVATE_KEY", &user_private_key)
+            .env("NO_COLOR", "1")
+            .env("RUST_LOG", "boundless_cli=debug,info");
+        Ok(cmd)
+    };
+
+    // Audit the allowances, including the additional spender
+    audit_cmd(&["--spender".into(), format!("{other_spender:#x}")])?
+        .assert()
+        .success()
+        .stdout(contains("unlimited"))
+        .stdout(contains(ctx.deployment.vezkc_address.to_string()))
+        .stdout(contains(other_spender.to_string()))
+        .stdout(contains(format_zkc(U256::from(100), ZKC_DECIMALS)))
+        .stdout(contains("Found 1 unlimited allowances"));
+
+    // Revoke the allowance of the staking contract
+    audit_cmd(&["--revoke".into(), format!("{:#x}", ctx.deployment.vezkc_address)])?
+        .assert()
+        .success()
+        .stdout(contains("Revoked the ZKC allowance"));
+
+    assert_eq!(
+        zkc.allowance(user.address(), ctx.deployment.vezkc_address).call().await?,
+        U256::ZERO
+    );
+    assert_eq!(zkc.allowance(user.address(), other_spender).call().await?, U256::from(100));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_withdrawal_status() -> anyhow::Result<()> {
+    // Set up a local Anvil node with the required contracts
+    let ctx = test_ctx().await?;
+    let rpc_url = ctx.anvil.lock().await.endpoint_url();
+
+    // Use an Anvil-provided signer for transaction signing (with balance)
+    let user: PrivateKeySigner = ctx.anvil.lock().await.keys()[1].clone().into();
+    let user_private_key = format!("0x{}", hex::encode(user.to_bytes()));
+
+    // Fund the user
+    let amount = U256::from(1_000_000_000);
+    ctx.zkc.initialMint(vec![user.address()], vec![amount]).send().await?.watch().await?;
+
+    let zkc_cmd = |args: &[&str]| -> anyhow::Result<Command> {
+        let mut cmd = Command::cargo_bin("boundless")?;
+        cmd.arg("zkc")
+            .args(args)
+            .env("ZKC_ADDRESS", format!("{:#x}", ctx.deployment.zkc_address))
+            .env("VEZKC_ADDRESS", format!("{:#x}", ctx.deployment.vezkc_address))
+            .env(
+                "STAKING_REWARDS_ADDRESS",
+                format!("{:#x}", ctx.deployment.staking_rewards_address),
+            )
+            .env("RPC_URL", rpc_url.as_str())
+            .env("PRIVATE_KEY", &user_private_key)
+            .env("NO_COLOR", "1")
+            .env("RUST_LOG", "boundless_cli=debug,info");
+        Ok(cmd)
+    };
+    let account = format!("{:#x}", user.address());
+
+    // Stake and initiate the unstake
+    zkc_cmd(&["stake", "--amount", &format_ether(amount)])?.write_stdin("yes\n").assert().success();
+    zkc_cmd(&["withdrawal-status", &account])?
+        .assert()
+        .success()
+        .stdout(contains("Withdrawal status: no withdrawal initiated"));
+    zkc_cmd(&["unstake"])?.write_stdin("yes\n").assert().success();
+    zkc_cmd(&["withdrawal-status", &account])?
+        .assert()
+        .success()
+        .stdout(contains("Withdrawal status: pending"));
+
+    // The withdrawal is ready once the withdrawal period ends
+    ctx.provider.anvil_increase_time(WITHDRAWAL_PERIOD_SECS + 1).await?;
+    ctx.provider.anvil_mine(Some(1), None).await?;
+    zkc_cmd(&["withdrawal-status", &account])?
+        .assert()
+        .success()
+        .stdout(contains("Withdrawal status: ready"));
+
+    // And overdue once it has been ready for longer than the threshold without being completed
+    ctx.provider.anvil_increase_time(8 * 24 * 60 * 60).await?;
+    ctx.provider.anvil_mine(Some(1), None).await?;
+    zkc_cmd(&["withdrawal-status", &account])?
+        .assert()
+        .success()
+        .stdout(contains("Withdrawal status: overdue"))
+        .stdout(contains("(8 days)"));
+    zkc_cmd(&["withdrawal-status", &account, "--overdue-days", "10"])?
+        .assert()
+        .success()
+        .stdout(contains("Withdrawal status: ready"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_transaction_cost_preview() -> anyhow::Result<()> {
+    // Set up a local Anvil node with the required contracts
+    let ctx = test_ctx().await?;
+    let rpc_url = ctx.anvil.lock().await.endpoint_url();
+
+    // Use an Anvil-provided signer for transaction signing (with balance)
+    let user: PrivateKeySigner = ctx.anvil.lock().await.keys()[1].clone().into();
+    let user_private_key = format!("0x{}", hex::encode(user.to_bytes()));
+
+    // Fund the user
+    let amount = U256::from(1_000_000_000);
+    let stake_amount = format_ether(U256::from(500_000_000));
+    ctx.zkc.initialMint(vec![user.address()], vec![amount]).send().await?.watch().await?;
+
+    let zkc_cmd = |args: &[&str]| -> anyhow::Result<Command> {
+        let mut cmd = Command::cargo_bin("boundless")?;
+        cmd.arg("zkc")
+            .args(args)
+            .env("ZKC_ADDRESS", format!("{:#x}", ctx.deployment.zkc_address))
+            .env("VEZKC_ADDRESS", format!("{:#x}", ctx.deployment.vezkc_address))
+            .env(
+                "STAKING_REWARDS_ADDRESS",
+                format!("{:#x}", ctx.deployment.staking_rewards_address),
+            )
+            .env("RPC_URL", rpc_url.as_str())
+            .env("PRIVATE_KEY", &user_private_key)
+            .env("NO_COLOR", "1")
+            .env("RUST_LOG", "boundless_cli=debug,info");
+        Ok(cmd)
+    };
+
+    // The cost is previewed before both the stake and the unstake are sent
+    zkc_cmd(&["stake", "--amount", &stake_amount])?
+        .write_stdin("yes\n")
+        .assert()
+        .success()
+        .stdout(contains("Estimated cost: up to"));
+    zkc_cmd(&["unstake"])?
+        .write_stdin("yes\n")
+        .assert()
+        .success()
+        .stdout(contains("Estimated cost: up to"));
+
+    // A call that would revert fails at estimation, before anything is sent
+    zkc_cmd(&["stake", "--amount", &stake_amount])?
+        .assert()
+        .failure()
+        .stderr(contains("Failed to estimate gas"))
+        .stderr(contains("CannotAddToWithdrawingPosition"));
+
+    Ok(())
+}
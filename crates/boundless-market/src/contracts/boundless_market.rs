@@ -1101,6 +1101,53 @@ impl<P: Provider> BoundlessMarketService<P> {
         Err(MarketError::RequestNotFound(request_id))
     }
 
+    /// Query the RequestLocked event based on request ID and block options.
+    ///
+    /// Searches the blocks the same way as [Self::query_request_submitted_event].
+    async fn query_request_locked_event(
+        &self,
+        request_id: U256,
+        lower_bound: Option<u64>,
+        upper_bound: Option<u64>,
+    ) -> Result<(ProofRequest, Address), MarketError> {
+        let mut upper_block = upper_bound.unwrap_or(self.get_latest_block_number().await?);
+        let start_block = lower_bound.unwrap_or(upper_block.saturating_sub(
+            self.event_query_config.block_range * self.event_query_config.max_iterations,
+        ));
+
+        // Loop to progressively search through blocks
+        for _ in 0..self.event_query_config.max_iterations {
+            // If the current end block is less than or equal to the starting block, stop searching
+            if upper_block <= start_block {
+                break;
+            }
+
+            // Calculate the block range to query: from [lower_block] to [upper_block]
+            let lower_block = upper_block.saturating_sub(self.event_query_config.block_range);
+
+            // Set up the event filter for the specified block range
+            let mut event_filter = self.instance.RequestLocked_filter();
+            event_filter.filter = event_filter
+                .filter
+                .topic1(request_id)
+                .from_block(lower_block)
+                .to_block(upper_block);
+
+            // Query the logs for the event
+            let logs = event_filter.query().await?;
+
+            if let Some((event, _)) = logs.first() {
+                return Ok((event.request.clone(), event.prover));
+            }
+
+            // Move the upper_block down for the next iteration
+            upper_block = lower_block.saturating_sub(1);
+        }
+
+        // Return error if no logs are found after all iterations
+        Err(MarketError::RequestNotFound(request_id))
+    }
+
     /// Returns fulfillment data and seal if the request is fulfilled.
     pub async fn get_request_fulfillment(
         &self,
@@ -1154,6 +1201,15 @@ impl<P: Provider> BoundlessMarketService<P> {
         self.query_request_submitted_event(request_id, None, None).await
     }
 
+    /// Returns the proof request and the address of the prover that locked it, for a locked
+    /// request.
+    pub async fn get_locked_request(
+        &self,
+        request_id: U256,
+    ) -> Result<(ProofRequest, Address), MarketError> {
+        self.query_request_locked_event(request_id, None, None).await
+    }
+
     /// Returns the fulfillment data and seal if the request is fulfilled.
     ///
     /// This method will poll the status of the request until it is Fulfilled or Expired.
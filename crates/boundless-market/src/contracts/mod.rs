@@ -104,8 +104,10 @@ pub mod token {
             error ERC20InsufficientAllowance(address spender, uint256 allowance, uint256 needed);
             error ERC20InvalidApprover(address approver);
             error ERC20InvalidSpender(address spender);
+            function allowance(address owner, address spender) external view returns (uint256);
             function approve(address spender, uint256 value) external returns (bool);
             function balanceOf(address account) external view returns (uint256);
+            function transfer(address to, uint256 value) external returns (bool);
             function symbol() external view returns (string memory);
             function decimals() external view returns (uint8);
         }
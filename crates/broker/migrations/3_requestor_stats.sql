@@ -0,0 +1,5 @@
+CREATE TABLE requestor_stats (
+    requestor TEXT PRIMARY KEY,
+    orders INTEGER NOT NULL DEFAULT 0,
+    preflight_failures INTEGER NOT NULL DEFAULT 0
+);
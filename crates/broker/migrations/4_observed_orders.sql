@@ -0,0 +1,9 @@
+CREATE TABLE observed_orders (
+    id TEXT PRIMARY KEY,
+    requestor TEXT NOT NULL,
+    fulfillment_type TEXT NOT NULL,
+    revenue TEXT NOT NULL,
+    collateral_reward TEXT NOT NULL,
+    gas_cost TEXT NOT NULL,
+    observed_at INTEGER NOT NULL
+);
@@ -803,6 +803,7 @@ mod tests {
             chain_id,
             total_cycles: None,
             proving_started_at: None,
+            fulfillment_path: None,
             cached_id: Default::default(),
         };
         db.add_order(&order).await.unwrap();
@@ -848,6 +849,7 @@ mod tests {
             chain_id,
             total_cycles: None,
             proving_started_at: None,
+            fulfillment_path: None,
             cached_id: Default::default(),
         };
         db.add_order(&order).await.unwrap();
@@ -961,6 +963,7 @@ mod tests {
             chain_id,
             total_cycles: None,
             proving_started_at: None,
+            fulfillment_path: None,
             cached_id: Default::default(),
         };
         db.add_order(&order).await.unwrap();
@@ -1021,6 +1024,7 @@ mod tests {
             chain_id,
             total_cycles: None,
             proving_started_at: None,
+            fulfillment_path: None,
             cached_id: Default::default(),
         };
         db.add_order(&order).await.unwrap();
@@ -1130,6 +1134,7 @@ mod tests {
             chain_id,
             total_cycles: None,
             proving_started_at: None,
+            fulfillment_path: None,
             cached_id: Default::default(),
         };
         db.add_order(&order).await.unwrap();
@@ -1242,6 +1247,7 @@ mod tests {
             chain_id,
             total_cycles: None,
             proving_started_at: None,
+            fulfillment_path: None,
             cached_id: Default::default(),
         };
         db.add_order(&order).await.unwrap();
@@ -1362,6 +1368,7 @@ mod tests {
             chain_id,
             total_cycles: None,
             proving_started_at: None,
+            fulfillment_path: None,
             cached_id: Default::default(),
         };
 
@@ -1402,6 +1409,7 @@ mod tests {
             chain_id,
             total_cycles: None,
             proving_started_at: None,
+            fulfillment_path: None,
             cached_id: Default::default(),
         };
 
@@ -1469,6 +1477,7 @@ mod tests {
             chain_id: 1,
             total_cycles: None,
             proving_started_at: None,
+            fulfillment_path: None,
             cached_id: Default::default(),
         };
         db.add_order(&expired_order).await.unwrap();
@@ -1505,6 +1514,7 @@ mod tests {
             chain_id: 1,
             total_cycles: None,
             proving_started_at: None,
+            fulfillment_path: None,
             cached_id: Default::default(),
         };
         db.add_order(&valid_order).await.unwrap();
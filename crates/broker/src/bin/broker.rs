@@ -18,7 +18,7 @@ use alloy::{
     rpc::client::RpcClient,
     transports::layers::RetryBackoffLayer,
 };
-use anyhow::{Context, Result};
+use anyhow::{ensure, Context, Result};
 use boundless_market::{
     balance_alerts_layer::{BalanceAlertConfig, BalanceAlertLayer},
     contracts::boundless_market::BoundlessMarketService,
@@ -90,6 +90,10 @@ async fn main() -> Result<()> {
 
     // TODO: Move this code somewhere else / monitor our balanceOf and top it up as needed
     if let Some(deposit_amount) = args.deposit_amount.as_ref() {
+        ensure!(
+            !config.market.observation_mode,
+            "Refusing to deposit collateral in observation mode, remove --deposit-amount"
+        );
         let boundless_market = BoundlessMarketService::new(
             broker.deployment().boundless_market_address,
             provider.clone(),
@@ -0,0 +1,132 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tracking of the market collateral available to lock orders, to skip orders the prover cannot
+//! afford before spending pricing work on them.
+
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use alloy::primitives::U256;
+
+/// Time a fetched collateral balance is used for before it is fetched again, as long as pricing
+/// is pinned to the block it was read at.
+pub(crate) const COLLATERAL_BALANCE_TTL: Duration = Duration::from_secs(12);
+
+/// Cached market collateral balance of the prover, along with the collateral reserved by orders
+/// priced for locking that have not been locked yet.
+#[derive(Debug, Default)]
+pub(crate) struct CollateralTracker {
+    /// Cached balance, with the block it was read at and the time it was fetched.
+    balance: Option<(U256, u64, Instant)>,
+    /// Reserved collateral by request ID, with the timestamp at which the lock expires.
+    reserved: HashMap<U256, (U256, u64)>,
+}
+
+impl CollateralTracker {
+    /// Return the cached balance minus the reserved collateral, or None if the cached balance is
+    /// missing, stale, or was read at another block than the given one, and must be fetched again.
+    pub(crate) fn available(&mut self, block: u64, now: Instant, now_secs: u64) -> Option<U256> {
+        let (balance, read_at, fetched_at) = self.balance?;
+        if now.saturating_duration_since(fetched_at) >= COLLATERAL_BALANCE_TTL {
+            self.balance = None;
+            return None;
+        }
+        if read_at != block {
+            return None;
+        }
+        // Orders that were not locked before their lock expired no longer need collateral.
+        self.reserved.retain(|_, (_, lock_expires_at)| *lock_expires_at > now_secs);
+        let reserved = self
+            .reserved
+            .values()
+            .fold(U256::ZERO, |total, (collateral, _)| total.saturating_add(*collateral));
+        Some(balance.saturating_sub(reserved))
+    }
+
+    /// Set the balance fetched from the market contract at the given block.
+    pub(crate) fn set_balance(&mut self, balance: U256, block: u64, now: Instant) {
+        self.balance = Some((balance, block, now));
+    }
+
+    /// Reserve the collateral for an order priced for locking, until it is locked or the lock
+    /// expires.
+    pub(crate) fn reserve(&mut self, request_id: U256, collateral: U256, lock_expires_at: u64) {
+        self.reserved.insert(request_id, (collateral, lock_expires_at));
+    }
+
+    /// Release the collateral reserved for a request that has been locked, by this prover or
+    /// another, or dropped before locking. The cached balance is dropped, as it changes when this
+    /// prover locks.
+    pub(crate) fn release(&mut self, request_id: U256) {
+        if self.reserved.remove(&request_id).is_some() {
+            self.balance = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserved_collateral_is_not_available() {
+        let mut tracker = CollateralTracker::default();
+        let now = Instant::now();
+        assert_eq!(tracker.available(1, now, 100), None);
+
+        tracker.set_balance(U256::from(150), 1, now);
+        tracker.reserve(U256::from(1), U256::from(100), 200);
+        assert_eq!(tracker.available(1, now, 100), Some(U256::from(50)));
+
+        // Reservations are dropped once the lock expires.
+        assert_eq!(tracker.available(1, now, 200), Some(U256::from(150)));
+    }
+
+    #[test]
+    fn release_drops_cached_balance() {
+        let mut tracker = CollateralTracker::default();
+        let now = Instant::now();
+        tracker.set_balance(U256::from(150), 1, now);
+        tracker.reserve(U256::from(1), U256::from(100), 200);
+
+        tracker.release(U256::from(2));
+        assert_eq!(tracker.available(1, now, 100), Some(U256::from(50)));
+        tracker.release(U256::from(1));
+        assert_eq!(tracker.available(1, now, 100), None);
+        tracker.set_balance(U256::from(50), 1, now);
+        assert_eq!(tracker.available(1, now, 100), Some(U256::from(50)));
+    }
+
+    #[test]
+    fn stale_balance_is_refetched() {
+        let mut tracker = CollateralTracker::default();
+        let now = Instant::now();
+        tracker.set_balance(U256::from(150), 1, now);
+        assert_eq!(tracker.available(1, now + COLLATERAL_BALANCE_TTL, 100), None);
+    }
+
+    #[test]
+    fn balance_read_at_other_block_is_refetched() {
+        let mut tracker = CollateralTracker::default();
+        let now = Instant::now();
+        tracker.set_balance(U256::from(150), 1, now);
+        assert_eq!(tracker.available(2, now, 100), None);
+
+        tracker.set_balance(U256::from(50), 2, now);
+        assert_eq!(tracker.available(2, now, 100), Some(U256::from(50)));
+    }
+}
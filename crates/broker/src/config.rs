@@ -13,12 +13,12 @@
 // limitations under the License.
 
 use std::{
-    collections::HashSet,
+    collections::{BTreeMap, HashSet},
     path::{Path, PathBuf},
     sync::{Arc, RwLock},
 };
 
-use alloy::primitives::Address;
+use alloy::primitives::{utils::parse_ether, Address};
 use anyhow::{Context, Result};
 use notify::{EventKind, Watcher};
 use serde::{Deserialize, Serialize};
@@ -29,7 +29,7 @@ use tokio::{
     time::{timeout, Duration},
 };
 
-use crate::{errors::CodedError, impl_coded_debug};
+use crate::{errors::CodedError, impl_coded_debug, latency};
 
 mod defaults {
     pub const fn max_journal_bytes() -> usize {
@@ -57,6 +57,29 @@ mod defaults {
         250_000
     }
 
+    pub const fn aggregation_batch_gas_estimate() -> u64 {
+        // Submitting a batch verifies the Groth16 proof of the aggregation root (~250k gas) and
+        // stores the root.
+        300_000
+    }
+
+    pub const fn aggregation_inclusion_gas_estimate() -> u64 {
+        // Verifying the Merkle inclusion proof of an order against a submitted root.
+        30_000
+    }
+
+    pub const fn aggregation_batch_history() -> u32 {
+        20
+    }
+
+    pub const fn max_pricing_block_drift() -> u64 {
+        10
+    }
+
+    pub const fn requestor_reputation_min_orders() -> u64 {
+        20
+    }
+
     pub const fn additional_proof_cycles() -> u64 {
         // 2 mcycles for assessor + 270k cycles for set builder by default
         2_000_000 + 270_000
@@ -74,10 +97,42 @@ mod defaults {
         10800
     }
 
+    pub const fn saturation_poll_secs() -> u64 {
+        10
+    }
+
     pub const fn max_concurrent_preflights() -> u32 {
         4
     }
 
+    pub const fn order_dedup_cache_size() -> usize {
+        5000
+    }
+
+    pub const fn order_dedup_cache_ttl_secs() -> u64 {
+        60 * 60
+    }
+
+    pub const fn preflight_cache_size() -> u64 {
+        5000
+    }
+
+    pub const fn preflight_cache_ttl_secs() -> u64 {
+        3 * 60 * 60
+    }
+
+    pub const fn price_oracle_ttl_secs() -> u64 {
+        60
+    }
+
+    pub const fn price_oracle_max_staleness_secs() -> u64 {
+        10 * 60
+    }
+
+    pub fn groth16_compression_cost() -> String {
+        "0".to_string()
+    }
+
     pub fn assessor_default_image_url() -> String {
         "https://signal-artifacts.beboundless.xyz/v3/assessor/assessor_guest.bin".to_string()
     }
@@ -85,6 +140,10 @@ mod defaults {
     pub fn set_builder_default_image_url() -> String {
         "https://signal-artifacts.beboundless.xyz/v2/set-builder/guest.bin".to_string()
     }
+
+    pub fn order_filters() -> Vec<String> {
+        ["expiry_margin", "requestor_lists", "max_collateral"].map(String::from).to_vec()
+    }
 }
 
 /// Order pricing priority mode for determining which orders to price first
@@ -121,6 +180,40 @@ impl Default for OrderCommitmentPriority {
     }
 }
 
+/// Proof path used to fulfill an order
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FulfillmentPath {
+    /// Aggregate the proof into a batch and fulfill with a set inclusion receipt
+    Inclusion,
+    /// Compress the proof and fulfill with a standalone Groth16 receipt
+    Groth16,
+}
+
+/// Source of the exchange rate between the collateral token and the native token
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PriceOracleConf {
+    /// Chainlink feed quoting the collateral token in the native token
+    Chainlink { feed_address: Address },
+    /// HTTP endpoint responding with a JSON object such as `{"price": "0.00005"}`, giving the
+    /// price of one whole collateral token in the native token
+    Http { url: String },
+}
+
+/// Size bracket of orders, with its own cap on concurrent proofs
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Eq)]
+pub struct SizeBracket {
+    /// Name of the bracket, used in logs
+    pub name: String,
+    /// Exclusive upper bound on the cycle count of orders in the bracket, in mcycles
+    ///
+    /// Unset for the last bracket, which holds all orders larger than the previous brackets.
+    pub max_mcycles: Option<u64>,
+    /// Maximum number of orders in the bracket to be committed to at once
+    pub max_concurrent_proofs: u32,
+}
+
 /// All configuration related to markets mechanics
 #[derive(Debug, Deserialize, Serialize)]
 #[non_exhaustive]
@@ -183,6 +276,14 @@ pub struct MarketConf {
     ///
     /// If enabled, all requests from clients in the deny list are skipped.
     pub deny_requestor_addresses: Option<HashSet<Address>>,
+    /// Filters run on each order before pricing, in order, until one of them skips the order
+    ///
+    /// The built-in filters are `expiry_margin`, applying `min_deadline`, `requestor_lists`,
+    /// applying `allow_client_addresses` and `deny_requestor_addresses`, and `max_collateral`.
+    /// Custom filters registered with the broker can be listed by name. An unknown filter fails
+    /// startup, and on reload the previous filters are kept.
+    #[serde(default = "defaults::order_filters")]
+    pub order_filters: Vec<String>,
     /// lockRequest priority gas
     ///
     /// Optional additional gas to add to the transaction for lockinRequest, good
@@ -211,6 +312,47 @@ pub struct MarketConf {
     /// conservative default will be used.
     #[serde(default = "defaults::groth16_verify_gas_estimate")]
     pub groth16_verify_gas_estimate: u64,
+    /// Per-order share of the gas cost of verifying an aggregated batch
+    ///
+    /// Used to compare the aggregation and Groth16 fulfillment paths for orders that accept either
+    /// proof type, and included in the gas estimate of orders fulfilled through aggregation. If
+    /// zero, the share is estimated from the batch posting cost and recent batch sizes instead,
+    /// once the broker has submitted a batch.
+    #[serde(default)]
+    pub aggregation_amortized_gas_estimate: u64,
+    /// Gas estimate for posting an aggregated batch, including the Groth16 verification of its root
+    ///
+    /// Shared between the orders of a batch when estimating the cost of the aggregation path.
+    #[serde(default = "defaults::aggregation_batch_gas_estimate")]
+    pub aggregation_batch_gas_estimate: u64,
+    /// Gas estimate for fulfilling a single order included in an aggregated batch
+    ///
+    /// Added to the order's share of the batch posting cost.
+    #[serde(default = "defaults::aggregation_inclusion_gas_estimate")]
+    pub aggregation_inclusion_gas_estimate: u64,
+    /// Number of recently submitted batches used to estimate the expected batch size
+    ///
+    /// With no submitted batches, `aggregation_amortized_gas_estimate` is used as is.
+    #[serde(default = "defaults::aggregation_batch_history")]
+    pub aggregation_batch_history: u32,
+    /// Cost of compressing a proof to Groth16, denominated in the native token (e.g. ETH)
+    ///
+    /// Added to the cost of the Groth16 fulfillment path when choosing how to fulfill orders that
+    /// accept either proof type.
+    #[serde(default = "defaults::groth16_compression_cost")]
+    pub groth16_compression_cost: String,
+    /// Force the fulfillment path for orders that accept either proof type
+    ///
+    /// If unset, the cheapest path is chosen based on the gas estimates and the Groth16
+    /// compression cost. Orders that require a specific proof type always use that proof type.
+    pub fulfillment_path: Option<FulfillmentPath>,
+    /// Number of orders from a requestor to price before its reputation is applied
+    ///
+    /// Requestors with most of their orders invalid (guest panics or unsatisfied predicates) are
+    /// priced after other requestors. Requestors with fewer priced orders than this keep a neutral
+    /// reputation.
+    #[serde(default = "defaults::requestor_reputation_min_orders")]
+    pub requestor_reputation_min_orders: u64,
     /// Additional cycles to be proven for each order.
     ///
     /// This is currently the sum of the cycles for the assessor and set builder.
@@ -273,6 +415,98 @@ pub struct MarketConf {
     /// - "shortest_expiry": Process orders by shortest expiry first (lock expiry for lock-and-fulfill orders, request expiry for others)
     #[serde(default, alias = "expired_order_fulfillment_priority")]
     pub order_commitment_priority: OrderCommitmentPriority,
+    /// Maximum number of order IDs retained for deduplication
+    ///
+    /// Orders still being priced are never evicted. Once this limit is exceeded, the orders that
+    /// finished pricing the longest time ago are evicted first.
+    #[serde(default = "defaults::order_dedup_cache_size")]
+    pub order_dedup_cache_size: usize,
+    /// Seconds to retain an order ID for deduplication after it has finished pricing
+    #[serde(default = "defaults::order_dedup_cache_ttl_secs")]
+    pub order_dedup_cache_ttl_secs: u64,
+    /// Maximum number of preflight results to cache
+    ///
+    /// Read on startup.
+    #[serde(default = "defaults::preflight_cache_size")]
+    pub preflight_cache_size: u64,
+    /// Seconds to retain a cached preflight result
+    ///
+    /// Read on startup.
+    #[serde(default = "defaults::preflight_cache_ttl_secs")]
+    pub preflight_cache_ttl_secs: u64,
+    /// Optional price oracle for the collateral token, denominated in the native token
+    ///
+    /// Used to convert the cost of locking collateral into the native token when pricing orders.
+    /// Read on startup.
+    pub price_oracle: Option<PriceOracleConf>,
+    /// Seconds to cache the collateral token price from the price oracle
+    ///
+    /// If refreshing the price fails, the last known price is used and the refresh is retried
+    /// with exponential backoff.
+    #[serde(default = "defaults::price_oracle_ttl_secs")]
+    pub price_oracle_ttl_secs: u64,
+    /// Max age in seconds of the last known collateral token price used when refreshing it fails
+    ///
+    /// Orders to lock are skipped while the price is older, rather than priced without the cost
+    /// of their collateral. Only applies if `collateral_cost_bps` is set.
+    #[serde(default = "defaults::price_oracle_max_staleness_secs")]
+    pub price_oracle_max_staleness_secs: u64,
+    /// Cost of locking collateral, in basis points of the lock collateral of an order
+    ///
+    /// Accounts for the risk of being slashed and the opportunity cost of the locked collateral.
+    /// Converted to the native token using the price oracle, and added to the cost of lock and
+    /// fulfill orders when pricing them. Ignored if no price oracle is configured.
+    #[serde(default)]
+    pub collateral_cost_bps: u32,
+    /// Optional maximum number of orders per minute to price from a single requestor
+    ///
+    /// Protects pricing capacity from requestors flooding the market with orders. Orders over the
+    /// limit stay queued and are priced once the requestor is back under the limit, those held
+    /// back the longest first. Orders from priority requestors are not limited unless
+    /// `rate_limit_priority_requestors` is set.
+    pub requestor_pricing_rate_limit: Option<u32>,
+    /// Number of orders a requestor can have priced at once before the rate limit applies
+    ///
+    /// Defaults to the per minute rate limit.
+    pub requestor_pricing_burst: Option<u32>,
+    /// Whether the requestor pricing rate limit also applies to priority requestors
+    #[serde(default)]
+    pub rate_limit_priority_requestors: bool,
+    /// Run the broker without committing to any new order
+    ///
+    /// Orders are priced and prioritized as usual, but instead of locking and proving them, the
+    /// broker records the orders it would have committed to, along with their hypothetical revenue
+    /// and gas cost. Useful to evaluate a pricing configuration against live market traffic.
+    ///
+    /// Turning the mode on while the broker has committed orders only stops new commitments.
+    /// Orders that are already locked or committed are still proven and fulfilled, so that their
+    /// collateral is not slashed.
+    #[serde(default)]
+    pub observation_mode: bool,
+    /// Optional order size brackets, each with its own cap on concurrent proofs
+    ///
+    /// Orders are classified by their cycle count, and each bracket is filled independently so
+    /// that large orders cannot starve small ones. Orders whose cycle count is unknown belong to
+    /// the last bracket. Brackets must be listed from smallest to largest. `max_concurrent_proofs`
+    /// still applies to the total.
+    pub size_brackets: Option<Vec<SizeBracket>>,
+    /// Whether brackets with idle capacity lend it to orders of adjacent brackets
+    #[serde(default)]
+    pub lend_idle_bracket_capacity: bool,
+    /// Optional latency budgets, in milliseconds, of the stages from observing an order to the
+    /// inclusion of its lock transaction
+    ///
+    /// Keys are stage names: `pricing_queue`, `pricing_setup`, `preflight`, `pricing_evaluation`,
+    /// `commitment_queue`, `lock_submission` and `lock_inclusion`, or `total` for the end-to-end
+    /// latency. A warning is logged for each locked order that exceeds a budget.
+    pub lock_latency_budgets_ms: Option<BTreeMap<String, u64>>,
+    /// Max number of blocks the chain can move between pricing an order and committing to it
+    ///
+    /// Pricing reads all chain state at a single block. Orders priced against a block further
+    /// behind the chain head by the time they are committed to are priced again. Blocks spent
+    /// waiting for the target time of an order are not counted.
+    #[serde(default = "defaults::max_pricing_block_drift")]
+    pub max_pricing_block_drift: u64,
 }
 
 impl Default for MarketConf {
@@ -292,12 +526,20 @@ impl Default for MarketConf {
             max_collateral: "0.1".to_string(),
             allow_client_addresses: None,
             deny_requestor_addresses: None,
+            order_filters: defaults::order_filters(),
             lockin_priority_gas: None,
             max_file_size: 50_000_000,
             max_fetch_retries: Some(2),
             lockin_gas_estimate: defaults::lockin_gas_estimate(),
             fulfill_gas_estimate: defaults::fulfill_gas_estimate(),
             groth16_verify_gas_estimate: defaults::groth16_verify_gas_estimate(),
+            aggregation_amortized_gas_estimate: 0,
+            aggregation_batch_gas_estimate: defaults::aggregation_batch_gas_estimate(),
+            aggregation_inclusion_gas_estimate: defaults::aggregation_inclusion_gas_estimate(),
+            aggregation_batch_history: defaults::aggregation_batch_history(),
+            groth16_compression_cost: defaults::groth16_compression_cost(),
+            fulfillment_path: None,
+            requestor_reputation_min_orders: defaults::requestor_reputation_min_orders(),
             additional_proof_cycles: defaults::additional_proof_cycles(),
             balance_warn_threshold: None,
             balance_error_threshold: None,
@@ -310,6 +552,22 @@ impl Default for MarketConf {
             max_concurrent_preflights: defaults::max_concurrent_preflights(),
             order_pricing_priority: OrderPricingPriority::default(),
             order_commitment_priority: OrderCommitmentPriority::default(),
+            order_dedup_cache_size: defaults::order_dedup_cache_size(),
+            order_dedup_cache_ttl_secs: defaults::order_dedup_cache_ttl_secs(),
+            preflight_cache_size: defaults::preflight_cache_size(),
+            preflight_cache_ttl_secs: defaults::preflight_cache_ttl_secs(),
+            price_oracle: None,
+            price_oracle_ttl_secs: defaults::price_oracle_ttl_secs(),
+            price_oracle_max_staleness_secs: defaults::price_oracle_max_staleness_secs(),
+            collateral_cost_bps: 0,
+            requestor_pricing_rate_limit: None,
+            requestor_pricing_burst: None,
+            rate_limit_priority_requestors: false,
+            observation_mode: false,
+            size_brackets: None,
+            lend_idle_bracket_capacity: false,
+            lock_latency_budgets_ms: None,
+            max_pricing_block_drift: defaults::max_pricing_block_drift(),
         }
     }
 }
@@ -366,6 +624,18 @@ pub struct ProverConf {
     /// If not set, it defaults to 30 seconds.
     #[serde(default = "defaults::reaper_grace_period_secs")]
     pub reaper_grace_period_secs: u32,
+    /// Prover backend queue depth at which the backend is considered saturated.
+    ///
+    /// While the number of queued or running proving jobs is at or above this value, the broker
+    /// stops committing to new orders and reduces pricing capacity, resuming once the backlog
+    /// clears. Queue depth is only reported by Bento and the local prover. If it cannot be read
+    /// several times in a row, the backend is treated as not saturated. If not set, saturation is
+    /// not checked.
+    #[serde(default)]
+    pub saturation_queue_depth: Option<u64>,
+    /// Interval for polling the prover backend queue depth (in seconds)
+    #[serde(default = "defaults::saturation_poll_secs")]
+    pub saturation_poll_secs: u64,
 }
 
 impl Default for ProverConf {
@@ -383,6 +653,8 @@ impl Default for ProverConf {
             max_critical_task_retries: None,
             reaper_interval_secs: defaults::reaper_interval_secs(),
             reaper_grace_period_secs: defaults::reaper_grace_period_secs(),
+            saturation_queue_depth: None,
+            saturation_poll_secs: defaults::saturation_poll_secs(),
         }
     }
 }
@@ -463,6 +735,150 @@ impl Config {
         toml::from_str(&data).context(format!("Failed to parse toml file from {path:?}"))
     }
 
+    /// Check the config for invalid values and inconsistent combinations of fields
+    pub fn validate(&self) -> Result<(), ConfigErr> {
+        let market = &self.market;
+        let mut errors = Vec::new();
+
+        for (key, value) in [
+            ("mcycle_price", Some(market.mcycle_price.as_str())),
+            ("mcycle_price_collateral_token", Some(market.mcycle_price_collateral_token.as_str())),
+            ("max_collateral", Some(market.max_collateral.as_str())),
+            ("groth16_compression_cost", Some(market.groth16_compression_cost.as_str())),
+            ("balance_warn_threshold", market.balance_warn_threshold.as_deref()),
+            ("balance_error_threshold", market.balance_error_threshold.as_deref()),
+            (
+                "collateral_balance_warn_threshold",
+                market.collateral_balance_warn_threshold.as_deref(),
+            ),
+            (
+                "collateral_balance_error_threshold",
+                market.collateral_balance_error_threshold.as_deref(),
+            ),
+        ] {
+            if let Some(Err(err)) = value.map(parse_ether) {
+                errors.push(format!("market.{key} is not a valid amount: {err}"));
+            }
+        }
+
+        for (warn_key, warn, error_key, error) in [
+            (
+                "balance_warn_threshold",
+                &market.balance_warn_threshold,
+                "balance_error_threshold",
+                &market.balance_error_threshold,
+            ),
+            (
+                "collateral_balance_warn_threshold",
+                &market.collateral_balance_warn_threshold,
+                "collateral_balance_error_threshold",
+                &market.collateral_balance_error_threshold,
+            ),
+        ] {
+            if let (Some(Ok(warn)), Some(Ok(error))) =
+                (warn.as_deref().map(parse_ether), error.as_deref().map(parse_ether))
+            {
+                if error > warn {
+                    errors.push(format!("market.{error_key} is above market.{warn_key}"));
+                }
+            }
+        }
+
+        if market.lockin_gas_estimate == 0 {
+            errors.push("market.lockin_gas_estimate must be greater than zero".to_string());
+        }
+        if market.fulfill_gas_estimate == 0 {
+            errors.push("market.fulfill_gas_estimate must be greater than zero".to_string());
+        }
+        if market.aggregation_batch_history == 0 {
+            errors.push("market.aggregation_batch_history must be greater than zero".to_string());
+        }
+        if market.priority_requestor_addresses.as_ref().is_some_and(Vec::is_empty) {
+            errors.push(
+                "market.priority_requestor_addresses is empty; remove it or add an address"
+                    .to_string(),
+            );
+        }
+        if market.collateral_cost_bps > 10_000 {
+            errors.push("market.collateral_cost_bps must be at most 10000".to_string());
+        }
+        if market.requestor_pricing_rate_limit == Some(0) {
+            errors.push(
+                "market.requestor_pricing_rate_limit must be greater than zero; remove it to disable"
+                    .to_string(),
+            );
+        }
+
+        if let Some(brackets) = &market.size_brackets {
+            if brackets.is_empty() {
+                errors
+                    .push("market.size_brackets is empty; remove it or add a bracket".to_string());
+            }
+            let bounds = brackets.iter().map(|bracket| bracket.max_mcycles).collect::<Vec<_>>();
+            let ordered = bounds.windows(2).all(|pair| match pair {
+                [Some(lower), Some(upper)] => lower < upper,
+                [Some(_), None] => true,
+                _ => false,
+            });
+            if !ordered {
+                errors.push(
+                    "market.size_brackets must be in increasing order of max_mcycles, with only the last bracket unbounded"
+                        .to_string(),
+                );
+            }
+        }
+
+        let mut order_filters = HashSet::new();
+        for filter in &market.order_filters {
+            if !order_filters.insert(filter) {
+                errors.push(format!("market.order_filters lists {filter} more than once"));
+            }
+        }
+
+        if let Some(budgets) = &market.lock_latency_budgets_ms {
+            for stage in budgets.keys() {
+                if stage != latency::TOTAL && !latency::STAGES.iter().any(|(name, _)| name == stage)
+                {
+                    errors
+                        .push(format!("market.lock_latency_budgets_ms has unknown stage {stage}"));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigErr::ValidationFailed(errors.join("; ")))
+        }
+    }
+
+    /// Keys whose values differ between this config and another, formatted as `section.key`
+    fn changed_keys(&self, other: &Config) -> Vec<String> {
+        let (Ok(toml::Value::Table(old)), Ok(toml::Value::Table(new))) =
+            (toml::Value::try_from(self), toml::Value::try_from(other))
+        else {
+            return vec!["<unknown>".to_string()];
+        };
+
+        let mut changed = Vec::new();
+        for (section, new_section) in &new {
+            let (Some(toml::Value::Table(old_section)), toml::Value::Table(new_section)) =
+                (old.get(section), new_section)
+            else {
+                continue;
+            };
+            for key in old_section
+                .keys()
+                .chain(new_section.keys().filter(|key| !old_section.contains_key(*key)))
+            {
+                if old_section.get(key) != new_section.get(key) {
+                    changed.push(format!("{section}.{key}"));
+                }
+            }
+        }
+        changed
+    }
+
     /// Write the config to disk
     #[cfg(feature = "test-utils")]
     pub async fn write(&self, path: &Path) -> Result<()> {
@@ -478,6 +894,9 @@ pub enum ConfigErr {
 
     #[error("Invalid configuration")]
     InvalidConfig,
+
+    #[error("Configuration failed validation: {0}")]
+    ValidationFailed(String),
 }
 
 impl_coded_debug!(ConfigErr);
@@ -487,6 +906,7 @@ impl CodedError for ConfigErr {
         match self {
             ConfigErr::LockFailed => "[B-CON-3012]",
             ConfigErr::InvalidConfig => "[B-CON-3013]",
+            ConfigErr::ValidationFailed(_) => "[B-CON-3014]",
         }
     }
 }
@@ -526,7 +946,9 @@ pub struct ConfigWatcher {
 impl ConfigWatcher {
     /// Initialize a new config watcher and handle
     pub async fn new(config_path: &Path) -> Result<Self> {
-        let config = Arc::new(RwLock::new(Config::load(config_path).await?));
+        let config = Config::load(config_path).await?;
+        config.validate()?;
+        let config = Arc::new(RwLock::new(config));
         let config_copy = config.clone();
         let config_path_copy = config_path.to_path_buf();
 
@@ -567,6 +989,12 @@ impl ConfigWatcher {
                                 continue;
                             }
                         };
+                        if let Err(err) = new_config.validate() {
+                            tracing::error!(
+                                "Rejected modified config, keeping the previous config: {err}"
+                            );
+                            continue;
+                        }
                         let mut config = match config_copy.write() {
                             Ok(val) => val,
                             Err(err) => {
@@ -576,7 +1004,16 @@ impl ConfigWatcher {
                                 continue;
                             }
                         };
+                        let changed_keys = config.changed_keys(&new_config);
                         *config = new_config;
+                        if changed_keys.is_empty() {
+                            tracing::debug!("Reloaded config with no changes");
+                        } else {
+                            tracing::info!(
+                                "Reloaded config, changed keys: {}",
+                                changed_keys.join(", ")
+                            );
+                        }
                     }
                     _ => {
                         tracing::debug!("unsupported config file event: {event:?}");
@@ -779,6 +1216,101 @@ error = ?"#;
         tracing::debug!("closing...");
     }
 
+    #[tokio::test]
+    #[traced_test]
+    async fn config_watcher_valid_reload() {
+        let mut config_temp = NamedTempFile::new().unwrap();
+        write_config(CONFIG_TEMPL, config_temp.as_file_mut());
+        let config_mgnr = ConfigWatcher::new(config_temp.path()).await.unwrap();
+
+        let updated = CONFIG_TEMPL
+            .replace(r#"mcycle_price = "0.1""#, r#"mcycle_price = "0.2""#)
+            .replace("peak_prove_khz = 500", "peak_prove_khz = 1000");
+        write_config(&updated, config_temp.as_file_mut());
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+
+        {
+            let config = config_mgnr.config.lock_all().unwrap();
+            assert_eq!(config.market.mcycle_price, "0.2");
+            assert_eq!(config.market.peak_prove_khz, Some(1000));
+        }
+        assert!(logs_contain("changed keys: market.mcycle_price, market.peak_prove_khz"));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn config_watcher_invalid_reload() {
+        let mut config_temp = NamedTempFile::new().unwrap();
+        write_config(CONFIG_TEMPL, config_temp.as_file_mut());
+        let config_mgnr = ConfigWatcher::new(config_temp.path()).await.unwrap();
+
+        let updated = CONFIG_TEMPL
+            .replace(r#"mcycle_price = "0.1""#, r#"mcycle_price = "cheap""#)
+            .replace("peak_prove_khz = 500", "peak_prove_khz = 1000");
+        write_config(&updated, config_temp.as_file_mut());
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+
+        {
+            let config = config_mgnr.config.lock_all().unwrap();
+            assert_eq!(config.market.mcycle_price, "0.1");
+            assert_eq!(config.market.peak_prove_khz, Some(500));
+        }
+        assert!(logs_contain("Rejected modified config"));
+        assert!(logs_contain("market.mcycle_price is not a valid amount"));
+    }
+
+    #[test]
+    fn validate_thresholds() {
+        let mut config = Config::default();
+        config.validate().unwrap();
+
+        config.market.balance_warn_threshold = Some("0.05".into());
+        config.market.balance_error_threshold = Some("0.1".into());
+        config.market.priority_requestor_addresses = Some(vec![]);
+        let err = config.validate().unwrap_err().to_string();
+        assert!(
+            err.contains("market.balance_error_threshold is above market.balance_warn_threshold")
+        );
+        assert!(err.contains("market.priority_requestor_addresses is empty"));
+    }
+
+    #[test]
+    fn validate_size_brackets() {
+        let bracket = |name: &str, max_mcycles| SizeBracket {
+            name: name.to_string(),
+            max_mcycles,
+            max_concurrent_proofs: 1,
+        };
+        let mut config = Config::default();
+        config.market.size_brackets = Some(vec![
+            bracket("small", Some(50)),
+            bracket("medium", Some(500)),
+            bracket("large", None),
+        ]);
+        config.validate().unwrap();
+
+        config.market.size_brackets =
+            Some(vec![bracket("large", None), bracket("small", Some(50))]);
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("market.size_brackets must be in increasing order"));
+
+        config.market.size_brackets = Some(vec![]);
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("market.size_brackets is empty"));
+    }
+
+    #[test]
+    fn validate_lock_latency_budgets() {
+        let mut config = Config::default();
+        config.market.lock_latency_budgets_ms =
+            Some(BTreeMap::from([("preflight".to_string(), 2_000), ("total".to_string(), 10_000)]));
+        config.validate().unwrap();
+
+        config.market.lock_latency_budgets_ms = Some(BTreeMap::from([("lock".to_string(), 1_000)]));
+        let err = config.validate().unwrap_err().to_string();
+        assert!(err.contains("market.lock_latency_budgets_ms has unknown stage lock"));
+    }
+
     #[tokio::test]
     #[traced_test]
     #[should_panic(expected = "Failed to parse toml file")]
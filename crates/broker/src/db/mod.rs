@@ -12,9 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{default::Default, str::FromStr, sync::Arc};
+use std::{collections::HashMap, default::Default, str::FromStr, sync::Arc};
 
-use alloy::primitives::{ruint::ParseError as RuintParseErr, Bytes, U256};
+use alloy::primitives::{ruint::ParseError as RuintParseErr, Address, Bytes, U256};
 use async_trait::async_trait;
 use chrono::Utc;
 use sqlx::{
@@ -24,7 +24,9 @@ use sqlx::{
 use thiserror::Error;
 
 use crate::{
+    config::FulfillmentPath,
     errors::{impl_coded_debug, CodedError},
+    reputation::RequestorStats,
     AggregationState, Batch, BatchStatus, FulfillmentType, Order, OrderRequest, OrderStatus,
     ProofRequest,
 };
@@ -135,7 +137,10 @@ pub trait BrokerDb {
     async fn get_submission_order(
         &self,
         id: &str,
-    ) -> Result<(ProofRequest, Bytes, String, String, U256, FulfillmentType), DbError>;
+    ) -> Result<
+        (ProofRequest, Bytes, String, String, U256, FulfillmentType, FulfillmentPath),
+        DbError,
+    >;
     async fn get_order_compressed_proof_id(&self, id: &str) -> Result<String, DbError>;
     async fn set_order_failure(&self, id: &str, failure_str: &'static str) -> Result<(), DbError>;
     async fn set_order_complete(&self, id: &str) -> Result<(), DbError>;
@@ -191,6 +196,25 @@ pub trait BrokerDb {
         assessor_proof_id: Option<String>,
     ) -> Result<(), DbError>;
     async fn get_batch(&self, batch_id: usize) -> Result<Batch, DbError>;
+    /// Number of orders in each of the most recently submitted batches, newest first.
+    async fn get_recent_batch_sizes(&self, limit: u32) -> Result<Vec<usize>, DbError>;
+    async fn record_requestor_order(
+        &self,
+        requestor: Address,
+        preflight_failed: bool,
+    ) -> Result<(), DbError>;
+    async fn get_requestor_stats(&self) -> Result<HashMap<Address, RequestorStats>, DbError>;
+    /// Record an order the broker would have locked and/or proven, when in observation mode.
+    ///
+    /// Returns false if the order was already recorded.
+    async fn record_observed_order(
+        &self,
+        order: &OrderRequest,
+        revenue: U256,
+        collateral_reward: U256,
+        gas_cost: U256,
+    ) -> Result<bool, DbError>;
+    async fn get_observed_order_stats(&self) -> Result<ObservedOrderStats, DbError>;
 
     #[cfg(test)]
     async fn add_order(&self, order: &Order) -> Result<(), DbError>;
@@ -308,6 +332,44 @@ struct DbLockedRequest {
     block_number: u64,
 }
 
+/// Totals of the orders recorded in observation mode. No transactions were sent for these
+/// orders, so the revenue and costs are hypothetical.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ObservedOrderStats {
+    /// Number of orders the broker would have locked and/or proven.
+    pub orders: u64,
+    /// Revenue in native token from the orders that would have been locked.
+    pub revenue: U256,
+    /// Collateral reward from the lock-expired orders that would have been proven.
+    pub collateral_reward: U256,
+    /// Gas cost in native token of locking and fulfilling the orders.
+    pub gas_cost: U256,
+}
+
+impl ObservedOrderStats {
+    /// Add an observed order to the totals.
+    pub fn add(&mut self, revenue: U256, collateral_reward: U256, gas_cost: U256) {
+        self.orders += 1;
+        self.revenue += revenue;
+        self.collateral_reward += collateral_reward;
+        self.gas_cost += gas_cost;
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct DbObservedOrder {
+    revenue: String,
+    collateral_reward: String,
+    gas_cost: String,
+}
+
+#[derive(sqlx::FromRow)]
+struct DbRequestorStats {
+    requestor: String,
+    orders: i64,
+    preflight_failures: i64,
+}
+
 #[async_trait]
 impl BrokerDb for SqliteDb {
     #[cfg(test)]
@@ -361,9 +423,13 @@ impl BrokerDb for SqliteDb {
     async fn get_submission_order(
         &self,
         id: &str,
-    ) -> Result<(ProofRequest, Bytes, String, String, U256, FulfillmentType), DbError> {
+    ) -> Result<
+        (ProofRequest, Bytes, String, String, U256, FulfillmentType, FulfillmentPath),
+        DbError,
+    > {
         let order = self.get_order(id).await?;
         if let Some(order) = order {
+            let fulfillment_path = order.fulfillment_path();
             Ok((
                 order.request.clone(),
                 order.client_sig.clone(),
@@ -371,6 +437,7 @@ impl BrokerDb for SqliteDb {
                 order.image_id.ok_or(DbError::MissingElm("image_id"))?,
                 order.lock_price.ok_or(DbError::MissingElm("lock_price"))?,
                 order.fulfillment_type,
+                fulfillment_path,
             ))
         } else {
             Err(DbError::OrderNotFound(id.to_string()))
@@ -949,6 +1016,24 @@ impl BrokerDb for SqliteDb {
         }
     }
 
+    #[instrument(level = "trace", skip(self))]
+    async fn get_recent_batch_sizes(&self, limit: u32) -> Result<Vec<usize>, DbError> {
+        let sizes: Vec<i64> = sqlx::query_scalar(
+            r#"
+            SELECT json_array_length(data, '$.orders')
+            FROM batches
+            WHERE data->>'status' = $1
+            ORDER BY id DESC
+            LIMIT $2"#,
+        )
+        .bind(BatchStatus::Submitted)
+        .bind(limit as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(sizes.into_iter().map(|size| size as usize).collect())
+    }
+
     #[instrument(level = "trace", skip(self))]
     async fn set_request_fulfilled(
         &self,
@@ -1017,6 +1102,90 @@ impl BrokerDb for SqliteDb {
         Ok(res.map(|r| (r.locker, r.block_number)))
     }
 
+    #[instrument(level = "trace", skip(self))]
+    async fn record_requestor_order(
+        &self,
+        requestor: Address,
+        preflight_failed: bool,
+    ) -> Result<(), DbError> {
+        sqlx::query(
+            r#"
+            INSERT INTO requestor_stats (requestor, orders, preflight_failures) VALUES ($1, 1, $2)
+            ON CONFLICT (requestor) DO UPDATE SET
+                orders = orders + 1,
+                preflight_failures = preflight_failures + excluded.preflight_failures"#,
+        )
+        .bind(format!("{requestor:x}"))
+        .bind(preflight_failed as i64)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn get_requestor_stats(&self) -> Result<HashMap<Address, RequestorStats>, DbError> {
+        let rows: Vec<DbRequestorStats> =
+            sqlx::query_as(r#"SELECT * FROM requestor_stats"#).fetch_all(&self.pool).await?;
+
+        rows.into_iter()
+            .map(|row| {
+                let requestor = Address::from_str(&row.requestor)
+                    .map_err(|_| DbError::MissingElm("requestor"))?;
+                let stats = RequestorStats {
+                    orders: row.orders as u64,
+                    preflight_failures: row.preflight_failures as u64,
+                };
+                Ok((requestor, stats))
+            })
+            .collect()
+    }
+
+    #[instrument(level = "trace", skip_all, fields(id = %format!("{}", order.id())))]
+    async fn record_observed_order(
+        &self,
+        order: &OrderRequest,
+        revenue: U256,
+        collateral_reward: U256,
+        gas_cost: U256,
+    ) -> Result<bool, DbError> {
+        let res = sqlx::query(
+            r#"
+            INSERT OR IGNORE INTO observed_orders
+                (id, requestor, fulfillment_type, revenue, collateral_reward, gas_cost, observed_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)"#,
+        )
+        .bind(order.id())
+        .bind(format!("{:x}", order.request.client_address()))
+        .bind(format!("{:?}", order.fulfillment_type))
+        .bind(revenue.to_string())
+        .bind(collateral_reward.to_string())
+        .bind(gas_cost.to_string())
+        .bind(Utc::now().timestamp())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() == 1)
+    }
+
+    #[instrument(level = "trace", skip(self))]
+    async fn get_observed_order_stats(&self) -> Result<ObservedOrderStats, DbError> {
+        let rows: Vec<DbObservedOrder> =
+            sqlx::query_as(r#"SELECT revenue, collateral_reward, gas_cost FROM observed_orders"#)
+                .fetch_all(&self.pool)
+                .await?;
+
+        let mut stats = ObservedOrderStats::default();
+        for row in rows {
+            stats.add(
+                U256::from_str(&row.revenue)?,
+                U256::from_str(&row.collateral_reward)?,
+                U256::from_str(&row.gas_cost)?,
+            );
+        }
+        Ok(stats)
+    }
+
     #[cfg(test)]
     async fn add_batch(&self, batch_id: usize, batch: Batch) -> Result<(), DbError> {
         let res = sqlx::query("INSERT INTO batches (id, data) VALUES ($1, $2)")
@@ -1152,14 +1321,22 @@ mod tests {
         order.lock_price = Some(U256::from(10));
         db.add_order(&order).await.unwrap();
 
-        let submit_order: (ProofRequest, Bytes, String, String, U256, FulfillmentType) =
-            db.get_submission_order(&order.id()).await.unwrap();
+        let submit_order: (
+            ProofRequest,
+            Bytes,
+            String,
+            String,
+            U256,
+            FulfillmentType,
+            FulfillmentPath,
+        ) = db.get_submission_order(&order.id()).await.unwrap();
         assert_eq!(submit_order.0, order.request);
         assert_eq!(submit_order.1, order.client_sig);
         assert_eq!(submit_order.2, order.proof_id.unwrap());
         assert_eq!(submit_order.3, order.image_id.unwrap());
         assert_eq!(submit_order.4, order.lock_price.unwrap());
         assert_eq!(submit_order.5, order.fulfillment_type);
+        assert_eq!(submit_order.6, FulfillmentPath::Inclusion);
     }
 
     #[sqlx::test]
@@ -1412,6 +1589,32 @@ mod tests {
         assert_eq!(db_batch.status, BatchStatus::Submitted);
     }
 
+    #[sqlx::test]
+    async fn get_recent_batch_sizes(pool: SqlitePool) {
+        let db: DbObj = Arc::new(SqliteDb::from(pool).await.unwrap());
+
+        for (batch_id, (status, size)) in [
+            (BatchStatus::Submitted, 2),
+            (BatchStatus::Failed, 7),
+            (BatchStatus::Submitted, 4),
+            (BatchStatus::Aggregating, 1),
+            (BatchStatus::Submitted, 3),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            let batch = Batch {
+                status,
+                orders: (0..size).map(|i| format!("order-{batch_id}-{i}")).collect(),
+                ..Default::default()
+            };
+            db.add_batch(batch_id + 1, batch).await.unwrap();
+        }
+
+        assert_eq!(db.get_recent_batch_sizes(10).await.unwrap(), vec![3, 4, 2]);
+        assert_eq!(db.get_recent_batch_sizes(2).await.unwrap(), vec![3, 4]);
+    }
+
     #[sqlx::test]
     async fn set_batch_failure(pool: SqlitePool) {
         let db: DbObj = Arc::new(SqliteDb::from(pool).await.unwrap());
@@ -1535,6 +1738,60 @@ mod tests {
         assert!(!db.is_request_locked(U256::from(413)).await.unwrap());
     }
 
+    #[sqlx::test]
+    async fn record_requestor_orders(pool: SqlitePool) {
+        let db: DbObj = Arc::new(SqliteDb::from(pool).await.unwrap());
+
+        let good = Address::repeat_byte(1);
+        let bad = Address::repeat_byte(2);
+        assert!(db.get_requestor_stats().await.unwrap().is_empty());
+
+        db.record_requestor_order(good, false).await.unwrap();
+        db.record_requestor_order(good, false).await.unwrap();
+        db.record_requestor_order(bad, true).await.unwrap();
+        db.record_requestor_order(bad, false).await.unwrap();
+        db.record_requestor_order(bad, true).await.unwrap();
+
+        let stats = db.get_requestor_stats().await.unwrap();
+        assert_eq!(stats[&good], RequestorStats { orders: 2, preflight_failures: 0 });
+        assert_eq!(stats[&bad], RequestorStats { orders: 3, preflight_failures: 2 });
+    }
+
+    #[sqlx::test]
+    async fn record_observed_orders(pool: SqlitePool) {
+        let db: DbObj = Arc::new(SqliteDb::from(pool).await.unwrap());
+        assert_eq!(db.get_observed_order_stats().await.unwrap(), ObservedOrderStats::default());
+
+        let mut order1 = create_order_request();
+        order1.request.id = U256::from(1);
+        let mut order2 = create_order_request();
+        order2.request.id = U256::from(2);
+        assert!(db
+            .record_observed_order(&order1, U256::from(10), U256::ZERO, U256::from(3))
+            .await
+            .unwrap());
+        assert!(db
+            .record_observed_order(&order2, U256::ZERO, U256::from(5), U256::from(4))
+            .await
+            .unwrap());
+        // Recording the same order again is ignored
+        assert!(!db
+            .record_observed_order(&order2, U256::ZERO, U256::from(5), U256::from(4))
+            .await
+            .unwrap());
+
+        let stats = db.get_observed_order_stats().await.unwrap();
+        assert_eq!(
+            stats,
+            ObservedOrderStats {
+                orders: 2,
+                revenue: U256::from(10),
+                collateral_reward: U256::from(5),
+                gas_cost: U256::from(7),
+            }
+        );
+    }
+
     #[sqlx::test]
     async fn get_expired_committed_orders(pool: SqlitePool) {
         let db: DbObj = Arc::new(SqliteDb::from(pool).await.unwrap());
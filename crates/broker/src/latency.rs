@@ -0,0 +1,278 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Latency tracking of the order pipeline, from the moment an order is observed to the moment
+//! its lock transaction is included in a block.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::Write,
+    sync::OnceLock,
+    time::{Duration, Instant},
+};
+
+/// Name of the end-to-end latency, usable as a key in the latency budgets.
+pub(crate) const TOTAL: &str = "total";
+
+/// Points in the order pipeline at which a timestamp is recorded, in pipeline order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Checkpoint {
+    Observed,
+    PricingStarted,
+    PreflightStarted,
+    PreflightFinished,
+    PricingFinished,
+    Committed,
+    LockSubmitted,
+    LockIncluded,
+}
+
+/// Pipeline stages, each named after the interval ending at the given checkpoint.
+pub(crate) const STAGES: [(&str, Checkpoint); 7] = [
+    ("pricing_queue", Checkpoint::PricingStarted),
+    ("pricing_setup", Checkpoint::PreflightStarted),
+    ("preflight", Checkpoint::PreflightFinished),
+    ("pricing_evaluation", Checkpoint::PricingFinished),
+    ("commitment_queue", Checkpoint::Committed),
+    ("lock_submission", Checkpoint::LockSubmitted),
+    ("lock_inclusion", Checkpoint::LockIncluded),
+];
+
+/// Monotonic timestamps of an order at each [Checkpoint] it went through.
+///
+/// Only the first timestamp recorded for a checkpoint is kept, so retried steps do not reset it.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct OrderTimings {
+    checkpoints: [OnceLock<Instant>; 8],
+}
+
+impl OrderTimings {
+    pub(crate) fn record(&self, checkpoint: Checkpoint) {
+        self.record_at(checkpoint, Instant::now());
+    }
+
+    fn record_at(&self, checkpoint: Checkpoint, at: Instant) {
+        let _ = self.checkpoints[checkpoint as usize].set(at);
+    }
+
+    fn get(&self, checkpoint: Checkpoint) -> Option<Instant> {
+        self.checkpoints[checkpoint as usize].get().copied()
+    }
+
+    /// Per-stage latency of an order, from its observation to the inclusion of its lock.
+    ///
+    /// Returns `None` unless both ends were recorded. A stage whose checkpoint was never reached,
+    /// such as preflight for an order priced without it, is folded into the following stage, so
+    /// the stages always add up to the total.
+    pub(crate) fn breakdown(&self) -> Option<LatencyBreakdown> {
+        let start = self.get(Checkpoint::Observed)?;
+        let end = self.get(Checkpoint::LockIncluded)?;
+
+        let mut stages = Vec::with_capacity(STAGES.len());
+        let mut prev = start;
+        for (stage, checkpoint) in STAGES {
+            let Some(at) = self.get(checkpoint) else {
+                continue;
+            };
+            stages.push((stage, at.saturating_duration_since(prev)));
+            prev = prev.max(at);
+        }
+        Some(LatencyBreakdown { stages, total: end.saturating_duration_since(start) })
+    }
+}
+
+/// Latency of a single order through each pipeline stage.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct LatencyBreakdown {
+    pub(crate) stages: Vec<(&'static str, Duration)>,
+    pub(crate) total: Duration,
+}
+
+impl LatencyBreakdown {
+    /// Stages, or the total, that exceeded their budget in milliseconds.
+    pub(crate) fn over_budget(
+        &self,
+        budgets_ms: &BTreeMap<String, u64>,
+    ) -> Vec<(&'static str, Duration, u64)> {
+        self.stages
+            .iter()
+            .copied()
+            .chain([(TOTAL, self.total)])
+            .filter_map(|(stage, latency)| {
+                let budget = *budgets_ms.get(stage)?;
+                (latency > Duration::from_millis(budget)).then_some((stage, latency, budget))
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for LatencyBreakdown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{TOTAL} {}ms", self.total.as_millis())?;
+        for (stage, latency) in &self.stages {
+            write!(f, ", {stage} {}ms", latency.as_millis())?;
+        }
+        Ok(())
+    }
+}
+
+/// Rolling window of recent latency breakdowns, for per-stage percentiles.
+#[derive(Debug)]
+pub(crate) struct LatencyStats {
+    window: usize,
+    samples: BTreeMap<&'static str, VecDeque<Duration>>,
+    recorded: u64,
+}
+
+impl LatencyStats {
+    pub(crate) fn new(window: usize) -> Self {
+        Self { window, samples: BTreeMap::new(), recorded: 0 }
+    }
+
+    pub(crate) fn record(&mut self, breakdown: &LatencyBreakdown) {
+        for (stage, latency) in breakdown.stages.iter().copied().chain([(TOTAL, breakdown.total)]) {
+            let samples = self.samples.entry(stage).or_default();
+            if samples.len() == self.window {
+                samples.pop_front();
+            }
+            samples.push_back(latency);
+        }
+        self.recorded += 1;
+    }
+
+    /// Number of breakdowns recorded since startup, including those out of the window.
+    pub(crate) fn recorded(&self) -> u64 {
+        self.recorded
+    }
+
+    /// Nearest-rank percentile of the latency of a stage over the window.
+    pub(crate) fn percentile(&self, stage: &str, percentile: f64) -> Option<Duration> {
+        let mut samples: Vec<Duration> = self.samples.get(stage)?.iter().copied().collect();
+        if samples.is_empty() {
+            return None;
+        }
+        samples.sort_unstable();
+        let rank = ((percentile / 100.0) * samples.len() as f64).ceil() as usize;
+        Some(samples[rank.clamp(1, samples.len()) - 1])
+    }
+
+    /// P50 and P95 latency of the total and of each stage, in pipeline order.
+    pub(crate) fn summary(&self) -> String {
+        let mut summary = String::new();
+        for stage in [TOTAL].into_iter().chain(STAGES.map(|(stage, _)| stage)) {
+            let (Some(p50), Some(p95)) =
+                (self.percentile(stage, 50.0), self.percentile(stage, 95.0))
+            else {
+                continue;
+            };
+            if !summary.is_empty() {
+                summary.push_str(", ");
+            }
+            let _ = write!(summary, "{stage} p50 {}ms p95 {}ms", p50.as_millis(), p95.as_millis());
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn timings(offsets_ms: &[(Checkpoint, u64)]) -> OrderTimings {
+        let start = Instant::now();
+        let timings = OrderTimings::default();
+        for &(checkpoint, offset) in offsets_ms {
+            timings.record_at(checkpoint, start + Duration::from_millis(offset));
+        }
+        timings
+    }
+
+    #[test]
+    fn breakdown_sums_to_total() {
+        let timings = timings(&[
+            (Checkpoint::Observed, 0),
+            (Checkpoint::PricingStarted, 15),
+            (Checkpoint::PreflightStarted, 20),
+            (Checkpoint::PreflightFinished, 900),
+            (Checkpoint::PricingFinished, 910),
+            (Checkpoint::Committed, 2_000),
+            (Checkpoint::LockSubmitted, 2_050),
+            (Checkpoint::LockIncluded, 4_050),
+        ]);
+        let breakdown = timings.breakdown().unwrap();
+        assert_eq!(breakdown.total, Duration::from_millis(4_050));
+        assert_eq!(breakdown.stages.len(), STAGES.len());
+        assert_eq!(breakdown.stages[2], ("preflight", Duration::from_millis(880)));
+        assert_eq!(breakdown.stages.iter().map(|(_, d)| *d).sum::<Duration>(), breakdown.total);
+    }
+
+    #[test]
+    fn missing_checkpoint_folds_into_next_stage() {
+        let timings = timings(&[
+            (Checkpoint::Observed, 0),
+            (Checkpoint::PricingStarted, 10),
+            (Checkpoint::PricingFinished, 30),
+            (Checkpoint::Committed, 100),
+            (Checkpoint::LockSubmitted, 120),
+            (Checkpoint::LockIncluded, 1_120),
+        ]);
+        let breakdown = timings.breakdown().unwrap();
+        assert!(breakdown.stages.iter().all(|(stage, _)| *stage != "preflight"));
+        assert!(breakdown.stages.contains(&("pricing_evaluation", Duration::from_millis(20))));
+        assert_eq!(breakdown.stages.iter().map(|(_, d)| *d).sum::<Duration>(), breakdown.total);
+
+        // Without observation or inclusion there is no end-to-end latency
+        assert!(self::timings(&[(Checkpoint::LockIncluded, 10)]).breakdown().is_none());
+        assert!(self::timings(&[(Checkpoint::Observed, 0)]).breakdown().is_none());
+    }
+
+    #[test]
+    fn over_budget() {
+        let breakdown = LatencyBreakdown {
+            stages: vec![
+                ("preflight", Duration::from_millis(500)),
+                ("lock_inclusion", Duration::from_millis(3_000)),
+            ],
+            total: Duration::from_millis(3_500),
+        };
+        let budgets = BTreeMap::from([
+            ("preflight".to_string(), 1_000),
+            ("lock_inclusion".to_string(), 2_000),
+            (TOTAL.to_string(), 3_000),
+        ]);
+        assert_eq!(
+            breakdown.over_budget(&budgets),
+            vec![
+                ("lock_inclusion", Duration::from_millis(3_000), 2_000),
+                (TOTAL, Duration::from_millis(3_500), 3_000),
+            ]
+        );
+    }
+
+    #[test]
+    fn percentiles_over_window() {
+        let mut stats = LatencyStats::new(100);
+        for ms in 1..=200 {
+            let latency = Duration::from_millis(ms);
+            stats
+                .record(&LatencyBreakdown { stages: vec![("preflight", latency)], total: latency });
+        }
+        assert_eq!(stats.recorded(), 200);
+        // Only the last 100 samples, 101ms to 200ms, are kept
+        assert_eq!(stats.percentile("preflight", 50.0), Some(Duration::from_millis(150)));
+        assert_eq!(stats.percentile(TOTAL, 95.0), Some(Duration::from_millis(195)));
+        assert_eq!(stats.percentile("lock_inclusion", 50.0), None);
+        assert_eq!(stats.summary(), "total p50 150ms p95 195ms, preflight p50 150ms p95 195ms");
+    }
+}
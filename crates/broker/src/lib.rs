@@ -35,7 +35,7 @@ use boundless_market::{
 use chrono::{serde::ts_seconds, DateTime, Utc};
 use clap::Parser;
 pub use config::Config;
-use config::ConfigWatcher;
+use config::{ConfigWatcher, FulfillmentPath};
 use db::{DbObj, SqliteDb};
 use provers::ProverObj;
 use risc0_ethereum_contracts::set_verifier::SetVerifierService;
@@ -54,19 +54,28 @@ const ORDER_STATE_CHANNEL_CAPACITY: usize = 1000;
 
 pub(crate) mod aggregator;
 pub(crate) mod chain_monitor;
+pub(crate) mod collateral;
 pub mod config;
 pub(crate) mod db;
 pub(crate) mod errors;
 pub mod futures_retry;
+pub(crate) mod latency;
 pub(crate) mod market_monitor;
 pub(crate) mod offchain_market_monitor;
+pub mod order_filter;
 pub(crate) mod order_monitor;
 pub(crate) mod order_picker;
+pub(crate) mod price_oracle;
 pub(crate) mod prioritization;
 pub(crate) mod provers;
 pub(crate) mod proving;
+pub(crate) mod rate_limit;
 pub(crate) mod reaper;
+pub(crate) mod reputation;
+pub(crate) mod retention;
 pub(crate) mod rpc_retry_policy;
+pub(crate) mod saturation;
+pub(crate) mod size_brackets;
 pub(crate) mod storage;
 pub(crate) mod submitter;
 pub(crate) mod task;
@@ -167,8 +176,9 @@ enum OrderStatus {
     Skipped,
 }
 
+/// How the broker intends to fulfill an order.
 #[derive(Clone, Copy, sqlx::Type, Debug, PartialEq, Serialize, Deserialize)]
-enum FulfillmentType {
+pub enum FulfillmentType {
     LockAndFulfill,
     FulfillAfterLockExpire,
     // Currently not supported
@@ -184,6 +194,15 @@ pub enum OrderStateChange {
     Fulfilled { request_id: U256 },
 }
 
+/// Fulfillment path implied by the request selector, used when none was chosen during pricing
+fn default_fulfillment_path(request: &ProofRequest) -> FulfillmentPath {
+    if is_groth16_selector(request.requirements.selector) {
+        FulfillmentPath::Groth16
+    } else {
+        FulfillmentPath::Inclusion
+    }
+}
+
 /// Helper function to format an order ID consistently
 fn format_order_id(
     request_id: &U256,
@@ -196,8 +215,8 @@ fn format_order_id(
 /// Order request from the network.
 ///
 /// This will turn into an [`Order`] once it is locked or skipped.
-#[derive(Serialize, Deserialize, Debug)]
-struct OrderRequest {
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct OrderRequest {
     request: ProofRequest,
     client_sig: Bytes,
     fulfillment_type: FulfillmentType,
@@ -208,8 +227,22 @@ struct OrderRequest {
     total_cycles: Option<u64>,
     target_timestamp: Option<u64>,
     expire_timestamp: Option<u64>,
+    fulfillment_path: Option<FulfillmentPath>,
     #[serde(skip)]
     cached_id: OnceLock<String>,
+    /// Pipeline checkpoints reached by the order, only tracked in memory
+    #[serde(skip)]
+    timings: latency::OrderTimings,
+    /// Block the chain state used to price the order was read at, only tracked in memory
+    #[serde(skip)]
+    pricing_block: Option<u64>,
+    /// Block from which the order was ready to commit to, only tracked in memory
+    #[serde(skip)]
+    ready_block: OnceLock<u64>,
+    /// When the order was first held back by the requestor pricing rate limit, only tracked in
+    /// memory
+    #[serde(skip)]
+    rate_limited_at: Option<std::time::Instant>,
 }
 
 impl OrderRequest {
@@ -220,7 +253,7 @@ impl OrderRequest {
         boundless_market_address: Address,
         chain_id: u64,
     ) -> Self {
-        Self {
+        let order = Self {
             request,
             client_sig,
             fulfillment_type,
@@ -231,8 +264,15 @@ impl OrderRequest {
             total_cycles: None,
             target_timestamp: None,
             expire_timestamp: None,
+            fulfillment_path: None,
             cached_id: OnceLock::new(),
-        }
+            timings: latency::OrderTimings::default(),
+            pricing_block: None,
+            ready_block: OnceLock::new(),
+            rate_limited_at: None,
+        };
+        order.timings.record(latency::Checkpoint::Observed);
+        order
     }
 
     // An Order is identified by the request_id, the fulfillment type, and the hash of the proof request.
@@ -250,6 +290,20 @@ impl OrderRequest {
             .clone()
     }
 
+    pub fn fulfillment_path(&self) -> FulfillmentPath {
+        self.fulfillment_path.unwrap_or_else(|| default_fulfillment_path(&self.request))
+    }
+
+    /// The proof request of the order.
+    pub fn request(&self) -> &ProofRequest {
+        &self.request
+    }
+
+    /// How the broker intends to fulfill the order.
+    pub fn fulfillment_type(&self) -> FulfillmentType {
+        self.fulfillment_type
+    }
+
     fn to_order(&self, status: OrderStatus) -> Order {
         Order {
             boundless_market_address: self.boundless_market_address,
@@ -264,6 +318,7 @@ impl OrderRequest {
             total_cycles: self.total_cycles,
             target_timestamp: self.target_timestamp,
             expire_timestamp: self.expire_timestamp,
+            fulfillment_path: self.fulfillment_path,
             proving_started_at: None,
             proof_id: None,
             compressed_proof_id: None,
@@ -362,6 +417,11 @@ struct Order {
     ///
     /// Populated during order picking
     expire_timestamp: Option<u64>,
+    /// Proof path chosen to fulfill the order
+    ///
+    /// Populated during order picking. If unset, the path is derived from the request selector.
+    #[serde(default)]
+    fulfillment_path: Option<FulfillmentPath>,
     /// Client Signature
     client_sig: Bytes,
     /// Price the lockin was set at
@@ -388,8 +448,12 @@ impl Order {
             .clone()
     }
 
+    pub fn fulfillment_path(&self) -> FulfillmentPath {
+        self.fulfillment_path.unwrap_or_else(|| default_fulfillment_path(&self.request))
+    }
+
     pub fn is_groth16(&self) -> bool {
-        is_groth16_selector(self.request.requirements.selector)
+        self.fulfillment_path() == FulfillmentPath::Groth16
     }
 }
 
@@ -455,6 +519,7 @@ pub struct Broker<P> {
     provider: Arc<P>,
     db: DbObj,
     config_watcher: ConfigWatcher,
+    order_filters: order_filter::OrderFilters,
 }
 
 impl<P> Broker<P>
@@ -484,7 +549,21 @@ where
             tracing::info!("Using default deployment configuration for chain ID {chain_id}");
         }
 
-        Ok(Self { args, db, provider: Arc::new(provider), config_watcher })
+        Ok(Self {
+            args,
+            db,
+            provider: Arc::new(provider),
+            config_watcher,
+            order_filters: Default::default(),
+        })
+    }
+
+    /// Make a custom filter available to the `market.order_filters` config, under its name.
+    ///
+    /// A custom filter registered under the name of a built-in filter replaces it.
+    pub fn with_order_filter(mut self, filter: impl order_filter::OrderFilter + 'static) -> Self {
+        self.order_filters.register(Arc::new(filter));
+        self
     }
 
     pub fn deployment(&self) -> &Deployment {
@@ -713,6 +792,7 @@ where
                 Ok(res) => res,
                 Err(err) => anyhow::bail!("Failed to lock config in watcher: {err:?}"),
             };
+            self.order_filters.check_chain(&config.market.order_filters)?;
             config.market.lookback_blocks
         };
 
@@ -832,6 +912,20 @@ where
             Arc::new(provers::DefaultProver::new())
         };
 
+        // Spin up the saturation monitor to track the prover backend backlog
+        let saturation_monitor =
+            Arc::new(saturation::SaturationMonitor::new(config.clone(), prover.clone()));
+        let saturation_rx = saturation_monitor.subscribe();
+        let cloned_config = config.clone();
+        let cancel_token = non_critical_cancel_token.clone();
+        supervisor_tasks.spawn(async move {
+            Supervisor::new(saturation_monitor, cloned_config, cancel_token)
+                .spawn()
+                .await
+                .context("Failed to start saturation monitor")?;
+            Ok(())
+        });
+
         let (pricing_tx, pricing_rx) = mpsc::channel(PRICING_CHANNEL_CAPACITY);
 
         let collateral_token_decimals = BoundlessMarketService::new(
@@ -843,19 +937,27 @@ where
         .await
         .context("Failed to get stake token decimals. Possible RPC error.")?;
 
+        // Collateral reserved by the orders priced for locking, released by the order monitor
+        let collateral = Arc::new(std::sync::Mutex::new(collateral::CollateralTracker::default()));
+
         // Spin up the order picker to pre-flight and find orders to lock
-        let order_picker = Arc::new(order_picker::OrderPicker::new(
-            self.db.clone(),
-            config.clone(),
-            prover.clone(),
-            self.deployment().boundless_market_address,
-            self.provider.clone(),
-            chain_monitor.clone(),
-            new_order_rx,
-            pricing_tx,
-            collateral_token_decimals,
-            order_state_tx.clone(),
-        ));
+        let order_picker = Arc::new(
+            order_picker::OrderPicker::new(
+                self.db.clone(),
+                config.clone(),
+                prover.clone(),
+                self.deployment().boundless_market_address,
+                self.provider.clone(),
+                chain_monitor.clone(),
+                new_order_rx,
+                pricing_tx,
+                collateral_token_decimals,
+                order_state_tx.clone(),
+            )
+            .with_saturation(saturation_rx.clone())
+            .with_collateral(collateral.clone())
+            .with_order_filters(self.order_filters.clone()),
+        );
         let cloned_config = config.clone();
         let cancel_token = non_critical_cancel_token.clone();
         supervisor_tasks.spawn(async move {
@@ -889,21 +991,26 @@ where
 
         let prover_addr = self.args.private_key.address();
 
-        let order_monitor = Arc::new(order_monitor::OrderMonitor::new(
-            self.db.clone(),
-            self.provider.clone(),
-            chain_monitor.clone(),
-            config.clone(),
-            block_times,
-            prover_addr,
-            self.deployment().boundless_market_address,
-            pricing_rx,
-            collateral_token_decimals,
-            order_monitor::RpcRetryConfig {
-                retry_count: self.args.rpc_retry_max.into(),
-                retry_sleep_ms: self.args.rpc_retry_backoff,
-            },
-        )?);
+        let order_monitor = Arc::new(
+            order_monitor::OrderMonitor::new(
+                self.db.clone(),
+                self.provider.clone(),
+                chain_monitor.clone(),
+                config.clone(),
+                block_times,
+                prover_addr,
+                self.deployment().boundless_market_address,
+                pricing_rx,
+                collateral_token_decimals,
+                order_monitor::RpcRetryConfig {
+                    retry_count: self.args.rpc_retry_max.into(),
+                    retry_sleep_ms: self.args.rpc_retry_backoff,
+                },
+            )?
+            .with_saturation(saturation_rx)
+            .with_collateral(collateral)
+            .with_repricing(new_order_tx.clone()),
+        );
         let cloned_config = config.clone();
         let cancel_token = non_critical_cancel_token.clone();
         supervisor_tasks.spawn(async move {
@@ -0,0 +1,381 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Filters deciding which orders are worth pricing, run in the order given by the
+//! `market.order_filters` config.
+//!
+//! The built-in filters implement the `min_deadline`, `allow_client_addresses`,
+//! `deny_requestor_addresses` and `max_collateral` settings. Operators with bespoke rules can
+//! implement [OrderFilter] in their own crate and register it with
+//! [Broker::with_order_filter](crate::Broker::with_order_filter).
+
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex},
+};
+
+use alloy::primitives::{utils::parse_units, U256};
+use anyhow::{bail, Result};
+
+use crate::{config::MarketConf, FulfillmentType, OrderRequest};
+
+/// Context available to the filters evaluating an order.
+#[non_exhaustive]
+pub struct FilterContext<'a> {
+    /// Current UNIX timestamp, in seconds.
+    pub now: u64,
+    /// Market configuration at the time of evaluation.
+    pub market: &'a MarketConf,
+    /// Decimals of the collateral token.
+    pub collateral_token_decimals: u8,
+}
+
+impl<'a> FilterContext<'a> {
+    pub(crate) fn new(now: u64, market: &'a MarketConf, collateral_token_decimals: u8) -> Self {
+        Self { now, market, collateral_token_decimals }
+    }
+}
+
+/// Decision of an [OrderFilter] about an order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FilterDecision {
+    /// The order may be priced.
+    Allow,
+    /// The order must be skipped, for the given reason.
+    Skip(String),
+}
+
+/// A rule deciding whether an order is worth pricing.
+pub trait OrderFilter: Send + Sync {
+    /// Name of the filter, as listed in the `market.order_filters` config.
+    fn name(&self) -> &str;
+
+    /// Decide whether the order may be priced.
+    fn allows(&self, order: &OrderRequest, ctx: &FilterContext) -> FilterDecision;
+}
+
+/// An order skipped by a filter.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct FilterSkip {
+    pub(crate) filter: String,
+    pub(crate) reason: String,
+}
+
+/// Registry of the filters available to the `market.order_filters` config.
+#[derive(Clone)]
+pub(crate) struct OrderFilters {
+    filters: BTreeMap<String, Arc<dyn OrderFilter>>,
+    state: Arc<Mutex<ChainState>>,
+}
+
+/// Chain of filters in use, and the number of orders each filter skipped.
+struct ChainState {
+    /// Last configured chain whose filters are all registered.
+    active: Vec<String>,
+    /// Last configured chain rejected for naming an unknown filter, so it is only logged once.
+    rejected: Option<Vec<String>>,
+    /// Number of orders skipped by each filter since the counts were last taken.
+    skips: BTreeMap<String, u64>,
+}
+
+impl Default for OrderFilters {
+    /// Registry of the built-in filters.
+    fn default() -> Self {
+        let state = ChainState {
+            active: MarketConf::default().order_filters,
+            rejected: None,
+            skips: BTreeMap::new(),
+        };
+        let mut filters = Self { filters: BTreeMap::new(), state: Arc::new(Mutex::new(state)) };
+        filters.register(Arc::new(ExpiryMarginFilter));
+        filters.register(Arc::new(RequestorListFilter));
+        filters.register(Arc::new(MaxCollateralFilter));
+        filters
+    }
+}
+
+impl OrderFilters {
+    /// Register a filter, replacing any filter registered under the same name.
+    pub(crate) fn register(&mut self, filter: Arc<dyn OrderFilter>) {
+        self.filters.insert(filter.name().to_string(), filter);
+    }
+
+    /// Check that every filter in the chain is registered.
+    pub(crate) fn check_chain(&self, chain: &[String]) -> Result<()> {
+        for name in chain {
+            if !self.filters.contains_key(name) {
+                bail!(
+                    "Unknown order filter {name} in market.order_filters, available filters: {}",
+                    self.filters.keys().cloned().collect::<Vec<_>>().join(", ")
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Run the filters of the chain in order, stopping at the first one skipping the order.
+    ///
+    /// If the chain names a filter that is not registered, as can happen when the config is
+    /// reloaded, the last valid chain is run instead.
+    pub(crate) fn evaluate(
+        &self,
+        chain: &[String],
+        order: &OrderRequest,
+        ctx: &FilterContext,
+    ) -> Result<(), FilterSkip> {
+        for name in self.active_chain(chain) {
+            let Some(filter) = self.filters.get(&name) else {
+                continue;
+            };
+            if let FilterDecision::Skip(reason) = filter.allows(order, ctx) {
+                *self.state.lock().unwrap().skips.entry(name.clone()).or_default() += 1;
+                return Err(FilterSkip { filter: name, reason });
+            }
+        }
+        Ok(())
+    }
+
+    /// Number of orders skipped by each filter since the last call.
+    pub(crate) fn take_skip_counts(&self) -> BTreeMap<String, u64> {
+        std::mem::take(&mut self.state.lock().unwrap().skips)
+    }
+
+    /// Return the configured chain if all its filters are registered, and otherwise the last
+    /// valid chain, logging the rejected chain once.
+    fn active_chain(&self, configured: &[String]) -> Vec<String> {
+        let mut state = self.state.lock().unwrap();
+        if state.active != configured {
+            match self.check_chain(configured) {
+                Ok(()) => {
+                    state.active = configured.to_vec();
+                    state.rejected = None;
+                }
+                Err(err) if state.rejected.as_deref() != Some(configured) => {
+                    tracing::error!(
+                        "{err}; keeping the previous order filters: {}",
+                        state.active.join(", ")
+                    );
+                    state.rejected = Some(configured.to_vec());
+                }
+                Err(_) => {}
+            }
+        }
+        state.active.clone()
+    }
+}
+
+/// Skips orders expiring within `min_deadline` seconds.
+struct ExpiryMarginFilter;
+
+impl OrderFilter for ExpiryMarginFilter {
+    fn name(&self) -> &str {
+        "expiry_margin"
+    }
+
+    fn allows(&self, order: &OrderRequest, ctx: &FilterContext) -> FilterDecision {
+        let seconds_left = order.expiry().saturating_sub(ctx.now);
+        let min_deadline = ctx.market.min_deadline;
+        if seconds_left <= min_deadline {
+            return FilterDecision::Skip(format!(
+                "expires within min_deadline: {seconds_left}, min_deadline: {min_deadline}"
+            ));
+        }
+        FilterDecision::Allow
+    }
+}
+
+/// Skips orders from requestors missing from `allow_client_addresses`, or listed in
+/// `deny_requestor_addresses`.
+struct RequestorListFilter;
+
+impl OrderFilter for RequestorListFilter {
+    fn name(&self) -> &str {
+        "requestor_lists"
+    }
+
+    fn allows(&self, order: &OrderRequest, ctx: &FilterContext) -> FilterDecision {
+        let client_addr = order.request.client_address();
+        if let Some(allow_addresses) = &ctx.market.allow_client_addresses {
+            if !allow_addresses.contains(&client_addr) {
+                return FilterDecision::Skip(format!("{client_addr} is not in allowed addrs"));
+            }
+        }
+        if let Some(deny_addresses) = &ctx.market.deny_requestor_addresses {
+            if deny_addresses.contains(&client_addr) {
+                return FilterDecision::Skip(format!("{client_addr} is in denied addrs"));
+            }
+        }
+        FilterDecision::Allow
+    }
+}
+
+/// Skips orders to lock with a collateral above `max_collateral`.
+///
+/// Orders to fulfill after their lock expired are never locked, and always allowed.
+struct MaxCollateralFilter;
+
+impl OrderFilter for MaxCollateralFilter {
+    fn name(&self) -> &str {
+        "max_collateral"
+    }
+
+    fn allows(&self, order: &OrderRequest, ctx: &FilterContext) -> FilterDecision {
+        if order.fulfillment_type == FulfillmentType::FulfillAfterLockExpire {
+            return FilterDecision::Allow;
+        }
+        let max_collateral: U256 =
+            match parse_units(&ctx.market.max_collateral, ctx.collateral_token_decimals) {
+                Ok(max_collateral) => max_collateral.into(),
+                Err(err) => return FilterDecision::Skip(format!("invalid max_collateral: {err}")),
+            };
+        let lock_collateral = order.request.offer.lockCollateral;
+        if lock_collateral > max_collateral {
+            return FilterDecision::Skip(format!(
+                "high stake order, lock stake: {lock_collateral}, max stake: {max_collateral}"
+            ));
+        }
+        FilterDecision::Allow
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use alloy::primitives::{Address, Bytes};
+    use boundless_market::contracts::{
+        Offer, Predicate, ProofRequest, RequestId, RequestInput, RequestInputType, Requirements,
+    };
+    use risc0_zkvm::sha::Digest;
+
+    use super::*;
+
+    /// Filter recording each evaluation, skipping orders if `skip` is set.
+    struct RecordingFilter {
+        name: &'static str,
+        skip: bool,
+        calls: Arc<Mutex<Vec<&'static str>>>,
+    }
+
+    impl OrderFilter for RecordingFilter {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn allows(&self, _order: &OrderRequest, _ctx: &FilterContext) -> FilterDecision {
+            self.calls.lock().unwrap().push(self.name);
+            match self.skip {
+                true => FilterDecision::Skip(format!("{} says no", self.name)),
+                false => FilterDecision::Allow,
+            }
+        }
+    }
+
+    fn order(expires_in: u32, lock_collateral: U256) -> OrderRequest {
+        let request = ProofRequest::new(
+            RequestId::new(Address::ZERO, 0),
+            Requirements::new(Predicate::prefix_match(Digest::ZERO, Bytes::default())),
+            "http://risczero.com/image",
+            RequestInput { inputType: RequestInputType::Inline, data: Default::default() },
+            Offer {
+                minPrice: U256::from(1),
+                maxPrice: U256::from(2),
+                rampUpStart: 0,
+                timeout: expires_in,
+                lockTimeout: expires_in,
+                rampUpPeriod: 1,
+                lockCollateral: lock_collateral,
+            },
+        );
+        OrderRequest::new(request, Bytes::new(), FulfillmentType::LockAndFulfill, Address::ZERO, 1)
+    }
+
+    fn chain(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn chain_runs_in_order_and_short_circuits() {
+        let calls = Arc::new(Mutex::new(vec![]));
+        let mut filters = OrderFilters::default();
+        for (name, skip) in [("first", false), ("second", true), ("third", false)] {
+            filters.register(Arc::new(RecordingFilter { name, skip, calls: calls.clone() }));
+        }
+        let market = MarketConf::default();
+        let ctx = FilterContext::new(0, &market, 18);
+        let order = order(1_000, U256::ZERO);
+
+        assert_eq!(filters.evaluate(&chain(&["third", "first"]), &order, &ctx), Ok(()));
+        assert_eq!(*calls.lock().unwrap(), vec!["third", "first"]);
+
+        calls.lock().unwrap().clear();
+        let skip = filters.evaluate(&chain(&["first", "second", "third"]), &order, &ctx);
+        assert_eq!(
+            skip,
+            Err(FilterSkip { filter: "second".to_string(), reason: "second says no".to_string() })
+        );
+        assert_eq!(*calls.lock().unwrap(), vec!["first", "second"]);
+    }
+
+    #[test]
+    fn unknown_filters() {
+        let filters = OrderFilters::default();
+        filters.check_chain(&chain(&["expiry_margin", "requestor_lists"])).unwrap();
+        let err = filters.check_chain(&chain(&["expiry_margin", "geofence"])).unwrap_err();
+        assert!(err.to_string().contains("Unknown order filter geofence"));
+
+        // A chain naming an unknown filter keeps the last valid chain.
+        let market = MarketConf { min_deadline: 100, ..Default::default() };
+        let ctx = FilterContext::new(0, &market, 18);
+        let expiring = order(100, U256::ZERO);
+        assert!(filters.evaluate(&chain(&["requestor_lists"]), &expiring, &ctx).is_ok());
+        assert!(filters.evaluate(&chain(&["expiry_margin"]), &expiring, &ctx).is_err());
+        let skip = filters.evaluate(&chain(&["geofence"]), &expiring, &ctx).unwrap_err();
+        assert_eq!(skip.filter, "expiry_margin");
+        assert!(filters.evaluate(&chain(&["requestor_lists"]), &expiring, &ctx).is_ok());
+
+        assert_eq!(filters.take_skip_counts(), BTreeMap::from([("expiry_margin".to_string(), 2)]));
+        assert!(filters.take_skip_counts().is_empty());
+    }
+
+    #[test]
+    fn builtin_filters() {
+        let filters = OrderFilters::default();
+        let mut market = MarketConf {
+            min_deadline: 100,
+            max_collateral: "1".to_string(),
+            deny_requestor_addresses: Some([Address::ZERO].into_iter().collect()),
+            ..Default::default()
+        };
+        let evaluate = |market: &MarketConf, name: &str, order: &OrderRequest| {
+            filters.evaluate(&chain(&[name]), order, &FilterContext::new(0, market, 18))
+        };
+        let one_token = U256::from(10).pow(U256::from(18));
+
+        assert!(evaluate(&market, "expiry_margin", &order(1_000, U256::ZERO)).is_ok());
+        assert!(evaluate(&market, "expiry_margin", &order(100, U256::ZERO)).is_err());
+
+        assert!(evaluate(&market, "max_collateral", &order(1_000, one_token)).is_ok());
+        assert!(
+            evaluate(&market, "max_collateral", &order(1_000, one_token + U256::from(1))).is_err()
+        );
+
+        let skip = evaluate(&market, "requestor_lists", &order(1_000, U256::ZERO)).unwrap_err();
+        assert!(skip.reason.contains("is in denied addrs"));
+        market.deny_requestor_addresses = None;
+        market.allow_client_addresses = Some(vec![Address::repeat_byte(1)]);
+        let skip = evaluate(&market, "requestor_lists", &order(1_000, U256::ZERO)).unwrap_err();
+        assert!(skip.reason.contains("is not in allowed addrs"));
+    }
+}
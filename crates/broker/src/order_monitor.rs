@@ -16,17 +16,22 @@ use crate::chain_monitor::ChainHead;
 use crate::OrderRequest;
 use crate::{
     chain_monitor::ChainMonitorService,
-    config::{ConfigLock, OrderCommitmentPriority},
-    db::DbObj,
+    collateral::CollateralTracker,
+    config::{ConfigLock, OrderCommitmentPriority, SizeBracket},
+    db::{DbObj, ObservedOrderStats},
     errors::CodedError,
-    impl_coded_debug, now_timestamp,
+    impl_coded_debug,
+    latency::{Checkpoint, LatencyStats},
+    now_timestamp,
+    saturation::ProverSaturation,
+    size_brackets,
     task::{RetryRes, RetryTask, SupervisorErr},
     utils, FulfillmentType, Order,
 };
 use alloy::{
     network::Ethereum,
     primitives::{
-        utils::{format_ether, parse_units},
+        utils::{format_ether, format_units, parse_units},
         Address, U256,
     },
     providers::{Provider, WalletProvider},
@@ -42,12 +47,18 @@ use moka::{future::Cache, Expiry};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use thiserror::Error;
-use tokio::sync::{mpsc, Mutex};
+use tokio::sync::{mpsc, watch, Mutex};
 use tokio_util::sync::CancellationToken;
 
 /// Hard limit on the number of orders to concurrently kick off proving work for.
 const MAX_PROVING_BATCH_SIZE: u32 = 10;
 
+/// Number of recently locked orders the lock latency percentiles are computed over.
+const LATENCY_WINDOW: usize = 200;
+
+/// Number of locked orders between two lock latency summaries in the logs.
+const LATENCY_SUMMARY_INTERVAL: u64 = 20;
+
 #[derive(Error)]
 pub enum OrderMonitorErr {
     #[error("{code} Failed to lock order: {0}", code = self.code())]
@@ -65,6 +76,9 @@ pub enum OrderMonitorErr {
     #[error("{code} RPC error: {0:?}", code = self.code())]
     RpcErr(anyhow::Error),
 
+    #[error("{code} Refusing to send lock tx in observation mode", code = self.code())]
+    ObservationMode,
+
     #[error("{code} Unexpected error: {0:?}", code = self.code())]
     UnexpectedError(#[from] anyhow::Error),
 }
@@ -79,6 +93,7 @@ impl CodedError for OrderMonitorErr {
             OrderMonitorErr::AlreadyLocked => "[B-OM-009]",
             OrderMonitorErr::InsufficientBalance => "[B-OM-010]",
             OrderMonitorErr::RpcErr(_) => "[B-OM-011]",
+            OrderMonitorErr::ObservationMode => "[B-OM-012]",
             OrderMonitorErr::UnexpectedError(_) => "[B-OM-500]",
         }
     }
@@ -134,6 +149,10 @@ struct OrderMonitorConfig {
     batch_buffer_time_secs: u64,
     order_commitment_priority: OrderCommitmentPriority,
     priority_addresses: Option<Vec<Address>>,
+    observation_mode: bool,
+    size_brackets: Option<Vec<SizeBracket>>,
+    lend_idle_bracket_capacity: bool,
+    max_pricing_block_drift: u64,
 }
 
 #[derive(Clone)]
@@ -156,6 +175,13 @@ pub struct OrderMonitor<P> {
     prove_cache: Arc<Cache<String, Arc<OrderRequest>>>,
     supported_selectors: SupportedSelectors,
     rpc_retry_config: RpcRetryConfig,
+    saturation: watch::Receiver<ProverSaturation>,
+    collateral_token_decimals: u8,
+    lock_latency: Arc<std::sync::Mutex<LatencyStats>>,
+    reprice_tx: Option<mpsc::Sender<Box<OrderRequest>>>,
+    /// Running totals of the orders observed in observation mode, loaded from the DB once.
+    observed_stats: Arc<Mutex<Option<ObservedOrderStats>>>,
+    collateral: Arc<std::sync::Mutex<CollateralTracker>>,
 }
 
 impl<P> OrderMonitor<P>
@@ -217,10 +243,40 @@ where
             prove_cache: Arc::new(Cache::builder().expire_after(OrderExpiry).build()),
             supported_selectors: SupportedSelectors::default(),
             rpc_retry_config,
+            saturation: watch::channel(ProverSaturation::default()).1,
+            collateral_token_decimals,
+            lock_latency: Arc::new(std::sync::Mutex::new(LatencyStats::new(LATENCY_WINDOW))),
+            reprice_tx: None,
+            observed_stats: Arc::new(Mutex::new(None)),
+            collateral: Default::default(),
         };
         Ok(monitor)
     }
 
+    /// Halt new commitments while the prover backend is saturated, as reported by the given
+    /// saturation state.
+    pub(crate) fn with_saturation(mut self, saturation: watch::Receiver<ProverSaturation>) -> Self {
+        self.saturation = saturation;
+        self
+    }
+
+    /// Release the collateral reserved by the order picker in the given tracker when an order
+    /// priced for locking is locked or dropped.
+    pub(crate) fn with_collateral(
+        mut self,
+        collateral: Arc<std::sync::Mutex<CollateralTracker>>,
+    ) -> Self {
+        self.collateral = collateral;
+        self
+    }
+
+    /// Send orders priced against a block too far behind the chain head back to the order
+    /// picker through the given channel, to price them again before committing to them.
+    pub(crate) fn with_repricing(mut self, reprice_tx: mpsc::Sender<Box<OrderRequest>>) -> Self {
+        self.reprice_tx = Some(reprice_tx);
+        self
+    }
+
     async fn lock_order(&self, order: &OrderRequest) -> Result<U256, OrderMonitorErr> {
         let request_id = order.request.id;
 
@@ -247,16 +303,24 @@ where
             return Err(OrderMonitorErr::AlreadyLocked);
         }
 
-        let conf_priority_gas = {
+        let (conf_priority_gas, observation_mode) = {
             let conf = self.config.lock_all().context("Failed to lock config")?;
-            conf.market.lockin_priority_gas
+            (conf.market.lockin_priority_gas, conf.market.observation_mode)
         };
+        // Orders are never passed here in observation mode, this guards against any path that
+        // would otherwise commit to a new order. Orders committed to before the mode was turned
+        // on are still proven and fulfilled.
+        if observation_mode {
+            tracing::error!("Attempted to lock request 0x{:x} in observation mode", request_id);
+            return Err(OrderMonitorErr::ObservationMode);
+        }
 
         tracing::info!(
             "Locking request: 0x{:x} for stake: {}",
             request_id,
             order.request.offer.lockCollateral
         );
+        order.timings.record(Checkpoint::LockSubmitted);
         let lock_block = self
             .market
             .lock_request(&order.request, order.client_sig.clone(), conf_priority_gas)
@@ -312,6 +376,7 @@ where
                     }
                 }
             })?;
+        order.timings.record(Checkpoint::LockIncluded);
 
         // Fetch the block to retrieve the lock timestamp. This has been observed to return
         // inconsistent state between the receipt being available but the block not yet.
@@ -396,6 +461,7 @@ where
         if let Err(e) = self.db.insert_skipped_request(order).await {
             tracing::error!("Failed to skip order ({}): {} - {e:?}", reason, order.id());
         }
+        self.release_collateral(order);
 
         match order.fulfillment_type {
             FulfillmentType::LockAndFulfill => {
@@ -407,6 +473,14 @@ where
         }
     }
 
+    /// Release the collateral reserved for an order priced for locking, once the order is locked
+    /// or dropped, so that it is available to price other orders.
+    fn release_collateral(&self, order: &OrderRequest) {
+        if order.fulfillment_type == FulfillmentType::LockAndFulfill {
+            self.collateral.lock().unwrap().release(order.request.id);
+        }
+    }
+
     async fn get_valid_orders(
         &self,
         current_block_timestamp: u64,
@@ -522,6 +596,91 @@ where
         Ok(candidate_orders)
     }
 
+    /// Send orders that have been ready for more than `max_drift` blocks since they were priced
+    /// back to the order picker, returning the orders whose pricing is still current.
+    ///
+    /// Blocks spent waiting for the target time of an order do not count, as orders are priced
+    /// ahead of time by design. Orders that could not be sent stay cached, and are checked again
+    /// on the next block.
+    async fn reprice_drifted_orders(
+        &self,
+        orders: Vec<Arc<OrderRequest>>,
+        block_number: u64,
+        max_drift: u64,
+    ) -> Vec<Arc<OrderRequest>> {
+        let Some(reprice_tx) = &self.reprice_tx else {
+            return orders;
+        };
+
+        let mut current_orders = Vec::with_capacity(orders.len());
+        for order in orders {
+            let drift = match order.pricing_block {
+                Some(pricing_block) => {
+                    let ready_block = order.ready_block.get().copied().unwrap_or(pricing_block);
+                    block_number.saturating_sub(pricing_block.max(ready_block))
+                }
+                None => 0,
+            };
+            if drift <= max_drift {
+                current_orders.push(order);
+                continue;
+            }
+
+            tracing::info!(
+                "Order {} has been ready for {drift} blocks since it was priced, more than the max of {max_drift}, pricing it again",
+                order.id()
+            );
+            if let Err(err) = reprice_tx.try_send(Box::new(OrderRequest::clone(&order))) {
+                tracing::warn!("Failed to send order {} to be priced again: {err}", order.id());
+                continue;
+            }
+            match order.fulfillment_type {
+                FulfillmentType::LockAndFulfill => {
+                    self.lock_and_prove_cache.invalidate(&order.id()).await;
+                }
+                FulfillmentType::FulfillAfterLockExpire
+                | FulfillmentType::FulfillWithoutLocking => {
+                    self.prove_cache.invalidate(&order.id()).await;
+                }
+            }
+        }
+        current_orders
+    }
+
+    /// Record the latency from observing a freshly locked order to the inclusion of its lock,
+    /// warning about any stage over its configured budget.
+    fn record_lock_latency(&self, order: &OrderRequest) {
+        let Some(breakdown) = order.timings.breakdown() else {
+            return;
+        };
+        tracing::debug!("Lock latency of order {}: {breakdown}", order.id());
+
+        let budgets = match self.config.lock_all() {
+            Ok(config) => config.market.lock_latency_budgets_ms.clone(),
+            Err(err) => {
+                tracing::warn!("Failed to read lock latency budgets: {err:?}");
+                None
+            }
+        };
+        for (stage, latency, budget) in breakdown.over_budget(&budgets.unwrap_or_default()) {
+            tracing::warn!(
+                "Order {} exceeded the {stage} latency budget: {}ms > {budget}ms",
+                order.id(),
+                latency.as_millis()
+            );
+        }
+
+        let mut stats = self.lock_latency.lock().unwrap();
+        stats.record(&breakdown);
+        if stats.recorded() % LATENCY_SUMMARY_INTERVAL == 0 {
+            tracing::info!(
+                "Lock latency over the last {} locked orders: {}",
+                stats.recorded().min(LATENCY_WINDOW as u64),
+                stats.summary()
+            );
+        }
+    }
+
     async fn lock_and_prove_orders(&self, orders: &[Arc<OrderRequest>]) -> Result<()> {
         let lock_jobs = orders.iter().map(|order| {
             async move {
@@ -531,6 +690,7 @@ where
                     match self.lock_order(order).await {
                         Ok(lock_price) => {
                             tracing::info!("Locked request: 0x{:x}", request_id);
+                            self.record_lock_latency(order);
                             if let Err(err) = self.db.insert_accepted_request(order, lock_price).await {
                                 tracing::error!(
                                     "FATAL STAKE AT RISK: {} failed to move from locking -> proving status {}",
@@ -566,6 +726,7 @@ where
                         }
                     }
                     self.lock_and_prove_cache.invalidate(&order_id).await;
+                    self.release_collateral(order);
                 } else {
                     if let Err(err) = self.db.insert_accepted_request(order, U256::ZERO).await {
                         tracing::error!(
@@ -583,11 +744,89 @@ where
         Ok(())
     }
 
+    /// Record the orders that would have been locked and/or proven in observation mode, along
+    /// with their hypothetical revenue and gas cost, without sending any transactions.
+    ///
+    /// Observed orders are skipped so they are not considered again.
+    async fn observe_orders(&self, orders: &[Arc<OrderRequest>]) -> Result<(), OrderMonitorErr> {
+        let gas_price =
+            self.chain_monitor.current_gas_price().await.context("Failed to get gas price")?;
+        let aggregation_cost = utils::aggregation_cost_model(&self.config, &self.db).await?;
+        let now = now_timestamp();
+
+        for order in orders {
+            let order_id = order.id();
+            let gas_cost =
+                self.calculate_order_gas_cost_wei(order, gas_price, &aggregation_cost).await?;
+            let (action, revenue, collateral_reward) = match order.fulfillment_type {
+                FulfillmentType::LockAndFulfill => {
+                    let price = order
+                        .request
+                        .offer
+                        .price_at(now)
+                        .context("Failed to compute price of order")?;
+                    ("lock and prove", price, U256::ZERO)
+                }
+                FulfillmentType::FulfillAfterLockExpire
+                | FulfillmentType::FulfillWithoutLocking => (
+                    "prove",
+                    U256::ZERO,
+                    order.request.offer.collateral_reward_if_locked_and_not_fulfilled(),
+                ),
+            };
+            tracing::info!(
+                "Observation mode: would {action} order {order_id} for revenue {} ether and collateral reward {} at gas cost {} ether",
+                format_ether(revenue),
+                format_units(collateral_reward, self.collateral_token_decimals).unwrap_or_default(),
+                format_ether(gas_cost),
+            );
+            match self.db.record_observed_order(order, revenue, collateral_reward, gas_cost).await {
+                Ok(true) => {
+                    if let Some(stats) = self.observed_stats.lock().await.as_mut() {
+                        stats.add(revenue, collateral_reward, gas_cost);
+                    }
+                }
+                Ok(false) => {}
+                Err(err) => tracing::error!("Failed to record observed order {order_id}: {err:?}"),
+            }
+            self.skip_order(order, "observed").await;
+        }
+
+        match self.observed_stats().await {
+            Ok(stats) => tracing::info!(
+                "Observation mode totals (observed only, no transactions sent): {} orders, revenue {} ether, collateral reward {}, gas cost {} ether",
+                stats.orders,
+                format_ether(stats.revenue),
+                format_units(stats.collateral_reward, self.collateral_token_decimals)
+                    .unwrap_or_default(),
+                format_ether(stats.gas_cost),
+            ),
+            Err(err) => tracing::warn!("Failed to get observed order stats: {err:?}"),
+        }
+
+        Ok(())
+    }
+
+    /// Running totals of the observed orders
+    ///
+    /// The totals are read from the DB on first use, which includes the orders recorded above, and
+    /// then kept up to date as orders are recorded.
+    async fn observed_stats(&self) -> Result<ObservedOrderStats> {
+        let mut observed_stats = self.observed_stats.lock().await;
+        if let Some(stats) = observed_stats.as_ref() {
+            return Ok(stats.clone());
+        }
+        let stats = self.db.get_observed_order_stats().await?;
+        *observed_stats = Some(stats.clone());
+        Ok(stats)
+    }
+
     /// Calculate the gas units needed for an order and the corresponding cost in wei
     async fn calculate_order_gas_cost_wei(
         &self,
         order: &OrderRequest,
         gas_price: u128,
+        aggregation_cost: &utils::AggregationCostModel,
     ) -> Result<U256, OrderMonitorErr> {
         // Calculate gas units needed for this order (lock + fulfill)
         let order_gas_units = if order.fulfillment_type == FulfillmentType::LockAndFulfill {
@@ -597,6 +836,8 @@ where
                         &self.config,
                         &self.supported_selectors,
                         &order.request,
+                        order.fulfillment_path(),
+                        aggregation_cost,
                     )
                     .await?,
                 ),
@@ -607,12 +848,20 @@ where
                     &self.config,
                     &self.supported_selectors,
                     &order.request,
+                    order.fulfillment_path(),
+                    aggregation_cost,
                 )
                 .await?,
             )
         };
 
         let order_cost_wei = U256::from(gas_price) * order_gas_units;
+        tracing::trace!(
+            "Order {} estimated to use {order_gas_units} gas ({} ether), using {}",
+            order.id(),
+            format_ether(order_cost_wei),
+            utils::describe_cost_model(order.fulfillment_path(), aggregation_cost)
+        );
 
         Ok(order_cost_wei)
     }
@@ -623,6 +872,29 @@ where
         config: &OrderMonitorConfig,
         prev_orders_by_status: &mut String,
     ) -> Result<Vec<Arc<OrderRequest>>> {
+        // Fill each size bracket independently before applying the overall limits.
+        let orders = match config.size_brackets.as_deref() {
+            Some(brackets) => {
+                let committed_orders = self.db.get_committed_orders().await?;
+                let num_orders = orders.len();
+                let orders = size_brackets::select_orders(
+                    brackets,
+                    config.lend_idle_bracket_capacity,
+                    committed_orders.iter().map(|order| order.total_cycles),
+                    orders,
+                    |order| order.total_cycles,
+                );
+                if orders.len() < num_orders {
+                    tracing::debug!(
+                        "Holding back {} orders until capacity frees up in their size bracket",
+                        num_orders - orders.len()
+                    );
+                }
+                orders
+            }
+            None => orders,
+        };
+
         let num_orders = orders.len();
         // Get our current capacity for proving orders given our config and the number of orders that are currently committed to be proven + fulfilled.
         let capacity = self
@@ -649,6 +921,7 @@ where
             .map_err(|err| OrderMonitorErr::RpcErr(err.into()))?;
 
         // Calculate gas units required for committed orders
+        let aggregation_cost = utils::aggregation_cost_model(&self.config, &self.db).await?;
         let committed_orders = self.db.get_committed_orders().await?;
         let committed_gas_units =
             futures::future::try_join_all(committed_orders.iter().map(|order| {
@@ -656,6 +929,8 @@ where
                     &self.config,
                     &self.supported_selectors,
                     &order.request,
+                    order.fulfillment_path(),
+                    &aggregation_cost,
                 )
             }))
             .await?
@@ -727,7 +1002,8 @@ where
                     break;
                 }
                 // Calculate gas and cost for this order using our helper method
-                let order_cost_wei = self.calculate_order_gas_cost_wei(&order, gas_price).await?;
+                let order_cost_wei =
+                    self.calculate_order_gas_cost_wei(&order, gas_price, &aggregation_cost).await?;
 
                 // Skip if not enough balance
                 if order_cost_wei > remaining_balance_wei {
@@ -787,7 +1063,8 @@ where
                 if final_orders.len() >= capacity_granted {
                     break;
                 }
-                let order_cost_wei = self.calculate_order_gas_cost_wei(&order, gas_price).await?;
+                let order_cost_wei =
+                    self.calculate_order_gas_cost_wei(&order, gas_price, &aggregation_cost).await?;
 
                 // Skip if not enough balance
                 if order_cost_wei > remaining_balance_wei {
@@ -874,6 +1151,10 @@ where
                                 batch_buffer_time_secs: config.batcher.block_deadline_buffer_secs,
                                 order_commitment_priority: config.market.order_commitment_priority,
                                 priority_addresses: config.market.priority_requestor_addresses.clone(),
+                                observation_mode: config.market.observation_mode,
+                                size_brackets: config.market.size_brackets.clone(),
+                                lend_idle_bracket_capacity: config.market.lend_idle_bracket_capacity,
+                                max_pricing_block_drift: config.market.max_pricing_block_drift,
                             }
                         };
 
@@ -887,6 +1168,24 @@ where
                             );
                             continue;
                         }
+                        for order in &valid_orders {
+                            order.ready_block.get_or_init(|| block_number);
+                        }
+
+                        // Hold off on new commitments until the prover backend works through its
+                        // backlog. Orders remain cached and are reconsidered once it clears.
+                        let saturation = *self.saturation.borrow();
+                        if saturation.is_saturated() {
+                            tracing::debug!(
+                                "Prover backend saturated ({:?} queued jobs), not committing to {} valid orders",
+                                saturation.queue_depth,
+                                valid_orders.len()
+                            );
+                            continue;
+                        }
+
+                        // Price again the orders priced against stale chain state.
+                        valid_orders = self.reprice_drifted_orders(valid_orders, block_number, monitor_config.max_pricing_block_drift).await;
 
                         // Prioritize the orders that intend to fulfill based on configured commitment priority.
                         valid_orders = self.prioritize_orders(valid_orders, monitor_config.order_commitment_priority, monitor_config.priority_addresses.as_deref());
@@ -899,6 +1198,9 @@ where
                                 &mut prev_orders_by_status,
                             )
                             .await?;
+                        for order in &final_orders {
+                            order.timings.record(Checkpoint::Committed);
+                        }
 
                         tracing::trace!("After processing block {}[timestamp {}], we will now start locking and/or proving {} orders.",
                             block_number,
@@ -906,7 +1208,13 @@ where
                             final_orders.len(),
                         );
 
-                        if !final_orders.is_empty() {
+                        if final_orders.is_empty() {
+                            continue;
+                        }
+                        if monitor_config.observation_mode {
+                            // Record what would have been locked and proven, without sending any transactions.
+                            self.observe_orders(&final_orders).await?;
+                        } else {
                             // Lock and prove filtered orders.
                             self.lock_and_prove_orders(&final_orders).await?;
                         }
@@ -926,6 +1234,14 @@ where
         &self,
         order: Box<OrderRequest>,
     ) -> Result<(), OrderMonitorErr> {
+        // Orders already past their target time were ready from the block they were priced at.
+        // Others are marked ready once the monitor first finds them ready.
+        if let Some(pricing_block) = order.pricing_block {
+            if order.target_timestamp.is_some_and(|target| target <= now_timestamp()) {
+                let _ = order.ready_block.set(pricing_block);
+            }
+        }
+
         match order.fulfillment_type {
             FulfillmentType::LockAndFulfill => {
                 // Note: this could be done without waiting for the batch to minimize latency, but
@@ -969,6 +1285,7 @@ pub(crate) mod tests {
         node_bindings::Anvil,
         primitives::{Address, U256},
         providers::{
+            ext::AnvilApi,
             fillers::{
                 BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller,
                 WalletFiller,
@@ -1060,7 +1377,12 @@ pub(crate) mod tests {
                 boundless_market_address: self.market_address,
                 chain_id: self.anvil.chain_id(),
                 total_cycles: None,
+                fulfillment_path: None,
                 cached_id: Default::default(),
+                timings: Default::default(),
+                pricing_block: None,
+                ready_block: Default::default(),
+                rate_limited_at: None,
             })
         }
     }
@@ -1201,6 +1523,115 @@ pub(crate) mod tests {
         .await;
     }
 
+    #[tokio::test]
+    #[traced_test]
+    async fn monitor_halts_while_prover_saturated() {
+        let mut ctx = setup_om_test_context().await;
+
+        let order =
+            ctx.create_test_order(FulfillmentType::LockAndFulfill, now_timestamp(), 100, 200).await;
+        let order_id = order.id();
+        ctx.market_service.submit_request(&order.request, &ctx.signer).await.unwrap();
+        ctx.priced_order_tx.send(order).await.unwrap();
+
+        let (saturation_tx, saturation_rx) =
+            watch::channel(ProverSaturation { queue_depth: Some(8), threshold: Some(5) });
+        let monitor = ctx.monitor.with_saturation(saturation_rx);
+
+        run_with_monitor(monitor, async move {
+            // Several blocks pass without committing to the order while saturated.
+            tokio::time::sleep(tokio::time::Duration::from_secs(6)).await;
+            assert!(ctx.db.get_order(&order_id).await.unwrap().is_none());
+            assert!(logs_contain("Prover backend saturated"));
+
+            // Commitments resume once the backlog clears.
+            saturation_tx
+                .send_replace(ProverSaturation { queue_depth: Some(1), threshold: Some(5) });
+            for _ in 0..20 {
+                if ctx.db.get_order(&order_id).await.unwrap().is_some() {
+                    break;
+                }
+                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+            }
+
+            let order = ctx.db.get_order(&order_id).await.unwrap().unwrap();
+            assert_eq!(order.status, OrderStatus::PendingProving);
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn monitor_reprices_orders_priced_on_stale_block() {
+        let mut ctx = setup_om_test_context().await;
+        ctx.config.load_write().unwrap().market.max_pricing_block_drift = 2;
+
+        // Deploying the market and submitting the request mines more than 2 blocks past genesis.
+        let mut order =
+            ctx.create_test_order(FulfillmentType::LockAndFulfill, now_timestamp(), 100, 200).await;
+        order.pricing_block = Some(0);
+        let order_id = order.id();
+        ctx.market_service.submit_request(&order.request, &ctx.signer).await.unwrap();
+        ctx.priced_order_tx.send(order).await.unwrap();
+
+        let (reprice_tx, mut reprice_rx) = mpsc::channel(1);
+        let monitor = ctx.monitor.with_repricing(reprice_tx);
+
+        run_with_monitor(monitor, async move {
+            let repriced =
+                tokio::time::timeout(tokio::time::Duration::from_secs(20), reprice_rx.recv())
+                    .await
+                    .expect("order was not sent to be priced again")
+                    .unwrap();
+            assert_eq!(repriced.id(), order_id);
+
+            // The order is not committed to until it comes back priced on a recent block.
+            tokio::time::sleep(tokio::time::Duration::from_secs(4)).await;
+            assert!(ctx.db.get_order(&order_id).await.unwrap().is_none());
+            assert!(reprice_rx.try_recv().is_err());
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn monitor_does_not_reprice_waiting_orders() {
+        let mut ctx = setup_om_test_context().await;
+        ctx.config.load_write().unwrap().market.max_pricing_block_drift = 2;
+        let provider = ctx.monitor.provider.clone();
+
+        // The order is priced well before its target time, as the order picker does for orders
+        // whose price has not yet reached the minimum.
+        let mut order =
+            ctx.create_test_order(FulfillmentType::LockAndFulfill, now_timestamp(), 100, 200).await;
+        order.target_timestamp = Some(now_timestamp() + 20);
+        let order_id = order.id();
+        ctx.market_service.submit_request(&order.request, &ctx.signer).await.unwrap();
+        order.pricing_block = Some(provider.get_block_number().await.unwrap());
+        ctx.priced_order_tx.send(order).await.unwrap();
+
+        let (reprice_tx, mut reprice_rx) = mpsc::channel(1);
+        let monitor = ctx.monitor.with_repricing(reprice_tx);
+
+        run_with_monitor(monitor, async move {
+            // Many blocks pass while the order waits for its target time.
+            tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+            provider.anvil_mine(Some(15), Some(2)).await.unwrap();
+
+            for _ in 0..20 {
+                if ctx.db.get_order(&order_id).await.unwrap().is_some() {
+                    break;
+                }
+                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+            }
+
+            let order = ctx.db.get_order(&order_id).await.unwrap().unwrap();
+            assert_eq!(order.status, OrderStatus::PendingProving);
+            assert!(reprice_rx.try_recv().is_err());
+        })
+        .await;
+    }
+
     // Capacity tests
     #[test]
     fn test_capacity_unlimited() {
@@ -1219,6 +1650,63 @@ pub(crate) mod tests {
     }
 
     // Filtering tests
+    #[tokio::test]
+    #[traced_test]
+    async fn monitor_observation_mode_sends_no_transactions() {
+        let mut ctx = setup_om_test_context().await;
+        ctx.config.load_write().unwrap().market.observation_mode = true;
+
+        let mut order_ids = vec![];
+        for _ in 0..3 {
+            let order = ctx
+                .create_test_order(FulfillmentType::LockAndFulfill, now_timestamp(), 100, 200)
+                .await;
+            ctx.market_service.submit_request(&order.request, &ctx.signer).await.unwrap();
+            order_ids.push(order.id());
+            ctx.priced_order_tx.send(order).await.unwrap();
+        }
+
+        let provider = ctx.monitor.provider.clone();
+        let prover_addr = ctx.signer.address();
+        let tx_count = provider.get_transaction_count(prover_addr).await.unwrap();
+
+        run_with_monitor(ctx.monitor, async move {
+            for _ in 0..20 {
+                if ctx.db.get_observed_order_stats().await.unwrap().orders == 3 {
+                    break;
+                }
+                tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+            }
+
+            let stats = ctx.db.get_observed_order_stats().await.unwrap();
+            assert_eq!(stats.orders, 3);
+            assert!(stats.revenue > U256::ZERO);
+            assert!(stats.gas_cost > U256::ZERO);
+            for order_id in &order_ids {
+                let order = ctx.db.get_order(order_id).await.unwrap().unwrap();
+                assert_eq!(order.status, OrderStatus::Skipped);
+            }
+            assert_eq!(provider.get_transaction_count(prover_addr).await.unwrap(), tx_count);
+            assert!(logs_contain("Observation mode: would lock and prove order"));
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn lock_order_refused_in_observation_mode() {
+        let mut ctx = setup_om_test_context().await;
+        ctx.config.load_write().unwrap().market.observation_mode = true;
+
+        let order =
+            ctx.create_test_order(FulfillmentType::LockAndFulfill, now_timestamp(), 100, 200).await;
+        ctx.market_service.submit_request(&order.request, &ctx.signer).await.unwrap();
+
+        let err = ctx.monitor.lock_order(&order).await.unwrap_err();
+        assert!(matches!(err, OrderMonitorErr::ObservationMode));
+        assert!(!ctx.market_service.is_locked(order.request.id).await.unwrap());
+    }
+
     #[tokio::test]
     #[traced_test]
     async fn test_filter_expired_orders() {
@@ -1273,6 +1761,35 @@ pub(crate) mod tests {
         assert_eq!(order.status, OrderStatus::Skipped);
     }
 
+    #[tokio::test]
+    #[traced_test]
+    async fn dropped_order_releases_collateral() {
+        let mut ctx = setup_om_test_context().await;
+        let collateral = Arc::new(std::sync::Mutex::new(CollateralTracker::default()));
+        ctx.monitor = ctx.monitor.clone().with_collateral(collateral.clone());
+        let current_timestamp = now_timestamp();
+        let now = Instant::now();
+
+        // The order picker reserved the collateral of the order when pricing it.
+        let order =
+            ctx.create_test_order(FulfillmentType::LockAndFulfill, current_timestamp, 45, 45).await;
+        {
+            let mut collateral = collateral.lock().unwrap();
+            collateral.set_balance(U256::from(150), 1, now);
+            collateral.reserve(order.request.id, U256::from(100), order.request.lock_expires_at());
+            assert_eq!(collateral.available(1, now, current_timestamp), Some(U256::from(50)));
+        }
+        ctx.monitor.lock_and_prove_cache.insert(order.id(), Arc::from(order)).await;
+
+        // The order is dropped for its insufficient deadline, long before its lock expires.
+        let result = ctx.monitor.get_valid_orders(current_timestamp, 100).await.unwrap();
+        assert!(result.is_empty());
+
+        let mut collateral = collateral.lock().unwrap();
+        collateral.set_balance(U256::from(150), 1, now);
+        assert_eq!(collateral.available(1, now, current_timestamp), Some(U256::from(150)));
+    }
+
     #[tokio::test]
     async fn test_filter_locked_by_others() {
         let mut ctx = setup_om_test_context().await;
@@ -1480,6 +1997,66 @@ pub(crate) mod tests {
         assert!(logs_contain("filtered to 0 orders: []"));
     }
 
+    #[tokio::test]
+    #[traced_test]
+    async fn test_apply_capacity_limits_size_brackets() {
+        let mut ctx = setup_om_test_context().await;
+        let current_timestamp = now_timestamp();
+
+        // Commit to a large order, filling the large bracket
+        let committed_order = ctx
+            .create_test_order(FulfillmentType::LockAndFulfill, current_timestamp, 100, 200)
+            .await;
+        let mut committed_order = committed_order.to_proving_order(Default::default());
+        committed_order.status = OrderStatus::Proving;
+        committed_order.total_cycles = Some(5_000_000_000);
+        committed_order.proving_started_at = Some(current_timestamp);
+        ctx.db.add_order(&committed_order).await.unwrap();
+
+        let mut orders = Vec::new();
+        let mut large_order = ctx
+            .create_test_order(FulfillmentType::LockAndFulfill, current_timestamp, 100, 200)
+            .await;
+        large_order.total_cycles = Some(5_000_000_000);
+        orders.push(Arc::from(large_order));
+        for _ in 0..3 {
+            let mut order = ctx
+                .create_test_order(FulfillmentType::LockAndFulfill, current_timestamp, 100, 200)
+                .await;
+            order.total_cycles = Some(1_000_000);
+            orders.push(Arc::from(order));
+        }
+
+        let filtered_orders = ctx
+            .monitor
+            .apply_capacity_limits(
+                orders,
+                &OrderMonitorConfig {
+                    size_brackets: Some(vec![
+                        SizeBracket {
+                            name: "small".to_string(),
+                            max_mcycles: Some(50),
+                            max_concurrent_proofs: 2,
+                        },
+                        SizeBracket {
+                            name: "large".to_string(),
+                            max_mcycles: None,
+                            max_concurrent_proofs: 1,
+                        },
+                    ]),
+                    ..Default::default()
+                },
+                &mut String::new(),
+            )
+            .await
+            .unwrap();
+
+        // The large order waits for the large bracket, while small orders fill their own bracket
+        assert_eq!(filtered_orders.len(), 2);
+        assert!(filtered_orders.iter().all(|order| order.total_cycles == Some(1_000_000)));
+        assert!(logs_contain("Holding back 2 orders"));
+    }
+
     #[tokio::test]
     async fn test_apply_capacity_limits_skip_proof_time_past_expiration() {
         let mut ctx = setup_om_test_context().await;
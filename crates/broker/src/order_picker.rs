@@ -15,16 +15,24 @@
 use hex::FromHex;
 use risc0_zkvm::sha::Digest;
 use sha2::{Digest as Sha2Digest, Sha256};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use crate::{
     chain_monitor::ChainMonitorService,
-    config::ConfigLock,
+    collateral::CollateralTracker,
+    config::{ConfigLock, MarketConf},
     db::DbObj,
     errors::CodedError,
+    latency::Checkpoint,
+    order_filter::{FilterContext, FilterSkip, OrderFilters},
+    price_oracle::{collateral_to_wei, PriceOracle},
     provers::{ProverError, ProverObj},
+    rate_limit::RequestorRateLimiter,
+    reputation::requestor_reputations,
+    retention::{RetentionLimits, RetentionStore},
+    saturation::ProverSaturation,
     storage::{upload_image_uri, upload_input_uri},
     task::{RetryRes, RetryTask, SupervisorErr},
     utils, FulfillmentType, OrderRequest, OrderStateChange,
@@ -34,6 +42,7 @@ use crate::{
     provers::{ExecutorResp, ProofResult},
 };
 use alloy::{
+    eips::BlockId,
     network::Ethereum,
     primitives::{
         utils::{format_ether, format_units, parse_ether, parse_units},
@@ -52,25 +61,23 @@ use boundless_market::{
 };
 use moka::future::Cache;
 use thiserror::Error;
-use tokio::sync::{broadcast, mpsc, Mutex};
+use tokio::sync::{broadcast, mpsc, watch, Mutex};
 use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
 
-use OrderPricingOutcome::{Lock, ProveAfterLockExpire, Skip};
+use OrderPricingOutcome::{Lock, ProveAfterLockExpire, Skip, SkipInvalid};
 
 const MIN_CAPACITY_CHECK_INTERVAL: Duration = Duration::from_secs(5);
 
-const ONE_MILLION: U256 = uint!(1_000_000_U256);
-
-/// Maximum number of orders to cache for deduplication
-const ORDER_DEDUP_CACHE_SIZE: u64 = 5000;
+/// Interval at which requestor reputations are read again from the DB when selecting orders.
+const REPUTATION_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
 
-/// In-memory LRU cache for order deduplication by ID (prevents duplicate order processing)
-type OrderCache = Arc<Cache<String, ()>>;
+const ONE_MILLION: U256 = uint!(1_000_000_U256);
 
-/// Configuration for preflight result caching
-const PREFLIGHT_CACHE_SIZE: u64 = 5000;
-const PREFLIGHT_CACHE_TTL_SECS: u64 = 3 * 60 * 60; // 3 hours
+/// In-memory store for order deduplication by ID (prevents duplicate order processing)
+///
+/// Entries are pinned while the order is being priced, so an in-progress order is never evicted.
+type OrderCache = Arc<RetentionStore<String>>;
 
 /// Cache for preflight results to avoid duplicate computations
 type PreflightCache = Arc<Cache<PreflightCacheKey, PreflightCacheValue>>;
@@ -133,7 +140,12 @@ pub struct OrderPicker<P> {
     collateral_token_decimals: u8,
     order_cache: OrderCache,
     preflight_cache: PreflightCache,
+    price_oracle: Option<Arc<PriceOracle>>,
     order_state_tx: broadcast::Sender<OrderStateChange>,
+    saturation: watch::Receiver<ProverSaturation>,
+    pub(crate) rate_limiter: Arc<std::sync::Mutex<RequestorRateLimiter>>,
+    collateral: Arc<std::sync::Mutex<CollateralTracker>>,
+    order_filters: OrderFilters,
 }
 
 #[derive(Debug)]
@@ -154,6 +166,8 @@ enum OrderPricingOutcome {
     },
     // Do not accept engage order
     Skip,
+    // Do not accept engage order, as it is invalid (e.g. the guest panicked in preflight)
+    SkipInvalid,
 }
 
 impl<P> OrderPicker<P>
@@ -179,6 +193,26 @@ where
             provider.default_signer_address(),
         );
 
+        let (dedup_limits, preflight_cache_size, preflight_cache_ttl_secs, price_oracle) = {
+            let default_conf = MarketConf::default();
+            let conf = config.lock_all();
+            let market_conf = conf.as_ref().map(|c| &c.market).unwrap_or(&default_conf);
+            let price_oracle = market_conf.price_oracle.as_ref().map(|oracle_conf| {
+                Arc::new(PriceOracle::from_conf(
+                    oracle_conf,
+                    Duration::from_secs(market_conf.price_oracle_ttl_secs),
+                    Duration::from_secs(market_conf.price_oracle_max_staleness_secs),
+                    provider.clone(),
+                ))
+            });
+            (
+                order_dedup_limits(market_conf),
+                market_conf.preflight_cache_size,
+                market_conf.preflight_cache_ttl_secs,
+                price_oracle,
+            )
+        };
+
         Self {
             db,
             config,
@@ -190,22 +224,45 @@ where
             new_order_rx: Arc::new(Mutex::new(new_order_rx)),
             priced_orders_tx: order_result_tx,
             collateral_token_decimals,
-            order_cache: Arc::new(
-                Cache::builder()
-                    .max_capacity(ORDER_DEDUP_CACHE_SIZE)
-                    .time_to_live(Duration::from_secs(60 * 60)) // 1 hour
-                    .build(),
-            ),
+            order_cache: Arc::new(RetentionStore::new("order_dedup", dedup_limits)),
             preflight_cache: Arc::new(
                 Cache::builder()
-                    .max_capacity(PREFLIGHT_CACHE_SIZE)
-                    .time_to_live(Duration::from_secs(PREFLIGHT_CACHE_TTL_SECS))
+                    .max_capacity(preflight_cache_size)
+                    .time_to_live(Duration::from_secs(preflight_cache_ttl_secs))
                     .build(),
             ),
+            price_oracle,
             order_state_tx,
+            saturation: watch::channel(ProverSaturation::default()).1,
+            rate_limiter: Default::default(),
+            collateral: Default::default(),
+            order_filters: OrderFilters::default(),
         }
     }
 
+    /// Shrink pricing capacity as the prover backend backlog grows, as reported by the given
+    /// saturation state.
+    pub(crate) fn with_saturation(mut self, saturation: watch::Receiver<ProverSaturation>) -> Self {
+        self.saturation = saturation;
+        self
+    }
+
+    /// Track the collateral reserved by orders priced for locking in the given tracker, shared
+    /// with the order monitor which releases it when the orders are locked or dropped.
+    pub(crate) fn with_collateral(
+        mut self,
+        collateral: Arc<std::sync::Mutex<CollateralTracker>>,
+    ) -> Self {
+        self.collateral = collateral;
+        self
+    }
+
+    /// Filters available to the `market.order_filters` chain, including any custom filters.
+    pub(crate) fn with_order_filters(mut self, order_filters: OrderFilters) -> Self {
+        self.order_filters = order_filters;
+        self
+    }
+
     async fn price_order_and_update_state(
         &self,
         mut order: Box<OrderRequest>,
@@ -213,6 +270,7 @@ where
     ) -> bool {
         let order_id = order.id();
         let f = || async {
+            order.timings.record(Checkpoint::PricingStarted);
             let pricing_result = tokio::select! {
                 result = self.price_order(&mut order) => result,
                 _ = cancel_token.cancelled() => {
@@ -225,6 +283,18 @@ where
                     return Ok(false);
                 }
             };
+            order.timings.record(Checkpoint::PricingFinished);
+
+            // Orders from requestors that repeatedly send invalid requests are priced after other
+            // orders. Failures to fetch the image or input may be transient, and are not counted.
+            let preflight_failed = matches!(pricing_result, Ok(SkipInvalid));
+            if let Err(e) = self
+                .db
+                .record_requestor_order(order.request.client_address(), preflight_failed)
+                .await
+            {
+                tracing::warn!("Failed to record requestor stats for order {order_id}: {e}");
+            }
 
             match pricing_result {
                 Ok(Lock { total_cycles, target_timestamp_secs, expiry_secs }) => {
@@ -237,6 +307,11 @@ where
                         target_timestamp_secs.saturating_sub(now_timestamp()),
                         target_timestamp_secs,
                     );
+                    self.collateral.lock().unwrap().reserve(
+                        order.request.id,
+                        order.request.offer.lockCollateral,
+                        order.request.lock_expires_at(),
+                    );
 
                     self.priced_orders_tx
                         .send(order)
@@ -262,8 +337,10 @@ where
 
                     Ok(true)
                 }
-                Ok(Skip) => {
+                Ok(Skip | SkipInvalid) => {
                     tracing::info!("Skipping order {order_id}");
+                    // An order sent back to be priced again may hold a reservation.
+                    self.collateral.lock().unwrap().release(order.request.id);
 
                     // Add the skipped order to the database
                     self.db
@@ -315,41 +392,16 @@ where
             return Ok(Skip);
         };
 
-        let (min_deadline, allowed_addresses_opt, denied_addresses_opt) = {
+        let skip = {
             let config = self.config.lock_all().context("Failed to read config")?;
-            (
-                config.market.min_deadline,
-                config.market.allow_client_addresses.clone(),
-                config.market.deny_requestor_addresses.clone(),
-            )
+            let ctx = FilterContext::new(now, &config.market, self.collateral_token_decimals);
+            self.order_filters.evaluate(&config.market.order_filters, order, &ctx)
         };
-
-        // Does the order expire within the min deadline
-        let seconds_left = expiration.saturating_sub(now);
-        if seconds_left <= min_deadline {
-            tracing::info!("Removing order {order_id} because it expires within min_deadline: {seconds_left}, min_deadline: {min_deadline}");
+        if let Err(FilterSkip { filter, reason }) = skip {
+            tracing::info!("Removing order {order_id}, skipped by the {filter} filter: {reason}");
             return Ok(Skip);
         }
 
-        // Initial sanity checks:
-        if let Some(allow_addresses) = allowed_addresses_opt {
-            let client_addr = order.request.client_address();
-            if !allow_addresses.contains(&client_addr) {
-                tracing::info!("Removing order {order_id} from {client_addr} because it is not in allowed addrs");
-                return Ok(Skip);
-            }
-        }
-
-        if let Some(deny_addresses) = denied_addresses_opt {
-            let client_addr = order.request.client_address();
-            if deny_addresses.contains(&client_addr) {
-                tracing::info!(
-                    "Removing order {order_id} from {client_addr} because it is in denied addrs"
-                );
-                return Ok(Skip);
-            }
-        }
-
         if !self.supported_selectors.is_supported(order.request.requirements.selector) {
             tracing::info!(
                 "Removing order {order_id} because it has an unsupported selector requirement. Requested: {:x}. Supported: {:?}",
@@ -364,18 +416,25 @@ where
             return Ok(Skip);
         };
 
-        // Check if the stake is sane and if we can afford it
-        // For lock expired orders, we don't check the max stake because we can't lock those orders.
-        let max_collateral: U256 = {
-            let config = self.config.lock_all().context("Failed to read config")?;
-            parse_units(&config.market.max_collateral, self.collateral_token_decimals)
-                .context("Failed to parse max_collateral")?
-                .into()
-        };
-
-        if !lock_expired && lockin_stake > max_collateral {
-            tracing::info!("Removing high stake order {order_id}, lock stake: {lockin_stake}, max stake: {max_collateral}");
-            return Ok(Skip);
+        // Read all chain state at the same block, so that a reorg between reads cannot mix
+        // state from different forks. The order monitor prices the order again if the chain
+        // moved too far by the time it commits to the order.
+        let pricing_block = self
+            .chain_monitor
+            .current_block_number()
+            .await
+            .context("Failed to get block number")?;
+        order.pricing_block = Some(pricing_block);
+
+        // Skip orders we cannot afford to lock before spending any pricing work on them.
+        if !lock_expired {
+            let available_stake = self.available_stake_balance(pricing_block).await?;
+            if lockin_stake > available_stake {
+                tracing::warn!(
+                    "Insufficient available stake to lock order {order_id}. Requires {lockin_stake}, has {available_stake}"
+                );
+                return Ok(Skip);
+            }
         }
 
         // Short circuit if the order has been locked.
@@ -407,6 +466,17 @@ where
         // a tight estimate, although improving this estimate will allow for a more profit.
         let gas_price =
             self.chain_monitor.current_gas_price().await.context("Failed to get gas price")?;
+        let aggregation_cost = utils::aggregation_cost_model(&self.config, &self.db).await?;
+        let fulfillment_path = utils::select_fulfillment_path(
+            &self.config,
+            &self.supported_selectors,
+            &order.request,
+            gas_price,
+            &aggregation_cost,
+        )
+        .await?;
+        tracing::debug!("Selected {fulfillment_path:?} fulfillment path for order {order_id}");
+        order.fulfillment_path = Some(fulfillment_path);
         let order_gas = if lock_expired {
             // No need to include lock gas if its a lock expired order
             U256::from(
@@ -414,6 +484,8 @@ where
                     &self.config,
                     &self.supported_selectors,
                     &order.request,
+                    fulfillment_path,
+                    &aggregation_cost,
                 )
                 .await?,
             )
@@ -424,18 +496,20 @@ where
                         &self.config,
                         &self.supported_selectors,
                         &order.request,
+                        fulfillment_path,
+                        &aggregation_cost,
                     )
                     .await?,
             )
         };
         let order_gas_cost = U256::from(gas_price) * order_gas;
-        let available_gas = self.available_gas_balance().await?;
-        let available_stake = self.available_stake_balance().await?;
+        let available_gas = self.available_gas_balance(pricing_block).await?;
         tracing::debug!(
-            "Estimated {order_gas} gas to {} order {order_id}; {} ether @ {} gwei",
+            "Estimated {order_gas} gas to {} order {order_id}; {} ether @ {} gwei, using {}",
             if lock_expired { "fulfill" } else { "lock and fulfill" },
             format_ether(order_gas_cost),
-            format_units(gas_price, "gwei").unwrap()
+            format_units(gas_price, "gwei").unwrap(),
+            utils::describe_cost_model(fulfillment_path, &aggregation_cost)
         );
 
         if order_gas_cost > order.request.offer.maxPrice && !lock_expired {
@@ -454,13 +528,6 @@ where
             return Ok(Skip);
         }
 
-        if !lock_expired && lockin_stake > available_stake {
-            tracing::warn!(
-                "Insufficient available stake to lock order {order_id}. Requires {lockin_stake}, has {available_stake}"
-            );
-            return Ok(Skip);
-        }
-
         // Calculate exec limit (handles priority requestors and config internally)
         let (exec_limit_cycles, prove_limit) = self.calculate_exec_limits(order, order_gas_cost)?;
 
@@ -505,6 +572,7 @@ where
         };
 
         // Loop while the cached result is skipped and has a lower exec limit than the current order.
+        order.timings.record(Checkpoint::PreflightStarted);
         let preflight_result = loop {
             let prover = self.prover.clone();
             let config = self.config.clone();
@@ -571,11 +639,7 @@ where
                                     } else if err_msg.contains("Guest panicked") || err_msg.contains("GuestPanic") {
                                         // Error message from bento and bonsai respectively for guest failures
                                         tracing::debug!("Skipping order {order_id_clone} due to guest panic (invalid request): {}", err_msg);
-                                        Ok(PreflightCacheValue::Skip {
-                                            // Use max cached limit, to avoid re-running preflight
-                                            // for an invalid request.
-                                            cached_limit: u64::MAX,
-                                        })
+                                        Ok(PreflightCacheValue::Invalid)
                                     } else {
                                         Err(OrderPickerErr::UnexpectedErr(Arc::new(err.into())))
                                     }
@@ -607,6 +671,7 @@ where
 
             break Ok(cached_value);
         };
+        order.timings.record(Checkpoint::PreflightFinished);
 
         // Handle the preflight result
         let (exec_session_id, cycle_count, image_id) = match preflight_result? {
@@ -623,6 +688,9 @@ where
 
                 (exec_session_id, cycle_count, image_id)
             }
+            PreflightCacheValue::Invalid => {
+                return Ok(SkipInvalid);
+            }
             PreflightCacheValue::Skip { .. } => {
                 return Ok(Skip);
             }
@@ -669,7 +737,7 @@ where
         );
         if predicate.eval(&eval_data).is_none() {
             tracing::info!("Order {order_id} predicate check failed, skipping");
-            return Ok(Skip);
+            return Ok(SkipInvalid);
         }
 
         self.evaluate_order(order, &proof_res, order_gas_cost, lock_expired).await
@@ -696,14 +764,21 @@ where
         proof_res: &ProofResult,
         order_gas_cost: U256,
     ) -> Result<OrderPricingOutcome, OrderPickerErr> {
-        let config_min_mcycle_price = {
+        let (config_min_mcycle_price, collateral_cost_bps) = {
             let config = self.config.lock_all().context("Failed to read config")?;
-            parse_ether(&config.market.mcycle_price).context("Failed to parse mcycle_price")?
+            (
+                parse_ether(&config.market.mcycle_price).context("Failed to parse mcycle_price")?,
+                config.market.collateral_cost_bps,
+            )
         };
 
         let order_id = order.id();
         let one_mill = U256::from(1_000_000);
 
+        // Include the cost of locking collateral, if a price oracle is configured
+        let collateral_cost = self.collateral_cost(order, collateral_cost_bps).await?;
+        let order_gas_cost = order_gas_cost + collateral_cost;
+
         let mcycle_price_min = U256::from(order.request.offer.minPrice)
             .saturating_sub(order_gas_cost)
             .saturating_mul(one_mill)
@@ -714,13 +789,14 @@ where
             / U256::from(proof_res.stats.total_cycles);
 
         tracing::debug!(
-            "Order {order_id} price: {}-{} ETH, {}-{} ETH per mcycle, {} stake required, {} ETH gas cost",
+            "Order {order_id} price: {}-{} ETH, {}-{} ETH per mcycle, {} stake required, {} ETH gas and collateral cost ({} ETH collateral cost)",
             format_ether(U256::from(order.request.offer.minPrice)),
             format_ether(U256::from(order.request.offer.maxPrice)),
             format_ether(mcycle_price_min),
             format_ether(mcycle_price_max),
             format_units(U256::from(order.request.offer.lockCollateral), self.collateral_token_decimals).unwrap_or_default(),
             format_ether(order_gas_cost),
+            format_ether(collateral_cost),
         );
 
         // Skip the order if it will never be worth it
@@ -757,6 +833,34 @@ where
         Ok(Lock { total_cycles: proof_res.stats.total_cycles, target_timestamp_secs, expiry_secs })
     }
 
+    /// Cost in ETH of locking the collateral for an order, as the configured fraction of the lock
+    /// collateral converted at the price oracle rate. Zero if no price oracle is configured.
+    ///
+    /// Fails if the price oracle has no recent enough rate, rather than pricing the order without
+    /// the cost of its collateral.
+    async fn collateral_cost(
+        &self,
+        order: &OrderRequest,
+        collateral_cost_bps: u32,
+    ) -> Result<U256, OrderPickerErr> {
+        if collateral_cost_bps == 0 {
+            return Ok(U256::ZERO);
+        }
+        let Some(price_oracle) = &self.price_oracle else {
+            tracing::trace!("No price oracle configured, ignoring collateral_cost_bps");
+            return Ok(U256::ZERO);
+        };
+        let price_wei = price_oracle
+            .collateral_price_wei()
+            .await
+            .with_context(|| format!("Failed to price the collateral of order {}", order.id()))?;
+
+        let collateral_cost = U256::from(order.request.offer.lockCollateral)
+            .saturating_mul(U256::from(collateral_cost_bps))
+            / U256::from(10_000);
+        Ok(collateral_to_wei(collateral_cost, price_wei, self.collateral_token_decimals))
+    }
+
     /// Evaluate if a lock expired order is worth picking based on how much of the slashed stake token we can recover
     /// and the configured min mcycle price in stake tokens
     async fn evaluate_lock_expired_order(
@@ -807,14 +911,35 @@ where
         })
     }
 
+    /// Reputation of each requestor with recorded pricing statistics.
+    async fn requestor_reputations(&self) -> HashMap<Address, f64> {
+        let min_orders = match self.config.lock_all() {
+            Ok(config) => config.market.requestor_reputation_min_orders,
+            Err(err) => {
+                tracing::warn!("Failed to read config for requestor reputations: {err}");
+                return HashMap::new();
+            }
+        };
+        match self.db.get_requestor_stats().await {
+            Ok(stats) => requestor_reputations(&stats, min_orders),
+            Err(err) => {
+                tracing::warn!("Failed to read requestor stats: {err}");
+                HashMap::new()
+            }
+        }
+    }
+
     /// Estimate of gas for fulfilling any orders either pending lock or locked
     async fn estimate_gas_to_fulfill_pending(&self) -> Result<u64> {
+        let aggregation_cost = utils::aggregation_cost_model(&self.config, &self.db).await?;
         let mut gas = 0;
         for order in self.db.get_committed_orders().await? {
             let gas_estimate = utils::estimate_gas_to_fulfill(
                 &self.config,
                 &self.supported_selectors,
                 &order.request,
+                order.fulfillment_path(),
+                &aggregation_cost,
             )
             .await?;
             gas += gas_estimate;
@@ -831,13 +956,14 @@ where
         Ok(U256::from(gas_price) * U256::from(fulfill_pending_gas))
     }
 
-    /// Return available gas balance.
+    /// Return available gas balance at the given block.
     ///
     /// This is defined as the balance of the signer account.
-    async fn available_gas_balance(&self) -> Result<U256, OrderPickerErr> {
+    async fn available_gas_balance(&self, block: u64) -> Result<U256, OrderPickerErr> {
         let balance = self
             .provider
             .get_balance(self.provider.default_signer_address())
+            .block_id(BlockId::number(block))
             .await
             .map_err(|err| OrderPickerErr::RpcErr(Arc::new(err.into())))?;
 
@@ -856,11 +982,29 @@ where
 
     /// Return available stake balance.
     ///
-    /// This is defined as the balance in staking tokens of the signer account minus any pending locked stake.
-    async fn available_stake_balance(&self) -> Result<U256> {
-        let balance =
-            self.market.balance_of_collateral(self.provider.default_signer_address()).await?;
-        Ok(balance)
+    /// This is defined as the balance in staking tokens of the signer account minus the stake of
+    /// orders priced for locking that have not been locked yet. The balance is read at the given
+    /// block, and only reused for pricing at that same block, for up to
+    /// [COLLATERAL_BALANCE_TTL](crate::collateral::COLLATERAL_BALANCE_TTL).
+    async fn available_stake_balance(&self, block: u64) -> Result<U256> {
+        let now = Instant::now();
+        if let Some(available) =
+            self.collateral.lock().unwrap().available(block, now, now_timestamp())
+        {
+            return Ok(available);
+        }
+
+        let balance = self
+            .market
+            .instance()
+            .balanceOfCollateral(self.provider.default_signer_address())
+            .block(BlockId::number(block))
+            .call()
+            .await
+            .context("Failed to get collateral balance")?;
+        let mut collateral = self.collateral.lock().unwrap();
+        collateral.set_balance(balance, block, now);
+        Ok(collateral.available(block, now, now_timestamp()).unwrap_or(balance))
     }
 
     /// Calculates the cycle limit for the preflight and also for the max cycles that this specific
@@ -1046,8 +1190,17 @@ struct PreflightCacheKey {
 /// Value type for the preflight cache
 #[derive(Clone, Debug)]
 enum PreflightCacheValue {
-    Success { exec_session_id: String, cycle_count: u64, image_id: String, input_id: String },
-    Skip { cached_limit: u64 },
+    Success {
+        exec_session_id: String,
+        cycle_count: u64,
+        image_id: String,
+        input_id: String,
+    },
+    Skip {
+        cached_limit: u64,
+    },
+    /// The guest panicked, so the request is invalid at any limit.
+    Invalid,
 }
 
 /// Handles a lock event for a request
@@ -1155,6 +1308,7 @@ where
                         "Failed to read config: {err}"
                     )))
                 })?;
+                picker.order_cache.set_limits(order_dedup_limits(&cfg.market));
                 Ok((
                     cfg.market.max_concurrent_preflights as usize,
                     cfg.market.order_pricing_priority,
@@ -1164,7 +1318,10 @@ where
 
             let (mut current_capacity, mut priority_mode, mut priority_addresses) =
                 read_config().map_err(SupervisorErr::Fault)?;
-            let mut tasks: JoinSet<(String, U256)> = JoinSet::new();
+            let mut tasks: JoinSet<()> = JoinSet::new();
+            // Order of each pricing task, kept outside the task so a panicked or aborted task
+            // still releases its order.
+            let mut task_orders: HashMap<tokio::task::Id, (String, U256)> = HashMap::new();
             let mut rx = picker.new_order_rx.lock().await;
             let mut order_state_rx = picker.order_state_tx.subscribe();
             let mut capacity_check_interval = tokio::time::interval(MIN_CAPACITY_CHECK_INTERVAL);
@@ -1172,6 +1329,8 @@ where
             let mut active_tasks: BTreeMap<U256, BTreeMap<String, CancellationToken>> =
                 BTreeMap::new();
             let mut last_active_tasks_log: String = String::new();
+            let mut reputations = HashMap::new();
+            let mut reputations_read_at: Option<Instant> = None;
 
             loop {
                 tokio::select! {
@@ -1192,6 +1351,7 @@ where
                                 tracing::debug!("Received order state change for request 0x{:x}: Locked by prover {:x}",
                                     request_id, prover);
 
+                                picker.collateral.lock().unwrap().release(request_id);
                                 handle_lock_event(request_id, &mut active_tasks, &mut pending_orders);
                             }
                             OrderStateChange::Fulfilled { request_id } => {
@@ -1202,8 +1362,18 @@ where
                             }
                         }
                     }
-                    Some(result) = tasks.join_next(), if !tasks.is_empty() => {
-                        if let Ok((order_id, request_id)) = result {
+                    Some(result) = tasks.join_next_with_id(), if !tasks.is_empty() => {
+                        let task_id = match &result {
+                            Ok((task_id, ())) => *task_id,
+                            Err(err) => {
+                                tracing::error!("Pricing task failed: {err}");
+                                err.id()
+                            }
+                        };
+                        if let Some((order_id, request_id)) = task_orders.remove(&task_id) {
+                            // Unpin the dedup entry so it can be evicted once it ages out
+                            picker.order_cache.complete(&order_id);
+
                             // Clean up the active task entry now that it's completed
                             if let Some(order_tasks) = active_tasks.get_mut(&request_id) {
                                 order_tasks.remove(&order_id);
@@ -1240,6 +1410,39 @@ where
                             tracing::debug!("Current pricing tasks: [{}]", current_tasks_log);
                             last_active_tasks_log = current_tasks_log;
                         }
+
+                        picker.report_rate_limited_requestors();
+
+                        let filter_skips = picker.order_filters.take_skip_counts();
+                        if !filter_skips.is_empty() {
+                            tracing::info!(
+                                "Orders skipped by order filters: {}",
+                                filter_skips
+                                    .iter()
+                                    .map(|(filter, count)| format!("{filter}: {count}"))
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            );
+                        }
+
+                        let evicted = picker.order_cache.evict();
+                        let stats = picker.order_cache.stats();
+                        if !evicted.is_empty() {
+                            tracing::debug!(
+                                "Evicted {} entries from {} ({} retained, {} pinned)",
+                                evicted.len(),
+                                picker.order_cache.name(),
+                                stats.total,
+                                stats.pinned
+                            );
+                        }
+                        tracing::trace!(
+                            "Cache sizes - {}: {} ({} pinned), preflight: {}",
+                            picker.order_cache.name(),
+                            stats.total,
+                            stats.pinned,
+                            picker.preflight_cache.entry_count()
+                        );
                     }
 
                     _ = cancel_token.cancelled() => {
@@ -1251,13 +1454,23 @@ where
                     }
                 }
 
-                // Process pending orders if we have capacity
-                if !pending_orders.is_empty() && tasks.len() < current_capacity {
-                    let available_capacity = current_capacity - tasks.len();
+                // Process pending orders if we have capacity, shrinking it while the prover
+                // backend works through a backlog.
+                let effective_capacity =
+                    picker.saturation.borrow().scale_capacity(current_capacity);
+                if !pending_orders.is_empty() && tasks.len() < effective_capacity {
+                    let available_capacity = effective_capacity - tasks.len();
+                    if reputations_read_at
+                        .is_none_or(|at| at.elapsed() >= REPUTATION_REFRESH_INTERVAL)
+                    {
+                        reputations = picker.requestor_reputations().await;
+                        reputations_read_at = Some(Instant::now());
+                    }
                     let selected_orders = picker.select_pricing_orders(
                         &mut pending_orders,
                         priority_mode,
                         priority_addresses.as_deref(),
+                        &reputations,
                         available_capacity,
                     );
 
@@ -1275,17 +1488,21 @@ where
                             }
                         }
 
-                        // Check if we've already started processing this order ID
-                        if picker.order_cache.get(&order_id).await.is_some() {
+                        // Check if we've already started processing this order ID, and mark it
+                        // as being processed immediately to prevent duplicates. Orders sent back
+                        // by the order monitor to be priced again are already in the cache, and
+                        // their collateral reservation is released as pricing reserves it again.
+                        if order.pricing_block.is_some() {
+                            tracing::debug!("Pricing order {order_id} again");
+                            picker.order_cache.repin(order_id.clone());
+                            picker.collateral.lock().unwrap().release(request_id);
+                        } else if !picker.order_cache.insert_pinned(order_id.clone()) {
                             tracing::debug!(
                                 "Skipping duplicate order {order_id}, already being processed"
                             );
                             continue;
                         }
 
-                        // Mark order as being processed immediately to prevent duplicates
-                        picker.order_cache.insert(order_id.clone(), ()).await;
-
                         let picker_clone = picker.clone();
                         let task_cancel_token = cancel_token.child_token();
 
@@ -1295,12 +1512,12 @@ where
                             .or_default()
                             .insert(order_id.clone(), task_cancel_token.clone());
 
-                        tasks.spawn(async move {
+                        let task = tasks.spawn(async move {
                             picker_clone
                                 .price_order_and_update_state(order, task_cancel_token)
                                 .await;
-                            (order_id, request_id)
                         });
+                        task_orders.insert(task.id(), (order_id, request_id));
                     }
                 }
             }
@@ -1332,6 +1549,13 @@ fn format_active_tasks(
     format_truncated(active_tasks.values().flat_map(|orders| orders.keys()))
 }
 
+fn order_dedup_limits(market: &MarketConf) -> RetentionLimits {
+    RetentionLimits {
+        max_entries: market.order_dedup_cache_size,
+        max_age: Duration::from_secs(market.order_dedup_cache_ttl_secs),
+    }
+}
+
 /// Returns the maximum cycles that can be proven within a given time period
 /// based on the proving rate provided, in khz.
 fn calculate_max_cycles_for_time(prove_khz: u64, time_seconds: u64) -> u64 {
@@ -1345,7 +1569,9 @@ pub(crate) mod tests {
     use super::*;
     use crate::{
         chain_monitor::ChainMonitorService,
+        config::FulfillmentPath,
         db::SqliteDb,
+        price_oracle::tests::MockPriceSource,
         provers::{DefaultProver, Prover},
         FulfillmentType, OrderStatus,
     };
@@ -1452,7 +1678,12 @@ pub(crate) mod tests {
                 boundless_market_address: *boundless_market_address,
                 chain_id,
                 total_cycles: None,
+                fulfillment_path: None,
                 cached_id: Default::default(),
+                timings: Default::default(),
+                pricing_block: None,
+                ready_block: Default::default(),
+                rate_limited_at: None,
             })
         }
 
@@ -1498,7 +1729,12 @@ pub(crate) mod tests {
                 boundless_market_address: *boundless_market_address,
                 chain_id,
                 total_cycles: None,
+                fulfillment_path: None,
                 cached_id: Default::default(),
+                timings: Default::default(),
+                pricing_block: None,
+                ready_block: Default::default(),
+                rate_limited_at: None,
             })
         }
     }
@@ -1664,6 +1900,58 @@ pub(crate) mod tests {
         assert!(logs_contain("predicate check failed, skipping"));
     }
 
+    #[tokio::test]
+    #[traced_test]
+    async fn bad_requestor_ranks_lower() {
+        let config = ConfigLock::default();
+        {
+            let mut config = config.load_write().unwrap();
+            config.market.mcycle_price = "0.0000001".into();
+            config.market.requestor_reputation_min_orders = 2;
+        }
+        let ctx = PickerTestCtxBuilder::default().with_config(config).build().await;
+        let bad_addr = ctx.provider.default_signer_address();
+        let good_addr = Address::repeat_byte(0x42);
+
+        let select_first = async || {
+            let bad_order = ctx.generate_next_order(Default::default()).await;
+            let mut good_order = ctx.generate_next_order(Default::default()).await;
+            good_order.request.id = RequestId::new(good_addr, 1).into();
+
+            let reputations = ctx.picker.requestor_reputations().await;
+            let mut orders = vec![bad_order, good_order];
+            let selected = ctx.picker.select_pricing_orders(
+                &mut orders,
+                crate::config::OrderPricingPriority::ObservationTime,
+                None,
+                &reputations,
+                1,
+            );
+            selected[0].request.client_address()
+        };
+
+        for index in 0..2 {
+            // Not enough orders have been priced to judge the requestor
+            assert_eq!(select_first().await, bad_addr);
+
+            let mut order = ctx
+                .generate_next_order(OrderParams { order_index: index, ..Default::default() })
+                .await;
+            order.request.requirements.predicate =
+                Predicate::digest_match(Digest::from(ECHO_ID), Digest::ZERO).into();
+            let locked =
+                ctx.picker.price_order_and_update_state(order, CancellationToken::new()).await;
+            assert!(!locked);
+        }
+
+        let stats = ctx.db.get_requestor_stats().await.unwrap();
+        assert_eq!(stats[&bad_addr].orders, 2);
+        assert_eq!(stats[&bad_addr].preflight_failures, 2);
+
+        // The requestor's orders now rank behind orders from other requestors
+        assert_eq!(select_first().await, good_addr);
+    }
+
     #[tokio::test]
     #[traced_test]
     async fn skip_unsupported_selector() {
@@ -1918,7 +2206,7 @@ pub(crate) mod tests {
         let db_order = ctx.db.get_order(&order_id).await.unwrap().unwrap();
         assert_eq!(db_order.status, OrderStatus::Skipped);
 
-        assert!(logs_contain("because it is not in allowed addrs"));
+        assert!(logs_contain("is not in allowed addrs"));
     }
 
     #[tokio::test]
@@ -1946,7 +2234,7 @@ pub(crate) mod tests {
         let db_order = ctx.db.get_order(&order_id).await.unwrap().unwrap();
         assert_eq!(db_order.status, OrderStatus::Skipped);
 
-        assert!(logs_contain("because it is in denied addrs"));
+        assert!(logs_contain("is in denied addrs"));
     }
 
     #[tokio::test]
@@ -2047,7 +2335,51 @@ pub(crate) mod tests {
             ctx.db.get_order(&order_id).await.unwrap().unwrap().status,
             OrderStatus::Skipped
         );
-        assert!(logs_contain("Removing high stake order"));
+        assert!(logs_contain("skipped by the max_collateral filter: high stake order"));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn skip_unaffordable_lock_before_preflight() {
+        let config = ConfigLock::default();
+        {
+            config.load_write().unwrap().market.mcycle_price = "0.0000001".into();
+            config.load_write().unwrap().market.max_collateral = "10".into();
+        }
+        let mut ctx = PickerTestCtxBuilder::default()
+            .with_initial_hp(U256::from(150))
+            .with_config(config)
+            .build()
+            .await;
+
+        // The first order reserves its collateral until it is locked.
+        let order = ctx
+            .generate_next_order(OrderParams { lock_stake: U256::from(100), ..Default::default() })
+            .await;
+        let first_request_id = order.request.id;
+        assert!(ctx.picker.price_order_and_update_state(order, CancellationToken::new()).await);
+        ctx.priced_orders_rx.try_recv().unwrap();
+
+        // The second order cannot be afforded with the remaining collateral, and is skipped
+        // without running preflight.
+        let order = ctx
+            .generate_next_order(OrderParams { lock_stake: U256::from(100), ..Default::default() })
+            .await;
+        let order_id = order.id();
+        assert!(!ctx.picker.price_order_and_update_state(order, CancellationToken::new()).await);
+        assert!(logs_contain(&format!("Insufficient available stake to lock order {order_id}")));
+        assert!(!logs_contain(&format!("Starting preflight execution of {order_id}")));
+        assert_eq!(
+            ctx.db.get_order(&order_id).await.unwrap().unwrap().status,
+            OrderStatus::Skipped
+        );
+
+        // Once the first order is locked by another prover, its collateral is available again.
+        ctx.picker.collateral.lock().unwrap().release(first_request_id);
+        let order = ctx
+            .generate_next_order(OrderParams { lock_stake: U256::from(100), ..Default::default() })
+            .await;
+        assert!(ctx.picker.price_order_and_update_state(order, CancellationToken::new()).await);
     }
 
     #[tokio::test]
@@ -2068,8 +2400,12 @@ pub(crate) mod tests {
 
         // Simulate order being locked
         let order = ctx.priced_orders_rx.try_recv().unwrap();
+        assert_eq!(order.fulfillment_path, Some(FulfillmentPath::Inclusion));
         ctx.db.insert_accepted_request(&order, order.request.offer.minPrice).await.unwrap();
 
+        // With no batch history, the default batch posting gas is shared over the default
+        // minimum batch size of 2, plus the inclusion gas.
+        let fulfill_gas = fulfill_gas + 300_000 / 2 + 30_000;
         assert_eq!(ctx.picker.estimate_gas_to_fulfill_pending().await.unwrap(), fulfill_gas);
 
         // add another order
@@ -2434,7 +2770,12 @@ pub(crate) mod tests {
             total_cycles: order1.total_cycles,
             target_timestamp: order1.target_timestamp,
             expire_timestamp: order1.expire_timestamp,
+            fulfillment_path: None,
             cached_id: Default::default(),
+            timings: Default::default(),
+            pricing_block: None,
+            ready_block: Default::default(),
+            rate_limited_at: None,
         });
 
         assert_eq!(order1.id(), order2.id(), "Both orders should have the same ID");
@@ -2461,6 +2802,36 @@ pub(crate) mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    #[traced_test]
+    async fn test_repriced_order_bypasses_dedup() -> Result<()> {
+        let mut ctx = PickerTestCtxBuilder::default().build().await;
+
+        let order = ctx.generate_next_order(Default::default()).await;
+        let order_id = order.id();
+
+        tokio::spawn(ctx.picker.spawn(CancellationToken::new()));
+
+        ctx.new_order_tx.send(order).await?;
+        let priced = tokio::time::timeout(Duration::from_secs(10), ctx.priced_orders_rx.recv())
+            .await?
+            .unwrap();
+        let pricing_block = priced.pricing_block.expect("priced orders are pinned to a block");
+
+        // Send the order back, as the order monitor does when the chain moved too far since the
+        // order was priced.
+        ctx.new_order_tx.send(priced).await?;
+        let repriced = tokio::time::timeout(Duration::from_secs(10), ctx.priced_orders_rx.recv())
+            .await?
+            .unwrap();
+
+        assert_eq!(repriced.id(), order_id);
+        assert!(repriced.pricing_block.unwrap() >= pricing_block);
+        assert!(logs_contain(&format!("Pricing order {order_id} again")));
+
+        Ok(())
+    }
+
     #[tokio::test]
     #[traced_test]
     async fn test_order_is_fulfilled_check() -> Result<()> {
@@ -2965,6 +3336,65 @@ pub(crate) mod tests {
         parse_units(amount, 6).unwrap().into()
     }
 
+    #[tokio::test]
+    #[traced_test]
+    async fn collateral_cost_shifts_lock_timing() {
+        let config = ConfigLock::default();
+        {
+            let mut config = config.load_write().unwrap();
+            config.market.mcycle_price = "0.0000001".into();
+            config.market.collateral_cost_bps = 100; // 1%
+        }
+        let mut ctx = PickerTestCtxBuilder::default().with_config(config).build().await;
+
+        // Orders priced at 0.02-0.04 ETH with increasing lock collateral (6 decimals).
+        let mut orders = Vec::new();
+        for (i, stake) in ["100", "2500", "5000"].into_iter().enumerate() {
+            orders.push(
+                ctx.generate_next_order(OrderParams {
+                    order_index: i as u32,
+                    lock_stake: parse_units(stake, 6).unwrap().into(),
+                    ..Default::default()
+                })
+                .await,
+            );
+        }
+        let proof_res = ProofResult {
+            id: "test".into(),
+            stats: ExecutorResp { total_cycles: 1_000_000, ..Default::default() },
+            elapsed_time: 0.0,
+        };
+
+        // Without a price oracle the collateral cost is ignored and all orders lock ASAP.
+        for order in &orders {
+            let outcome =
+                ctx.picker.evaluate_lockable_order(order, &proof_res, U256::ZERO).await.unwrap();
+            assert!(matches!(outcome, Lock { target_timestamp_secs: 0, .. }));
+        }
+
+        // At 0.001 ETH per collateral token, the collateral costs are 0.001, 0.025 and 0.05 ETH.
+        ctx.picker.price_oracle = Some(Arc::new(PriceOracle::new(
+            Box::new(MockPriceSource::fixed(parse_ether("0.001").unwrap())),
+            Duration::from_secs(60),
+            Duration::from_secs(600),
+        )));
+
+        let outcome =
+            ctx.picker.evaluate_lockable_order(&orders[0], &proof_res, U256::ZERO).await.unwrap();
+        assert!(matches!(outcome, Lock { target_timestamp_secs: 0, .. }));
+
+        let outcome =
+            ctx.picker.evaluate_lockable_order(&orders[1], &proof_res, U256::ZERO).await.unwrap();
+        let Lock { target_timestamp_secs, .. } = outcome else {
+            panic!("expected order to be locked later, got {outcome:?}");
+        };
+        assert!(target_timestamp_secs > 0);
+
+        let outcome =
+            ctx.picker.evaluate_lockable_order(&orders[2], &proof_res, U256::ZERO).await.unwrap();
+        assert!(matches!(outcome, Skip));
+    }
+
     #[tokio::test]
     async fn test_calculate_exec_limits_eth_higher_than_stake() {
         let market_config = crate::config::MarketConf {
@@ -0,0 +1,363 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Exchange rate between the collateral token and the native token (e.g. ETH).
+//!
+//! Revenue for lock-and-fulfill orders is paid in ETH, while the collateral put at risk is
+//! denominated in the collateral token. The [PriceOracle] supplies the rate used to express the
+//! cost of locking collateral in ETH, so that it can be included in the profitability of an order.
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use alloy::{
+    network::Ethereum,
+    primitives::{utils::parse_ether, Address, U256},
+    providers::Provider,
+    sol,
+};
+use anyhow::{ensure, Context, Result};
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use crate::config::PriceOracleConf;
+
+sol! {
+    #[sol(rpc)]
+    interface AggregatorV3Interface {
+        function decimals() external view returns (uint8);
+        function latestRoundData() external view returns (uint80 roundId, int256 answer, uint256 startedAt, uint256 updatedAt, uint80 answeredInRound);
+    }
+}
+
+/// A source of the exchange rate between the collateral token and ETH.
+#[async_trait]
+pub(crate) trait PriceSource: Send + Sync {
+    /// Returns the price of one whole collateral token, in wei.
+    async fn collateral_price_wei(&self) -> Result<U256>;
+}
+
+/// Price source reading a Chainlink feed that quotes the collateral token in ETH.
+pub(crate) struct ChainlinkPriceSource<P> {
+    feed: Address,
+    provider: Arc<P>,
+}
+
+impl<P> ChainlinkPriceSource<P> {
+    pub(crate) fn new(feed: Address, provider: Arc<P>) -> Self {
+        Self { feed, provider }
+    }
+}
+
+#[async_trait]
+impl<P> PriceSource for ChainlinkPriceSource<P>
+where
+    P: Provider<Ethereum> + 'static,
+{
+    async fn collateral_price_wei(&self) -> Result<U256> {
+        let feed = AggregatorV3Interface::new(self.feed, self.provider.clone());
+        let decimals = feed.decimals().call().await.context("Failed to get feed decimals")?;
+        let round = feed.latestRoundData().call().await.context("Failed to get latest round")?;
+        ensure!(round.answer.is_positive(), "Price feed returned non-positive answer");
+
+        let answer = round.answer.into_raw();
+        Ok(answer * U256::from(10).pow(U256::from(18)) / U256::from(10).pow(U256::from(decimals)))
+    }
+}
+
+#[derive(Deserialize)]
+struct HttpPriceResponse {
+    /// Price of one whole collateral token, in ETH, as a decimal string.
+    price: String,
+}
+
+/// Price source fetching the price from an HTTP endpoint.
+///
+/// The endpoint must respond to a GET request with a JSON object such as `{"price": "0.00005"}`,
+/// giving the price of one whole collateral token in ETH.
+pub(crate) struct HttpPriceSource {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl HttpPriceSource {
+    pub(crate) fn new(url: String) -> Self {
+        Self { url, client: reqwest::Client::new() }
+    }
+}
+
+#[async_trait]
+impl PriceSource for HttpPriceSource {
+    async fn collateral_price_wei(&self) -> Result<U256> {
+        let body = self
+            .client
+            .get(&self.url)
+            .send()
+            .await
+            .and_then(|resp| resp.error_for_status())
+            .with_context(|| format!("Failed to fetch price from {}", self.url))?
+            .text()
+            .await
+            .context("Failed to read price response")?;
+        let resp: HttpPriceResponse =
+            serde_json::from_str(&body).context("Failed to decode price response")?;
+        parse_ether(&resp.price).context("Failed to parse price")
+    }
+}
+
+/// Delay before retrying a failed refresh, doubled after each consecutive failure.
+const MIN_RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Longest delay between retries of a failed refresh.
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(300);
+
+/// Caching wrapper around a [PriceSource].
+///
+/// Rates are cached for the configured TTL. If refreshing the rate fails, the last known rate is
+/// used until it is older than the configured max staleness, and the refresh is retried with
+/// exponential backoff.
+pub(crate) struct PriceOracle {
+    source: Box<dyn PriceSource>,
+    ttl: Duration,
+    max_staleness: Duration,
+    state: Mutex<OracleState>,
+}
+
+#[derive(Default)]
+struct OracleState {
+    /// Last fetched rate, with the time it was fetched.
+    last: Option<(U256, Instant)>,
+    /// Number of consecutive failed refreshes.
+    failures: u32,
+    /// Time before which a failed refresh is not retried.
+    retry_at: Option<Instant>,
+}
+
+impl PriceOracle {
+    pub(crate) fn new(
+        source: Box<dyn PriceSource>,
+        ttl: Duration,
+        max_staleness: Duration,
+    ) -> Self {
+        Self { source, ttl, max_staleness, state: Mutex::new(OracleState::default()) }
+    }
+
+    /// Build the oracle described by the given config.
+    pub(crate) fn from_conf<P>(
+        conf: &PriceOracleConf,
+        ttl: Duration,
+        max_staleness: Duration,
+        provider: Arc<P>,
+    ) -> Self
+    where
+        P: Provider<Ethereum> + 'static,
+    {
+        let source: Box<dyn PriceSource> = match conf {
+            PriceOracleConf::Chainlink { feed_address } => {
+                Box::new(ChainlinkPriceSource::new(*feed_address, provider))
+            }
+            PriceOracleConf::Http { url } => Box::new(HttpPriceSource::new(url.clone())),
+        };
+        Self::new(source, ttl, max_staleness)
+    }
+
+    /// Returns the price of one whole collateral token in wei.
+    ///
+    /// Fails if no rate was ever fetched, or the last known rate is older than the max staleness.
+    pub(crate) async fn collateral_price_wei(&self) -> Result<U256> {
+        let (last, backing_off) = {
+            let state = self.state.lock().unwrap();
+            (state.last, state.retry_at.is_some_and(|at| Instant::now() < at))
+        };
+        if let Some((price, fetched_at)) = last {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(price);
+            }
+        }
+
+        if !backing_off {
+            match self.source.collateral_price_wei().await {
+                Ok(price) => {
+                    tracing::debug!("Refreshed collateral token price: {price} wei");
+                    let mut state = self.state.lock().unwrap();
+                    *state =
+                        OracleState { last: Some((price, Instant::now())), ..Default::default() };
+                    return Ok(price);
+                }
+                Err(err) => {
+                    let mut state = self.state.lock().unwrap();
+                    state.failures += 1;
+                    let backoff = MIN_RETRY_BACKOFF
+                        .saturating_mul(2u32.saturating_pow(state.failures - 1))
+                        .min(MAX_RETRY_BACKOFF);
+                    state.retry_at = Some(Instant::now() + backoff);
+                    tracing::warn!(
+                        "Failed to refresh collateral token price, retrying in {}s: {err:?}",
+                        backoff.as_secs()
+                    );
+                }
+            }
+        }
+
+        let (price, fetched_at) = last.context("No collateral token price available")?;
+        let age = fetched_at.elapsed();
+        ensure!(
+            age <= self.max_staleness,
+            "Last known collateral token price is {}s old, more than the max staleness of {}s",
+            age.as_secs(),
+            self.max_staleness.as_secs()
+        );
+        tracing::debug!(
+            "Using last known collateral token price of {price} wei from {}s ago",
+            age.as_secs()
+        );
+        Ok(price)
+    }
+}
+
+/// Convert an amount of collateral token, in base units, to wei at the given price.
+pub(crate) fn collateral_to_wei(amount: U256, price_wei: U256, collateral_decimals: u8) -> U256 {
+    amount.saturating_mul(price_wei) / U256::from(10).pow(U256::from(collateral_decimals))
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use super::*;
+    use alloy::primitives::utils::parse_units;
+    use httpmock::prelude::*;
+    use tracing_test::traced_test;
+
+    /// Price source returning a sequence of results, repeating the last one.
+    pub(crate) struct MockPriceSource {
+        results: Vec<Option<U256>>,
+        calls: AtomicUsize,
+    }
+
+    impl MockPriceSource {
+        pub(crate) fn new(results: Vec<Option<U256>>) -> Self {
+            Self { results, calls: AtomicUsize::new(0) }
+        }
+
+        pub(crate) fn fixed(price: U256) -> Self {
+            Self::new(vec![Some(price)])
+        }
+    }
+
+    #[async_trait]
+    impl PriceSource for MockPriceSource {
+        async fn collateral_price_wei(&self) -> Result<U256> {
+            let call = self.calls.fetch_add(1, Ordering::SeqCst);
+            let result = self.results[call.min(self.results.len() - 1)];
+            result.context("price source unavailable")
+        }
+    }
+
+    const HOUR: Duration = Duration::from_secs(3600);
+
+    #[tokio::test]
+    async fn caches_within_ttl() {
+        let oracle = PriceOracle::new(
+            Box::new(MockPriceSource::new(vec![Some(U256::from(1)), Some(U256::from(2))])),
+            HOUR,
+            HOUR,
+        );
+        assert_eq!(oracle.collateral_price_wei().await.unwrap(), U256::from(1));
+        assert_eq!(oracle.collateral_price_wei().await.unwrap(), U256::from(1));
+    }
+
+    #[tokio::test]
+    async fn refreshes_after_ttl() {
+        let oracle = PriceOracle::new(
+            Box::new(MockPriceSource::new(vec![Some(U256::from(1)), Some(U256::from(2))])),
+            Duration::ZERO,
+            HOUR,
+        );
+        assert_eq!(oracle.collateral_price_wei().await.unwrap(), U256::from(1));
+        assert_eq!(oracle.collateral_price_wei().await.unwrap(), U256::from(2));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn falls_back_to_last_known_price() {
+        let oracle = PriceOracle::new(
+            Box::new(MockPriceSource::new(vec![Some(U256::from(1)), None])),
+            Duration::ZERO,
+            HOUR,
+        );
+        assert_eq!(oracle.collateral_price_wei().await.unwrap(), U256::from(1));
+        assert_eq!(oracle.collateral_price_wei().await.unwrap(), U256::from(1));
+        assert!(logs_contain("Failed to refresh collateral token price"));
+    }
+
+    #[tokio::test]
+    async fn backs_off_after_failed_refresh() {
+        let oracle = PriceOracle::new(
+            Box::new(MockPriceSource::new(vec![Some(U256::from(1)), None, Some(U256::from(2))])),
+            Duration::ZERO,
+            HOUR,
+        );
+        assert_eq!(oracle.collateral_price_wei().await.unwrap(), U256::from(1));
+        assert_eq!(oracle.collateral_price_wei().await.unwrap(), U256::from(1));
+
+        // The source is not queried again until the backoff elapses
+        assert_eq!(oracle.collateral_price_wei().await.unwrap(), U256::from(1));
+        tokio::time::sleep(MIN_RETRY_BACKOFF).await;
+        assert_eq!(oracle.collateral_price_wei().await.unwrap(), U256::from(2));
+    }
+
+    #[tokio::test]
+    async fn fails_when_price_is_too_stale() {
+        let oracle = PriceOracle::new(
+            Box::new(MockPriceSource::new(vec![Some(U256::from(1)), None])),
+            Duration::ZERO,
+            Duration::from_millis(10),
+        );
+        assert_eq!(oracle.collateral_price_wei().await.unwrap(), U256::from(1));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        let err = oracle.collateral_price_wei().await.unwrap_err();
+        assert!(err.to_string().contains("more than the max staleness"));
+    }
+
+    #[tokio::test]
+    async fn no_price_without_successful_fetch() {
+        let oracle =
+            PriceOracle::new(Box::new(MockPriceSource::new(vec![None])), Duration::ZERO, HOUR);
+        assert!(oracle.collateral_price_wei().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn http_price_source() {
+        let server = MockServer::start();
+        let mock = server.mock(|when, then| {
+            when.method(GET).path("/price");
+            then.status(200).body(r#"{"price": "0.00005"}"#);
+        });
+
+        let source = HttpPriceSource::new(server.url("/price"));
+        let price = source.collateral_price_wei().await.unwrap();
+        mock.assert();
+        assert_eq!(price, parse_ether("0.00005").unwrap());
+    }
+
+    #[test]
+    fn converts_collateral_to_wei() {
+        let price = parse_ether("0.00005").unwrap();
+        let amount: U256 = parse_units("20", 6).unwrap().into();
+        assert_eq!(collateral_to_wei(amount, price, 6), parse_ether("0.001").unwrap());
+    }
+}
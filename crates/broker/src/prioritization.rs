@@ -16,11 +16,18 @@ use crate::{
     config::{OrderCommitmentPriority, OrderPricingPriority},
     order_monitor::OrderMonitor,
     order_picker::OrderPicker,
+    rate_limit::RateLimit,
+    reputation::LOW_REPUTATION,
     OrderRequest,
 };
 
+use alloy::primitives::Address;
 use rand::seq::SliceRandom;
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Instant,
+};
 
 /// Unified priority mode for both pricing and commitment
 #[derive(Debug, Clone, Copy)]
@@ -87,13 +94,76 @@ where
     }
 }
 
+/// Move orders from requestors with a low reputation behind the other orders.
+///
+/// Orders from priority requestors stay first. Reputation only applies as a penalty and the sort
+/// is stable, so orders otherwise keep the order of the configured priority mode.
+fn penalize_low_reputation<T>(
+    orders: &mut [T],
+    priority_addresses: Option<&[Address]>,
+    reputations: &HashMap<Address, f64>,
+) where
+    T: AsRef<OrderRequest>,
+{
+    if reputations.is_empty() {
+        return;
+    }
+
+    let is_priority = |order: &T| {
+        priority_addresses
+            .is_some_and(|addrs| addrs.contains(&order.as_ref().request.client_address()))
+    };
+    let is_low_reputation = |order: &T| {
+        reputations
+            .get(&order.as_ref().request.client_address())
+            .is_some_and(|reputation| *reputation < LOW_REPUTATION)
+    };
+    orders.sort_by_key(|order| (!is_priority(order), is_low_reputation(order)));
+}
+
 impl<P> OrderPicker<P> {
+    /// Configured per-requestor pricing rate limit, and whether it applies to priority requestors.
+    fn requestor_rate_limit(&self) -> Option<(RateLimit, bool)> {
+        let config = match self.config.lock_all() {
+            Ok(config) => config,
+            Err(err) => {
+                tracing::warn!("Failed to read config for requestor rate limit: {err}");
+                return None;
+            }
+        };
+        let per_minute = config.market.requestor_pricing_rate_limit?;
+        let burst = config.market.requestor_pricing_burst.unwrap_or(per_minute);
+        Some((RateLimit { per_minute, burst }, config.market.rate_limit_priority_requestors))
+    }
+
+    /// Log the requestors with the most orders held back by the pricing rate limit since the last
+    /// call, and forget requestors that are no longer limited.
+    pub(crate) fn report_rate_limited_requestors(&self) {
+        let Some((limit, _)) = self.requestor_rate_limit() else {
+            return;
+        };
+        let mut limiter = self.rate_limiter.lock().unwrap();
+        limiter.prune(limit, Instant::now());
+        let offenders = limiter.take_top_offenders(5);
+        if !offenders.is_empty() {
+            tracing::info!(
+                "Orders held back by the requestor pricing rate limit: {}",
+                offenders
+                    .iter()
+                    .map(|(addr, count)| format!("{addr}: {count}"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+        }
+    }
+
     #[allow(clippy::vec_box)]
     pub(crate) fn select_pricing_orders(
         &self,
         orders: &mut Vec<Box<OrderRequest>>,
         priority_mode: OrderPricingPriority,
         priority_addresses: Option<&[alloy::primitives::Address]>,
+        reputations: &HashMap<Address, f64>,
         capacity: usize,
     ) -> Vec<Box<OrderRequest>> {
         if orders.is_empty() || capacity == 0 {
@@ -101,9 +171,64 @@ impl<P> OrderPicker<P> {
         }
 
         sort_orders_by_priority_and_mode(orders, priority_addresses, priority_mode.into());
-
-        let take_count = std::cmp::min(capacity, orders.len());
-        orders.drain(..take_count).collect()
+        penalize_low_reputation(orders, priority_addresses, reputations);
+
+        let Some((limit, limit_priority)) = self.requestor_rate_limit() else {
+            let take_count = std::cmp::min(capacity, orders.len());
+            return orders.drain(..take_count).collect();
+        };
+
+        // Orders held back by the rate limit age: they move ahead of the other orders, oldest
+        // first, so a limited requestor's orders are priced in the order they were held back
+        // instead of being starved by its newer orders. Priority orders stay first.
+        let is_priority = |order: &OrderRequest| {
+            priority_addresses.is_some_and(|addrs| addrs.contains(&order.request.client_address()))
+        };
+        orders.sort_by_key(|order| {
+            (!is_priority(order), order.rate_limited_at.is_none(), order.rate_limited_at)
+        });
+
+        // The limiter is only consulted once per requestor found over the limit, and the scan
+        // stops once capacity is reached, leaving the rest of the queue untouched.
+        let now = Instant::now();
+        let mut selected = Vec::new();
+        let mut held_back = Vec::new();
+        let mut limited = HashSet::new();
+        let mut newly_held_back = Vec::new();
+        let mut queue = std::mem::take(orders).into_iter();
+        for mut order in queue.by_ref() {
+            let requestor = order.request.client_address();
+            let exempt = !limit_priority && is_priority(&order);
+            if exempt
+                || (!limited.contains(&requestor)
+                    && self.rate_limiter.lock().unwrap().try_acquire(requestor, limit, now))
+            {
+                selected.push(order);
+                if selected.len() >= capacity {
+                    break;
+                }
+            } else {
+                limited.insert(requestor);
+                if order.rate_limited_at.is_none() {
+                    tracing::trace!(
+                        "Holding back order {} over the requestor rate limit",
+                        order.id()
+                    );
+                    order.rate_limited_at = Some(now);
+                    newly_held_back.push(requestor);
+                }
+                held_back.push(order);
+            }
+        }
+        held_back.extend(queue);
+        if !newly_held_back.is_empty() {
+            let mut limiter = self.rate_limiter.lock().unwrap();
+            for requestor in newly_held_back {
+                limiter.hold_back(requestor);
+            }
+        }
+        *orders = held_back;
+        selected
     }
 }
 
@@ -133,6 +258,7 @@ mod tests {
     use std::collections::HashSet;
 
     use super::*;
+    use crate::config::ConfigLock;
     use crate::now_timestamp;
     use crate::order_monitor::tests::setup_om_test_context;
     use crate::order_picker::tests::{OrderParams, PickerTestCtxBuilder};
@@ -162,6 +288,7 @@ mod tests {
                 &mut orders,
                 OrderPricingPriority::ObservationTime,
                 None,
+                &HashMap::new(),
                 1,
             );
             if let Some(order) = selected_orders.into_iter().next() {
@@ -206,6 +333,7 @@ mod tests {
                 &mut orders,
                 OrderPricingPriority::ShortestExpiry,
                 None,
+                &HashMap::new(),
                 1,
             );
             if let Some(order) = selected_orders.into_iter().next() {
@@ -220,6 +348,58 @@ mod tests {
         assert_eq!(selected_order_indices, vec![1, 3, 0, 4, 2]);
     }
 
+    #[tokio::test]
+    #[traced_test]
+    async fn test_order_pricing_priority_penalizes_low_reputation_only() {
+        let ctx = PickerTestCtxBuilder::default().build().await;
+
+        let base_time = now_timestamp();
+        let fair_requestor = Address::repeat_byte(0x01);
+        let bad_requestor = Address::repeat_byte(0x02);
+        let reputations = HashMap::from([(fair_requestor, 0.6), (bad_requestor, 0.2)]);
+
+        // The requestor of the last order has no reputation yet
+        let requestors = [Some(fair_requestor), Some(bad_requestor), None];
+        let lock_timeouts = [100, 150, 200];
+        let mut orders = Vec::new();
+        for (i, (requestor, timeout)) in requestors.iter().zip(lock_timeouts).enumerate() {
+            let mut order = ctx
+                .generate_next_order(OrderParams {
+                    order_index: i as u32,
+                    bidding_start: base_time,
+                    lock_timeout: timeout,
+                    ..Default::default()
+                })
+                .await;
+            if let Some(requestor) = requestor {
+                order.request.id =
+                    boundless_market::contracts::RequestId::new(*requestor, i as u32).into();
+            }
+            orders.push(order);
+        }
+
+        // A lower but acceptable reputation does not override the expiry order
+        let mut selected_order_indices = Vec::new();
+        while !orders.is_empty() {
+            let selected_orders = ctx.picker.select_pricing_orders(
+                &mut orders,
+                OrderPricingPriority::ShortestExpiry,
+                None,
+                &reputations,
+                1,
+            );
+            if let Some(order) = selected_orders.into_iter().next() {
+                let order_index =
+                    boundless_market::contracts::RequestId::try_from(order.request.id)
+                        .unwrap()
+                        .index;
+                selected_order_indices.push(order_index);
+            }
+        }
+
+        assert_eq!(selected_order_indices, vec![0, 2, 1]);
+    }
+
     #[tokio::test]
     #[traced_test]
     async fn test_order_pricing_priority_shortest_expiry_with_lock_expired() {
@@ -276,6 +456,7 @@ mod tests {
                 &mut orders,
                 OrderPricingPriority::ShortestExpiry,
                 None,
+                &HashMap::new(),
                 1,
             );
             if let Some(order) = selected_orders.into_iter().next() {
@@ -318,6 +499,7 @@ mod tests {
                     &mut orders,
                     OrderPricingPriority::Random,
                     None,
+                    &HashMap::new(),
                     1,
                 );
                 if let Some(order) = selected_orders.into_iter().next() {
@@ -604,6 +786,7 @@ mod tests {
             &mut test_orders,
             OrderPricingPriority::ShortestExpiry,
             None,
+            &HashMap::new(),
             1,
         );
         let selected_order = selected_orders.into_iter().next().unwrap();
@@ -637,6 +820,7 @@ mod tests {
             &mut test_orders,
             OrderPricingPriority::ShortestExpiry,
             Some(&priority_addresses),
+            &HashMap::new(),
             1,
         );
         let selected_order = selected_orders.into_iter().next().unwrap();
@@ -693,4 +877,124 @@ mod tests {
         assert_eq!(prioritized_orders[0].request.client_address(), priority_addr);
         assert_eq!(prioritized_orders[1].request.lock_expires_at(), current_timestamp + 100);
     }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn test_requestor_rate_limit_pricing() {
+        let config = ConfigLock::default();
+        {
+            let mut config = config.load_write().unwrap();
+            config.market.requestor_pricing_rate_limit = Some(2);
+        }
+        let ctx = PickerTestCtxBuilder::default().with_config(config).build().await;
+
+        let spammer_addr = alloy::primitives::Address::from([0x11; 20]);
+        let other_addr = alloy::primitives::Address::from([0x22; 20]);
+        let priority_addr = alloy::primitives::Address::from([0x99; 20]);
+        let priority_addresses = vec![priority_addr];
+
+        // Flood from a single requestor, followed by one order each from other requestors
+        let mut orders = Vec::new();
+        for (i, addr) in std::iter::repeat_n(spammer_addr, 10)
+            .chain([other_addr, priority_addr, priority_addr, priority_addr])
+            .enumerate()
+        {
+            let mut order = ctx
+                .generate_next_order(OrderParams { order_index: i as u32, ..Default::default() })
+                .await;
+            order.request.id = boundless_market::contracts::RequestId::new(addr, i as u32).into();
+            orders.push(order);
+        }
+
+        let selected = ctx.picker.select_pricing_orders(
+            &mut orders,
+            OrderPricingPriority::ObservationTime,
+            Some(&priority_addresses),
+            &HashMap::new(),
+            20,
+        );
+        let count =
+            |addr| selected.iter().filter(|order| order.request.client_address() == addr).count();
+        // The spammer is limited to its burst, other requestors still get priced and priority
+        // requestors are not limited.
+        assert_eq!(count(spammer_addr), 2);
+        assert_eq!(count(other_addr), 1);
+        assert_eq!(count(priority_addr), 3);
+
+        // Orders over the limit stay queued in order
+        assert_eq!(orders.len(), 8);
+        assert!(orders.iter().all(|order| order.request.client_address() == spammer_addr));
+        let selected = ctx.picker.select_pricing_orders(
+            &mut orders,
+            OrderPricingPriority::ObservationTime,
+            Some(&priority_addresses),
+            &HashMap::new(),
+            20,
+        );
+        assert!(selected.is_empty());
+        assert_eq!(orders.len(), 8);
+
+        // Orders are counted once, however many times they are held back
+        ctx.picker.report_rate_limited_requestors();
+        assert!(logs_contain(&format!("{spammer_addr}: 8")));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn test_requestor_rate_limit_ages_held_back_orders() {
+        let config = ConfigLock::default();
+        {
+            let mut config = config.load_write().unwrap();
+            config.market.requestor_pricing_rate_limit = Some(60);
+            config.market.requestor_pricing_burst = Some(1);
+        }
+        let ctx = PickerTestCtxBuilder::default().with_config(config).build().await;
+
+        let base_time = now_timestamp();
+        let mut orders = Vec::new();
+        for (i, timeout) in [(0, 500), (1, 400)] {
+            let order = ctx
+                .generate_next_order(OrderParams {
+                    order_index: i,
+                    bidding_start: base_time,
+                    lock_timeout: timeout,
+                    ..Default::default()
+                })
+                .await;
+            orders.push(order);
+        }
+        let index = |order: &OrderRequest| {
+            boundless_market::contracts::RequestId::try_from(order.request.id).unwrap().index
+        };
+
+        let selected = ctx.picker.select_pricing_orders(
+            &mut orders,
+            OrderPricingPriority::ShortestExpiry,
+            None,
+            &HashMap::new(),
+            10,
+        );
+        assert_eq!(selected.iter().map(|order| index(order)).collect::<Vec<_>>(), vec![1]);
+
+        // A newer order expiring sooner does not overtake the order held back by the limit
+        orders.push(
+            ctx.generate_next_order(OrderParams {
+                order_index: 2,
+                bidding_start: base_time,
+                lock_timeout: 100,
+                ..Default::default()
+            })
+            .await,
+        );
+        tokio::time::sleep(std::time::Duration::from_millis(1100)).await;
+        let selected = ctx.picker.select_pricing_orders(
+            &mut orders,
+            OrderPricingPriority::ShortestExpiry,
+            None,
+            &HashMap::new(),
+            10,
+        );
+        assert_eq!(selected.iter().map(|order| index(order)).collect::<Vec<_>>(), vec![0]);
+        assert_eq!(orders.iter().map(|order| index(order)).collect::<Vec<_>>(), vec![2]);
+    }
 }
@@ -42,6 +42,8 @@ pub struct Bonsai {
     status_poll_ms: u64,
     status_poll_retry_count: u64,
     prover_type: ProverType,
+    /// Connection pool to the Bento task DB, created on first use to read the queue depth.
+    bento_db: tokio::sync::OnceCell<sqlx::PgPool>,
 }
 
 impl Bonsai {
@@ -77,6 +79,7 @@ impl Bonsai {
             status_poll_ms,
             status_poll_retry_count,
             prover_type,
+            bento_db: tokio::sync::OnceCell::new(),
         })
     }
 
@@ -523,6 +526,30 @@ impl Prover for Bonsai {
 
         Ok(Some(receipt_buf))
     }
+
+    async fn queue_depth(&self) -> Result<Option<u64>, ProverError> {
+        // Bonsai does not expose its backlog. Bento jobs are read from its task DB, as is done for
+        // cancellation.
+        match self.prover_type {
+            ProverType::Bonsai => Ok(None),
+            ProverType::Bento => {
+                let pool = self.bento_db.get_or_try_init(create_pg_pool).await.map_err(|e| {
+                    ProverError::ProverInternalError(format!("Failed to connect to Bento DB: {e}"))
+                })?;
+                // Jobs with tasks waiting on prerequisites or a worker are queued, and jobs with
+                // tasks on a worker are running.
+                let jobs: i64 = sqlx::query_scalar(
+                    "SELECT COUNT(DISTINCT job_id) FROM tasks WHERE state IN ('pending', 'ready', 'running')",
+                )
+                .fetch_one(pool)
+                .await
+                .map_err(|e| {
+                    ProverError::ProverInternalError(format!("Failed to query Bento job count: {e}"))
+                })?;
+                Ok(Some(jobs.try_into().unwrap_or_default()))
+            }
+        }
+    }
 }
 
 async fn create_pg_pool() -> Result<sqlx::PgPool, sqlx::Error> {
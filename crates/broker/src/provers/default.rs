@@ -395,6 +395,12 @@ impl Prover for DefaultProver {
             .ok_or_else(|| ProverError::NotFound(format!("proof {proof_id}")))?;
         Ok(proof_data.compressed_receipt.as_ref().cloned())
     }
+
+    async fn queue_depth(&self) -> Result<Option<u64>, ProverError> {
+        let proofs = self.state.proofs.read().await;
+        let running = proofs.values().filter(|proof| matches!(proof.status, Status::Running));
+        Ok(Some(running.count() as u64))
+    }
 }
 
 #[cfg(test)]
@@ -140,6 +140,12 @@ pub trait Prover {
     async fn get_journal(&self, proof_id: &str) -> Result<Option<Vec<u8>>, ProverError>;
     async fn compress(&self, proof_id: &str) -> Result<String, ProverError>;
     async fn get_compressed_receipt(&self, proof_id: &str) -> Result<Option<Vec<u8>>, ProverError>;
+    /// Number of proving jobs queued or running on the backend.
+    ///
+    /// Returns `None` if the backend does not report its backlog.
+    async fn queue_depth(&self) -> Result<Option<u64>, ProverError> {
+        Ok(None)
+    }
 }
 
 pub type ProverObj = Arc<dyn Prover + Send + Sync>;
@@ -509,6 +509,7 @@ mod tests {
             chain_id: 1,
             total_cycles: None,
             proving_started_at: None,
+            fulfillment_path: None,
             cached_id: Default::default(),
         }
     }
@@ -661,6 +662,7 @@ mod tests {
             chain_id: 1,
             total_cycles: None,
             proving_started_at: None,
+            fulfillment_path: None,
             cached_id: Default::default(),
         };
         db.add_order(&order).await.unwrap();
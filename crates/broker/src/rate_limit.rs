@@ -0,0 +1,159 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-requestor rate limit on the number of orders priced, to keep a single requestor from
+//! consuming all preflight capacity.
+
+use std::{collections::HashMap, time::Instant};
+
+use alloy::primitives::Address;
+
+/// Token bucket of a single requestor.
+#[derive(Clone, Copy, Debug)]
+struct Bucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+/// Rate limit settings, one token per order priced.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct RateLimit {
+    /// Number of orders per minute each requestor can have priced.
+    pub(crate) per_minute: u32,
+    /// Number of orders a requestor can have priced at once after being idle.
+    pub(crate) burst: u32,
+}
+
+impl RateLimit {
+    fn capacity(&self) -> f64 {
+        self.burst.max(1) as f64
+    }
+
+    fn refill(&self, bucket: &mut Bucket, now: Instant) {
+        let elapsed = now.saturating_duration_since(bucket.updated_at);
+        let refilled = elapsed.as_secs_f64() * self.per_minute as f64 / 60.0;
+        bucket.tokens = (bucket.tokens + refilled).min(self.capacity());
+        bucket.updated_at = now;
+    }
+}
+
+/// Token bucket rate limiter keyed by requestor address.
+///
+/// Orders held back by the limit stay queued and are priced once the requestor's bucket
+/// refills. The number of distinct orders held back is tracked per requestor to report the top
+/// offenders.
+#[derive(Debug, Default)]
+pub(crate) struct RequestorRateLimiter {
+    buckets: HashMap<Address, Bucket>,
+    held_back: HashMap<Address, u64>,
+}
+
+impl RequestorRateLimiter {
+    /// Take a token for pricing an order from `requestor`, returning false if the requestor is
+    /// over the limit.
+    pub(crate) fn try_acquire(
+        &mut self,
+        requestor: Address,
+        limit: RateLimit,
+        now: Instant,
+    ) -> bool {
+        let bucket = self
+            .buckets
+            .entry(requestor)
+            .or_insert(Bucket { tokens: limit.capacity(), updated_at: now });
+        limit.refill(bucket, now);
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            return true;
+        }
+        false
+    }
+
+    /// Count an order from `requestor` newly held back by the limit.
+    pub(crate) fn hold_back(&mut self, requestor: Address) {
+        *self.held_back.entry(requestor).or_default() += 1;
+    }
+
+    /// Drop the buckets of requestors that have been idle long enough to be full again.
+    pub(crate) fn prune(&mut self, limit: RateLimit, now: Instant) {
+        self.buckets.retain(|_, bucket| {
+            limit.refill(bucket, now);
+            bucket.tokens < limit.capacity()
+        });
+    }
+
+    /// Return the requestors with the most orders held back since the last call, most first,
+    /// and reset the counts.
+    pub(crate) fn take_top_offenders(&mut self, count: usize) -> Vec<(Address, u64)> {
+        let mut offenders: Vec<_> = self.held_back.drain().collect();
+        offenders.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        offenders.truncate(count);
+        offenders
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    const LIMIT: RateLimit = RateLimit { per_minute: 6, burst: 2 };
+
+    #[test]
+    fn burst_then_refill() {
+        let mut limiter = RequestorRateLimiter::default();
+        let addr = Address::repeat_byte(0x11);
+        let start = Instant::now();
+
+        assert!(limiter.try_acquire(addr, LIMIT, start));
+        assert!(limiter.try_acquire(addr, LIMIT, start));
+        assert!(!limiter.try_acquire(addr, LIMIT, start));
+
+        // One order every 10 seconds at 6 per minute
+        assert!(!limiter.try_acquire(addr, LIMIT, start + Duration::from_secs(9)));
+        assert!(limiter.try_acquire(addr, LIMIT, start + Duration::from_secs(10)));
+        assert!(!limiter.try_acquire(addr, LIMIT, start + Duration::from_secs(10)));
+    }
+
+    #[test]
+    fn requestors_are_limited_independently() {
+        let mut limiter = RequestorRateLimiter::default();
+        let spammer = Address::repeat_byte(0x11);
+        let other = Address::repeat_byte(0x22);
+        let now = Instant::now();
+
+        for _ in 0..10 {
+            if !limiter.try_acquire(spammer, LIMIT, now) {
+                limiter.hold_back(spammer);
+            }
+        }
+        assert!(limiter.try_acquire(other, LIMIT, now));
+        assert_eq!(limiter.take_top_offenders(5), vec![(spammer, 8)]);
+        assert!(limiter.take_top_offenders(5).is_empty());
+    }
+
+    #[test]
+    fn prune_drops_idle_requestors() {
+        let mut limiter = RequestorRateLimiter::default();
+        let addr = Address::repeat_byte(0x11);
+        let now = Instant::now();
+
+        assert!(limiter.try_acquire(addr, LIMIT, now));
+        limiter.prune(LIMIT, now);
+        assert_eq!(limiter.buckets.len(), 1);
+        limiter.prune(LIMIT, now + Duration::from_secs(10));
+        assert!(limiter.buckets.is_empty());
+    }
+}
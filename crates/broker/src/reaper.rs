@@ -187,6 +187,7 @@ mod tests {
             chain_id: 1,
             total_cycles: None,
             proving_started_at: None,
+            fulfillment_path: None,
             cached_id: Default::default(),
         }
     }
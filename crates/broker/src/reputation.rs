@@ -0,0 +1,77 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-requestor reputation, derived from the outcome of pricing their orders.
+
+use std::collections::HashMap;
+
+use alloy::primitives::Address;
+
+/// Reputation of requestors without enough priced orders to judge.
+pub(crate) const NEUTRAL_REPUTATION: f64 = 1.0;
+
+/// Reputation below which the orders of a requestor are priced after the orders of others.
+pub(crate) const LOW_REPUTATION: f64 = 0.5;
+
+/// Pricing statistics of a single requestor.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct RequestorStats {
+    /// Number of orders from the requestor that completed pricing.
+    pub(crate) orders: u64,
+    /// Number of those orders that were invalid, failing preflight or their predicate.
+    pub(crate) preflight_failures: u64,
+}
+
+impl RequestorStats {
+    /// Reputation score between 0.0 and 1.0, the share of priced orders that were valid.
+    ///
+    /// Requestors with fewer than `min_orders` priced orders have a neutral reputation, so new
+    /// requestors are not penalized.
+    pub(crate) fn reputation(&self, min_orders: u64) -> f64 {
+        if self.orders == 0 || self.orders < min_orders {
+            return NEUTRAL_REPUTATION;
+        }
+        let failures = self.preflight_failures.min(self.orders);
+        1.0 - failures as f64 / self.orders as f64
+    }
+}
+
+/// Compute the reputation of each requestor with recorded statistics.
+pub(crate) fn requestor_reputations(
+    stats: &HashMap<Address, RequestorStats>,
+    min_orders: u64,
+) -> HashMap<Address, f64> {
+    stats.iter().map(|(addr, stats)| (*addr, stats.reputation(min_orders))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_requestors_are_neutral() {
+        let stats = RequestorStats { orders: 5, preflight_failures: 5 };
+        assert_eq!(stats.reputation(10), NEUTRAL_REPUTATION);
+        assert_eq!(RequestorStats::default().reputation(0), NEUTRAL_REPUTATION);
+    }
+
+    #[test]
+    fn reputation_reflects_failure_rate() {
+        let stats = RequestorStats { orders: 20, preflight_failures: 5 };
+        assert_eq!(stats.reputation(10), 0.75);
+
+        let stats = RequestorStats { orders: 20, preflight_failures: 20 };
+        assert_eq!(stats.reputation(10), 0.0);
+    }
+}
@@ -0,0 +1,266 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Bounded in-memory retention for per-order metadata.
+//!
+//! Stores track when each entry was completed. Entries are pinned while the order
+//! they belong to is still being worked on, and pinned entries are never evicted. Once an entry
+//! is completed it becomes eligible for eviction, either because it exceeded the configured age
+//! or because the store grew past its configured size, in which case the oldest completed entries
+//! are evicted first.
+
+use std::{
+    collections::HashMap,
+    hash::Hash,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Size and age limits applied to a [RetentionStore].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RetentionLimits {
+    /// Maximum number of entries to retain. Pinned entries count towards this limit, but are
+    /// never evicted to satisfy it.
+    pub max_entries: usize,
+    /// Maximum time to retain an entry after it has been completed.
+    pub max_age: Duration,
+}
+
+#[derive(Debug)]
+struct Inner<K> {
+    limits: RetentionLimits,
+    /// Completion time of each entry. `None` means the entry is pinned.
+    entries: HashMap<K, Option<Instant>>,
+}
+
+/// Snapshot of the current size of a [RetentionStore].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct RetentionStats {
+    pub total: usize,
+    pub pinned: usize,
+}
+
+/// In-memory store with bounded growth and pinning of in-flight entries.
+#[derive(Debug)]
+pub(crate) struct RetentionStore<K> {
+    name: &'static str,
+    inner: Mutex<Inner<K>>,
+}
+
+impl<K> RetentionStore<K>
+where
+    K: Hash + Eq + Clone,
+{
+    pub(crate) fn new(name: &'static str, limits: RetentionLimits) -> Self {
+        Self { name, inner: Mutex::new(Inner { limits, entries: HashMap::new() }) }
+    }
+
+    /// Name of the store, used for logging.
+    pub(crate) fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Update the limits applied on the next eviction pass.
+    pub(crate) fn set_limits(&self, limits: RetentionLimits) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.limits != limits {
+            tracing::debug!("Retention limits for {} changed to {:?}", self.name, limits);
+            inner.limits = limits;
+        }
+    }
+
+    /// Insert a pinned entry. Returns false, leaving the store unchanged, if the key is
+    /// already present.
+    pub(crate) fn insert_pinned(&self, key: K) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.entries.contains_key(&key) {
+            return false;
+        }
+        inner.entries.insert(key, None);
+        true
+    }
+
+    /// Pin an entry again, inserting it if it is not present, so that it is not evicted while
+    /// its order is worked on again.
+    pub(crate) fn repin(&self, key: K) {
+        self.inner.lock().unwrap().entries.insert(key, None);
+    }
+
+    /// Unpin an entry, marking it as completed and eligible for eviction.
+    pub(crate) fn complete(&self, key: &K) {
+        self.complete_at(key, Instant::now())
+    }
+
+    fn complete_at(&self, key: &K, now: Instant) {
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(completed_at) = inner.entries.get_mut(key) {
+            completed_at.get_or_insert(now);
+        }
+    }
+
+    pub(crate) fn stats(&self) -> RetentionStats {
+        let inner = self.inner.lock().unwrap();
+        let pinned = inner.entries.values().filter(|at| at.is_none()).count();
+        RetentionStats { total: inner.entries.len(), pinned }
+    }
+
+    /// Evict completed entries past the age limit, then the oldest completed entries until the
+    /// store is within its size limit. Returns the evicted keys, oldest first.
+    pub(crate) fn evict(&self) -> Vec<K> {
+        self.evict_at(Instant::now())
+    }
+
+    fn evict_at(&self, now: Instant) -> Vec<K> {
+        let mut inner = self.inner.lock().unwrap();
+        let limits = inner.limits;
+
+        let mut completed: Vec<(Instant, K)> = inner
+            .entries
+            .iter()
+            .filter_map(|(key, completed_at)| completed_at.map(|at| (at, key.clone())))
+            .collect();
+        completed.sort_by_key(|(at, _)| *at);
+
+        let mut excess = inner.entries.len().saturating_sub(limits.max_entries);
+        let mut evicted = Vec::new();
+        for (completed_at, key) in completed {
+            let expired = now.saturating_duration_since(completed_at) > limits.max_age;
+            if !expired && excess == 0 {
+                break;
+            }
+            inner.entries.remove(&key);
+            excess = excess.saturating_sub(1);
+            evicted.push(key);
+        }
+
+        if excess > 0 {
+            tracing::warn!(
+                "Retention store {} holds {} entries, exceeding its limit of {}; remaining entries are pinned by in-progress orders",
+                self.name,
+                inner.entries.len(),
+                limits.max_entries
+            );
+        }
+
+        evicted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    impl RetentionStore<String> {
+        fn contains(&self, key: &str) -> bool {
+            self.inner.lock().unwrap().entries.contains_key(key)
+        }
+    }
+
+    fn store(max_entries: usize, max_age_secs: u64) -> RetentionStore<String> {
+        RetentionStore::new(
+            "test",
+            RetentionLimits { max_entries, max_age: Duration::from_secs(max_age_secs) },
+        )
+    }
+
+    #[test]
+    fn evicts_oldest_completed_first() {
+        let store = store(3, 3600);
+        let start = Instant::now();
+
+        for i in 0..5 {
+            assert!(store.insert_pinned(format!("order-{i}")));
+        }
+        // Complete out of insertion order, so completion time decides eviction order.
+        for (offset, i) in [3, 0, 4, 1].into_iter().enumerate() {
+            store.complete_at(&format!("order-{i}"), start + Duration::from_secs(offset as u64));
+        }
+
+        let evicted = store.evict_at(start + Duration::from_secs(10));
+        assert_eq!(evicted, vec!["order-3".to_string(), "order-0".to_string()]);
+        assert_eq!(store.stats(), RetentionStats { total: 3, pinned: 1 });
+        assert!(store.contains("order-2"));
+    }
+
+    #[test]
+    fn pinned_entries_are_never_evicted() {
+        let store = store(2, 1);
+        let start = Instant::now();
+
+        for i in 0..4 {
+            store.insert_pinned(format!("order-{i}"));
+        }
+        store.complete_at(&"order-1".to_string(), start);
+
+        // Well past the age limit and over the size limit, only the completed entry goes.
+        let evicted = store.evict_at(start + Duration::from_secs(3600));
+        assert_eq!(evicted, vec!["order-1".to_string()]);
+        assert_eq!(store.stats(), RetentionStats { total: 3, pinned: 3 });
+        for i in [0, 2, 3] {
+            assert!(store.contains(&format!("order-{i}")));
+        }
+    }
+
+    #[test]
+    fn evicts_completed_entries_past_max_age() {
+        let store = store(100, 60);
+        let start = Instant::now();
+
+        for i in 0..3 {
+            store.insert_pinned(format!("order-{i}"));
+            store.complete_at(&format!("order-{i}"), start + Duration::from_secs(i * 30));
+        }
+
+        let evicted = store.evict_at(start + Duration::from_secs(91));
+        assert_eq!(evicted, vec!["order-0".to_string(), "order-1".to_string()]);
+        assert_eq!(store.stats(), RetentionStats { total: 1, pinned: 0 });
+    }
+
+    #[test]
+    fn duplicate_insert_is_rejected() {
+        let store = store(10, 60);
+        assert!(store.insert_pinned("order".to_string()));
+        store.complete(&"order".to_string());
+        assert!(!store.insert_pinned("order".to_string()));
+        assert_eq!(store.stats(), RetentionStats { total: 1, pinned: 0 });
+    }
+
+    #[test]
+    fn repinned_entries_are_not_evicted() {
+        let store = store(10, 1);
+        let start = Instant::now();
+        store.insert_pinned("order".to_string());
+        store.complete_at(&"order".to_string(), start);
+
+        store.repin("order".to_string());
+        assert!(store.evict_at(start + Duration::from_secs(3600)).is_empty());
+        assert_eq!(store.stats(), RetentionStats { total: 1, pinned: 1 });
+    }
+
+    #[test]
+    fn limits_can_be_updated() {
+        let store = store(10, 3600);
+        let start = Instant::now();
+        for i in 0..4 {
+            store.insert_pinned(format!("order-{i}"));
+            store.complete_at(&format!("order-{i}"), start + Duration::from_secs(i));
+        }
+        assert!(store.evict_at(start + Duration::from_secs(5)).is_empty());
+
+        store.set_limits(RetentionLimits { max_entries: 1, max_age: Duration::from_secs(3600) });
+        let evicted = store.evict_at(start + Duration::from_secs(5));
+        assert_eq!(evicted.len(), 3);
+        assert!(store.contains("order-3"));
+    }
+}
@@ -0,0 +1,213 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::time::Duration;
+
+use thiserror::Error;
+use tokio::sync::watch;
+use tokio_util::sync::CancellationToken;
+
+use crate::{
+    config::{ConfigErr, ConfigLock},
+    errors::CodedError,
+    provers::ProverObj,
+    task::{RetryRes, RetryTask, SupervisorErr},
+};
+
+/// Consecutive failed queue depth queries after which the backlog is treated as unknown, so that
+/// an unreachable backend does not halt order commitments indefinitely.
+const MAX_CONSECUTIVE_POLL_FAILURES: u32 = 3;
+
+#[derive(Error, Debug)]
+pub enum SaturationMonitorErr {
+    #[error("{code} Config error {0}", code = self.code())]
+    ConfigReadErr(#[from] ConfigErr),
+}
+
+impl CodedError for SaturationMonitorErr {
+    fn code(&self) -> &str {
+        match self {
+            SaturationMonitorErr::ConfigReadErr(_) => "[B-SAT-001]",
+        }
+    }
+}
+
+/// Backlog of the prover backend, as last observed by the [SaturationMonitor].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub(crate) struct ProverSaturation {
+    /// Number of proving jobs queued or running on the backend, if reported.
+    pub queue_depth: Option<u64>,
+    /// Queue depth at which the backend is considered saturated, if configured.
+    pub threshold: Option<u64>,
+}
+
+impl ProverSaturation {
+    /// Whether the backlog has reached the configured threshold.
+    ///
+    /// An unknown queue depth or an unset threshold is never considered saturated.
+    pub fn is_saturated(&self) -> bool {
+        match (self.queue_depth, self.threshold) {
+            (Some(depth), Some(threshold)) => depth >= threshold,
+            _ => false,
+        }
+    }
+
+    /// Scales a capacity down in proportion to how close the backlog is to the threshold.
+    ///
+    /// Returns the full capacity when the backend is idle or the backlog is unknown, and shrinks
+    /// it linearly as the queue deepens, keeping at least one slot so orders continue to be
+    /// evaluated while commitments are halted.
+    pub fn scale_capacity(&self, capacity: usize) -> usize {
+        let (Some(depth), Some(threshold)) = (self.queue_depth, self.threshold) else {
+            return capacity;
+        };
+        if threshold == 0 {
+            return capacity.min(1);
+        }
+        let remaining = threshold.saturating_sub(depth) as u128;
+        let scaled = (capacity as u128 * remaining).div_ceil(threshold as u128) as usize;
+        scaled.max(capacity.min(1))
+    }
+}
+
+/// Periodically polls the prover backend for its queue depth and publishes the saturation state
+/// to the order picker and order monitor.
+#[derive(Clone)]
+pub struct SaturationMonitor {
+    config: ConfigLock,
+    prover: ProverObj,
+    state: watch::Sender<ProverSaturation>,
+}
+
+impl SaturationMonitor {
+    pub fn new(config: ConfigLock, prover: ProverObj) -> Self {
+        let (state, _) = watch::channel(ProverSaturation::default());
+        Self { config, prover, state }
+    }
+
+    /// Subscribe to changes in the saturation state.
+    pub(crate) fn subscribe(&self) -> watch::Receiver<ProverSaturation> {
+        self.state.subscribe()
+    }
+
+    /// Poll the queue depth of the prover backend, counting consecutive failures in `failures`.
+    ///
+    /// A failed query keeps the last known queue depth, until
+    /// [MAX_CONSECUTIVE_POLL_FAILURES] is reached and the queue depth is cleared.
+    async fn poll(&self, failures: &mut u32) -> Result<Duration, SaturationMonitorErr> {
+        let (threshold, poll_secs) = {
+            let config = self.config.lock_all()?;
+            (config.prover.saturation_queue_depth, config.prover.saturation_poll_secs)
+        };
+
+        let queue_depth = match threshold {
+            Some(_) => match self.prover.queue_depth().await {
+                Ok(depth) => {
+                    *failures = 0;
+                    depth
+                }
+                Err(err) => {
+                    *failures += 1;
+                    if *failures >= MAX_CONSECUTIVE_POLL_FAILURES {
+                        tracing::error!(
+                            "Failed to query prover backend queue depth {failures} times in a row, treating it as unknown: {err}"
+                        );
+                        None
+                    } else {
+                        tracing::warn!("Failed to query prover backend queue depth: {err}");
+                        self.state.borrow().queue_depth
+                    }
+                }
+            },
+            None => None,
+        };
+
+        let saturation = ProverSaturation { queue_depth, threshold };
+        let prev = self.state.send_replace(saturation);
+        match (prev.is_saturated(), saturation.is_saturated()) {
+            (false, true) => tracing::warn!(
+                "Prover backend saturated with {} queued jobs (threshold {}), halting new order commitments",
+                queue_depth.unwrap_or_default(),
+                threshold.unwrap_or_default()
+            ),
+            (true, false) => tracing::info!(
+                "Prover backend backlog cleared ({} queued jobs), resuming order commitments",
+                queue_depth.unwrap_or_default()
+            ),
+            _ => {
+                if prev != saturation {
+                    tracing::debug!("Prover backend saturation: {saturation:?}");
+                }
+            }
+        }
+
+        Ok(Duration::from_secs(poll_secs.max(1)))
+    }
+
+    async fn run(&self, cancel_token: CancellationToken) -> Result<(), SaturationMonitorErr> {
+        let mut failures = 0;
+        loop {
+            let poll_interval = self.poll(&mut failures).await?;
+            tokio::select! {
+                _ = tokio::time::sleep(poll_interval) => {}
+                _ = cancel_token.cancelled() => {
+                    tracing::debug!("Saturation monitor received cancellation");
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+impl RetryTask for SaturationMonitor {
+    type Error = SaturationMonitorErr;
+
+    fn spawn(&self, cancel_token: CancellationToken) -> RetryRes<Self::Error> {
+        let this = self.clone();
+        Box::pin(async move {
+            tracing::info!("Starting prover saturation monitor");
+            this.run(cancel_token).await.map_err(SupervisorErr::Recover)?;
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn saturation_threshold() {
+        let unknown = ProverSaturation { queue_depth: None, threshold: Some(10) };
+        assert!(!unknown.is_saturated());
+        assert_eq!(unknown.scale_capacity(8), 8);
+
+        let disabled = ProverSaturation { queue_depth: Some(100), threshold: None };
+        assert!(!disabled.is_saturated());
+        assert_eq!(disabled.scale_capacity(8), 8);
+
+        let idle = ProverSaturation { queue_depth: Some(0), threshold: Some(10) };
+        assert!(!idle.is_saturated());
+        assert_eq!(idle.scale_capacity(8), 8);
+
+        let half = ProverSaturation { queue_depth: Some(5), threshold: Some(10) };
+        assert!(!half.is_saturated());
+        assert_eq!(half.scale_capacity(8), 4);
+
+        let saturated = ProverSaturation { queue_depth: Some(12), threshold: Some(10) };
+        assert!(saturated.is_saturated());
+        assert_eq!(saturated.scale_capacity(8), 1);
+        assert_eq!(saturated.scale_capacity(0), 0);
+    }
+}
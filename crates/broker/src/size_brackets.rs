@@ -0,0 +1,175 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Order size brackets, each with its own pool of concurrent proving capacity, so that huge
+//! orders cannot starve the flow of small orders.
+
+use crate::config::SizeBracket;
+
+/// Index of the bracket an order with the given cycle count belongs to.
+///
+/// Orders without a cycle count, and orders larger than every bound, belong to the last bracket.
+pub(crate) fn bracket_of(brackets: &[SizeBracket], cycles: Option<u64>) -> usize {
+    let last = brackets.len().saturating_sub(1);
+    let Some(cycles) = cycles else {
+        return last;
+    };
+    brackets
+        .iter()
+        .position(|bracket| {
+            bracket.max_mcycles.is_none_or(|max| cycles < max.saturating_mul(1_000_000))
+        })
+        .unwrap_or(last)
+}
+
+/// Select the orders to commit to, in priority order, given the cycle counts of the orders
+/// already committed.
+///
+/// Each bracket fills independently up to its `max_concurrent_proofs`. If `lend` is set, orders
+/// left over once their own bracket is full may use the idle capacity of an adjacent bracket.
+/// Committed orders over the cap of their own bracket are counted against its neighbours.
+pub(crate) fn select_orders<T>(
+    brackets: &[SizeBracket],
+    lend: bool,
+    committed: impl IntoIterator<Item = Option<u64>>,
+    orders: Vec<T>,
+    cycles: impl Fn(&T) -> Option<u64>,
+) -> Vec<T> {
+    if brackets.is_empty() {
+        return orders;
+    }
+
+    let mut available: Vec<u32> =
+        brackets.iter().map(|bracket| bracket.max_concurrent_proofs).collect();
+    let mut overflow = vec![0u32; brackets.len()];
+    for committed_cycles in committed {
+        let idx = bracket_of(brackets, committed_cycles);
+        if available[idx] > 0 {
+            available[idx] -= 1;
+        } else {
+            overflow[idx] += 1;
+        }
+    }
+    if lend {
+        for (idx, count) in overflow.into_iter().enumerate() {
+            for _ in 0..count {
+                if let Some(neighbour) = adjacent_with_capacity(&available, idx) {
+                    available[neighbour] -= 1;
+                }
+            }
+        }
+    }
+
+    let classes: Vec<usize> =
+        orders.iter().map(|order| bracket_of(brackets, cycles(order))).collect();
+    let mut selected = vec![false; orders.len()];
+    for (order_idx, &idx) in classes.iter().enumerate() {
+        if available[idx] > 0 {
+            available[idx] -= 1;
+            selected[order_idx] = true;
+        }
+    }
+    if lend {
+        for (order_idx, &idx) in classes.iter().enumerate() {
+            if selected[order_idx] {
+                continue;
+            }
+            if let Some(neighbour) = adjacent_with_capacity(&available, idx) {
+                tracing::debug!(
+                    "Lending capacity of size bracket {} to an order in size bracket {}",
+                    brackets[neighbour].name,
+                    brackets[idx].name
+                );
+                available[neighbour] -= 1;
+                selected[order_idx] = true;
+            }
+        }
+    }
+
+    orders
+        .into_iter()
+        .zip(selected)
+        .filter_map(|(order, selected)| selected.then_some(order))
+        .collect()
+}
+
+/// Adjacent bracket with idle capacity, preferring the smaller one.
+fn adjacent_with_capacity(available: &[u32], idx: usize) -> Option<usize> {
+    [idx.checked_sub(1), Some(idx + 1)]
+        .into_iter()
+        .flatten()
+        .find(|&neighbour| available.get(neighbour).is_some_and(|&slots| slots > 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn brackets() -> Vec<SizeBracket> {
+        vec![
+            SizeBracket {
+                name: "small".to_string(),
+                max_mcycles: Some(50),
+                max_concurrent_proofs: 2,
+            },
+            SizeBracket {
+                name: "medium".to_string(),
+                max_mcycles: Some(1_000),
+                max_concurrent_proofs: 1,
+            },
+            SizeBracket { name: "large".to_string(), max_mcycles: None, max_concurrent_proofs: 1 },
+        ]
+    }
+
+    const SMALL: u64 = 1_000_000;
+    const MEDIUM: u64 = 100_000_000;
+    const LARGE: u64 = 5_000_000_000;
+
+    #[test]
+    fn classify_by_cycles() {
+        let brackets = brackets();
+        assert_eq!(bracket_of(&brackets, Some(SMALL)), 0);
+        assert_eq!(bracket_of(&brackets, Some(50_000_000)), 1);
+        assert_eq!(bracket_of(&brackets, Some(MEDIUM)), 1);
+        assert_eq!(bracket_of(&brackets, Some(LARGE)), 2);
+        assert_eq!(bracket_of(&brackets, None), 2);
+    }
+
+    #[test]
+    fn large_order_does_not_block_small_orders() {
+        let orders = vec![LARGE, SMALL, LARGE, SMALL, SMALL];
+        let selected = select_orders(&brackets(), false, [Some(LARGE)], orders, |c| Some(*c));
+        assert_eq!(selected, vec![SMALL, SMALL]);
+    }
+
+    #[test]
+    fn idle_capacity_lent_to_adjacent_bracket() {
+        let orders = vec![SMALL, SMALL, SMALL, SMALL];
+        let selected = select_orders(&brackets(), false, [], orders.clone(), |c| Some(*c));
+        assert_eq!(selected.len(), 2);
+
+        // The medium bracket is idle and lends its slot, the large bracket is not adjacent
+        let selected = select_orders(&brackets(), true, [], orders, |c| Some(*c));
+        assert_eq!(selected.len(), 3);
+    }
+
+    #[test]
+    fn committed_overflow_counts_against_lender() {
+        // Three small orders committed, one of them on capacity borrowed from the medium bracket
+        let committed = [Some(SMALL), Some(SMALL), Some(SMALL)];
+        let orders = vec![MEDIUM, SMALL];
+        let selected = select_orders(&brackets(), true, committed, orders, |c| Some(*c));
+        assert!(selected.is_empty());
+    }
+}
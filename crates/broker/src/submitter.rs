@@ -24,13 +24,10 @@ use alloy::{
     sol_types::{SolStruct, SolValue},
 };
 use anyhow::{anyhow, Context, Result};
-use boundless_market::{
-    contracts::{
-        boundless_market::{BoundlessMarketService, FulfillmentTx, MarketError, UnlockedRequest},
-        encode_seal, AssessorJournal, AssessorReceipt, Fulfillment,
-        FulfillmentDataImageIdAndJournal, FulfillmentDataType, PredicateType,
-    },
-    selector::is_groth16_selector,
+use boundless_market::contracts::{
+    boundless_market::{BoundlessMarketService, FulfillmentTx, MarketError, UnlockedRequest},
+    encode_seal, AssessorJournal, AssessorReceipt, Fulfillment, FulfillmentDataImageIdAndJournal,
+    FulfillmentDataType, PredicateType,
 };
 use hex::FromHex;
 use risc0_aggregation::{SetInclusionReceipt, SetInclusionReceiptVerifierParameters};
@@ -41,7 +38,7 @@ use risc0_zkvm::{
 };
 
 use crate::{
-    config::ConfigLock,
+    config::{ConfigLock, FulfillmentPath},
     db::DbObj,
     impl_coded_debug, now_timestamp,
     provers::ProverObj,
@@ -271,6 +268,7 @@ where
                     order_img_id,
                     lock_price,
                     fulfillment_type,
+                    fulfillment_path,
                 ) =
                     self.db.get_submission_order(order_id).await.context(
                         "Failed to get order from DB for submission, order NOT finalized",
@@ -299,7 +297,7 @@ where
                 let order_claim =
                     ReceiptClaim::ok(order_img_id, MaybePruned::Pruned(order_journal.digest()));
                 let order_claim_digest = order_claim.digest();
-                let seal = if is_groth16_selector(order_request.requirements.selector) {
+                let seal = if fulfillment_path == FulfillmentPath::Groth16 {
                     let compressed_proof_id =
                         self.db.get_order_compressed_proof_id(order_id).await.context(
                             "Failed to get order compressed proof ID from DB for submission",
@@ -410,6 +408,8 @@ where
             callbacks: assessor_journal.callbacks,
         };
 
+        // Batches are submitted even in observation mode, so that orders committed to before the
+        // mode was turned on are fulfilled rather than slashed. Only new commitments are refused.
         let (single_txn_fulfill, withdraw) = {
             let config = self.config.lock_all().context("Failed to read config")?;
             (config.batcher.single_txn_fulfill, config.batcher.withdraw)
@@ -872,6 +872,7 @@ mod tests {
             chain_id,
             total_cycles: None,
             proving_started_at: None,
+            fulfillment_path: None,
             cached_id: Default::default(),
         };
         let order_id = order.id();
@@ -940,6 +941,17 @@ mod tests {
         process_next_batch(submitter, db, batch_id).await;
     }
 
+    #[tokio::test]
+    #[traced_test]
+    async fn submit_batch_in_observation_mode() {
+        let config = ConfigLock::default();
+        let (_anvil, submitter, db, batch_id) = build_submitter_and_batch(config.clone()).await;
+        // The orders of the batch were committed to before observation mode was turned on, so
+        // they are still fulfilled rather than left to be slashed.
+        config.load_write().unwrap().market.observation_mode = true;
+        process_next_batch(submitter, db, batch_id).await;
+    }
+
     #[tokio::test]
     #[traced_test]
     async fn submit_batch_retry_max_attempts() {
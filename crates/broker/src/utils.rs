@@ -12,14 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use alloy::primitives::aliases::U96;
+use std::fmt;
+
+use alloy::primitives::{aliases::U96, utils::parse_ether, U256};
 use anyhow::{Context, Result};
 use boundless_market::{
     contracts::ProofRequest,
     selector::{ProofType, SupportedSelectors},
 };
 
-use crate::{config::ConfigLock, Order, OrderRequest, OrderStatus};
+use crate::{
+    config::{ConfigLock, FulfillmentPath, MarketConf},
+    db::DbObj,
+    Order, OrderRequest, OrderStatus,
+};
 
 /// Gas allocated to verifying a smart contract signature. Copied from BoundlessMarket.sol.
 pub const ERC1271_MAX_GAS_FOR_CHECK: u64 = 100000;
@@ -66,12 +72,106 @@ pub async fn estimate_gas_to_lock(config: &ConfigLock, order: &OrderRequest) ->
     Ok(estimate)
 }
 
+/// Cost model for the share of an aggregated batch paid by each order in it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregationCostModel {
+    /// Fixed per-order gas, set by `aggregation_amortized_gas_estimate`
+    Fixed { gas: u64 },
+    /// Batch posting gas shared over the expected batch size, plus the per-order inclusion gas
+    Amortized { batch_gas: u64, expected_batch_size: u64, inclusion_gas: u64 },
+}
+
+impl AggregationCostModel {
+    /// Build the cost model from the market config and the sizes of recently submitted batches
+    ///
+    /// The expected batch size is the average size of the recent batches. When no batch was
+    /// submitted yet, the fixed `aggregation_amortized_gas_estimate` is used instead, so a fresh
+    /// broker prices orders as it did before batch sizes were tracked.
+    pub fn new(market: &MarketConf, recent_sizes: &[usize]) -> Self {
+        if market.aggregation_amortized_gas_estimate > 0 || recent_sizes.is_empty() {
+            return Self::Fixed { gas: market.aggregation_amortized_gas_estimate };
+        }
+
+        let expected_batch_size = (recent_sizes.iter().sum::<usize>() / recent_sizes.len()) as u64;
+        Self::Amortized {
+            batch_gas: market.aggregation_batch_gas_estimate,
+            expected_batch_size: expected_batch_size.max(1),
+            inclusion_gas: market.aggregation_inclusion_gas_estimate,
+        }
+    }
+
+    /// Gas paid by a single order fulfilled through aggregation
+    pub fn gas_per_order(&self) -> u64 {
+        match *self {
+            Self::Fixed { gas } => gas,
+            Self::Amortized { inclusion_gas, .. } => self.batch_share() + inclusion_gas,
+        }
+    }
+
+    /// Share of the batch posting gas paid by each order submitted in a batch
+    ///
+    /// Orders fulfilled with a standalone Groth16 proof are still submitted with a batch, which
+    /// posts the batch root and the assessor seal, so they pay this share as well.
+    pub fn batch_share(&self) -> u64 {
+        match *self {
+            Self::Fixed { gas } => gas,
+            Self::Amortized { batch_gas, expected_batch_size, .. } => {
+                batch_gas.div_ceil(expected_batch_size)
+            }
+        }
+    }
+}
+
+impl fmt::Display for AggregationCostModel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Fixed { gas } => write!(f, "fixed aggregation share of {gas} gas"),
+            Self::Amortized { batch_gas, expected_batch_size, inclusion_gas } => write!(
+                f,
+                "aggregation share of {batch_gas} gas over {expected_batch_size} orders + {inclusion_gas} gas inclusion"
+            ),
+        }
+    }
+}
+
+/// Build the aggregation cost model from the config and the recently submitted batches
+pub async fn aggregation_cost_model(
+    config: &ConfigLock,
+    db: &DbObj,
+) -> Result<AggregationCostModel> {
+    let history =
+        config.lock_all().context("Failed to read config")?.market.aggregation_batch_history;
+    let recent_sizes =
+        db.get_recent_batch_sizes(history).await.context("Failed to get recent batch sizes")?;
+    let config = config.lock_all().context("Failed to read config")?;
+    Ok(AggregationCostModel::new(&config.market, &recent_sizes))
+}
+
+/// Describe the gas cost model applied to an order fulfilled through the given path
+pub fn describe_cost_model(path: FulfillmentPath, aggregation: &AggregationCostModel) -> String {
+    match path {
+        FulfillmentPath::Inclusion => aggregation.to_string(),
+        FulfillmentPath::Groth16 => match aggregation {
+            AggregationCostModel::Fixed { gas } => {
+                format!("fixed batch share of {gas} gas + groth16 verification")
+            }
+            AggregationCostModel::Amortized { batch_gas, expected_batch_size, .. } => format!(
+                "batch share of {batch_gas} gas over {expected_batch_size} orders + groth16 verification"
+            ),
+        },
+    }
+}
+
 /// Estimate of gas for to fulfill a single order
-/// Currently just uses the config estimate but this may change in the future
+///
+/// Orders pay their share of the batch they are submitted in, as given by the cost model, on
+/// both fulfillment paths.
 pub async fn estimate_gas_to_fulfill(
     config: &ConfigLock,
     supported_selectors: &SupportedSelectors,
     request: &ProofRequest,
+    path: FulfillmentPath,
+    aggregation: &AggregationCostModel,
 ) -> Result<u64> {
     // TODO: Add gas costs for orders with large journals.
     let (base, groth16) = {
@@ -79,6 +179,10 @@ pub async fn estimate_gas_to_fulfill(
         (config.market.fulfill_gas_estimate, config.market.groth16_verify_gas_estimate)
     };
 
+    supported_selectors
+        .proof_type(request.requirements.selector)
+        .context("unsupported selector")?;
+
     let mut estimate = base;
 
     // Add gas for orders that make use of the callbacks feature.
@@ -91,17 +195,294 @@ pub async fn estimate_gas_to_fulfill(
             .unwrap_or(U96::ZERO),
     )?;
 
-    estimate += match supported_selectors
+    estimate += match path {
+        FulfillmentPath::Inclusion => aggregation.gas_per_order(),
+        FulfillmentPath::Groth16 => aggregation.batch_share() + groth16,
+    };
+
+    Ok(estimate)
+}
+
+/// Choose the fulfillment path for a request
+///
+/// Requests that require a specific proof type always use that proof type. For requests that
+/// accept either, the configured path is used if set, otherwise the cheapest path at the given
+/// gas price.
+pub async fn select_fulfillment_path(
+    config: &ConfigLock,
+    supported_selectors: &SupportedSelectors,
+    request: &ProofRequest,
+    gas_price: u128,
+    aggregation: &AggregationCostModel,
+) -> Result<FulfillmentPath> {
+    match supported_selectors
         .proof_type(request.requirements.selector)
         .context("unsupported selector")?
     {
-        ProofType::Any | ProofType::Inclusion => 0,
-        ProofType::Groth16 => groth16,
+        ProofType::Groth16 => Ok(FulfillmentPath::Groth16),
+        ProofType::Inclusion => Ok(FulfillmentPath::Inclusion),
+        ProofType::Any => {
+            let config = config.lock_all().context("Failed to read config")?;
+            match config.market.fulfillment_path {
+                Some(path) => Ok(path),
+                None => cheapest_fulfillment_path(&config.market, gas_price, aggregation),
+            }
+        }
         proof_type => {
-            tracing::warn!("Unknown proof type in gas cost estimation: {proof_type:?}");
-            0
+            tracing::warn!("Unknown proof type in fulfillment path selection: {proof_type:?}");
+            Ok(FulfillmentPath::Inclusion)
         }
+    }
+}
+
+/// Compare the cost of fulfilling through aggregation against a standalone Groth16 proof
+///
+/// Aggregation is preferred when both paths cost the same.
+fn cheapest_fulfillment_path(
+    market: &MarketConf,
+    gas_price: u128,
+    aggregation: &AggregationCostModel,
+) -> Result<FulfillmentPath> {
+    let gas_price = U256::from(gas_price);
+    let inclusion_cost = gas_price * U256::from(aggregation.gas_per_order());
+    let groth16_cost = gas_price
+        * U256::from(aggregation.batch_share() + market.groth16_verify_gas_estimate)
+        + parse_ether(&market.groth16_compression_cost)
+            .context("Failed to parse groth16_compression_cost")?;
+
+    if groth16_cost < inclusion_cost {
+        Ok(FulfillmentPath::Groth16)
+    } else {
+        Ok(FulfillmentPath::Inclusion)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{Address, Bytes, FixedBytes};
+    use boundless_market::contracts::{
+        Offer, Predicate, RequestId, RequestInput, RequestInputType, Requirements,
     };
+    use risc0_ethereum_contracts::selector::Selector;
+    use risc0_zkvm::sha::Digest;
 
-    Ok(estimate)
+    fn request_with_selector(selector: FixedBytes<4>) -> ProofRequest {
+        ProofRequest::new(
+            RequestId::new(Address::ZERO, 1),
+            Requirements::new(Predicate::prefix_match(Digest::ZERO, Bytes::default()))
+                .with_selector(selector),
+            "http://risczero.com",
+            RequestInput { inputType: RequestInputType::Inline, data: "".into() },
+            Offer::default(),
+        )
+    }
+
+    fn cost_model(config: &ConfigLock, recent_sizes: &[usize]) -> AggregationCostModel {
+        let config = config.lock_all().unwrap();
+        AggregationCostModel::new(&config.market, recent_sizes)
+    }
+
+    #[tokio::test]
+    async fn any_selector_uses_cheapest_path() {
+        let config = ConfigLock::default();
+        let supported_selectors = SupportedSelectors::default();
+        let request = request_with_selector(FixedBytes::ZERO);
+        let gas_price = 1_000_000_000;
+
+        // Aggregating is cheaper than verifying a Groth16 proof onchain.
+        {
+            let mut config = config.load_write().unwrap();
+            config.market.aggregation_amortized_gas_estimate = 50_000;
+            config.market.groth16_verify_gas_estimate = 250_000;
+            config.market.groth16_compression_cost = "0".into();
+        }
+        let model = cost_model(&config, &[]);
+        let path =
+            select_fulfillment_path(&config, &supported_selectors, &request, gas_price, &model)
+                .await
+                .unwrap();
+        assert_eq!(path, FulfillmentPath::Inclusion);
+        assert_eq!(
+            estimate_gas_to_fulfill(&config, &supported_selectors, &request, path, &model)
+                .await
+                .unwrap(),
+            config.lock_all().unwrap().market.fulfill_gas_estimate + 50_000
+        );
+
+        // Both paths pay the batch share, so Groth16 is cheaper only when verifying the inclusion
+        // of an order costs more than verifying its Groth16 proof.
+        {
+            let mut config = config.load_write().unwrap();
+            config.market.aggregation_amortized_gas_estimate = 0;
+            config.market.aggregation_batch_gas_estimate = 300_000;
+            config.market.aggregation_inclusion_gas_estimate = 400_000;
+        }
+        let model = cost_model(&config, &[10]);
+        let path =
+            select_fulfillment_path(&config, &supported_selectors, &request, gas_price, &model)
+                .await
+                .unwrap();
+        assert_eq!(path, FulfillmentPath::Groth16);
+        assert_eq!(
+            estimate_gas_to_fulfill(&config, &supported_selectors, &request, path, &model)
+                .await
+                .unwrap(),
+            config.lock_all().unwrap().market.fulfill_gas_estimate + 30_000 + 250_000
+        );
+
+        // The compression cost tips the balance back to aggregation.
+        config.load_write().unwrap().market.groth16_compression_cost = "0.001".into();
+        let model = cost_model(&config, &[10]);
+        let path =
+            select_fulfillment_path(&config, &supported_selectors, &request, gas_price, &model)
+                .await
+                .unwrap();
+        assert_eq!(path, FulfillmentPath::Inclusion);
+    }
+
+    #[tokio::test]
+    async fn forced_path_applies_only_to_any_selector() {
+        let config = ConfigLock::default();
+        let supported_selectors = SupportedSelectors::default();
+        config.load_write().unwrap().market.fulfillment_path = Some(FulfillmentPath::Groth16);
+
+        let request = request_with_selector(FixedBytes::ZERO);
+        let model = cost_model(&config, &[]);
+        let path = select_fulfillment_path(&config, &supported_selectors, &request, 1, &model)
+            .await
+            .unwrap();
+        assert_eq!(path, FulfillmentPath::Groth16);
+
+        config.load_write().unwrap().market.fulfillment_path = Some(FulfillmentPath::Inclusion);
+        let request = request_with_selector(FixedBytes::from(Selector::groth16_latest() as u32));
+        let model = cost_model(&config, &[]);
+        let path = select_fulfillment_path(&config, &supported_selectors, &request, 1, &model)
+            .await
+            .unwrap();
+        assert_eq!(path, FulfillmentPath::Groth16);
+    }
+
+    #[test]
+    fn aggregation_cost_model_uses_batch_history() {
+        let config = ConfigLock::default();
+        {
+            let mut config = config.load_write().unwrap();
+            config.market.aggregation_batch_gas_estimate = 300_000;
+            config.market.aggregation_inclusion_gas_estimate = 30_000;
+        }
+
+        // With no submitted batches, the fixed estimate is kept, which is zero by default.
+        let model = cost_model(&config, &[]);
+        assert_eq!(model, AggregationCostModel::Fixed { gas: 0 });
+        assert_eq!(model.gas_per_order(), 0);
+
+        // A single batch of two orders pays half of the posting cost each.
+        let model = cost_model(&config, &[2]);
+        assert_eq!(
+            model,
+            AggregationCostModel::Amortized {
+                batch_gas: 300_000,
+                expected_batch_size: 2,
+                inclusion_gas: 30_000
+            }
+        );
+        assert_eq!(model.gas_per_order(), 180_000);
+
+        // Larger batches spread the posting cost over more orders.
+        let model = cost_model(&config, &[8, 12, 10]);
+        assert_eq!(model.gas_per_order(), 60_000);
+
+        // A fixed estimate overrides the batch history.
+        config.load_write().unwrap().market.aggregation_amortized_gas_estimate = 50_000;
+        let model = cost_model(&config, &[8, 12, 10]);
+        assert_eq!(model, AggregationCostModel::Fixed { gas: 50_000 });
+        assert_eq!(model.gas_per_order(), 50_000);
+    }
+
+    #[tokio::test]
+    async fn fulfillment_path_changes_order_cost() {
+        let config = ConfigLock::default();
+        let supported_selectors = SupportedSelectors::default();
+        let request = request_with_selector(FixedBytes::ZERO);
+        let fulfill_gas = config.lock_all().unwrap().market.fulfill_gas_estimate;
+        let model = cost_model(&config, &[10]);
+
+        config.load_write().unwrap().market.fulfillment_path = Some(FulfillmentPath::Inclusion);
+        let path = select_fulfillment_path(&config, &supported_selectors, &request, 1, &model)
+            .await
+            .unwrap();
+        let inclusion_gas =
+            estimate_gas_to_fulfill(&config, &supported_selectors, &request, path, &model)
+                .await
+                .unwrap();
+        // The Groth16 verification of the batch root is shared, not paid by each order.
+        assert_eq!(inclusion_gas, fulfill_gas + 300_000 / 10 + 30_000);
+
+        config.load_write().unwrap().market.fulfillment_path = Some(FulfillmentPath::Groth16);
+        let path = select_fulfillment_path(&config, &supported_selectors, &request, 1, &model)
+            .await
+            .unwrap();
+        let groth16_gas =
+            estimate_gas_to_fulfill(&config, &supported_selectors, &request, path, &model)
+                .await
+                .unwrap();
+        assert_eq!(groth16_gas, fulfill_gas + 300_000 / 10 + 250_000);
+        assert_eq!(
+            describe_cost_model(path, &model),
+            "batch share of 300000 gas over 10 orders + groth16 verification"
+        );
+        assert_eq!(
+            describe_cost_model(FulfillmentPath::Inclusion, &model),
+            "aggregation share of 300000 gas over 10 orders + 30000 gas inclusion"
+        );
+    }
+
+    #[tokio::test]
+    async fn both_paths_pay_batch_share() {
+        let config = ConfigLock::default();
+        let supported_selectors = SupportedSelectors::default();
+        let request = request_with_selector(FixedBytes::ZERO);
+        {
+            let mut config = config.load_write().unwrap();
+            config.market.aggregation_batch_gas_estimate = 300_000;
+            config.market.aggregation_inclusion_gas_estimate = 30_000;
+            config.market.groth16_verify_gas_estimate = 250_000;
+        }
+
+        for (sizes, share) in [(&[2][..], 150_000), (&[10][..], 30_000), (&[100][..], 3_000)] {
+            let model = cost_model(&config, sizes);
+            assert_eq!(model.batch_share(), share);
+            let inclusion = estimate_gas_to_fulfill(
+                &config,
+                &supported_selectors,
+                &request,
+                FulfillmentPath::Inclusion,
+                &model,
+            )
+            .await
+            .unwrap();
+            let groth16 = estimate_gas_to_fulfill(
+                &config,
+                &supported_selectors,
+                &request,
+                FulfillmentPath::Groth16,
+                &model,
+            )
+            .await
+            .unwrap();
+            // The batch share cancels out, so small batches do not bias the choice to Groth16.
+            assert_eq!(groth16 - inclusion, 250_000 - 30_000);
+            let path = select_fulfillment_path(
+                &config,
+                &supported_selectors,
+                &request,
+                1_000_000_000,
+                &model,
+            )
+            .await
+            .unwrap();
+            assert_eq!(path, FulfillmentPath::Inclusion);
+        }
+    }
 }
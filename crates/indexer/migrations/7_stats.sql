@@ -0,0 +1,17 @@
+CREATE TABLE IF NOT EXISTS requestor_stats (
+  address             TEXT      PRIMARY KEY,
+  requests            BIGINT    NOT NULL, -- Number of requests submitted
+  fulfilled           BIGINT    NOT NULL, -- Number of requests fulfilled
+  total_price         TEXT      NOT NULL, -- Sum of the prices of fulfilled requests, in wei
+  total_latency_secs  BIGINT    NOT NULL, -- Sum of the seconds from submission to fulfillment
+  updated_at          BIGINT    NOT NULL  -- Block timestamp of the last update
+);
+
+CREATE TABLE IF NOT EXISTS prover_stats (
+  address             TEXT      PRIMARY KEY,
+  requests            BIGINT    NOT NULL, -- Number of requests locked, or fulfilled without a lock
+  fulfilled           BIGINT    NOT NULL, -- Number of requests fulfilled
+  total_price         TEXT      NOT NULL, -- Sum of the prices of fulfilled requests, in wei
+  total_latency_secs  BIGINT    NOT NULL, -- Sum of the seconds from submission to fulfillment
+  updated_at          BIGINT    NOT NULL  -- Block timestamp of the last update
+);
@@ -0,0 +1,7 @@
+-- Lease held by the single indexer instance allowed to write to the database.
+CREATE TABLE IF NOT EXISTS indexer_state (
+  id          INTEGER   PRIMARY KEY,
+  holder      TEXT      NOT NULL, -- Instance ID of the lock holder
+  heartbeat   BIGINT    NOT NULL, -- UNIX timestamp of the last renewal of the lock
+  expires_at  BIGINT    NOT NULL  -- UNIX timestamp after which the lock can be taken over
+);
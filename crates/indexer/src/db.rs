@@ -12,21 +12,48 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{str::FromStr, sync::Arc};
+use std::{collections::HashSet, str::FromStr, sync::Arc};
 
 use alloy::primitives::{Address, B256, U256};
 use async_trait::async_trait;
 use boundless_market::contracts::{
-    AssessorReceipt, Fulfillment, FulfillmentDataType, PredicateType, ProofRequest,
+    AssessorReceipt, Fulfillment, FulfillmentDataType, Offer, PredicateType, ProofRequest,
     RequestInputType,
 };
 use sqlx::{
     any::{install_default_drivers, AnyConnectOptions, AnyPoolOptions},
-    AnyPool, Row,
+    AnyConnection, AnyPool, Connection, Row,
 };
 use thiserror::Error;
 
 const SQL_BLOCK_KEY: i64 = 0;
+const SQL_WRITER_LOCK_KEY: i64 = 0;
+
+// Tables read or written by the methods of [IndexerDb].
+const INDEXER_TABLES: &[&str] = &[
+    "last_block",
+    "blocks",
+    "transactions",
+    "proof_requests",
+    "assessor_receipts",
+    "fulfillments",
+    "request_submitted_events",
+    "request_locked_events",
+    "proof_delivered_events",
+    "request_fulfilled_events",
+    "prover_slashed_events",
+    "deposit_events",
+    "withdrawal_events",
+    "collateral_deposit_events",
+    "collateral_withdrawal_events",
+    "callback_failed_events",
+    "requestor_stats",
+    "prover_stats",
+    "indexer_state",
+];
+
+// Value of `PRAGMA auto_vacuum` when incremental vacuuming is enabled.
+const SQLITE_AUTO_VACUUM_INCREMENTAL: i64 = 2;
 
 #[derive(Debug, Clone)]
 pub struct TxMetadata {
@@ -42,6 +69,58 @@ impl TxMetadata {
     }
 }
 
+/// Aggregate statistics of a requestor or prover address.
+///
+/// Totals are stored rather than averages, so they can be updated incrementally. The average
+/// price and latency are the totals divided by `fulfilled`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AggregateStats {
+    pub address: Address,
+    /// For requestors, the number of requests submitted. For provers, the number of requests
+    /// locked, or fulfilled without holding the lock.
+    pub requests: u64,
+    pub fulfilled: u64,
+    /// Sum of the prices of the fulfilled requests, in wei.
+    pub total_price: U256,
+    /// Sum of the seconds from submission to fulfillment of the fulfilled requests.
+    pub total_latency_secs: u64,
+    /// Block timestamp of the last update.
+    pub updated_at: u64,
+}
+
+/// Change to apply to the [AggregateStats] of an address.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StatsDelta {
+    pub requests: u64,
+    pub fulfilled: u64,
+    pub total_price: U256,
+    pub total_latency_secs: u64,
+}
+
+/// A fulfilled request, with the details needed to update the aggregate statistics.
+#[derive(Debug, Clone)]
+pub struct FulfilledRequest {
+    pub client_address: Address,
+    pub offer: Offer,
+    /// Block timestamp of the submission, or of the lock for requests submitted offchain.
+    pub submitted_at: u64,
+    /// Prover holding the lock on the request and the block timestamp of the lock, if locked.
+    pub locked: Option<(Address, u64)>,
+    /// Prover that delivered the first proof.
+    pub prover_address: Option<Address>,
+}
+
+/// Lease on the database held by the indexer instance allowed to write to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WriterLock {
+    /// Instance ID of the holder.
+    pub holder: String,
+    /// UNIX timestamp of the last renewal.
+    pub heartbeat: u64,
+    /// UNIX timestamp after which another instance can take the lock over.
+    pub expires_at: u64,
+}
+
 #[derive(Error, Debug)]
 pub enum DbError {
     #[error("SQL error {0:?}")]
@@ -58,24 +137,39 @@ pub enum DbError {
 
     #[error("Invalid transaction: {0}")]
     BadTransaction(String),
+
+    #[error("Invalid stored value: {0}")]
+    BadValue(String),
+
+    #[error("Migration check failed: {0}")]
+    MigrationCheck(String),
 }
 
 #[async_trait]
 pub trait IndexerDb {
     async fn get_last_block(&self) -> Result<Option<u64>, DbError>;
     async fn set_last_block(&self, block_numb: u64) -> Result<(), DbError>;
+    /// Set the last processed block, if the writer lock is held by the given holder.
+    ///
+    /// Returns false without updating the block if another holder has taken over the lock.
+    async fn set_last_block_as_holder(
+        &self,
+        block_numb: u64,
+        holder: &str,
+    ) -> Result<bool, DbError>;
 
     async fn add_block(&self, block_numb: u64, block_timestamp: u64) -> Result<(), DbError>;
     async fn get_block_timestamp(&self, block_numb: u64) -> Result<Option<u64>, DbError>;
 
     async fn add_tx(&self, metadata: &TxMetadata) -> Result<(), DbError>;
 
+    /// Add a proof request. Returns false if the request was already recorded.
     async fn add_proof_request(
         &self,
         request_digest: B256,
         request: ProofRequest,
         metadata: &TxMetadata,
-    ) -> Result<(), DbError>;
+    ) -> Result<bool, DbError>;
 
     async fn has_proof_request(&self, request_digest: B256) -> Result<bool, DbError>;
 
@@ -99,13 +193,14 @@ pub trait IndexerDb {
         metadata: &TxMetadata,
     ) -> Result<(), DbError>;
 
+    /// Add a request locked event. Returns false if the event was already recorded.
     async fn add_request_locked_event(
         &self,
         request_digest: B256,
         request_id: U256,
         prover_address: Address,
         metadata: &TxMetadata,
-    ) -> Result<(), DbError>;
+    ) -> Result<bool, DbError>;
 
     async fn add_proof_delivered_event(
         &self,
@@ -114,12 +209,13 @@ pub trait IndexerDb {
         metadata: &TxMetadata,
     ) -> Result<(), DbError>;
 
+    /// Add a request fulfilled event. Returns false if the event was already recorded.
     async fn add_request_fulfilled_event(
         &self,
         request_digest: B256,
         request_id: U256,
         metadata: &TxMetadata,
-    ) -> Result<(), DbError>;
+    ) -> Result<bool, DbError>;
 
     async fn add_prover_slashed_event(
         &self,
@@ -165,10 +261,129 @@ pub trait IndexerDb {
         error_data: Vec<u8>,
         metadata: &TxMetadata,
     ) -> Result<(), DbError>;
+
+    /// Get a fulfilled request along with its lock and the prover that fulfilled it.
+    async fn get_fulfilled_request(
+        &self,
+        request_digest: B256,
+    ) -> Result<Option<FulfilledRequest>, DbError>;
+
+    /// Apply a change to the aggregate statistics of a requestor, creating them if needed.
+    async fn upsert_requestor_stats(
+        &self,
+        address: Address,
+        delta: &StatsDelta,
+        block_timestamp: u64,
+    ) -> Result<(), DbError>;
+
+    /// Apply a change to the aggregate statistics of a prover, creating them if needed.
+    async fn upsert_prover_stats(
+        &self,
+        address: Address,
+        delta: &StatsDelta,
+        block_timestamp: u64,
+    ) -> Result<(), DbError>;
+
+    /// Get a page of the requestor statistics, ordered by address.
+    async fn get_requestor_stats(
+        &self,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<AggregateStats>, DbError>;
+
+    /// Get a page of the prover statistics, ordered by address.
+    async fn get_prover_stats(
+        &self,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<AggregateStats>, DbError>;
+
+    /// Reclaim free pages and refresh the query planner statistics.
+    ///
+    /// A SQLite database that is not in incremental auto-vacuum mode is only switched to it, with
+    /// a full `VACUUM`, if `allow_full` is set. Otherwise, only its statistics are refreshed.
+    ///
+    /// Returns the number of pages reclaimed, if the backend reports it.
+    async fn vacuum(&self, allow_full: bool) -> Result<Option<u64>, DbError>;
+
+    /// Acquire or renew the writer lock for the given holder, until `now + ttl_secs`.
+    ///
+    /// Returns false if another holder has a lock that has not expired.
+    async fn try_acquire_writer_lock(
+        &self,
+        holder: &str,
+        now: u64,
+        ttl_secs: u64,
+    ) -> Result<bool, DbError>;
+
+    /// Release the writer lock, if held by the given holder.
+    async fn release_writer_lock(&self, holder: &str) -> Result<(), DbError>;
+
+    async fn get_writer_lock(&self) -> Result<Option<WriterLock>, DbError>;
 }
 
 pub type DbObj = Arc<dyn IndexerDb + Send + Sync>;
 
+/// Check that all migrations apply cleanly on the backend of the given database, without
+/// modifying it.
+///
+/// On Postgres the migrations are applied to a temporary schema inside a transaction that is
+/// rolled back. On SQLite they are applied to a fresh in-memory database, whose foreign keys are
+/// then checked to reference existing tables, as SQLite only resolves them when rows are written.
+/// In both cases every table used by [IndexerDb] must exist after migrating.
+pub async fn validate_migrations(database_url: &str) -> Result<(), DbError> {
+    install_default_drivers();
+    let opts = AnyConnectOptions::from_str(database_url)?;
+    let is_sqlite = opts.database_url.scheme() == "sqlite";
+    let mut conn = if is_sqlite {
+        AnyConnection::connect("sqlite::memory:").await?
+    } else {
+        AnyConnection::connect_with(&opts).await?
+    };
+
+    let mut tx = conn.begin().await?;
+    if !is_sqlite {
+        let schema = format!("indexer_migration_check_{}", std::process::id());
+        sqlx::query(&format!("CREATE SCHEMA {schema}")).execute(&mut *tx).await?;
+        sqlx::query(&format!("SET LOCAL search_path TO {schema}")).execute(&mut *tx).await?;
+    }
+
+    sqlx::migrate!().run(&mut *tx).await?;
+
+    for table in INDEXER_TABLES {
+        sqlx::query(&format!("SELECT 1 FROM {table} LIMIT 1"))
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|err| DbError::MigrationCheck(format!("table {table} is unusable: {err}")))?;
+    }
+
+    if is_sqlite {
+        let tables: HashSet<String> =
+            sqlx::query("SELECT name FROM sqlite_master WHERE type = 'table'")
+                .fetch_all(&mut *tx)
+                .await?
+                .iter()
+                .map(|row| row.try_get("name"))
+                .collect::<Result<_, _>>()?;
+        for table in &tables {
+            let rows = sqlx::query(&format!("PRAGMA foreign_key_list({table})"))
+                .fetch_all(&mut *tx)
+                .await?;
+            for row in rows {
+                let parent: String = row.try_get("table")?;
+                if !tables.contains(&parent) {
+                    return Err(DbError::MigrationCheck(format!(
+                        "table {table} references missing table {parent}"
+                    )));
+                }
+            }
+        }
+    }
+
+    tx.rollback().await?;
+    Ok(())
+}
+
 #[derive(Debug, Clone)]
 pub struct AnyDb {
     pub pool: AnyPool,
@@ -195,6 +410,138 @@ impl AnyDb {
     pub fn pool(&self) -> &AnyPool {
         &self.pool
     }
+
+    fn is_sqlite(&self) -> bool {
+        self.pool.connect_options().database_url.scheme() == "sqlite"
+    }
+
+    async fn vacuum_sqlite(&self, allow_full: bool) -> Result<u64, DbError> {
+        // Pragmas are connection scoped, so run the whole sequence on a single connection.
+        let mut conn = self.pool.acquire().await?;
+
+        let free_before: i64 =
+            sqlx::query("PRAGMA freelist_count").fetch_one(&mut *conn).await?.try_get(0)?;
+
+        let auto_vacuum: i64 =
+            sqlx::query("PRAGMA auto_vacuum").fetch_one(&mut *conn).await?.try_get(0)?;
+        if auto_vacuum == SQLITE_AUTO_VACUUM_INCREMENTAL {
+            sqlx::raw_sql("PRAGMA incremental_vacuum").execute(&mut *conn).await?;
+        } else if allow_full {
+            // Incremental vacuuming only takes effect once the database has been switched to
+            // incremental auto-vacuum, which requires a full vacuum to rebuild the file.
+            tracing::warn!(
+                "Switching sqlite database to incremental auto-vacuum with a full VACUUM, the database is locked until it completes"
+            );
+            let start = std::time::Instant::now();
+            sqlx::raw_sql("PRAGMA auto_vacuum = INCREMENTAL").execute(&mut *conn).await?;
+            sqlx::raw_sql("VACUUM").execute(&mut *conn).await?;
+            tracing::info!("Full VACUUM completed in {}s", start.elapsed().as_secs());
+        } else {
+            tracing::warn!(
+                "sqlite database is not in incremental auto-vacuum mode, skipping vacuum; set --sqlite-full-vacuum to switch it with a one-time full VACUUM"
+            );
+        }
+        sqlx::raw_sql("ANALYZE").execute(&mut *conn).await?;
+
+        let free_after: i64 =
+            sqlx::query("PRAGMA freelist_count").fetch_one(&mut *conn).await?.try_get(0)?;
+
+        Ok(free_before.saturating_sub(free_after).max(0) as u64)
+    }
+
+    // The table name is never user provided, only one of the stats tables.
+    async fn upsert_stats(
+        &self,
+        table: &'static str,
+        address: Address,
+        delta: &StatsDelta,
+        block_timestamp: u64,
+    ) -> Result<(), DbError> {
+        let address = format!("{address:x}");
+        // The price total is a U256 stored as text, so it can't be summed in SQL. Read and
+        // write it in a single transaction.
+        let mut tx = self.pool.begin().await?;
+        let row = sqlx::query(&format!(
+            "SELECT requests, fulfilled, total_price, total_latency_secs FROM {table}
+             WHERE address = $1"
+        ))
+        .bind(&address)
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        let (requests, fulfilled, total_price, total_latency_secs) = match row {
+            Some(row) => {
+                let total_price: String = row.try_get("total_price")?;
+                (
+                    row.try_get::<i64, _>("requests")? as u64,
+                    row.try_get::<i64, _>("fulfilled")? as u64,
+                    U256::from_str(&total_price).map_err(|_| DbError::BadValue(total_price))?,
+                    row.try_get::<i64, _>("total_latency_secs")? as u64,
+                )
+            }
+            None => (0, 0, U256::ZERO, 0),
+        };
+
+        sqlx::query(&format!(
+            "INSERT INTO {table} (
+                address,
+                requests,
+                fulfilled,
+                total_price,
+                total_latency_secs,
+                updated_at
+            ) VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (address) DO UPDATE SET
+                requests = EXCLUDED.requests,
+                fulfilled = EXCLUDED.fulfilled,
+                total_price = EXCLUDED.total_price,
+                total_latency_secs = EXCLUDED.total_latency_secs,
+                updated_at = EXCLUDED.updated_at"
+        ))
+        .bind(&address)
+        .bind((requests + delta.requests) as i64)
+        .bind((fulfilled + delta.fulfilled) as i64)
+        .bind(total_price.saturating_add(delta.total_price).to_string())
+        .bind((total_latency_secs + delta.total_latency_secs) as i64)
+        .bind(block_timestamp as i64)
+        .execute(&mut *tx)
+        .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn get_stats(
+        &self,
+        table: &'static str,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<AggregateStats>, DbError> {
+        let rows = sqlx::query(&format!(
+            "SELECT address, requests, fulfilled, total_price, total_latency_secs, updated_at
+             FROM {table} ORDER BY address LIMIT $1 OFFSET $2"
+        ))
+        .bind(limit as i64)
+        .bind(offset as i64)
+        .fetch_all(&self.pool)
+        .await?;
+
+        rows.iter()
+            .map(|row| {
+                let address: String = row.try_get("address")?;
+                let total_price: String = row.try_get("total_price")?;
+                Ok(AggregateStats {
+                    address: Address::from_str(&address).map_err(|_| DbError::BadValue(address))?,
+                    requests: row.try_get::<i64, _>("requests")? as u64,
+                    fulfilled: row.try_get::<i64, _>("fulfilled")? as u64,
+                    total_price: U256::from_str(&total_price)
+                        .map_err(|_| DbError::BadValue(total_price))?,
+                    total_latency_secs: row.try_get::<i64, _>("total_latency_secs")? as u64,
+                    updated_at: row.try_get::<i64, _>("updated_at")? as u64,
+                })
+            })
+            .collect()
+    }
 }
 
 #[async_trait]
@@ -231,6 +578,30 @@ impl IndexerDb for AnyDb {
         Ok(())
     }
 
+    async fn set_last_block_as_holder(
+        &self,
+        block_numb: u64,
+        holder: &str,
+    ) -> Result<bool, DbError> {
+        // A single conditional upsert, so that the lock cannot change hands between the check and
+        // the update.
+        let res = sqlx::query(
+            "INSERT INTO last_block (id, block)
+         SELECT $1, $2 WHERE EXISTS (
+            SELECT 1 FROM indexer_state WHERE id = $3 AND holder = $4
+         )
+         ON CONFLICT (id) DO UPDATE SET block = EXCLUDED.block",
+        )
+        .bind(SQL_BLOCK_KEY)
+        .bind(block_numb.to_string())
+        .bind(SQL_WRITER_LOCK_KEY)
+        .bind(holder)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
     async fn add_block(&self, block_numb: u64, block_timestamp: u64) -> Result<(), DbError> {
         sqlx::query(
             "INSERT INTO blocks (block_number, block_timestamp) VALUES ($1, $2)
@@ -292,7 +663,7 @@ impl IndexerDb for AnyDb {
         request_digest: B256,
         request: ProofRequest,
         metadata: &TxMetadata,
-    ) -> Result<(), DbError> {
+    ) -> Result<bool, DbError> {
         self.add_tx(metadata).await?;
         let predicate_type = match request.requirements.predicate.predicateType {
             PredicateType::DigestMatch => "DigestMatch",
@@ -306,7 +677,7 @@ impl IndexerDb for AnyDb {
             _ => return Err(DbError::BadTransaction("Invalid input type".to_string())),
         };
 
-        sqlx::query(
+        let result = sqlx::query(
             "INSERT INTO proof_requests (
                 request_digest,
                 request_id, 
@@ -353,7 +724,7 @@ impl IndexerDb for AnyDb {
         .bind(metadata.block_timestamp as i64)
         .execute(&self.pool)
         .await?;
-        Ok(())
+        Ok(result.rows_affected() > 0)
     }
 
     async fn add_assessor_receipt(
@@ -460,9 +831,9 @@ impl IndexerDb for AnyDb {
         request_id: U256,
         prover_address: Address,
         metadata: &TxMetadata,
-    ) -> Result<(), DbError> {
+    ) -> Result<bool, DbError> {
         self.add_tx(metadata).await?;
-        sqlx::query(
+        let result = sqlx::query(
             "INSERT INTO request_locked_events (
                 request_digest,
                 request_id, 
@@ -482,7 +853,7 @@ impl IndexerDb for AnyDb {
         .execute(&self.pool)
         .await?;
 
-        Ok(())
+        Ok(result.rows_affected() > 0)
     }
 
     async fn add_proof_delivered_event(
@@ -518,9 +889,9 @@ impl IndexerDb for AnyDb {
         request_digest: B256,
         request_id: U256,
         metadata: &TxMetadata,
-    ) -> Result<(), DbError> {
+    ) -> Result<bool, DbError> {
         self.add_tx(metadata).await?;
-        sqlx::query(
+        let result = sqlx::query(
             "INSERT INTO request_fulfilled_events (
                 request_digest,
                 request_id, 
@@ -538,7 +909,7 @@ impl IndexerDb for AnyDb {
         .execute(&self.pool)
         .await?;
 
-        Ok(())
+        Ok(result.rows_affected() > 0)
     }
 
     async fn add_prover_slashed_event(
@@ -735,6 +1106,184 @@ impl IndexerDb for AnyDb {
 
         Ok(())
     }
+
+    async fn get_fulfilled_request(
+        &self,
+        request_digest: B256,
+    ) -> Result<Option<FulfilledRequest>, DbError> {
+        let result = sqlx::query(
+            "SELECT
+                p.client_address,
+                p.min_price,
+                p.max_price,
+                p.lock_collateral,
+                p.bidding_start,
+                p.expires_at,
+                p.lock_end,
+                p.ramp_up_period,
+                p.block_timestamp,
+                l.prover_address AS lock_prover_address,
+                l.block_timestamp AS lock_timestamp,
+                f.prover_address
+            FROM proof_requests p
+            LEFT JOIN request_locked_events l ON l.request_digest = p.request_digest
+            LEFT JOIN fulfillments f ON f.request_digest = p.request_digest
+            WHERE p.request_digest = $1
+            ORDER BY f.block_number ASC
+            LIMIT 1",
+        )
+        .bind(format!("{request_digest:x}"))
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let Some(row) = result else {
+            return Ok(None);
+        };
+
+        let parse_address =
+            |value: String| Address::from_str(&value).map_err(|_| DbError::BadValue(value));
+        let parse_u256 =
+            |value: String| U256::from_str(&value).map_err(|_| DbError::BadValue(value));
+
+        let ramp_up_start = row.try_get::<i64, _>("bidding_start")? as u64;
+        // Offsets from the start of bidding, stored as absolute timestamps
+        let offset = |column: &str| -> Result<u32, DbError> {
+            let timestamp = row.try_get::<i64, _>(column)? as u64;
+            timestamp
+                .checked_sub(ramp_up_start)
+                .and_then(|offset| u32::try_from(offset).ok())
+                .ok_or_else(|| {
+                    DbError::BadValue(format!(
+                        "{column} {timestamp} out of range of bidding_start {ramp_up_start}"
+                    ))
+                })
+        };
+        let ramp_up_period = row.try_get::<i64, _>("ramp_up_period")?;
+        let offer = Offer {
+            minPrice: parse_u256(row.try_get("min_price")?)?,
+            maxPrice: parse_u256(row.try_get("max_price")?)?,
+            rampUpStart: ramp_up_start,
+            rampUpPeriod: u32::try_from(ramp_up_period)
+                .map_err(|_| DbError::BadValue(format!("ramp_up_period {ramp_up_period}")))?,
+            timeout: offset("expires_at")?,
+            lockTimeout: offset("lock_end")?,
+            lockCollateral: parse_u256(row.try_get("lock_collateral")?)?,
+        };
+        let locked = match row.try_get::<Option<String>, _>("lock_prover_address")? {
+            Some(prover) => {
+                Some((parse_address(prover)?, row.try_get::<i64, _>("lock_timestamp")? as u64))
+            }
+            None => None,
+        };
+
+        Ok(Some(FulfilledRequest {
+            client_address: parse_address(row.try_get("client_address")?)?,
+            offer,
+            submitted_at: row.try_get::<i64, _>("block_timestamp")? as u64,
+            locked,
+            prover_address: row
+                .try_get::<Option<String>, _>("prover_address")?
+                .map(parse_address)
+                .transpose()?,
+        }))
+    }
+
+    async fn upsert_requestor_stats(
+        &self,
+        address: Address,
+        delta: &StatsDelta,
+        block_timestamp: u64,
+    ) -> Result<(), DbError> {
+        self.upsert_stats("requestor_stats", address, delta, block_timestamp).await
+    }
+
+    async fn upsert_prover_stats(
+        &self,
+        address: Address,
+        delta: &StatsDelta,
+        block_timestamp: u64,
+    ) -> Result<(), DbError> {
+        self.upsert_stats("prover_stats", address, delta, block_timestamp).await
+    }
+
+    async fn get_requestor_stats(
+        &self,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<AggregateStats>, DbError> {
+        self.get_stats("requestor_stats", offset, limit).await
+    }
+
+    async fn get_prover_stats(
+        &self,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<AggregateStats>, DbError> {
+        self.get_stats("prover_stats", offset, limit).await
+    }
+
+    async fn vacuum(&self, allow_full: bool) -> Result<Option<u64>, DbError> {
+        if self.is_sqlite() {
+            return Ok(Some(self.vacuum_sqlite(allow_full).await?));
+        }
+        // VACUUM cannot run inside a transaction block, so use the simple query protocol.
+        sqlx::raw_sql("VACUUM (ANALYZE)").execute(&self.pool).await?;
+        Ok(None)
+    }
+
+    async fn try_acquire_writer_lock(
+        &self,
+        holder: &str,
+        now: u64,
+        ttl_secs: u64,
+    ) -> Result<bool, DbError> {
+        // A single conditional upsert, so that two instances can never both acquire the lock.
+        let res = sqlx::query(
+            "INSERT INTO indexer_state (id, holder, heartbeat, expires_at) VALUES ($1, $2, $3, $4)
+         ON CONFLICT (id) DO UPDATE SET
+            holder = EXCLUDED.holder,
+            heartbeat = EXCLUDED.heartbeat,
+            expires_at = EXCLUDED.expires_at
+         WHERE indexer_state.holder = EXCLUDED.holder
+            OR indexer_state.expires_at < EXCLUDED.heartbeat",
+        )
+        .bind(SQL_WRITER_LOCK_KEY)
+        .bind(holder)
+        .bind(now as i64)
+        .bind(now.saturating_add(ttl_secs) as i64)
+        .execute(&self.pool)
+        .await?;
+
+        Ok(res.rows_affected() > 0)
+    }
+
+    async fn release_writer_lock(&self, holder: &str) -> Result<(), DbError> {
+        sqlx::query("DELETE FROM indexer_state WHERE id = $1 AND holder = $2")
+            .bind(SQL_WRITER_LOCK_KEY)
+            .bind(holder)
+            .execute(&self.pool)
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_writer_lock(&self) -> Result<Option<WriterLock>, DbError> {
+        let res =
+            sqlx::query("SELECT holder, heartbeat, expires_at FROM indexer_state WHERE id = $1")
+                .bind(SQL_WRITER_LOCK_KEY)
+                .fetch_optional(&self.pool)
+                .await?;
+
+        let Some(row) = res else {
+            return Ok(None);
+        };
+
+        Ok(Some(WriterLock {
+            holder: row.try_get("holder")?,
+            heartbeat: row.try_get::<i64, _>("heartbeat")? as u64,
+            expires_at: row.try_get::<i64, _>("expires_at")? as u64,
+        }))
+    }
 }
 
 #[cfg(test)]
@@ -785,6 +1334,87 @@ mod tests {
         assert_eq!(block_numb, db_block);
     }
 
+    #[tokio::test]
+    async fn test_validate_migrations_sqlite() {
+        let test_db = TestDb::new().await.unwrap();
+        validate_migrations(&test_db.db_url).await.unwrap();
+        validate_migrations("sqlite::memory:").await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "Requires a Postgres instance at DATABASE_URL_PG"]
+    async fn test_validate_migrations_postgres() {
+        // e.g. `docker run -e POSTGRES_PASSWORD=pg -p 5432:5432 postgres` with
+        // DATABASE_URL_PG=postgres://postgres:pg@localhost:5432/postgres
+        let database_url = std::env::var("DATABASE_URL_PG").expect("DATABASE_URL_PG must be set");
+        validate_migrations(&database_url).await.unwrap();
+
+        // The temporary schema was rolled back, so the check is repeatable.
+        validate_migrations(&database_url).await.unwrap();
+        let pool = AnyPool::connect(&database_url).await.unwrap();
+        let schemas: i64 = sqlx::query(
+            "SELECT COUNT(*) FROM information_schema.schemata
+             WHERE schema_name LIKE 'indexer_migration_check_%'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap()
+        .try_get(0)
+        .unwrap();
+        assert_eq!(schemas, 0);
+    }
+
+    #[tokio::test]
+    async fn test_writer_lock() {
+        let test_db = TestDb::new().await.unwrap();
+        let db: DbObj = test_db.db;
+        assert_eq!(db.get_writer_lock().await.unwrap(), None);
+
+        assert!(db.try_acquire_writer_lock("a", 100, 60).await.unwrap());
+        // Another holder cannot take over a live lock, but the holder can renew it
+        assert!(!db.try_acquire_writer_lock("b", 120, 60).await.unwrap());
+        assert!(db.try_acquire_writer_lock("a", 150, 60).await.unwrap());
+        assert_eq!(
+            db.get_writer_lock().await.unwrap(),
+            Some(WriterLock { holder: "a".to_string(), heartbeat: 150, expires_at: 210 })
+        );
+
+        // A stale lock is taken over once it expires
+        assert!(!db.try_acquire_writer_lock("b", 210, 60).await.unwrap());
+        assert!(db.try_acquire_writer_lock("b", 211, 60).await.unwrap());
+        assert!(!db.try_acquire_writer_lock("a", 212, 60).await.unwrap());
+        assert_eq!(db.get_writer_lock().await.unwrap().unwrap().holder, "b");
+
+        // Only the holder can release the lock
+        db.release_writer_lock("a").await.unwrap();
+        assert!(db.get_writer_lock().await.unwrap().is_some());
+        db.release_writer_lock("b").await.unwrap();
+        assert_eq!(db.get_writer_lock().await.unwrap(), None);
+        assert!(db.try_acquire_writer_lock("a", 213, 60).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_set_last_block_as_holder() {
+        let test_db = TestDb::new().await.unwrap();
+        let db: DbObj = test_db.db;
+
+        // Without a lock, nobody can set the block
+        assert!(!db.set_last_block_as_holder(10, "a").await.unwrap());
+        assert_eq!(db.get_last_block().await.unwrap(), None);
+
+        assert!(db.try_acquire_writer_lock("a", 100, 60).await.unwrap());
+        assert!(db.set_last_block_as_holder(10, "a").await.unwrap());
+        assert!(db.set_last_block_as_holder(11, "a").await.unwrap());
+        assert_eq!(db.get_last_block().await.unwrap(), Some(11));
+
+        // Once the lock is taken over, the previous holder can no longer set the block
+        assert!(db.try_acquire_writer_lock("b", 200, 60).await.unwrap());
+        assert!(!db.set_last_block_as_holder(12, "a").await.unwrap());
+        assert_eq!(db.get_last_block().await.unwrap(), Some(11));
+        assert!(db.set_last_block_as_holder(12, "b").await.unwrap());
+        assert_eq!(db.get_last_block().await.unwrap(), Some(12));
+    }
+
     #[tokio::test]
     async fn test_transactions() {
         let test_db = TestDb::new().await.unwrap();
@@ -1053,4 +1683,151 @@ mod tests {
             .unwrap();
         assert_eq!(result.get::<Vec<u8>, _>("error_data"), error_data);
     }
+
+    #[tokio::test]
+    async fn test_stats() {
+        let test_db = TestDb::new().await.unwrap();
+        let db: DbObj = test_db.db;
+
+        let requestor = Address::from([1; 20]);
+        let other = Address::from([2; 20]);
+        let submitted = StatsDelta { requests: 1, ..Default::default() };
+        let fulfilled = StatsDelta {
+            requests: 0,
+            fulfilled: 1,
+            total_price: U256::from(100),
+            total_latency_secs: 30,
+        };
+
+        db.upsert_requestor_stats(requestor, &submitted, 10).await.unwrap();
+        db.upsert_requestor_stats(requestor, &submitted, 11).await.unwrap();
+        db.upsert_requestor_stats(requestor, &fulfilled, 12).await.unwrap();
+        db.upsert_requestor_stats(other, &submitted, 13).await.unwrap();
+
+        let stats = db.get_requestor_stats(0, 10).await.unwrap();
+        assert_eq!(
+            stats,
+            vec![
+                AggregateStats {
+                    address: requestor,
+                    requests: 2,
+                    fulfilled: 1,
+                    total_price: U256::from(100),
+                    total_latency_secs: 30,
+                    updated_at: 12,
+                },
+                AggregateStats {
+                    address: other,
+                    requests: 1,
+                    fulfilled: 0,
+                    total_price: U256::ZERO,
+                    total_latency_secs: 0,
+                    updated_at: 13,
+                },
+            ]
+        );
+
+        // Pages are ordered by address.
+        let page = db.get_requestor_stats(1, 10).await.unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].address, other);
+
+        // Requestor and prover statistics are kept separately.
+        assert!(db.get_prover_stats(0, 10).await.unwrap().is_empty());
+        db.upsert_prover_stats(other, &fulfilled, 14).await.unwrap();
+        let stats = db.get_prover_stats(0, 10).await.unwrap();
+        assert_eq!(stats.len(), 1);
+        assert_eq!(stats[0].total_price, U256::from(100));
+    }
+
+    #[tokio::test]
+    async fn test_get_fulfilled_request() {
+        let test_db = TestDb::new().await.unwrap();
+        let db: DbObj = test_db.db;
+
+        let client = Address::from([1; 20]);
+        let prover = Address::from([2; 20]);
+        let request_digest = B256::from([3; 32]);
+        let request = generate_request(0, &client);
+        let submitted = TxMetadata::new(B256::from([4; 32]), client, 100, 1000);
+        let locked = TxMetadata::new(B256::from([5; 32]), prover, 101, 1010);
+        let delivered = TxMetadata::new(B256::from([6; 32]), prover, 102, 1020);
+
+        assert!(db.get_fulfilled_request(request_digest).await.unwrap().is_none());
+        assert!(db.add_proof_request(request_digest, request.clone(), &submitted).await.unwrap());
+        assert!(!db.add_proof_request(request_digest, request.clone(), &submitted).await.unwrap());
+
+        let fulfilled = db.get_fulfilled_request(request_digest).await.unwrap().unwrap();
+        assert_eq!(fulfilled.client_address, client);
+        assert_eq!(fulfilled.offer, request.offer);
+        assert_eq!(fulfilled.submitted_at, 1000);
+        assert_eq!(fulfilled.locked, None);
+        assert_eq!(fulfilled.prover_address, None);
+
+        db.add_request_locked_event(request_digest, request.id, prover, &locked).await.unwrap();
+        let fill = Fulfillment {
+            requestDigest: request_digest,
+            id: request.id,
+            claimDigest: B256::ZERO,
+            fulfillmentData: Bytes::default(),
+            fulfillmentDataType: FulfillmentDataType::None,
+            seal: Bytes::default(),
+        };
+        db.add_fulfillment(fill, prover, &delivered).await.unwrap();
+
+        let fulfilled = db.get_fulfilled_request(request_digest).await.unwrap().unwrap();
+        assert_eq!(fulfilled.locked, Some((prover, 1010)));
+        assert_eq!(fulfilled.prover_address, Some(prover));
+
+        // A malformed row expiring before bidding starts is reported rather than wrapped
+        sqlx::query("UPDATE proof_requests SET expires_at = bidding_start - 1")
+            .execute(&test_db.pool)
+            .await
+            .unwrap();
+        let err = db.get_fulfilled_request(request_digest).await.unwrap_err();
+        assert!(matches!(err, DbError::BadValue(value) if value.starts_with("expires_at")));
+    }
+
+    #[tokio::test]
+    async fn test_vacuum() {
+        let test_db = TestDb::new().await.unwrap();
+        let db: DbObj = test_db.db;
+
+        for i in 0..2000u64 {
+            let metadata = TxMetadata::new(B256::from(U256::from(i)), Address::ZERO, i, i);
+            db.add_tx(&metadata).await.unwrap();
+        }
+        sqlx::query("DELETE FROM transactions").execute(&test_db.pool).await.unwrap();
+
+        async fn free_pages(pool: &AnyPool) -> i64 {
+            sqlx::query("PRAGMA freelist_count").fetch_one(pool).await.unwrap().get(0)
+        }
+        assert!(free_pages(&test_db.pool).await > 0);
+        async fn auto_vacuum(pool: &AnyPool) -> i64 {
+            sqlx::query("PRAGMA auto_vacuum").fetch_one(pool).await.unwrap().get(0)
+        }
+
+        // Without a full vacuum allowed, the free pages are not reclaimed.
+        db.vacuum(false).await.unwrap();
+        assert!(free_pages(&test_db.pool).await > 0);
+        assert_ne!(auto_vacuum(&test_db.pool).await, SQLITE_AUTO_VACUUM_INCREMENTAL);
+
+        // The first run with a full vacuum allowed switches it to incremental auto-vacuum.
+        let reclaimed = db.vacuum(true).await.unwrap().unwrap();
+        assert!(reclaimed > 0);
+        assert_eq!(free_pages(&test_db.pool).await, 0);
+        assert_eq!(auto_vacuum(&test_db.pool).await, SQLITE_AUTO_VACUUM_INCREMENTAL);
+
+        // Subsequent runs reclaim free pages incrementally.
+        for i in 0..2000u64 {
+            let metadata = TxMetadata::new(B256::from(U256::from(i)), Address::ZERO, i, i);
+            db.add_tx(&metadata).await.unwrap();
+        }
+        sqlx::query("DELETE FROM transactions").execute(&test_db.pool).await.unwrap();
+        assert!(free_pages(&test_db.pool).await > 0);
+
+        let reclaimed = db.vacuum(false).await.unwrap().unwrap();
+        assert!(reclaimed > 0);
+        assert_eq!(free_pages(&test_db.pool).await, 0);
+    }
 }
@@ -12,10 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::{cmp::min, collections::HashMap, sync::Arc};
+use std::{
+    cmp::min,
+    collections::HashMap,
+    sync::Arc,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use ::boundless_market::contracts::{
     boundless_market::{BoundlessMarketService, MarketError},
+    token::IERC20,
     EIP712DomainSaltless,
 };
 use alloy::{
@@ -31,12 +37,12 @@ use alloy::{
     transports::{RpcError, TransportErrorKind},
 };
 use anyhow::{anyhow, Context};
-use db::{AnyDb, DbError, DbObj, TxMetadata};
+use db::{AnyDb, DbError, DbObj, StatsDelta, TxMetadata};
 use thiserror::Error;
-use tokio::time::Duration;
+use tokio::time::{Duration, Instant};
 use url::Url;
 
-mod db;
+pub mod db;
 pub mod test_utils;
 
 const MAX_BATCH_SIZE: u64 = 500;
@@ -65,6 +71,30 @@ pub enum ServiceError {
 
     #[error("Request not expired")]
     RequestNotExpired,
+
+    #[error("{name} address {address} does not respond as expected: {reason}")]
+    InvalidContractAddress { name: &'static str, address: Address, reason: String },
+
+    #[error("RPC endpoint is for chain ID {actual}, but chain ID {expected} is configured")]
+    ChainIdMismatch { expected: u64, actual: u64 },
+
+    #[error("Writer lock is held by indexer instance {0}")]
+    WriterLockHeld(String),
+
+    #[error("Writer lock was taken over by another instance during the pass")]
+    WriterLockLost,
+}
+
+/// What an indexer does at the start of a pass when another instance holds the writer lock.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, clap::ValueEnum)]
+pub enum WriterLockPolicy {
+    /// Wait until the lock is released or expires.
+    #[default]
+    Wait,
+    /// Skip the pass, and try again at the next interval.
+    Skip,
+    /// Stop the indexer with an error.
+    Exit,
 }
 
 #[derive(Clone)]
@@ -81,6 +111,26 @@ pub struct IndexerService<P> {
 pub struct IndexerServiceConfig {
     pub interval: Duration,
     pub retries: u32,
+    /// Interval between database maintenance runs. Zero disables maintenance.
+    pub vacuum_interval: Duration,
+    /// Allow maintenance to run a one-time full `VACUUM` to switch a SQLite database to
+    /// incremental auto-vacuum. The full vacuum locks the database while it rebuilds the file.
+    pub sqlite_full_vacuum: bool,
+    /// Chain ID the RPC endpoint is expected to serve, if any.
+    pub chain_id: Option<u64>,
+    /// Skip probing the configured contracts on startup, for deployments with non-standard
+    /// contracts.
+    pub skip_address_validation: bool,
+    /// Identity of this instance, recorded as the holder of the writer lock.
+    pub instance_id: String,
+    /// Time after its last renewal at which the writer lock of a crashed instance expires. Should
+    /// exceed the duration of the longest pass, as a pass is discarded if another instance takes
+    /// over the lock before it completes.
+    pub lock_ttl: Duration,
+    /// What to do when another instance holds the writer lock.
+    pub lock_policy: WriterLockPolicy,
+    /// Write without holding the writer lock, for recovering from a lock that can't be released.
+    pub disable_writer_lock: bool,
 }
 
 impl IndexerService<ProviderWallet> {
@@ -98,6 +148,11 @@ impl IndexerService<ProviderWallet> {
             .connect_http(rpc_url);
         let boundless_market =
             BoundlessMarketService::new(boundless_market_address, provider.clone(), caller);
+        if config.skip_address_validation {
+            tracing::warn!("Skipping validation of the configured contract addresses");
+        } else {
+            validate_contracts(&boundless_market, config.chain_id).await?;
+        }
         let db: DbObj = Arc::new(AnyDb::new(db_conn).await?);
         let domain = boundless_market.eip712_domain().await?;
         let cache = HashMap::new();
@@ -106,45 +161,143 @@ impl IndexerService<ProviderWallet> {
     }
 }
 
+/// Check that the RPC endpoint serves the expected chain, and that the BoundlessMarket and its
+/// collateral token respond to calls unique to their interfaces.
+///
+/// Catches misconfigured addresses on startup, rather than silently indexing nothing.
+pub async fn validate_contracts<P: Provider<Ethereum>>(
+    boundless_market: &BoundlessMarketService<P>,
+    expected_chain_id: Option<u64>,
+) -> Result<(), ServiceError> {
+    let provider = boundless_market.instance().provider();
+    if let Some(expected) = expected_chain_id {
+        let actual = provider.get_chain_id().await?;
+        if actual != expected {
+            return Err(ServiceError::ChainIdMismatch { expected, actual });
+        }
+    }
+
+    let market_address = *boundless_market.instance().address();
+    let invalid_market = |reason: String| ServiceError::InvalidContractAddress {
+        name: "BoundlessMarket",
+        address: market_address,
+        reason,
+    };
+    if provider.get_code_at(market_address).await?.is_empty() {
+        return Err(invalid_market("no contract is deployed at the address".to_string()));
+    }
+    boundless_market
+        .image_info()
+        .await
+        .map_err(|err| invalid_market(format!("imageInfo call failed: {err:#}")))?;
+    let collateral_address = boundless_market
+        .collateral_token_address()
+        .await
+        .map_err(|err| invalid_market(format!("COLLATERAL_TOKEN_CONTRACT call failed: {err}")))?;
+
+    let invalid_collateral = |reason: String| ServiceError::InvalidContractAddress {
+        name: "collateral token",
+        address: collateral_address,
+        reason,
+    };
+    if provider.get_code_at(collateral_address).await?.is_empty() {
+        return Err(invalid_collateral("no contract is deployed at the address".to_string()));
+    }
+    IERC20::new(collateral_address, provider)
+        .decimals()
+        .call()
+        .await
+        .map_err(|err| invalid_collateral(format!("decimals call failed: {err}")))?;
+
+    tracing::debug!(
+        "Validated BoundlessMarket at {market_address} and collateral token at {collateral_address}"
+    );
+    Ok(())
+}
+
 impl<P> IndexerService<P>
 where
     P: Provider<Ethereum> + 'static + Clone,
 {
     pub async fn run(&mut self, starting_block: Option<u64>) -> Result<(), ServiceError> {
+        if self.config.disable_writer_lock {
+            tracing::warn!(
+                "Writer lock disabled, other instances writing to the database may corrupt it"
+            );
+            return self.run_passes(starting_block).await;
+        }
+        let result = self.run_passes(starting_block).await;
+        if let Err(e) = self.db.release_writer_lock(&self.config.instance_id).await {
+            tracing::warn!("Failed to release the writer lock: {:?}", e);
+        }
+        result
+    }
+
+    async fn run_passes(&mut self, starting_block: Option<u64>) -> Result<(), ServiceError> {
         let mut interval = tokio::time::interval(self.config.interval);
 
-        let mut from_block: u64 = self.starting_block(starting_block).await?;
-        tracing::info!("Starting indexer at block {}", from_block);
+        // Resolved whenever the writer lock is (re)acquired, as another instance may have
+        // indexed blocks while this one did not hold it.
+        let mut from_block: Option<u64> = None;
 
+        let mut last_vacuum = Instant::now();
         let mut attempt = 0;
         loop {
             interval.tick().await;
 
+            if !self.acquire_writer_lock().await? {
+                from_block = None;
+                continue;
+            }
+            let from = match from_block {
+                Some(block) => block,
+                None => {
+                    let block = self.starting_block(starting_block).await?;
+                    tracing::info!("Indexing as {} from block {}", self.config.instance_id, block);
+                    from_block = Some(block);
+                    block
+                }
+            };
+
+            // Maintenance runs between passes, so it never overlaps with indexing.
+            if !self.config.vacuum_interval.is_zero()
+                && last_vacuum.elapsed() >= self.config.vacuum_interval
+            {
+                // Maintenance failures are not fatal; the next run will retry.
+                if let Err(e) = self.run_maintenance().await {
+                    tracing::warn!("Database maintenance failed: {:?}", e);
+                }
+                last_vacuum = Instant::now();
+            }
+
             match self.current_block().await {
                 Ok(to_block) => {
-                    if to_block < from_block {
+                    if to_block < from {
                         continue;
                     }
 
                     // cap to at most 500 blocks per batch
-                    let batch_end = min(to_block, from_block.saturating_add(MAX_BATCH_SIZE));
+                    let batch_end = min(to_block, from.saturating_add(MAX_BATCH_SIZE));
 
-                    tracing::info!("Processing blocks from {} to {}", from_block, batch_end);
+                    tracing::info!("Processing blocks from {} to {}", from, batch_end);
 
-                    match self.process_blocks(from_block, batch_end).await {
+                    match self.process_blocks(from, batch_end).await {
                         Ok(_) => {
                             attempt = 0;
-                            from_block = batch_end + 1;
+                            from_block = Some(batch_end + 1);
                         }
                         Err(e) => match e {
                             // Irrecoverable errors
                             ServiceError::DatabaseError(_)
                             | ServiceError::MaxRetries
                             | ServiceError::RequestNotExpired
-                            | ServiceError::Error(_) => {
+                            | ServiceError::Error(_)
+                            | ServiceError::InvalidContractAddress { .. }
+                            | ServiceError::ChainIdMismatch { .. }
+                            | ServiceError::WriterLockHeld(_) => {
                                 tracing::error!(
                                     "Failed to process blocks from {} to {}: {:?}",
-                                    from_block,
+                                    from,
                                     batch_end,
                                     e
                                 );
@@ -160,7 +313,7 @@ where
                                     std::time::Duration::from_secs(2u64.pow(attempt - 1).min(120));
                                 tracing::warn!(
                                     "Failed to process blocks from {} to {}: {:?}, attempt number {}, retrying in {}s",
-                                    from_block,
+                                    from,
                                     batch_end,
                                     e,
                                     attempt,
@@ -168,6 +321,17 @@ where
                                 );
                                 tokio::time::sleep(delay).await;
                             }
+                            // Another instance took over while the pass ran, so its progress
+                            // was not recorded. Resume from the recorded block once the lock is
+                            // back.
+                            ServiceError::WriterLockLost => {
+                                tracing::warn!(
+                                    "Writer lock was taken over while processing blocks from {} to {}, discarding the pass",
+                                    from,
+                                    batch_end
+                                );
+                                from_block = None;
+                            }
                         },
                     }
                 }
@@ -187,6 +351,77 @@ where
         }
     }
 
+    /// Acquire or renew the writer lock of the database, so that only one instance writes to it.
+    ///
+    /// Returns false if the pass should be skipped because another instance holds the lock.
+    /// Depending on the configured [WriterLockPolicy], waits for the lock or fails instead.
+    pub async fn acquire_writer_lock(&self) -> Result<bool, ServiceError> {
+        if self.config.disable_writer_lock {
+            return Ok(true);
+        }
+        loop {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .context("Time went backwards")?
+                .as_secs();
+            if self
+                .db
+                .try_acquire_writer_lock(
+                    &self.config.instance_id,
+                    now,
+                    self.config.lock_ttl.as_secs(),
+                )
+                .await?
+            {
+                return Ok(true);
+            }
+
+            let Some(lock) = self.db.get_writer_lock().await? else {
+                // Released since the acquisition attempt
+                continue;
+            };
+            match self.config.lock_policy {
+                WriterLockPolicy::Exit => return Err(ServiceError::WriterLockHeld(lock.holder)),
+                WriterLockPolicy::Skip => {
+                    tracing::info!(
+                        "Writer lock is held by {} (last heartbeat at {}), skipping pass",
+                        lock.holder,
+                        lock.heartbeat
+                    );
+                    return Ok(false);
+                }
+                WriterLockPolicy::Wait => {
+                    let wait = lock.expires_at.saturating_sub(now).clamp(1, 60);
+                    tracing::info!(
+                        "Writer lock is held by {} (last heartbeat at {}), retrying in {}s",
+                        lock.holder,
+                        lock.heartbeat,
+                        wait
+                    );
+                    tokio::time::sleep(Duration::from_secs(wait)).await;
+                }
+            }
+        }
+    }
+
+    /// Reclaim free pages and refresh the query planner statistics of the database.
+    pub async fn run_maintenance(&self) -> Result<(), ServiceError> {
+        tracing::info!("Starting database maintenance");
+        let start = Instant::now();
+        let reclaimed = self.db.vacuum(self.config.sqlite_full_vacuum).await?;
+        let duration = start.elapsed();
+        match reclaimed {
+            Some(pages) => tracing::info!(
+                "Database maintenance completed in {}ms, reclaimed {} pages",
+                duration.as_millis(),
+                pages
+            ),
+            None => tracing::info!("Database maintenance completed in {}ms", duration.as_millis()),
+        }
+
+        Ok(())
+    }
+
     async fn process_blocks(&mut self, from: u64, to: u64) -> Result<(), ServiceError> {
         self.process_request_submitted_events(from, to).await?;
         self.process_locked_events(from, to).await?;
@@ -209,8 +444,18 @@ where
         Ok(self.db.get_last_block().await?)
     }
 
+    /// Record the last processed block, as long as this instance still holds the writer lock.
+    ///
+    /// Fails with [ServiceError::WriterLockLost] if the lock expired during the pass and another
+    /// instance took it over, in which case the block is not recorded.
     async fn update_last_processed_block(&self, block_number: u64) -> Result<(), ServiceError> {
-        Ok(self.db.set_last_block(block_number).await?)
+        if self.config.disable_writer_lock {
+            return Ok(self.db.set_last_block(block_number).await?);
+        }
+        if !self.db.set_last_block_as_holder(block_number, &self.config.instance_id).await? {
+            return Err(ServiceError::WriterLockLost);
+        }
+        Ok(())
     }
 
     async fn process_request_submitted_events(
@@ -253,7 +498,10 @@ where
                     event.requestId
                 ))?;
 
-            self.db.add_proof_request(request_digest, request, &metadata).await?;
+            let client_address = request.client_address();
+            if self.db.add_proof_request(request_digest, request, &metadata).await? {
+                self.record_submitted_request(client_address, &metadata).await?;
+            }
             self.db.add_request_submitted_event(request_digest, event.requestId, &metadata).await?;
         }
 
@@ -304,11 +552,19 @@ where
             let request_exists = self.db.has_proof_request(request_digest).await?;
             if !request_exists {
                 tracing::debug!("Detected request locked for unseen request. Likely submitted off-chain: 0x{:x}", event.requestId);
-                self.db.add_proof_request(request_digest, request, &metadata).await?;
+                let client_address = request.client_address();
+                if self.db.add_proof_request(request_digest, request, &metadata).await? {
+                    self.record_submitted_request(client_address, &metadata).await?;
+                }
             }
-            self.db
+            let inserted = self
+                .db
                 .add_request_locked_event(request_digest, event.requestId, event.prover, &metadata)
                 .await?;
+            if inserted {
+                let delta = StatsDelta { requests: 1, ..Default::default() };
+                self.db.upsert_prover_stats(event.prover, &delta, metadata.block_timestamp).await?;
+            }
         }
 
         Ok(())
@@ -386,14 +642,72 @@ where
                 metadata.block_number,
                 metadata.block_timestamp
             );
-            self.db
+            let inserted = self
+                .db
                 .add_request_fulfilled_event(event.requestDigest, event.requestId, &metadata)
                 .await?;
+            // Only count each fulfillment once, even if the blocks are processed again.
+            if inserted {
+                self.record_fulfillment(event.requestDigest, &metadata).await?;
+            }
         }
 
         Ok(())
     }
 
+    async fn record_submitted_request(
+        &self,
+        client_address: Address,
+        metadata: &TxMetadata,
+    ) -> Result<(), ServiceError> {
+        let delta = StatsDelta { requests: 1, ..Default::default() };
+        self.db.upsert_requestor_stats(client_address, &delta, metadata.block_timestamp).await?;
+        Ok(())
+    }
+
+    /// Update the requestor and prover statistics with a newly observed fulfillment.
+    ///
+    /// The price is the offer price when the request was locked, or when it was fulfilled if it
+    /// was never locked. The latency is the time from submission to fulfillment.
+    async fn record_fulfillment(
+        &self,
+        request_digest: B256,
+        metadata: &TxMetadata,
+    ) -> Result<(), ServiceError> {
+        let Some(request) = self.db.get_fulfilled_request(request_digest).await? else {
+            tracing::warn!(
+                "Missing proof request for fulfilled request digest 0x{:x}, skipping statistics",
+                request_digest
+            );
+            return Ok(());
+        };
+
+        let priced_at =
+            request.locked.map(|(_, locked_at)| locked_at).unwrap_or(metadata.block_timestamp);
+        let delta = StatsDelta {
+            requests: 0,
+            fulfilled: 1,
+            total_price: request.offer.price_at(priced_at)?,
+            total_latency_secs: metadata.block_timestamp.saturating_sub(request.submitted_at),
+        };
+        self.db
+            .upsert_requestor_stats(request.client_address, &delta, metadata.block_timestamp)
+            .await?;
+
+        let Some(prover) = request.prover_address else {
+            tracing::warn!(
+                "Missing fulfillment for fulfilled request digest 0x{:x}, skipping prover statistics",
+                request_digest
+            );
+            return Ok(());
+        };
+        // Requests fulfilled without holding the lock were not counted when locked.
+        let held_lock = request.locked.is_some_and(|(locked_by, _)| locked_by == prover);
+        let delta = StatsDelta { requests: u64::from(!held_lock), ..delta };
+        self.db.upsert_prover_stats(prover, &delta, metadata.block_timestamp).await?;
+        Ok(())
+    }
+
     async fn process_slashed_events(
         &mut self,
         from_block: u64,
@@ -713,6 +1027,8 @@ fn find_starting_block(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::TestDb;
+    use tracing_test::traced_test;
 
     #[tokio::test]
     async fn test_find_starting_block() {
@@ -752,4 +1068,112 @@ mod tests {
         let block = find_starting_block(starting_block, last_processed, current_block);
         assert_eq!(block, 10);
     }
+
+    fn test_service(
+        db: DbObj,
+        instance_id: &str,
+        lock_ttl: Duration,
+        lock_policy: WriterLockPolicy,
+    ) -> IndexerService<ProviderWallet> {
+        let provider = ProviderBuilder::new()
+            .disable_recommended_fillers()
+            .filler(ChainIdFiller::default())
+            .connect_http("http://localhost:8545".parse().unwrap());
+        IndexerService {
+            boundless_market: BoundlessMarketService::new(Address::ZERO, provider, Address::ZERO),
+            db,
+            domain: EIP712DomainSaltless {
+                name: "IBoundlessMarket".into(),
+                version: "1".into(),
+                chain_id: 1,
+                verifying_contract: Address::ZERO,
+            },
+            config: IndexerServiceConfig {
+                interval: Duration::from_secs(1),
+                retries: 0,
+                vacuum_interval: Duration::from_secs(1),
+                sqlite_full_vacuum: false,
+                chain_id: None,
+                skip_address_validation: false,
+                instance_id: instance_id.to_string(),
+                lock_ttl,
+                lock_policy,
+                disable_writer_lock: false,
+            },
+            cache: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn test_single_writer_per_pass() {
+        let test_db = TestDb::new().await.unwrap();
+        let ttl = Duration::from_secs(1);
+        let first = test_service(test_db.get_db(), "first", ttl, WriterLockPolicy::Skip);
+        let second = test_service(test_db.get_db(), "second", ttl, WriterLockPolicy::Skip);
+
+        // Of two instances sharing a database, only the lock holder runs its pass
+        assert!(first.acquire_writer_lock().await.unwrap());
+        assert!(!second.acquire_writer_lock().await.unwrap());
+        assert!(logs_contain("Writer lock is held by first"));
+        assert!(first.acquire_writer_lock().await.unwrap());
+        assert_eq!(test_db.get_db().get_writer_lock().await.unwrap().unwrap().holder, "first");
+
+        let exiting = test_service(test_db.get_db(), "second", ttl, WriterLockPolicy::Exit);
+        assert!(matches!(
+            exiting.acquire_writer_lock().await,
+            Err(ServiceError::WriterLockHeld(holder)) if holder == "first"
+        ));
+
+        // Without renewals from the first instance, as if it crashed, its lock expires and a
+        // waiting instance takes over
+        let waiting = test_service(test_db.get_db(), "second", ttl, WriterLockPolicy::Wait);
+        let acquired = tokio::time::timeout(Duration::from_secs(10), waiting.acquire_writer_lock())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(acquired);
+        assert_eq!(test_db.get_db().get_writer_lock().await.unwrap().unwrap().holder, "second");
+        assert!(!first.acquire_writer_lock().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_lost_writer_lock_discards_pass() {
+        let test_db = TestDb::new().await.unwrap();
+        let ttl = Duration::from_secs(1);
+        let first = test_service(test_db.get_db(), "first", ttl, WriterLockPolicy::Skip);
+        let second = test_service(test_db.get_db(), "second", ttl, WriterLockPolicy::Wait);
+
+        assert!(first.acquire_writer_lock().await.unwrap());
+        first.update_last_processed_block(10).await.unwrap();
+
+        // The lock of the first instance expires during a long pass, and the second takes over
+        tokio::time::timeout(Duration::from_secs(10), second.acquire_writer_lock())
+            .await
+            .unwrap()
+            .unwrap();
+        second.update_last_processed_block(20).await.unwrap();
+
+        // The end of the pass of the first instance is not recorded
+        assert!(matches!(
+            first.update_last_processed_block(15).await,
+            Err(ServiceError::WriterLockLost)
+        ));
+        assert_eq!(test_db.get_db().get_last_block().await.unwrap(), Some(20));
+    }
+
+    #[tokio::test]
+    async fn test_disabled_writer_lock() {
+        let test_db = TestDb::new().await.unwrap();
+        let ttl = Duration::from_secs(300);
+        let holder = test_service(test_db.get_db(), "holder", ttl, WriterLockPolicy::Exit);
+        let mut unlocked = test_service(test_db.get_db(), "unlocked", ttl, WriterLockPolicy::Exit);
+        unlocked.config.disable_writer_lock = true;
+
+        assert!(holder.acquire_writer_lock().await.unwrap());
+        assert!(unlocked.acquire_writer_lock().await.unwrap());
+        // The lock is neither taken over nor renewed by the instance ignoring it
+        let lock = test_db.get_db().get_writer_lock().await.unwrap().unwrap();
+        assert_eq!(lock.holder, "holder");
+    }
 }
@@ -12,11 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use alloy::{primitives::Address, signers::local::PrivateKeySigner};
 use anyhow::{bail, Result};
-use boundless_indexer::{IndexerService, IndexerServiceConfig};
+use boundless_indexer::{
+    db::validate_migrations, IndexerService, IndexerServiceConfig, WriterLockPolicy,
+};
 use clap::Parser;
 use url::Url;
 
@@ -25,14 +27,19 @@ use url::Url;
 #[clap(author, version, about, long_about = None)]
 struct MainArgs {
     /// URL of the Ethereum RPC endpoint.
-    #[clap(short, long, env)]
-    rpc_url: Url,
+    #[clap(short, long, env, required_unless_present = "check_migrations")]
+    rpc_url: Option<Url>,
     /// Address of the BoundlessMarket contract.
-    #[clap(short, long, env)]
-    boundless_market_address: Address,
+    #[clap(short, long, env, required_unless_present = "check_migrations")]
+    boundless_market_address: Option<Address>,
     /// DB connection string.
     #[clap(long, env = "DATABASE_URL")]
     db: String,
+    /// Check that the migrations apply cleanly on the database backend and exit.
+    ///
+    /// The database itself is left unmodified.
+    #[clap(long)]
+    check_migrations: bool,
     /// Starting block number.
     #[clap(long)]
     start_block: Option<u64>,
@@ -42,6 +49,47 @@ struct MainArgs {
     /// Number of retries before quitting after an error.
     #[clap(long, default_value = "10")]
     retries: u32,
+    /// Interval in seconds between database maintenance runs (vacuum and analyze).
+    ///
+    /// Defaults to one week. Set to 0 to disable maintenance.
+    #[clap(long, default_value = "604800")]
+    vacuum_interval: u64,
+    /// Allow maintenance to switch a SQLite database to incremental auto-vacuum.
+    ///
+    /// Switching requires a one-time full VACUUM, which locks the database while it rebuilds the
+    /// file. Without this flag, maintenance of such a database only refreshes its statistics.
+    #[clap(long)]
+    sqlite_full_vacuum: bool,
+    /// Chain ID the RPC endpoint is expected to serve.
+    ///
+    /// If set, the indexer refuses to start when connected to a different chain.
+    #[clap(long, env)]
+    chain_id: Option<u64>,
+    /// Skip probing the configured contract addresses on startup.
+    ///
+    /// Only intended for deployments with non-standard contracts.
+    #[clap(long)]
+    skip_address_validation: bool,
+    /// Identity of this instance, recorded as the holder of the database writer lock.
+    ///
+    /// Defaults to an identifier unique to this process.
+    #[clap(long, env = "INDEXER_INSTANCE_ID")]
+    instance_id: Option<String>,
+    /// Seconds after its last renewal at which the writer lock of a crashed instance expires.
+    ///
+    /// Should exceed the duration of the longest indexing pass. A pass that outlasts it is
+    /// discarded if another instance takes over the lock in the meantime.
+    #[clap(long, default_value = "300")]
+    lock_ttl: u64,
+    /// What to do when another instance holds the database writer lock.
+    #[clap(long, value_enum, default_value_t = WriterLockPolicy::Wait)]
+    lock_policy: WriterLockPolicy,
+    /// Write to the database without holding the writer lock.
+    ///
+    /// Only intended for recovery; running several instances with this flag corrupts the
+    /// indexed data.
+    #[clap(long)]
+    no_lock: bool,
     /// Whether to log in JSON format.
     #[clap(long, env, default_value_t = false)]
     log_json: bool,
@@ -64,15 +112,40 @@ async fn main() -> Result<()> {
     }
 
     let args = MainArgs::parse();
+    if args.check_migrations {
+        if let Err(err) = validate_migrations(&args.db).await {
+            bail!("FATAL: Error checking migrations: {err}");
+        }
+        tracing::info!("All migrations applied cleanly");
+        return Ok(());
+    }
+    let (Some(rpc_url), Some(boundless_market_address)) =
+        (args.rpc_url.clone(), args.boundless_market_address)
+    else {
+        bail!("--rpc-url and --boundless-market-address are required");
+    };
+
+    let instance_id = args.instance_id.clone().unwrap_or_else(|| {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().subsec_nanos();
+        format!("indexer-{}-{nanos:08x}", std::process::id())
+    });
 
     let mut indexer_service = IndexerService::new(
-        args.rpc_url.clone(),
+        rpc_url,
         &PrivateKeySigner::random(),
-        args.boundless_market_address,
+        boundless_market_address,
         &args.db,
         IndexerServiceConfig {
             interval: Duration::from_secs(args.interval),
             retries: args.retries,
+            vacuum_interval: Duration::from_secs(args.vacuum_interval),
+            sqlite_full_vacuum: args.sqlite_full_vacuum,
+            chain_id: args.chain_id,
+            skip_address_validation: args.skip_address_validation,
+            instance_id,
+            lock_ttl: Duration::from_secs(args.lock_ttl),
+            lock_policy: args.lock_policy,
+            disable_writer_lock: args.no_lock,
         },
     )
     .await?;
@@ -17,12 +17,16 @@ use std::{process::Command, time::Duration};
 use alloy::{
     node_bindings::Anvil,
     primitives::{Address, Bytes, U256},
-    providers::Provider,
+    providers::{ext::AnvilApi, Provider},
     rpc::types::BlockNumberOrTag,
-    signers::Signer,
+    signers::{local::PrivateKeySigner, Signer},
 };
 use boundless_cli::{DefaultProver, OrderFulfilled};
-use boundless_indexer::test_utils::TestDb;
+use boundless_indexer::{
+    db::{DbObj, IndexerDb},
+    test_utils::TestDb,
+    IndexerService, IndexerServiceConfig, ServiceError, WriterLockPolicy,
+};
 use boundless_market::contracts::{
     boundless_market::FulfillmentTx, Offer, Predicate, ProofRequest, RequestId, RequestInput,
     Requirements,
@@ -385,3 +389,197 @@ async fn test_monitoring() {
 
     cli_process.kill().unwrap();
 }
+
+#[tokio::test]
+async fn test_startup_address_validation() {
+    let test_db = TestDb::new().await.unwrap();
+    let anvil = Anvil::new().spawn();
+    let ctx = create_test_ctx(&anvil).await.unwrap();
+    let config = |chain_id, skip_address_validation| IndexerServiceConfig {
+        interval: Duration::from_secs(1),
+        retries: 1,
+        vacuum_interval: Duration::ZERO,
+        sqlite_full_vacuum: false,
+        chain_id,
+        skip_address_validation,
+        instance_id: "test".to_string(),
+        lock_ttl: Duration::from_secs(300),
+        lock_policy: Default::default(),
+        disable_writer_lock: false,
+    };
+    let signer = PrivateKeySigner::random();
+    let new_service = |address, config| {
+        IndexerService::new(anvil.endpoint_url(), &signer, address, &test_db.db_url, config)
+    };
+
+    // A transposed address pointing at another contract is rejected, naming the address
+    let wrong_address = ctx.deployment.verifier_router_address.unwrap();
+    let err = new_service(wrong_address, config(None, false)).await.err().unwrap();
+    assert!(matches!(
+        err,
+        ServiceError::InvalidContractAddress { name: "BoundlessMarket", address, .. }
+            if address == wrong_address
+    ));
+    assert!(err.to_string().contains(&wrong_address.to_string()));
+
+    // So is an address without a contract
+    let err = new_service(Address::repeat_byte(0x42), config(None, false)).await.err().unwrap();
+    assert!(err.to_string().contains("no contract is deployed at the address"));
+
+    // The configured chain ID must match the RPC endpoint
+    let market_address = ctx.deployment.boundless_market_address;
+    let err =
+        new_service(market_address, config(Some(anvil.chain_id() + 1), false)).await.err().unwrap();
+    assert!(
+        matches!(err, ServiceError::ChainIdMismatch { actual, .. } if actual == anvil.chain_id())
+    );
+
+    new_service(market_address, config(Some(anvil.chain_id()), false)).await.unwrap();
+}
+
+fn lock_test_config(instance_id: &str, lock_policy: WriterLockPolicy) -> IndexerServiceConfig {
+    IndexerServiceConfig {
+        interval: Duration::from_millis(500),
+        retries: 1,
+        vacuum_interval: Duration::ZERO,
+        sqlite_full_vacuum: false,
+        chain_id: None,
+        skip_address_validation: false,
+        instance_id: instance_id.to_string(),
+        lock_ttl: Duration::from_secs(2),
+        lock_policy,
+        disable_writer_lock: false,
+    }
+}
+
+async fn writer_lock_holder(db: &DbObj) -> Option<String> {
+    db.get_writer_lock().await.unwrap().map(|lock| lock.holder)
+}
+
+async fn wait_for_holder(db: &DbObj, holder: &str) {
+    tokio::time::timeout(Duration::from_secs(30), async {
+        while writer_lock_holder(db).await.as_deref() != Some(holder) {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("timed out waiting for {holder} to hold the writer lock"));
+}
+
+/// Mine blocks until the last processed block advances past the given block.
+async fn wait_for_progress(db: &DbObj, provider: &impl Provider, after: Option<u64>) -> u64 {
+    tokio::time::timeout(Duration::from_secs(30), async {
+        loop {
+            provider.anvil_mine(Some(1), None).await.unwrap();
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            match db.get_last_block().await.unwrap() {
+                Some(block) if after.is_none_or(|after| block > after) => return block,
+                _ => {}
+            }
+        }
+    })
+    .await
+    .expect("timed out waiting for indexing to progress")
+}
+
+#[tokio::test]
+async fn test_single_writer() {
+    let test_db = TestDb::new().await.unwrap();
+    let db = test_db.get_db();
+    let anvil = Anvil::new().spawn();
+    let ctx = create_test_ctx(&anvil).await.unwrap();
+    let signer = PrivateKeySigner::random();
+    let new_service = |config| {
+        IndexerService::new(
+            anvil.endpoint_url(),
+            &signer,
+            ctx.deployment.boundless_market_address,
+            &test_db.db_url,
+            config,
+        )
+    };
+
+    let mut first = new_service(lock_test_config("first", WriterLockPolicy::Wait)).await.unwrap();
+    let first_task = tokio::spawn(async move { first.run(None).await });
+    wait_for_holder(&db, "first").await;
+    let indexed = wait_for_progress(&db, &ctx.customer_provider, None).await;
+
+    // While the first instance runs, it keeps the lock and indexes, and the second one waits
+    let mut second = new_service(lock_test_config("second", WriterLockPolicy::Wait)).await.unwrap();
+    let second_task = tokio::spawn(async move { second.run(None).await });
+    let indexed = wait_for_progress(&db, &ctx.customer_provider, Some(indexed)).await;
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    wait_for_progress(&db, &ctx.customer_provider, Some(indexed)).await;
+    assert_eq!(writer_lock_holder(&db).await.as_deref(), Some("first"));
+    assert!(!second_task.is_finished());
+
+    // Once the first instance stops without releasing the lock, as on a crash, the second takes
+    // over after the lease expires and indexing resumes from the last processed block
+    first_task.abort();
+    let indexed = db.get_last_block().await.unwrap();
+    wait_for_holder(&db, "second").await;
+    wait_for_progress(&db, &ctx.customer_provider, indexed).await;
+    assert!(!second_task.is_finished());
+    second_task.abort();
+}
+
+#[tokio::test]
+async fn test_writer_lock_policies() {
+    let test_db = TestDb::new().await.unwrap();
+    let db = test_db.get_db();
+    let anvil = Anvil::new().spawn();
+    let ctx = create_test_ctx(&anvil).await.unwrap();
+    let signer = PrivateKeySigner::random();
+    let new_service = |config| {
+        IndexerService::new(
+            anvil.endpoint_url(),
+            &signer,
+            ctx.deployment.boundless_market_address,
+            &test_db.db_url,
+            config,
+        )
+    };
+
+    let mut first = new_service(lock_test_config("first", WriterLockPolicy::Wait)).await.unwrap();
+    let first_task = tokio::spawn(async move { first.run(None).await });
+    wait_for_holder(&db, "first").await;
+    wait_for_progress(&db, &ctx.customer_provider, None).await;
+
+    // Under the exit policy, a second instance stops, naming the holder of the lock
+    let mut exiting =
+        new_service(lock_test_config("exiting", WriterLockPolicy::Exit)).await.unwrap();
+    let err = tokio::time::timeout(Duration::from_secs(10), exiting.run(None))
+        .await
+        .expect("exiting instance did not stop")
+        .unwrap_err();
+    assert!(matches!(err, ServiceError::WriterLockHeld(holder) if holder == "first"));
+
+    // Under the skip policy, it keeps running and skips its passes while the lock is held
+    let mut skipping =
+        new_service(lock_test_config("skipping", WriterLockPolicy::Skip)).await.unwrap();
+    let skipping_task = tokio::spawn(async move { skipping.run(None).await });
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    assert!(!skipping_task.is_finished());
+    assert_eq!(writer_lock_holder(&db).await.as_deref(), Some("first"));
+
+    // It takes over on a later pass, once the lease of the stopped holder expires
+    first_task.abort();
+    let _ = first_task.await;
+    let indexed = db.get_last_block().await.unwrap();
+    wait_for_holder(&db, "skipping").await;
+    wait_for_progress(&db, &ctx.customer_provider, indexed).await;
+    skipping_task.abort();
+    let _ = skipping_task.await;
+
+    // With the lock disabled, an instance indexes without taking or renewing the lock, even
+    // under the exit policy
+    let mut config = lock_test_config("unlocked", WriterLockPolicy::Exit);
+    config.disable_writer_lock = true;
+    let mut unlocked = new_service(config).await.unwrap();
+    let unlocked_task = tokio::spawn(async move { unlocked.run(None).await });
+    let indexed = db.get_last_block().await.unwrap();
+    wait_for_progress(&db, &ctx.customer_provider, indexed).await;
+    assert!(!unlocked_task.is_finished());
+    assert_eq!(writer_lock_holder(&db).await.as_deref(), Some("skipping"));
+    unlocked_task.abort();
+}
@@ -12,7 +12,8 @@ mod build_contracts {
     const ZKC_INTERFACE_FILES: [&str; 1] = ["IZKC.sol"];
 
     // Contracts to generate bytecode for (used for deployment in tests)
-    const BYTECODE_CONTRACTS: [&str; 2] = ["PovwAccounting", "PovwMint"];
+    const BYTECODE_CONTRACTS: [&str; 4] =
+        ["PovwAccounting", "PovwMint", "RiscZeroMockVerifier", "ERC1967Proxy"];
 
     // Output filename for the generated bytecode module
     const BYTECODE_MODULE: &str = "bytecode.rs";
@@ -131,6 +132,8 @@ mod build_contracts {
         match contract {
             "PovwAccounting" => "constructor(address verifier, address zkc, bytes32 logUpdaterId) {}\n        function initialize(address initialOwner) {}",
             "PovwMint" => "constructor(address verifier, address povwAccounting, bytes32 mintCalculatorId, address zkc, address zkcRewards) {}\n        function initialize(address initialOwner) {}",
+            "RiscZeroMockVerifier" => "constructor(bytes4 selector) {}",
+            "ERC1967Proxy" => "constructor(address implementation, bytes memory data) payable {}",
             _ => "",
         }
     }
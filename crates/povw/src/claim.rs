@@ -0,0 +1,728 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Use of this source code is governed by the Business Source License
+// as found in the LICENSE-BSL file.
+
+//! Orchestration of PoVW reward claims, from searching for the work log updates to claim through
+//! sending the mint transaction.
+//!
+//! The [claim_rewards] function does not log or print. Progress is reported through a callback
+//! taking [ClaimProgress] values, so callers can surface it as they see fit. The [report_claim]
+//! function plans the same claim without proving or sending it, and reports its expected size and
+//! cost.
+
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use alloy_contract::Event;
+use alloy_primitives::{Address, B256, U256};
+use alloy_provider::Provider;
+use alloy_sol_types::{SolEvent, SolValue};
+use anyhow::{bail, ensure, Context};
+use risc0_povw::PovwLogId;
+use risc0_zkvm::{Digest, Prover, ProverOpts};
+use url::Url;
+
+use crate::{
+    deployments::Deployment,
+    log_updater::IPovwAccounting::{self, EpochFinalized, IPovwAccountingInstance, WorkLogUpdated},
+    mint_calculator::{
+        prover::MintCalculatorProver, IPovwMint, Input, MintCalculatorJournal, MintCalculatorMint,
+        MintCalculatorUpdate, CHAIN_SPECS,
+    },
+};
+
+const HOUR: Duration = Duration::from_secs(60 * 60);
+
+/// Parameters of a PoVW reward claim.
+#[derive(Clone, Debug)]
+pub struct ClaimParams {
+    /// Work log IDs for the reward claim. The rewards for all of them are claimed with a single
+    /// mint proof and transaction.
+    pub log_ids: Vec<PovwLogId>,
+    /// Deployment of the PoVW and ZKC contracts.
+    pub deployment: Deployment,
+    /// URL for an Ethereum Beacon chain API, used to build historical data access proofs.
+    pub beacon_api_url: Option<Url>,
+    /// Maximum number of days back to search for work log update events.
+    pub days: u32,
+    /// Chunk size to use when querying the RPC node for events using `eth_getLogs`.
+    pub event_query_chunk_size: u64,
+    /// [ProverOpts] to use when proving the mint calculation.
+    pub prover_opts: ProverOpts,
+    /// Timeout to wait for the claim transaction receipt. Defaults to the provider timeout.
+    pub tx_timeout: Option<Duration>,
+}
+
+impl ClaimParams {
+    /// Create parameters for a claim on the given log ID with default settings.
+    pub fn new(log_id: PovwLogId, deployment: Deployment) -> Self {
+        Self::new_group([log_id], deployment)
+    }
+
+    /// Create parameters for a single claim on the given group of log IDs with default settings.
+    pub fn new_group(log_ids: impl IntoIterator<Item = PovwLogId>, deployment: Deployment) -> Self {
+        Self {
+            log_ids: log_ids.into_iter().collect(),
+            deployment,
+            beacon_api_url: None,
+            days: 30,
+            event_query_chunk_size: 10000,
+            prover_opts: ProverOpts::groth16(),
+            tx_timeout: None,
+        }
+    }
+}
+
+/// Progress of a reward claim, reported to the callback passed to [claim_rewards].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub enum ClaimProgress {
+    /// Searching for work log update events in the given number of past days.
+    SearchingUpdates { days: u32 },
+    /// Found the given number of work log update events to claim.
+    FoundUpdates { count: usize },
+    /// Skipping an update in an epoch that has not been finalized.
+    SkippedUnfinalizedEpoch { epoch: U256 },
+    /// Skipping a work log of a group claim, which has no updates in finalized epochs.
+    SkippedUnfinalizedLog { log_id: PovwLogId },
+    /// Searching for the finalization events of the epochs from `first` to `last`.
+    SearchingEpochFinalizations { first: U256, last: U256 },
+    /// Found the given number of epoch finalization events.
+    FoundEpochFinalizations { count: usize },
+    /// Building the input for the Mint Calculator guest.
+    BuildingInput,
+    /// Proving the Mint Calculator guest.
+    Proving,
+    /// Sending the reward claim transaction.
+    SendingTransaction,
+    /// Sent the reward claim transaction and waiting for its receipt.
+    TransactionSent { tx_hash: B256 },
+}
+
+/// Outcome of a reward claim.
+#[derive(Clone, Debug)]
+pub enum ClaimOutcome {
+    /// All rewards for submitted work log updates have already been claimed.
+    AlreadyClaimed,
+    /// Rewards were claimed.
+    Claimed {
+        /// Hash of the reward claim transaction.
+        tx_hash: B256,
+        /// Mints issued by the claim.
+        mints: Vec<MintCalculatorMint>,
+        /// Epochs covered by the claim.
+        epochs: BTreeSet<U256>,
+    },
+}
+
+/// Static model of the gas used by a mint transaction, for when it cannot be simulated.
+///
+/// The default values are approximations for a mint verifying a Groth16 seal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MintGasModel {
+    /// Gas used by every mint, including the intrinsic gas, calldata, and Steel commitment check.
+    pub base: u64,
+    /// Gas used to verify the seal.
+    pub seal_verification: u64,
+    /// Gas used for each work log update.
+    pub per_update: u64,
+    /// Gas used for each minted recipient.
+    pub per_mint: u64,
+}
+
+impl MintGasModel {
+    /// Default model for mints verified with a Groth16 seal.
+    pub const DEFAULT: Self =
+        Self { base: 60_000, seal_verification: 280_000, per_update: 30_000, per_mint: 55_000 };
+
+    /// Estimate the gas used by a mint with the given number of updates and mints.
+    pub fn estimate(&self, updates: usize, mints: usize) -> u64 {
+        self.base
+            + self.seal_verification
+            + self.per_update * updates as u64
+            + self.per_mint * mints as u64
+    }
+}
+
+impl Default for MintGasModel {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// How the gas of a planned mint was estimated.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GasEstimate {
+    /// Estimated with `eth_estimateGas`, using a dev-mode receipt against a test deployment.
+    Simulated(u64),
+    /// Estimated with the [MintGasModel].
+    Modeled(u64),
+}
+
+impl GasEstimate {
+    /// The estimated amount of gas.
+    pub fn gas(&self) -> u64 {
+        match self {
+            Self::Simulated(gas) | Self::Modeled(gas) => *gas,
+        }
+    }
+}
+
+/// Report of a planned mint, produced by [report_claim] without proving or sending it.
+#[derive(Clone, Debug)]
+pub struct MintReport {
+    /// Epochs covered by the mint.
+    pub epochs: BTreeSet<U256>,
+    /// Number of work log updates applied by the mint.
+    pub updates: usize,
+    /// Number of recipients minted to.
+    pub mints: usize,
+    /// Size of the encoded Mint Calculator input, in bytes.
+    pub input_size: usize,
+    /// Size of the ABI-encoded journal posted with the mint, in bytes.
+    pub journal_size: usize,
+    /// Estimated gas used by the mint transaction.
+    pub gas: GasEstimate,
+}
+
+/// Work log updates to claim, with the Mint Calculator input built to claim them.
+struct PreparedMint<R, P> {
+    mint_calculator_prover: MintCalculatorProver<R, P>,
+    input: Input,
+    epochs: BTreeSet<U256>,
+    update_events: Vec<WorkLogUpdated>,
+}
+
+/// Claim the PoVW rewards for the work log updates submitted under the given log IDs.
+///
+/// Searches for the unclaimed work log updates in finalized epochs, proves the Mint Calculator
+/// with the given prover, and sends the mint transaction with the wallet of the given provider.
+/// Updates in epochs that have not been finalized are skipped, and can be claimed once they are.
+/// When more than one log ID is given, all of their updates are claimed with a single proof, and
+/// the log IDs without updates in finalized epochs are skipped.
+pub async fn claim_rewards<P, R>(
+    provider: P,
+    prover: R,
+    params: &ClaimParams,
+    mut progress: impl FnMut(ClaimProgress),
+) -> anyhow::Result<ClaimOutcome>
+where
+    P: Provider + Clone + 'static,
+    R: Prover,
+{
+    let Some(prepared) = prepare_mint(provider.clone(), prover, params, &mut progress).await?
+    else {
+        return Ok(ClaimOutcome::AlreadyClaimed);
+    };
+    let PreparedMint { mint_calculator_prover, input: mint_input, epochs, .. } = prepared;
+    let povw_mint = IPovwMint::new(params.deployment.povw_mint_address, provider);
+
+    progress(ClaimProgress::Proving);
+    let mint_prove_info = mint_calculator_prover
+        .prove_mint(&mint_input)
+        .await
+        .context("Failed to prove Mint Calculator guest")?;
+    let journal = MintCalculatorJournal::abi_decode(&mint_prove_info.receipt.journal.bytes)
+        .context("Failed to decode journal from Mint Calculator receipt")?;
+
+    progress(ClaimProgress::SendingTransaction);
+    let tx_result = povw_mint
+        .mint_with_receipt(&mint_prove_info.receipt)
+        .context("Failed to construct reward claim transaction")?
+        .send()
+        .await
+        .context("Failed to send reward claim transaction")?;
+    let tx_hash = *tx_result.tx_hash();
+    progress(ClaimProgress::TransactionSent { tx_hash });
+
+    let timeout = params.tx_timeout.or(tx_result.timeout());
+    let tx_receipt = tx_result
+        .with_timeout(timeout)
+        .get_receipt()
+        .await
+        .context("Failed to receive receipt reward claim transaction")?;
+
+    ensure!(
+        tx_receipt.status(),
+        "Reward claim transaction failed: tx_hash = {}",
+        tx_receipt.transaction_hash
+    );
+
+    Ok(ClaimOutcome::Claimed { tx_hash, mints: journal.mints, epochs })
+}
+
+/// Plan the claim of the PoVW rewards for the given log IDs, and report the size and expected gas
+/// of the mint, without proving or sending it. Returns `None` if there are no rewards to claim.
+///
+/// The journal size is exact, since the ABI encoding of the journal only depends on the number of
+/// updates and mints. When the prover options are in dev mode, the mint is executed with the given
+/// prover and the gas is estimated with `eth_estimateGas`, which requires a deployment accepting
+/// dev-mode seals. Otherwise, the gas is estimated with the given [MintGasModel].
+pub async fn report_claim<P, R>(
+    provider: P,
+    prover: R,
+    params: &ClaimParams,
+    gas_model: &MintGasModel,
+    mut progress: impl FnMut(ClaimProgress),
+) -> anyhow::Result<Option<MintReport>>
+where
+    P: Provider + Clone + 'static,
+    R: Prover,
+{
+    let Some(prepared) = prepare_mint(provider.clone(), prover, params, &mut progress).await?
+    else {
+        return Ok(None);
+    };
+
+    let input_size = prepared.input.encode()?.len();
+    let journal = planned_journal(params, &prepared.update_events);
+    let journal_size = journal.abi_encode().len();
+    let (updates, mints) = (journal.updates.len(), journal.mints.len());
+
+    let gas = if params.prover_opts.dev_mode() {
+        progress(ClaimProgress::Proving);
+        let mint_prove_info = prepared
+            .mint_calculator_prover
+            .prove_mint(&prepared.input)
+            .await
+            .context("Failed to execute Mint Calculator guest")?;
+        let povw_mint = IPovwMint::new(params.deployment.povw_mint_address, provider);
+        let gas = povw_mint
+            .mint_with_receipt(&mint_prove_info.receipt)
+            .context("Failed to construct reward claim transaction")?
+            .estimate_gas()
+            .await
+            .context("Failed to estimate gas for reward claim transaction")?;
+        GasEstimate::Simulated(gas)
+    } else {
+        GasEstimate::Modeled(gas_model.estimate(updates, mints))
+    };
+
+    Ok(Some(MintReport { epochs: prepared.epochs, updates, mints, input_size, journal_size, gas }))
+}
+
+/// Build a journal of the same shape as the one the Mint Calculator will commit for the given
+/// updates, with placeholder values for the mint values and Steel commitment.
+fn planned_journal(
+    params: &ClaimParams,
+    update_events: &[WorkLogUpdated],
+) -> MintCalculatorJournal {
+    // The guest mints once per recipient of a non-zero update value, and commits one update per
+    // work log, ordered by log ID. Events of each work log are in chain order.
+    let recipients = update_events
+        .iter()
+        .filter(|event| event.updateValue > U256::ZERO)
+        .map(|event| event.valueRecipient)
+        .collect::<BTreeSet<_>>();
+    let mut updates = BTreeMap::<Address, MintCalculatorUpdate>::new();
+    for event in update_events {
+        updates
+            .entry(event.workLogId)
+            .and_modify(|update| update.updatedCommit = event.updatedCommit)
+            .or_insert(MintCalculatorUpdate {
+                workLogId: event.workLogId,
+                initialCommit: event.initialCommit,
+                updatedCommit: event.updatedCommit,
+            });
+    }
+    MintCalculatorJournal {
+        mints: recipients
+            .into_iter()
+            .map(|recipient| MintCalculatorMint { recipient, value: U256::ZERO })
+            .collect(),
+        updates: updates.into_values().collect(),
+        povwAccountingAddress: params.deployment.povw_accounting_address,
+        zkcRewardsAddress: params.deployment.vezkc_address,
+        zkcAddress: params.deployment.zkc_address,
+        steelCommit: Default::default(),
+    }
+}
+
+/// Search for the unclaimed work log updates in finalized epochs, and build the Mint Calculator
+/// input to claim them. Returns `None` if all updates have been claimed.
+async fn prepare_mint<P, R>(
+    provider: P,
+    prover: R,
+    params: &ClaimParams,
+    progress: &mut impl FnMut(ClaimProgress),
+) -> anyhow::Result<Option<PreparedMint<R, P>>>
+where
+    P: Provider + Clone + 'static,
+    R: Prover,
+{
+    let deployment = &params.deployment;
+    let chain_id = provider.get_chain_id().await.context("Failed to query the chain ID")?;
+    let chain_spec = CHAIN_SPECS
+        .get(&chain_id)
+        .with_context(|| format!("No known Steel chain specification for chain ID {chain_id}"))?;
+
+    // Determine the limits on the blocks that will be searched for events.
+    let latest_block_number =
+        provider.get_block_number().await.context("Failed to query the block number")?;
+    let search_limit_time =
+        SystemTime::now().checked_sub(params.days * 24 * HOUR).context("Invalid number of days")?;
+    let lower_limit_block_number =
+        block_number_near_timestamp(&provider, latest_block_number, search_limit_time, Some(HOUR))
+            .await
+            .context("Failed to determine the block number for the event search limit")?;
+
+    let povw_accounting =
+        IPovwAccounting::new(deployment.povw_accounting_address, provider.clone());
+    let povw_mint = IPovwMint::new(deployment.povw_mint_address, provider.clone());
+    let pending_epoch = povw_accounting
+        .pendingEpoch()
+        .call()
+        .await
+        .context("Failed to check the pending epoch")?
+        .number;
+
+    let mut claimed_log_ids = Vec::new();
+    let mut finalized_update_events = Vec::new();
+    for &log_id in &params.log_ids {
+        let Some(log_events) = search_finalized_updates(
+            &povw_accounting,
+            &povw_mint,
+            log_id,
+            pending_epoch,
+            latest_block_number,
+            lower_limit_block_number,
+            params,
+            progress,
+        )
+        .await
+        .with_context(|| format!("Failed to prepare the claim for work log {log_id:x}"))?
+        else {
+            continue;
+        };
+        claimed_log_ids.push(log_id);
+        finalized_update_events.extend(log_events);
+    }
+
+    if claimed_log_ids.is_empty() {
+        return Ok(None);
+    }
+
+    // We can refine the range we search for EpochFinalized events using the earliest event.
+    let lower_limit_block_number = finalized_update_events
+        .iter()
+        .map(|(_, block_number)| *block_number)
+        .min()
+        .unwrap_or(lower_limit_block_number);
+    let epochs =
+        finalized_update_events.iter().map(|(event, _)| event.epochNumber).collect::<BTreeSet<_>>();
+
+    ensure!(!epochs.is_empty(), "List of epochs for claim is empty");
+    let first = *epochs.first().unwrap();
+    let last = *epochs.last().unwrap();
+    progress(ClaimProgress::SearchingEpochFinalizations { first, last });
+    let epoch_events = search_epoch_finalized(
+        &povw_accounting,
+        epochs.clone(),
+        latest_block_number,
+        lower_limit_block_number,
+        params.event_query_chunk_size,
+    )
+    .await
+    .context("Search for epoch finalized events failed")?;
+    progress(ClaimProgress::FoundEpochFinalizations { count: epoch_events.len() });
+
+    let event_block_numbers = BTreeSet::from_iter(
+        finalized_update_events
+            .iter()
+            .map(|(_, block_number)| *block_number)
+            .chain(epoch_events.keys().copied()),
+    );
+
+    let mint_calculator_prover = MintCalculatorProver::builder()
+        .prover(prover)
+        .provider(provider.clone())
+        .beacon_api(params.beacon_api_url.clone())
+        .povw_accounting_address(deployment.povw_accounting_address)
+        .zkc_address(deployment.zkc_address)
+        .zkc_rewards_address(deployment.vezkc_address)
+        .chain_spec(chain_spec)
+        .prover_opts(params.prover_opts.clone())
+        .build()?;
+
+    progress(ClaimProgress::BuildingInput);
+    let mint_input = mint_calculator_prover
+        .build_input(event_block_numbers, claimed_log_ids)
+        .await
+        .context("Failed to build input for Mint Calculator Guest")?;
+
+    Ok(Some(PreparedMint {
+        mint_calculator_prover,
+        input: mint_input,
+        epochs,
+        update_events: finalized_update_events.into_iter().map(|(event, _)| event).collect(),
+    }))
+}
+
+/// Search for the unclaimed work log updates of the given log ID in finalized epochs, returning
+/// them in chain order along with the block number at which they were emitted. Returns `None` if
+/// all updates have been claimed, or in a group claim, if none of them are in finalized epochs.
+#[allow(clippy::too_many_arguments)]
+async fn search_finalized_updates<P: Provider + Clone>(
+    povw_accounting: &IPovwAccountingInstance<P>,
+    povw_mint: &IPovwMint::IPovwMintInstance<P>,
+    log_id: PovwLogId,
+    pending_epoch: U256,
+    latest_block_number: u64,
+    lower_limit_block_number: u64,
+    params: &ClaimParams,
+    progress: &mut impl FnMut(ClaimProgress),
+) -> anyhow::Result<Option<Vec<(WorkLogUpdated, u64)>>> {
+    let deployment = &params.deployment;
+
+    // Determine the commit range for which we can mint. This is the difference between the
+    // recoreded work log commit on the accounting contract and on the mint contract.
+    let initial_commit =
+        Digest::from(*povw_mint.workLogCommit(log_id.into()).call().await.with_context(|| {
+            format!("Failed to call IPovwMint.workLogCommit on {}", deployment.povw_mint_address)
+        })?);
+    let final_commit = Digest::from(
+        *povw_accounting.workLogCommit(log_id.into()).call().await.with_context(|| {
+            format!(
+                "Failed to call IPovwAccounting.workLogCommit on {}",
+                deployment.povw_accounting_address
+            )
+        })?,
+    );
+
+    if initial_commit == final_commit {
+        return Ok(None);
+    }
+
+    // Search for the WorkLogUpdated events.
+    progress(ClaimProgress::SearchingUpdates { days: params.days });
+    let update_events = search_work_log_updated(
+        povw_accounting,
+        log_id,
+        initial_commit,
+        final_commit,
+        latest_block_number,
+        lower_limit_block_number,
+        params.event_query_chunk_size,
+    )
+    .await
+    .context("Search for work log update events failed")?;
+    progress(ClaimProgress::FoundUpdates { count: update_events.len() });
+
+    // Filter out update events with an epoch that has not finalized.
+    let finalized_update_events = update_events
+        .into_iter()
+        .filter(|(event, _)| {
+            if event.epochNumber >= pending_epoch {
+                progress(ClaimProgress::SkippedUnfinalizedEpoch { epoch: event.epochNumber });
+                false
+            } else {
+                true
+            }
+        })
+        .collect::<Vec<_>>();
+
+    // NOTE: At least one epoch must be skipped to reach this point.
+    if finalized_update_events.is_empty() {
+        // In a group claim, leave the work log to a later claim rather than failing the group.
+        if params.log_ids.len() > 1 {
+            progress(ClaimProgress::SkippedUnfinalizedLog { log_id });
+            return Ok(None);
+        }
+        bail!("No update events found for finalized epochs; no rewards to claim")
+    }
+    Ok(Some(finalized_update_events))
+}
+
+async fn block_number_near_timestamp(
+    provider: impl Provider,
+    latest_block_number: u64,
+    timestamp: SystemTime,
+    approx: Option<Duration>,
+) -> anyhow::Result<u64> {
+    // Phase 1: Linear search backwards in chunks until we find a block <= target_timestamp
+    const LINEAR_SEARCH_CHUNK_SIZE: u64 = 100000;
+    let mut probe = latest_block_number;
+    loop {
+        let block = provider
+            .get_block_by_number(probe.into())
+            .await
+            .with_context(|| format!("Failed to get block {probe}"))?
+            .with_context(|| format!("Block {probe} not found"))?;
+
+        let block_timestamp = UNIX_EPOCH + Duration::from_secs(block.header.timestamp);
+        if block_timestamp <= timestamp {
+            break;
+        }
+
+        probe = probe.saturating_sub(LINEAR_SEARCH_CHUNK_SIZE);
+        if probe == 0 {
+            // We've reached the block 0. This the closest possible block to the timestamp.
+            return Ok(0);
+        }
+    }
+
+    // Phase 2: binary search between [low, high]
+    // NOTE: If the latest block is less than the target timestamp, the binary search will not run.
+    let mut high = u64::min(probe + LINEAR_SEARCH_CHUNK_SIZE, latest_block_number);
+    let mut low = probe;
+    while low < high {
+        let mid = (low + high).div_ceil(2);
+        let block = provider
+            .get_block_by_number(mid.into())
+            .await
+            .with_context(|| format!("Failed to get block {mid}"))?
+            .with_context(|| format!("Block {mid} not found"))?;
+
+        let block_timestamp = UNIX_EPOCH + Duration::from_secs(block.header.timestamp);
+        if block_timestamp <= timestamp {
+            low = mid; // candidate, move up
+
+            // If an approximation factor is provided, see if we are close enough.
+            if let Some(approx) = approx {
+                if block_timestamp >= timestamp.checked_sub(approx).unwrap_or(UNIX_EPOCH) {
+                    break;
+                }
+            }
+        } else {
+            high = mid - 1;
+        }
+    }
+
+    Ok(low)
+}
+
+/// Search for work log updated events required for the mint operation.
+///
+/// This function pregressively searches backwards in chunks, start at the upoer limit block, until
+/// it finds all the events needed or hits the lower limit block. It returns the sorted list of
+/// found [WorkLogUpdated] events along with the block number at which they were emitted.
+async fn search_work_log_updated<P: Provider + Clone>(
+    povw_accounting: &IPovwAccountingInstance<P>,
+    log_id: PovwLogId,
+    initial_commit: Digest,
+    final_commit: Digest,
+    upper_limit_block_number: u64,
+    lower_limit_block_number: u64,
+    chunk_size: u64,
+) -> anyhow::Result<Vec<(WorkLogUpdated, u64)>> {
+    let mut events = HashMap::<Digest, (WorkLogUpdated, u64)>::new();
+    let search_predicate = |query_logs: &[(WorkLogUpdated, u64)]| {
+        for (event, block_number) in query_logs {
+            let commit = Digest::from(*event.initialCommit);
+            events.insert(commit, (event.clone(), *block_number));
+        }
+        Ok(!events.contains_key(&initial_commit))
+    };
+
+    let event = povw_accounting.WorkLogUpdated_filter().topic1(Address::from(log_id));
+    search_events(
+        &event,
+        lower_limit_block_number,
+        upper_limit_block_number,
+        chunk_size,
+        search_predicate,
+    )
+    .await
+    .context("Failed to search for WorkLogUpdated events")?;
+
+    // Reconstruct the chain of WorkLogUpdated from initial_commit to final_commit.
+    let mut commit = initial_commit;
+    let mut sorted_events = Vec::new();
+    while commit != final_commit {
+        match events.remove(&commit) {
+            Some((event, block_number)) => {
+                sorted_events.push((event.clone(), block_number));
+                commit = Digest::from(*event.updatedCommit);
+            }
+            None => bail!("Missing WorkLogUpdated event in chain with initial commit {commit}; did not reach final commit {final_commit}")
+        }
+    }
+
+    Ok(sorted_events)
+}
+
+async fn search_epoch_finalized<P: Provider + Clone>(
+    povw_accounting: &IPovwAccountingInstance<P>,
+    mut epochs: BTreeSet<U256>,
+    upper_limit_block_number: u64,
+    lower_limit_block_number: u64,
+    chunk_size: u64,
+) -> anyhow::Result<BTreeMap<u64, EpochFinalized>> {
+    let mut events = BTreeMap::<u64, EpochFinalized>::new();
+    let search_predicate = |query_logs: &[(EpochFinalized, u64)]| {
+        for (event, block_number) in query_logs {
+            // Remove the epoch from the set we are searching for.
+            if epochs.remove(&event.epoch) {
+                events.insert(*block_number, event.clone());
+            }
+        }
+        Ok(!epochs.is_empty())
+    };
+
+    let event = povw_accounting.EpochFinalized_filter();
+    search_events(
+        &event,
+        lower_limit_block_number,
+        upper_limit_block_number,
+        chunk_size,
+        search_predicate,
+    )
+    .await
+    .context("Failed to search for EpochFinalized events")?;
+
+    Ok(events)
+}
+
+/// Search backwards from the upper limit block for events matching the given event filter, in
+/// chunks, until the predicate returns false. The predicate receives each event along with the
+/// block number at which it was emitted.
+async fn search_events<P: Provider + Clone, E: SolEvent>(
+    event: &Event<P, E>,
+    lower_limit_block_number: u64,
+    upper_limit_block_number: u64,
+    chunk_size: u64,
+    mut f: impl FnMut(&[(E, u64)]) -> anyhow::Result<bool>,
+) -> anyhow::Result<()> {
+    let mut upper_block = upper_limit_block_number;
+    loop {
+        // The scan has reach block 0. This can only really happen in tests.
+        if upper_block == 0 {
+            break;
+        }
+        if upper_block < lower_limit_block_number {
+            bail!("Search reached lower limit block number {lower_limit_block_number}");
+        }
+
+        // Calculate the block range to query: from lower_block to upper_block. Range is
+        // inclusive of both lower and upper block.
+        let lower_block =
+            u64::max(upper_block.saturating_sub(chunk_size) + 1, lower_limit_block_number);
+
+        let query_logs = Event::<P, E>::new(
+            event.provider.clone(),
+            event.filter.clone().from_block(lower_block).to_block(upper_block),
+        )
+        .query()
+        .await
+        .with_context(|| format!("Query for events in the range {lower_block} to {upper_block}"))?
+        .into_iter()
+        .map(|(event, log)| {
+            let block_number =
+                log.block_number.context("Log from range does not have block number")?;
+            Ok((event, block_number))
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+
+        // Check the predicate to see if the search should continue.
+        if !f(&query_logs)? {
+            break;
+        }
+
+        // Move the window down and continue.
+        upper_block = lower_block.saturating_sub(1);
+    }
+    Ok(())
+}
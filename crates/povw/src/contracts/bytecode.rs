@@ -14,3 +14,15 @@ alloy_sol_types::sol! {
         function initialize(address initialOwner) {}
     }
 }
+alloy_sol_types::sol! {
+    #[sol(rpc, bytecode = "60a034607557601f6106a438819003918201601f19168301916001600160401b03831184841017607957808492602094604052833981010312607557516001600160e01b031981168103607557608052604051610616908161008e82396080518181816101b2015281816102af015261031e0152f35b5f80fd5b634e487b7160e01b5f52604160045260245ffdfe6080806040526004361015610012575f80fd5b5f3560e01c908163053c238d146101a0575080631599ead51461012d5780633a115bb11461010e57806366cf0e4b146100c85763ab750e7514610053575f80fd5b346100c45760603660031901126100c4576004356001600160401b0381116100c457366023820112156100c4578060040135906001600160401b0382116100c45736602483830101116100c4576100c29160246100bb6100b660443583356103e5565b610518565b920161030a565b005b5f80fd5b346100c45760403660031901126100c4576100e1610288565b5061010a6100fe6100f96100b66024356004356103e5565b6102a1565b604051918291826101e2565b0390f35b346100c45760203660031901126100c45761010a6100fe6004356102a1565b346100c45760203660031901126100c4576004356001600160401b0381116100c45780360360406003198201126100c457600482013590602219018112156100c45781016004810135906001600160401b0382116100c4576024019080360382136100c45760246100c29301359161030a565b346100c4575f3660031901126100c4577f00000000000000000000000000000000000000000000000000000000000000006001600160e01b0319168152602090f35b60208060809381845280516040838601528051938491826060880152018686015e5f84840186015201516040830152601f01601f1916010190565b604081019081106001600160401b0382111761023857604052565b634e487b7160e01b5f52604160045260245ffd5b60a081019081106001600160401b0382111761023857604052565b90601f801991011681019081106001600160401b0382111761023857604052565b604051906102958261021d565b5f602083606081520152565b6102a9610288565b506040517f00000000000000000000000000000000000000000000000000000000000000006001600160e01b031916602082015260248082018390528152906102f3604483610267565b604051916103008361021d565b8252602082015290565b81600411806100c4576001600160e01b03197f00000000000000000000000000000000000000000000000000000000000000008116908335168082036103d05750506100c45760031982016001600160401b038111610238576040519161037b601b8501601f191660200184610267565b818352602083019336818301116100c4575f926004601c93018637830101525190209060405160208101918252602081526103b7604082610267565b519020036103c157565b63439cc0cd60e01b5f5260045ffd5b632e2ce35360e21b5f5260045260245260445ffd5b905f60806040516103f58161024c565b82815282602082015260405161040a8161021d565b838152836020820152604082015282606082015201526040519061042d8261021d565b5f82525f6020830152604051906104438261021d565b8152602081015f815260205f600c6040516b1c9a5cd8cc0b93dd5d1c1d5d60a21b815260025afa1561050d576020915f918251915190516040519185830193845260408301526060820152600160f91b6080820152606281526104a7608282610267565b604051918291518091835e8101838152039060025afa1561050d575f5190604051926104d28461024c565b83527fa3acc27117418996340b84e5a90f3ef4c49d22c79e44aad822ec9c313e1eb8e2602084015260408301525f6060830152608082015290565b6040513d5f823e3d90fd5b60205f60126040517172697363302e52656365697074436c61696d60701b815260025afa1561050d575f5190606081015191815192602083015193604060808501519401938451519060038210156105f557945160209081015160408051808401978852908101959095526060850193909352608084019690965260a08301949094526001600160f81b031960f894851b811660c0840152931b90921660c4830152600160fa1b60c883015260aa82525f916105d560ca82610267565b604051918291518091835e8101838152039060025afa1561050d575f5190565b634e487b7160e01b5f52602160045260245ffdfea164736f6c634300081a000a")]
+    contract RiscZeroMockVerifier {
+        constructor(bytes4 selector) {}
+    }
+}
+alloy_sol_types::sol! {
+    #[sol(rpc, bytecode = "60806040526102748038038061001481610168565b92833981016040828203126101645781516001600160a01b03811692909190838303610164576020810151906001600160401b03821161016457019281601f8501121561016457835161006e610069826101a1565b610168565b9481865260208601936020838301011161016457815f926020809301865e86010152823b15610152577f360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc80546001600160a01b031916821790557fbc7cd75a20ee27fd9adebab32041f755214dbc6bffa90cc0225b39da2e5c2d3b5f80a282511561013a575f8091610122945190845af43d15610132573d91610113610069846101a1565b9283523d5f602085013e6101bc565b505b6040516059908161021b8239f35b6060916101bc565b50505034156101245763b398979f60e01b5f5260045ffd5b634c9c8ce360e01b5f5260045260245ffd5b5f80fd5b6040519190601f01601f191682016001600160401b0381118382101761018d57604052565b634e487b7160e01b5f52604160045260245ffd5b6001600160401b03811161018d57601f01601f191660200190565b906101e057508051156101d157602081519101fd5b63d6bda27560e01b5f5260045ffd5b81511580610211575b6101f1575090565b639996b31560e01b5f9081526001600160a01b0391909116600452602490fd5b50803b156101e956fe60806040527f360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc545f9081906001600160a01b0316368280378136915af43d5f803e156048573d5ff35b3d5ffdfea164736f6c634300081a000a")]
+    contract ERC1967Proxy {
+        constructor(address implementation, bytes memory data) payable {}
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Use of this source code is governed by the Business Source License
+// as found in the LICENSE-BSL file.
+
+//! Deployment of the PoVW contracts to a development chain, such as a local Anvil node.
+
+use alloy_primitives::{Address, FixedBytes};
+use alloy_provider::Provider;
+use alloy_sol_types::SolCall;
+use anyhow::{Context, Result};
+use derive_builder::Builder;
+
+use super::bytecode::{ERC1967Proxy, PovwAccounting, PovwMint, RiscZeroMockVerifier};
+use crate::{
+    deployments::Deployment,
+    log_updater::{IPovwAccounting::IPovwAccountingInstance, BOUNDLESS_POVW_LOG_UPDATER_ID},
+    mint_calculator::{IPovwMint::IPovwMintInstance, BOUNDLESS_POVW_MINT_CALCULATOR_ID},
+};
+
+/// Selector used by the mock verifier deployed by [deploy_povw_stack].
+pub const MOCK_VERIFIER_SELECTOR: [u8; 4] = [0xFF; 4];
+
+/// Verifier used by the PoVW contracts deployed by [deploy_povw_stack].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum DeployVerifier {
+    /// Deploy a mock verifier that accepts fake receipts, for use in dev mode.
+    #[default]
+    Mock,
+    /// Use an existing verifier, such as a verifier router, at the given address.
+    Existing(Address),
+}
+
+/// Parameters for [deploy_povw_stack].
+#[non_exhaustive]
+#[derive(Clone, Debug, Builder)]
+pub struct DeployParams {
+    /// Owner of the deployed contracts, allowed to upgrade them.
+    #[builder(setter(into))]
+    pub owner: Address,
+    /// Address of the ZKC token contract.
+    #[builder(setter(into))]
+    pub zkc_address: Address,
+    /// Address of the ZKC rewards (veZKC) contract.
+    #[builder(setter(into))]
+    pub zkc_rewards_address: Address,
+    /// Verifier used to verify the log updater and mint calculator receipts.
+    #[builder(default)]
+    pub verifier: DeployVerifier,
+}
+
+impl DeployParams {
+    /// Create a new [DeployParamsBuilder].
+    pub fn builder() -> DeployParamsBuilder {
+        Default::default()
+    }
+}
+
+/// A freshly deployed set of PoVW contracts.
+#[derive(Clone, Debug)]
+pub struct PovwDeployment<P> {
+    /// Addresses of the deployed contracts, along with the ZKC contracts they were deployed with.
+    pub deployment: Deployment,
+    /// Address of the verifier used by the deployed contracts.
+    pub verifier_address: Address,
+    /// The PoVW accounting contract, behind its proxy.
+    pub povw_accounting: IPovwAccountingInstance<P>,
+    /// The PoVW mint contract, behind its proxy.
+    pub povw_mint: IPovwMintInstance<P>,
+}
+
+/// Deploy the PoVW accounting and mint contracts, each behind an ERC1967 proxy.
+///
+/// The ZKC and ZKC rewards contracts must already be deployed. If the verifier is
+/// [DeployVerifier::Mock], a mock verifier is deployed first, and the contracts accept fake
+/// receipts with the [MOCK_VERIFIER_SELECTOR] seal selector.
+pub async fn deploy_povw_stack<P: Provider + Clone>(
+    provider: P,
+    params: DeployParams,
+) -> Result<PovwDeployment<P>> {
+    let chain_id = provider.get_chain_id().await.context("failed to get chain ID")?;
+
+    let verifier_address = match params.verifier {
+        DeployVerifier::Mock => {
+            let verifier =
+                RiscZeroMockVerifier::deploy(provider.clone(), FixedBytes(MOCK_VERIFIER_SELECTOR))
+                    .await
+                    .context("failed to deploy RiscZeroMockVerifier")?;
+            *verifier.address()
+        }
+        DeployVerifier::Existing(address) => address,
+    };
+
+    let povw_accounting_impl = PovwAccounting::deploy(
+        provider.clone(),
+        verifier_address,
+        params.zkc_address,
+        bytemuck::cast::<_, [u8; 32]>(BOUNDLESS_POVW_LOG_UPDATER_ID).into(),
+    )
+    .await
+    .context("failed to deploy PovwAccounting")?;
+    let povw_accounting_proxy = ERC1967Proxy::deploy(
+        provider.clone(),
+        *povw_accounting_impl.address(),
+        PovwAccounting::initializeCall { initialOwner: params.owner }.abi_encode().into(),
+    )
+    .await
+    .context("failed to deploy PovwAccounting proxy")?;
+    let povw_accounting =
+        IPovwAccountingInstance::new(*povw_accounting_proxy.address(), provider.clone());
+
+    let povw_mint_impl = PovwMint::deploy(
+        provider.clone(),
+        verifier_address,
+        *povw_accounting.address(),
+        bytemuck::cast::<_, [u8; 32]>(BOUNDLESS_POVW_MINT_CALCULATOR_ID).into(),
+        params.zkc_address,
+        params.zkc_rewards_address,
+    )
+    .await
+    .context("failed to deploy PovwMint")?;
+    let povw_mint_proxy = ERC1967Proxy::deploy(
+        provider.clone(),
+        *povw_mint_impl.address(),
+        PovwMint::initializeCall { initialOwner: params.owner }.abi_encode().into(),
+    )
+    .await
+    .context("failed to deploy PovwMint proxy")?;
+    let povw_mint = IPovwMintInstance::new(*povw_mint_proxy.address(), provider);
+
+    let deployment = Deployment::builder()
+        .chain_id(chain_id)
+        .povw_accounting_address(*povw_accounting.address())
+        .povw_mint_address(*povw_mint.address())
+        .zkc_address(params.zkc_address)
+        .vezkc_address(params.zkc_rewards_address)
+        .build()
+        .context("failed to build deployment")?;
+
+    Ok(PovwDeployment { deployment, verifier_address, povw_accounting, povw_mint })
+}
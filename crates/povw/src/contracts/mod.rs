@@ -7,3 +7,11 @@
 
 #[cfg(feature = "host")]
 pub mod bytecode;
+#[cfg(feature = "host")]
+mod deploy;
+
+#[cfg(feature = "host")]
+pub use deploy::{
+    deploy_povw_stack, DeployParams, DeployParamsBuilder, DeployVerifier, PovwDeployment,
+    MOCK_VERIFIER_SELECTOR,
+};
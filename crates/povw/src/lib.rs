@@ -22,6 +22,8 @@ mod guest_artifacts {
         bytemuck::must_cast(*include_bytes!("../elfs/boundless-povw-log-updater.iid"));
 }
 
+#[cfg(feature = "prover")]
+pub mod claim;
 pub mod contracts;
 #[cfg(feature = "host")]
 pub mod deployments;
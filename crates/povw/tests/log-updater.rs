@@ -11,16 +11,18 @@
 // cargo update -p risc0-povw --manifest-path Cargo.toml && cargo update -p risc0-povw --manifest-path crates/povw/log-updater/Cargo.toml
 // ```
 
-use alloy::signers::local::PrivateKeySigner;
+use alloy::{node_bindings::Anvil, providers::ProviderBuilder, signers::local::PrivateKeySigner};
 use alloy_primitives::{address, aliases::U96, Address, B256, U256};
 use alloy_sol_types::SolValue;
-use boundless_povw::log_updater::{
-    Input, LogBuilderJournal, WorkLogUpdate, BOUNDLESS_POVW_LOG_UPDATER_ID,
+use boundless_povw::{
+    contracts::{deploy_povw_stack, DeployParams},
+    log_updater::{Input, LogBuilderJournal, WorkLogUpdate, BOUNDLESS_POVW_LOG_UPDATER_ID},
 };
-use boundless_test_utils::povw::{execute_log_updater_guest, test_ctx};
+use boundless_test_utils::povw::{execute_log_updater_guest, test_ctx, MockZKC, MockZKCRewards};
 use risc0_ethereum_contracts::encode_seal;
 use risc0_povw::guest::RISC0_POVW_LOG_BUILDER_ID;
 use risc0_povw::WorkLog;
+use risc0_steel::ethereum::STEEL_TEST_PRAGUE_CHAIN_SPEC;
 use risc0_zkvm::{Digest, FakeReceipt, Receipt, ReceiptClaim};
 
 #[tokio::test]
@@ -774,3 +776,61 @@ async fn multiple_recipients_same_work_log() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn deploy_stack_and_update_work_log() -> anyhow::Result<()> {
+    let anvil = Anvil::new().chain_id(STEEL_TEST_PRAGUE_CHAIN_SPEC.chain_id).prague().spawn();
+    let deployer: PrivateKeySigner = anvil.keys()[0].clone().into();
+    let provider =
+        ProviderBuilder::new().wallet(deployer.clone()).connect_http(anvil.endpoint_url());
+
+    let zkc = MockZKC::deploy(provider.clone()).await?;
+    let zkc_rewards = MockZKCRewards::deploy(provider.clone()).await?;
+    let params = DeployParams::builder()
+        .owner(deployer.address())
+        .zkc_address(*zkc.address())
+        .zkc_rewards_address(*zkc_rewards.address())
+        .build()?;
+    let stack = deploy_povw_stack(provider.clone(), params).await?;
+    assert_eq!(stack.deployment.chain_id, Some(anvil.chain_id()));
+    assert_eq!(stack.deployment.povw_accounting_address, *stack.povw_accounting.address());
+    assert_eq!(stack.deployment.povw_mint_address, *stack.povw_mint.address());
+
+    let signer = PrivateKeySigner::random();
+    let update = LogBuilderJournal::builder()
+        .self_image_id(RISC0_POVW_LOG_BUILDER_ID)
+        .initial_commit(WorkLog::EMPTY.commit())
+        .updated_commit(Digest::new(rand::random()))
+        .update_value(10)
+        .work_log_id(signer.address())
+        .build()?;
+    let input = Input::builder()
+        .update(update.clone())
+        .contract_address(*stack.povw_accounting.address())
+        .chain_id(anvil.chain_id())
+        .sign_and_build(&signer)
+        .await?;
+    let journal = execute_log_updater_guest(&input)?;
+    let receipt: Receipt =
+        FakeReceipt::new(ReceiptClaim::ok(BOUNDLESS_POVW_LOG_UPDATER_ID, journal.abi_encode()))
+            .try_into()?;
+
+    stack
+        .povw_accounting
+        .updateWorkLog(
+            journal.update.workLogId,
+            journal.update.updatedCommit,
+            journal.update.updateValue,
+            journal.update.valueRecipient,
+            encode_seal(&receipt)?.into(),
+        )
+        .send()
+        .await?
+        .watch()
+        .await?;
+
+    let commit = stack.povw_accounting.workLogCommit(signer.address()).call().await?;
+    assert_eq!(commit, B256::from(<[u8; 32]>::from(update.updated_commit)));
+
+    Ok(())
+}
@@ -11,13 +11,17 @@ use std::ops::Deref;
 use alloy::{primitives::U256, signers::local::PrivateKeySigner};
 use alloy_provider::Provider;
 use boundless_povw::{
-    log_updater::{prover::LogUpdaterProver, IPovwAccounting},
+    claim::{claim_rewards, report_claim, ClaimOutcome, ClaimParams, GasEstimate, MintGasModel},
+    deployments::Deployment,
+    log_updater::{prover::LogUpdaterProver, IPovwAccounting, LogBuilderJournal},
     mint_calculator::{prover::MintCalculatorProver, WorkLogFilter},
 };
 use boundless_test_utils::povw::{make_work_claim, test_ctx};
-use risc0_povw::{prover::WorkLogUpdateProver, PovwLogId};
+use risc0_povw::{
+    guest::RISC0_POVW_LOG_BUILDER_ID, prover::WorkLogUpdateProver, PovwLogId, WorkLog,
+};
 use risc0_steel::ethereum::STEEL_TEST_PRAGUE_CHAIN_SPEC;
-use risc0_zkvm::{default_prover, FakeReceipt, ProverOpts, VerifierContext};
+use risc0_zkvm::{default_prover, Digest, FakeReceipt, ProverOpts, VerifierContext};
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_workflow() -> anyhow::Result<()> {
@@ -131,3 +135,207 @@ async fn test_workflow() -> anyhow::Result<()> {
     assert_eq!(final_balance, epoch_reward, "Minted amount should match expected calculation");
     Ok(())
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_claim_rewards() -> anyhow::Result<()> {
+    let ctx = test_ctx().await?;
+    let signer = PrivateKeySigner::random();
+    let log_id: PovwLogId = signer.address().into();
+
+    // Post a work log update and finalize its epoch.
+    let update = LogBuilderJournal::builder()
+        .self_image_id(RISC0_POVW_LOG_BUILDER_ID)
+        .initial_commit(WorkLog::EMPTY.commit())
+        .updated_commit(Digest::new(rand::random()))
+        .update_value(25)
+        .work_log_id(signer.address())
+        .build()
+        .unwrap();
+    ctx.post_work_log_update(&signer, &update, signer.address()).await?;
+
+    let initial_epoch = ctx.zkc.getCurrentEpoch().call().await?;
+    ctx.advance_epochs(U256::ONE).await?;
+    ctx.finalize_epoch().await?;
+
+    let deployment = Deployment::builder()
+        .povw_accounting_address(*ctx.povw_accounting.address())
+        .povw_mint_address(*ctx.povw_mint.address())
+        .zkc_address(*ctx.zkc.address())
+        .vezkc_address(*ctx.zkc_rewards.address())
+        .build()?;
+    let params = ClaimParams {
+        prover_opts: ProverOpts::default().with_dev_mode(true),
+        ..ClaimParams::new(log_id, deployment)
+    };
+
+    let mut progress = Vec::new();
+    let outcome =
+        claim_rewards(ctx.provider.clone(), default_prover(), &params, |p| progress.push(p))
+            .await?;
+    let ClaimOutcome::Claimed { mints, epochs, .. } = outcome else {
+        panic!("expected rewards to be claimed, got {outcome:?}");
+    };
+    assert!(!progress.is_empty());
+    assert_eq!(epochs.into_iter().collect::<Vec<_>>(), vec![initial_epoch]);
+
+    let epoch_reward = ctx.zkc.getPoVWEmissionsForEpoch(initial_epoch).call().await?;
+    assert_eq!(mints.len(), 1);
+    assert_eq!(mints[0].recipient, signer.address());
+    assert_eq!(mints[0].value, epoch_reward);
+    assert_eq!(ctx.zkc.balanceOf(signer.address()).call().await?, epoch_reward);
+
+    // A second claim finds nothing left to claim.
+    let outcome = claim_rewards(ctx.provider.clone(), default_prover(), &params, |_| {}).await?;
+    assert!(matches!(outcome, ClaimOutcome::AlreadyClaimed));
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_claim_rewards_group() -> anyhow::Result<()> {
+    let ctx = test_ctx().await?;
+    let signers = [PrivateKeySigner::random(), PrivateKeySigner::random()];
+    let log_ids = signers.iter().map(|signer| signer.address().into()).collect::<Vec<PovwLogId>>();
+
+    // Post an update to each work log and finalize their epoch.
+    for signer in &signers {
+        let update = LogBuilderJournal::builder()
+            .self_image_id(RISC0_POVW_LOG_BUILDER_ID)
+            .initial_commit(WorkLog::EMPTY.commit())
+            .updated_commit(Digest::new(rand::random()))
+            .update_value(25)
+            .work_log_id(signer.address())
+            .build()
+            .unwrap();
+        ctx.post_work_log_update(signer, &update, signer.address()).await?;
+    }
+
+    let initial_epoch = ctx.zkc.getCurrentEpoch().call().await?;
+    ctx.advance_epochs(U256::ONE).await?;
+    ctx.finalize_epoch().await?;
+
+    let deployment = Deployment::builder()
+        .povw_accounting_address(*ctx.povw_accounting.address())
+        .povw_mint_address(*ctx.povw_mint.address())
+        .zkc_address(*ctx.zkc.address())
+        .vezkc_address(*ctx.zkc_rewards.address())
+        .build()?;
+    let params = ClaimParams {
+        prover_opts: ProverOpts::default().with_dev_mode(true),
+        ..ClaimParams::new_group(log_ids.clone(), deployment.clone())
+    };
+
+    // Both work logs are claimed with a single mint.
+    let model = MintGasModel::DEFAULT;
+    let report = report_claim(ctx.provider.clone(), default_prover(), &params, &model, |_| {})
+        .await?
+        .expect("expected rewards to claim");
+    assert_eq!((report.updates, report.mints), (2, 2));
+
+    let outcome = claim_rewards(ctx.provider.clone(), default_prover(), &params, |_| {}).await?;
+    let ClaimOutcome::Claimed { mints, epochs, .. } = outcome else {
+        panic!("expected rewards to be claimed, got {outcome:?}");
+    };
+    assert_eq!(epochs.into_iter().collect::<Vec<_>>(), vec![initial_epoch]);
+    assert_eq!(mints.len(), 2);
+
+    let epoch_reward = ctx.zkc.getPoVWEmissionsForEpoch(initial_epoch).call().await?;
+    let mut total = U256::ZERO;
+    for signer in &signers {
+        let balance = ctx.zkc.balanceOf(signer.address()).call().await?;
+        assert!(balance > U256::ZERO);
+        total += balance;
+    }
+    assert!(total <= epoch_reward);
+
+    // Each work log of the group has been claimed.
+    for log_id in log_ids {
+        let params = ClaimParams {
+            prover_opts: ProverOpts::default().with_dev_mode(true),
+            ..ClaimParams::new(log_id, deployment.clone())
+        };
+        let outcome =
+            claim_rewards(ctx.provider.clone(), default_prover(), &params, |_| {}).await?;
+        assert!(matches!(outcome, ClaimOutcome::AlreadyClaimed));
+    }
+    Ok(())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_report_claim() -> anyhow::Result<()> {
+    let ctx = test_ctx().await?;
+    let signer = PrivateKeySigner::random();
+    let log_id: PovwLogId = signer.address().into();
+
+    // Post a work log update and finalize its epoch.
+    let update = LogBuilderJournal::builder()
+        .self_image_id(RISC0_POVW_LOG_BUILDER_ID)
+        .initial_commit(WorkLog::EMPTY.commit())
+        .updated_commit(Digest::new(rand::random()))
+        .update_value(25)
+        .work_log_id(signer.address())
+        .build()
+        .unwrap();
+    ctx.post_work_log_update(&signer, &update, signer.address()).await?;
+    ctx.advance_epochs(U256::ONE).await?;
+    ctx.finalize_epoch().await?;
+
+    let deployment = Deployment::builder()
+        .povw_accounting_address(*ctx.povw_accounting.address())
+        .povw_mint_address(*ctx.povw_mint.address())
+        .zkc_address(*ctx.zkc.address())
+        .vezkc_address(*ctx.zkc_rewards.address())
+        .build()?;
+    let params = ClaimParams {
+        prover_opts: ProverOpts::default().with_dev_mode(true),
+        ..ClaimParams::new(log_id, deployment.clone())
+    };
+    let model = MintGasModel::DEFAULT;
+
+    // In dev mode, the mint is simulated against the test deployment.
+    let report = report_claim(ctx.provider.clone(), default_prover(), &params, &model, |_| {})
+        .await?
+        .expect("expected rewards to claim");
+    assert_eq!((report.updates, report.mints), (1, 1));
+    let GasEstimate::Simulated(simulated_gas) = report.gas else {
+        panic!("expected a simulated gas estimate, got {:?}", report.gas);
+    };
+
+    // Otherwise, the static model is used.
+    let model_params = ClaimParams {
+        prover_opts: ProverOpts::default().with_dev_mode(false),
+        ..ClaimParams::new(log_id, deployment)
+    };
+    let modeled =
+        report_claim(ctx.provider.clone(), default_prover(), &model_params, &model, |_| {})
+            .await?
+            .expect("expected rewards to claim");
+    assert_eq!(modeled.gas, GasEstimate::Modeled(model.estimate(1, 1)));
+    assert_eq!(modeled.journal_size, report.journal_size);
+
+    // The report matches the actual mint.
+    let outcome = claim_rewards(ctx.provider.clone(), default_prover(), &params, |_| {}).await?;
+    let ClaimOutcome::Claimed { tx_hash, .. } = outcome else {
+        panic!("expected rewards to be claimed, got {outcome:?}");
+    };
+    let receipt = ctx.provider.get_transaction_receipt(tx_hash).await?.unwrap();
+    let gas_used = receipt.gas_used;
+    assert!(simulated_gas >= gas_used, "estimate {simulated_gas} below gas used {gas_used}");
+    assert!(
+        simulated_gas <= gas_used * 11 / 10,
+        "estimate {simulated_gas} more than 10% above gas used {gas_used}"
+    );
+
+    // The test deployment verifies mock seals, so leave the seal verification out of the model.
+    let modeled_gas = MintGasModel { seal_verification: 0, ..model }.estimate(1, 1);
+    let tolerance = gas_used * 3 / 10;
+    assert!(
+        modeled_gas.abs_diff(gas_used) <= tolerance,
+        "modeled gas {modeled_gas} is not within {tolerance} of gas used {gas_used}"
+    );
+
+    // Nothing is left to report once claimed.
+    assert!(report_claim(ctx.provider.clone(), default_prover(), &params, &model, |_| {})
+        .await?
+        .is_none());
+    Ok(())
+}
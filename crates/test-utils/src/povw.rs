@@ -26,16 +26,13 @@ use alloy::{
     rpc::types::{TransactionReceipt, TransactionRequest},
     signers::{local::PrivateKeySigner, Signer},
     sol,
-    sol_types::{SolCall, SolValue},
+    sol_types::SolValue,
 };
-use anyhow::Context;
-use boundless_market::contracts::bytecode::ERC1967Proxy;
 use boundless_povw::{
-    contracts::bytecode::{PovwAccounting, PovwMint},
+    contracts::{deploy_povw_stack, DeployParams, PovwDeployment},
     log_updater::{
-        self,
-        IPovwAccounting::{self, IPovwAccountingInstance},
-        LogBuilderJournal, BOUNDLESS_POVW_LOG_UPDATER_ELF, BOUNDLESS_POVW_LOG_UPDATER_ID,
+        self, IPovwAccounting, LogBuilderJournal, BOUNDLESS_POVW_LOG_UPDATER_ELF,
+        BOUNDLESS_POVW_LOG_UPDATER_ID,
     },
     mint_calculator::{
         self, IPovwMint::IPovwMintInstance, WorkLogFilter, BOUNDLESS_POVW_MINT_CALCULATOR_ELF,
@@ -52,8 +49,6 @@ use risc0_zkvm::{
 };
 use tokio::sync::Mutex;
 
-use crate::verifier::deploy_mock_verifier;
-
 // Import the Solidity contracts using alloy's sol! macro
 // Use the compiled contracts output to allow for deploying the contracts.
 // NOTE: This requires running `forge build` before running this test.
@@ -115,10 +110,6 @@ pub async fn test_ctx_with(
     // Deploy PovwAccounting and PovwMint contracts to the Anvil instance, using a MockRiscZeroVerifier and a
     // basic ERC-20.
 
-    // Setup verifiers (will use mock in dev mode, real Groth16 otherwise)
-    let mock_verifier = deploy_mock_verifier(provider.clone()).await?;
-    println!("Mock verifier deployed at: {:?}", mock_verifier);
-
     // Deploy MockZKC
     let zkc_contract = MockZKC::deploy(provider.clone()).await?;
     println!("MockZKC deployed at: {:?}", zkc_contract.address());
@@ -127,46 +118,16 @@ pub async fn test_ctx_with(
     let zkc_rewards_contract = MockZKCRewards::deploy(provider.clone()).await?;
     println!("MockZKCRewards deployed at: {:?}", zkc_rewards_contract.address());
 
-    // Deploy PovwAccounting contract (needs verifier, zkc, and log updater ID)
-    let povw_accounting_contract = PovwAccounting::deploy(
-        provider.clone(),
-        mock_verifier,
-        *zkc_contract.address(),
-        bytemuck::cast::<_, [u8; 32]>(BOUNDLESS_POVW_LOG_UPDATER_ID).into(),
-    )
-    .await?;
-    println!("PovwAccounting contract deployed at: {:?}", povw_accounting_contract.address());
-    let povw_accounting_proxy = ERC1967Proxy::deploy(
-        provider.clone(),
-        *povw_accounting_contract.address(),
-        PovwAccounting::initializeCall { initialOwner: owner.address() }.abi_encode().into(),
-    )
-    .await
-    .context("Failed to deploy PovwAccounting proxy")?;
-    println!("PovwAccounting proxy deployed at: {:?}", povw_accounting_proxy.address());
-    let povw_accounting_interface =
-        IPovwAccountingInstance::new(*povw_accounting_proxy.address(), provider.clone());
-
-    // Deploy PovwMint contract (needs verifier, povw accounting, mint calculator ID, zkc, zkc rewards)
-    let povw_mint_contract = PovwMint::deploy(
-        provider.clone(),
-        mock_verifier,
-        *povw_accounting_interface.address(),
-        bytemuck::cast::<_, [u8; 32]>(BOUNDLESS_POVW_MINT_CALCULATOR_ID).into(),
-        *zkc_contract.address(),
-        *zkc_rewards_contract.address(),
-    )
-    .await?;
-    println!("PovwMint contract deployed at: {:?}", povw_mint_contract.address());
-    let povw_mint_proxy = ERC1967Proxy::deploy(
-        provider.clone(),
-        *povw_mint_contract.address(),
-        PovwMint::initializeCall { initialOwner: owner.address() }.abi_encode().into(),
-    )
-    .await
-    .context("Failed to deploy PovwMint proxy")?;
-    println!("PovwMint proxy deployed at: {:?}", povw_mint_proxy.address());
-    let povw_mint_interface = IPovwMintInstance::new(*povw_mint_proxy.address(), provider.clone());
+    // Deploy the PovwAccounting and PovwMint contracts behind proxies, with a mock verifier
+    let params = DeployParams::builder()
+        .owner(owner.address())
+        .zkc_address(*zkc_contract.address())
+        .zkc_rewards_address(*zkc_rewards_contract.address())
+        .build()?;
+    let PovwDeployment { povw_accounting, povw_mint, .. } =
+        deploy_povw_stack(provider.clone(), params).await?;
+    println!("PovwAccounting proxy deployed at: {:?}", povw_accounting.address());
+    println!("PovwMint proxy deployed at: {:?}", povw_mint.address());
 
     let chain_id = anvil.lock().await.chain_id();
     Ok(TestCtx {
@@ -175,8 +136,8 @@ pub async fn test_ctx_with(
         provider,
         zkc: zkc_contract,
         zkc_rewards: zkc_rewards_contract,
-        povw_accounting: povw_accounting_interface,
-        povw_mint: povw_mint_interface,
+        povw_accounting,
+        povw_mint,
         owner,
     })
 }
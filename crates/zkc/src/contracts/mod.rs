@@ -33,6 +33,19 @@ alloy::sol!(
     "src/contracts/artifacts/IStakingRewards.sol"
 );
 
+alloy::sol!(
+    #![sol(rpc, all_derives)]
+    /// Subset of the OpenZeppelin IVotes interface implemented by veZKC.
+    interface IVotes {
+        event DelegateVotesChanged(address indexed delegate, uint256 previousVotes, uint256 newVotes);
+
+        function getVotes(address account) external view returns (uint256);
+        function getPastVotes(address account, uint256 timepoint) external view returns (uint256);
+        /// Next nonce of the account for signature-based delegation of votes or rewards.
+        function nonces(address owner) external view returns (uint256);
+    }
+);
+
 pub fn extract_tx_log<E: SolEvent + Debug + Clone>(
     receipt: &TransactionReceipt,
 ) -> Result<Log<E>, anyhow::Error> {
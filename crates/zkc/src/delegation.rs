@@ -0,0 +1,173 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Use of this source code is governed by the Business Source License
+// as found in the LICENSE-BSL file.
+
+//! Signature-based delegation of veZKC rewards, allowing a cold wallet to sign a delegation
+//! offline and anyone to submit it.
+
+use alloy::{
+    primitives::{Address, Bytes, Signature, B256, U256},
+    signers::Signer,
+    sol,
+    sol_types::{eip712_domain, Eip712Domain, SolStruct},
+};
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::contracts::IRewards;
+
+/// Name of the EIP-712 domain of the veZKC contract.
+pub const VEZKC_EIP712_NAME: &str = "Vote Escrowed ZK Coin";
+
+/// Version of the EIP-712 domain of the veZKC contract.
+pub const VEZKC_EIP712_VERSION: &str = "1";
+
+sol! {
+    /// EIP-712 message authorizing the delegation of the rewards of the signer.
+    #[derive(Debug, PartialEq, Eq)]
+    struct RewardDelegation {
+        address delegatee;
+        uint256 nonce;
+        uint256 expiry;
+    }
+}
+
+impl RewardDelegation {
+    /// EIP-712 domain of the veZKC contract at the given address.
+    pub fn eip712_domain(vezkc_address: Address, chain_id: u64) -> Eip712Domain {
+        eip712_domain! {
+            name: VEZKC_EIP712_NAME,
+            version: VEZKC_EIP712_VERSION,
+            chain_id: chain_id,
+            verifying_contract: vezkc_address,
+        }
+    }
+
+    /// Returns the EIP-712 signing hash for the [RewardDelegation].
+    pub fn signing_hash(&self, vezkc_address: Address, chain_id: u64) -> B256 {
+        self.eip712_signing_hash(&Self::eip712_domain(vezkc_address, chain_id))
+    }
+}
+
+/// A [RewardDelegation] signed by the delegator, along with the deployment it is valid for.
+///
+/// This is the content of the file passed from the signing wallet to the submitter.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedRewardDelegation {
+    /// EIP-155 chain ID of the network.
+    pub chain_id: u64,
+    /// Address of the veZKC contract.
+    pub vezkc_address: Address,
+    /// Account whose rewards are delegated.
+    pub delegator: Address,
+    /// Account the rewards are delegated to.
+    pub delegatee: Address,
+    /// veZKC signature nonce of the delegator.
+    pub nonce: U256,
+    /// Timestamp after which the signature is no longer accepted.
+    pub expiry: u64,
+    /// Signature of the delegator over the [RewardDelegation].
+    pub signature: Bytes,
+}
+
+impl SignedRewardDelegation {
+    /// Sign a delegation of the rewards of the signer to the delegatee.
+    pub async fn sign(
+        signer: &impl Signer,
+        delegatee: Address,
+        nonce: U256,
+        expiry: u64,
+        vezkc_address: Address,
+        chain_id: u64,
+    ) -> Result<Self> {
+        let delegation = RewardDelegation { delegatee, nonce, expiry: U256::from(expiry) };
+        let signature = signer
+            .sign_hash(&delegation.signing_hash(vezkc_address, chain_id))
+            .await
+            .context("Failed to sign the reward delegation")?;
+        Ok(Self {
+            chain_id,
+            vezkc_address,
+            delegator: signer.address(),
+            delegatee,
+            nonce,
+            expiry,
+            signature: signature.as_bytes().into(),
+        })
+    }
+
+    /// The signed [RewardDelegation].
+    pub fn delegation(&self) -> RewardDelegation {
+        RewardDelegation {
+            delegatee: self.delegatee,
+            nonce: self.nonce,
+            expiry: U256::from(self.expiry),
+        }
+    }
+
+    /// Verify that the signature was produced by the delegator.
+    pub fn verify(&self) -> Result<()> {
+        let signature = Signature::try_from(self.signature.as_ref())?;
+        let signing_hash = self.delegation().signing_hash(self.vezkc_address, self.chain_id);
+        let recovered = signature.recover_address_from_prehash(&signing_hash)?;
+        if recovered != self.delegator {
+            bail!("signature is from {recovered}, not from the delegator {}", self.delegator);
+        }
+        Ok(())
+    }
+
+    /// Build the call to [IRewards::delegateRewardsBySig] submitting the delegation.
+    pub fn to_call(&self) -> Result<IRewards::delegateRewardsBySigCall> {
+        let sig = Signature::try_from(self.signature.as_ref())?.as_bytes();
+        Ok(IRewards::delegateRewardsBySigCall {
+            delegatee: self.delegatee,
+            nonce: self.nonce,
+            expiry: U256::from(self.expiry),
+            v: sig[64],
+            r: B256::from_slice(&sig[..32]),
+            s: B256::from_slice(&sig[32..64]),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::signers::{local::PrivateKeySigner, SignerSync};
+
+    use super::*;
+
+    #[test]
+    fn verify_signed_delegation() {
+        let signer = PrivateKeySigner::random();
+        let vezkc_address = Address::repeat_byte(0x11);
+        let delegatee = Address::repeat_byte(0x22);
+        let delegation =
+            RewardDelegation { delegatee, nonce: U256::from(3), expiry: U256::from(1_000) };
+        let signature = signer.sign_hash_sync(&delegation.signing_hash(vezkc_address, 1)).unwrap();
+        let signed = SignedRewardDelegation {
+            chain_id: 1,
+            vezkc_address,
+            delegator: signer.address(),
+            delegatee,
+            nonce: U256::from(3),
+            expiry: 1_000,
+            signature: signature.as_bytes().into(),
+        };
+        assert_eq!(signed.delegation(), delegation);
+        signed.verify().unwrap();
+
+        let call = signed.to_call().unwrap();
+        assert_eq!(call.delegatee, delegatee);
+        assert_eq!(call.nonce, U256::from(3));
+        assert!(call.v == 27 || call.v == 28);
+
+        // The signature is bound to the delegatee, nonce, expiry and domain.
+        let tampered = SignedRewardDelegation { delegatee: signer.address(), ..signed.clone() };
+        assert!(tampered.verify().is_err());
+        let tampered = SignedRewardDelegation { expiry: 2_000, ..signed.clone() };
+        assert!(tampered.verify().is_err());
+        let tampered = SignedRewardDelegation { chain_id: 2, ..signed };
+        assert!(tampered.verify().is_err());
+    }
+}
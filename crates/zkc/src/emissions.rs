@@ -0,0 +1,296 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Use of this source code is governed by the Business Source License
+// as found in the LICENSE-BSL file.
+
+//! Per-epoch ZKC emissions schedule, and checks of its invariants against the ZKC contract.
+
+use std::{fmt, ops::Range};
+
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+};
+use anyhow::{Context, Result};
+
+use crate::contracts::IZKC;
+
+/// Emissions minted at the end of a single epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EpochEmissions {
+    /// Epoch number.
+    pub epoch: u64,
+    /// Total emissions for the epoch, the sum of the PoVW and staking emissions.
+    pub total: U256,
+    /// PoVW emissions for the epoch.
+    pub povw: U256,
+    /// Staking emissions for the epoch.
+    pub staking: U256,
+}
+
+/// Difference between the generated and reported emissions of an epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmissionsMismatch {
+    /// Epoch number.
+    pub epoch: u64,
+    /// Name of the mismatched value.
+    pub field: &'static str,
+    /// Value generated from the supply schedule.
+    pub expected: U256,
+    /// Value reported by the contract.
+    pub reported: U256,
+}
+
+impl fmt::Display for EmissionsMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "epoch {}: {} emissions expected {} but the contract reported {}",
+            self.epoch, self.field, self.expected, self.reported
+        )
+    }
+}
+
+/// Parameters of a ZKC supply schedule.
+///
+/// The supply grows by the annual inflation rate of the current year, compounded every epoch. The
+/// rate starts at `initial_rate_bps` and drops by `rate_step_bps` every year, down to
+/// `final_rate_bps`. The emissions of each epoch are the growth in supply over the epoch, split
+/// between PoVW and staking.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SupplySchedule {
+    /// Supply at the start of epoch 0, in wei.
+    pub initial_supply: u128,
+    /// Number of epochs in a year.
+    pub epochs_per_year: u64,
+    /// Annual inflation rate of the first year, in basis points.
+    pub initial_rate_bps: u64,
+    /// Yearly decrease of the annual inflation rate, in basis points.
+    pub rate_step_bps: u64,
+    /// Lowest annual inflation rate, in basis points.
+    pub final_rate_bps: u64,
+    /// Share of the emissions of each epoch going to PoVW, in basis points. The rest goes to
+    /// staking.
+    pub povw_bps: u64,
+}
+
+impl SupplySchedule {
+    /// Supply schedule of the ZKC token: one billion ZKC at launch, 2-day epochs, 7% inflation in
+    /// the first year dropping by 0.5% a year down to 3%, and 75% of the emissions to PoVW.
+    pub const ZKC: Self = Self {
+        initial_supply: 1_000_000_000 * 10u128.pow(18),
+        epochs_per_year: 182,
+        initial_rate_bps: 700,
+        rate_step_bps: 50,
+        final_rate_bps: 300,
+        povw_bps: 7_500,
+    };
+
+    /// Annual inflation rate in the given year, in basis points.
+    pub fn rate_bps(&self, year: u64) -> u64 {
+        self.initial_rate_bps
+            .saturating_sub(year.saturating_mul(self.rate_step_bps))
+            .max(self.final_rate_bps)
+    }
+}
+
+/// Generate the emissions schedule for the given epochs from the parameters of the supply
+/// schedule.
+///
+/// The supply is compounded in floating point, so the generated values can differ from the
+/// fixed-point arithmetic of the contract by a rounding error. Compare them with a tolerance.
+pub fn generate_emissions(schedule: &SupplySchedule, epochs: Range<u64>) -> Vec<EpochEmissions> {
+    let mut supply = schedule.initial_supply as f64;
+    let mut table = Vec::with_capacity(epochs.end.saturating_sub(epochs.start) as usize);
+    for epoch in 0..epochs.end {
+        let year = epoch / schedule.epochs_per_year;
+        let rate = schedule.rate_bps(year) as f64 / 10_000.0;
+        let growth = (1.0 + rate).powf(1.0 / schedule.epochs_per_year as f64);
+        let next = supply * growth;
+        if epochs.contains(&epoch) {
+            let total = U256::from((next - supply).round() as u128);
+            let povw = total * U256::from(schedule.povw_bps) / U256::from(10_000);
+            table.push(EpochEmissions { epoch, total, povw, staking: total - povw });
+        }
+        supply = next;
+    }
+    table
+}
+
+/// Fetch the emissions reported by the ZKC contract for the given epochs.
+pub async fn fetch_emissions(
+    provider: impl Provider,
+    zkc_address: Address,
+    epochs: Range<u64>,
+) -> Result<Vec<EpochEmissions>> {
+    let zkc = IZKC::new(zkc_address, provider);
+
+    let mut table = Vec::with_capacity(epochs.end.saturating_sub(epochs.start) as usize);
+    for epoch in epochs {
+        let epoch_u256 = U256::from(epoch);
+        let total = zkc
+            .getEmissionsForEpoch(epoch_u256)
+            .call()
+            .await
+            .with_context(|| format!("failed to get emissions for epoch {epoch}"))?;
+        let povw = zkc
+            .getPoVWEmissionsForEpoch(epoch_u256)
+            .call()
+            .await
+            .with_context(|| format!("failed to get PoVW emissions for epoch {epoch}"))?;
+        let staking = zkc
+            .getStakingEmissionsForEpoch(epoch_u256)
+            .call()
+            .await
+            .with_context(|| format!("failed to get staking emissions for epoch {epoch}"))?;
+        table.push(EpochEmissions { epoch, total, povw, staking });
+    }
+    Ok(table)
+}
+
+/// Compare generated emissions against the emissions reported by the contract.
+///
+/// Generated and reported values match if they differ by at most `tolerance_bps` basis points of
+/// the generated value. Also checks that the PoVW and staking emissions add up exactly to the
+/// total for each reported epoch. Epochs missing from either table are ignored.
+pub fn compare_emissions(
+    expected: &[EpochEmissions],
+    reported: &[EpochEmissions],
+    tolerance_bps: u64,
+) -> Vec<EmissionsMismatch> {
+    let mut mismatches = vec![];
+    for reported_epoch in reported {
+        let epoch = reported_epoch.epoch;
+        // A sum that overflows cannot be the total, and is reported as U256::MAX.
+        let split_total = reported_epoch.povw.checked_add(reported_epoch.staking);
+        if split_total != Some(reported_epoch.total) {
+            mismatches.push(EmissionsMismatch {
+                epoch,
+                field: "PoVW plus staking",
+                expected: reported_epoch.total,
+                reported: split_total.unwrap_or(U256::MAX),
+            });
+        }
+        let Some(expected_epoch) = expected.iter().find(|e| e.epoch == epoch) else {
+            continue;
+        };
+        for (field, expected, reported) in [
+            ("total", expected_epoch.total, reported_epoch.total),
+            ("PoVW", expected_epoch.povw, reported_epoch.povw),
+            ("staking", expected_epoch.staking, reported_epoch.staking),
+        ] {
+            let diff = expected.abs_diff(reported);
+            let allowed = expected.saturating_mul(U256::from(tolerance_bps)) / U256::from(10_000);
+            if diff > allowed {
+                mismatches.push(EmissionsMismatch { epoch, field, expected, reported });
+            }
+        }
+    }
+    mismatches
+}
+
+/// Generate the emissions schedule for the given epochs from the ZKC supply schedule and compare
+/// it against the emissions reported by the ZKC contract, see [compare_emissions].
+///
+/// Returns the reported emissions along with any mismatches found.
+pub async fn verify_against_chain(
+    provider: impl Provider,
+    zkc_address: Address,
+    epochs: Range<u64>,
+    tolerance_bps: u64,
+) -> Result<(Vec<EpochEmissions>, Vec<EmissionsMismatch>)> {
+    let expected = generate_emissions(&SupplySchedule::ZKC, epochs.clone());
+    let reported = fetch_emissions(provider, zkc_address, epochs).await?;
+    let mismatches = compare_emissions(&expected, &reported, tolerance_bps);
+    Ok((reported, mismatches))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zkc(amount: u64) -> U256 {
+        U256::from(amount) * U256::from(10).pow(U256::from(18))
+    }
+
+    #[test]
+    fn generates_zkc_schedule() {
+        let table = generate_emissions(&SupplySchedule::ZKC, 0..200);
+        assert_eq!(table.len(), 200);
+        for row in &table {
+            assert_eq!(row.povw + row.staking, row.total);
+            assert_eq!(row.povw, row.total * U256::from(3) / U256::from(4));
+        }
+
+        // 7% a year compounded over 182 epochs is about 372k ZKC in the first epoch.
+        assert!(table[0].total > zkc(371_000) && table[0].total < zkc(373_000));
+        // Emissions grow with the supply within a year, and drop with the rate in the next.
+        assert!(table[181].total > table[0].total);
+        assert!(table[182].total < table[181].total);
+
+        // A range starting past epoch 0 yields the same values.
+        assert_eq!(generate_emissions(&SupplySchedule::ZKC, 180..185), table[180..185]);
+    }
+
+    #[test]
+    fn rate_drops_to_final_rate() {
+        let schedule = SupplySchedule::ZKC;
+        assert_eq!(schedule.rate_bps(0), 700);
+        assert_eq!(schedule.rate_bps(1), 650);
+        assert_eq!(schedule.rate_bps(8), 300);
+        assert_eq!(schedule.rate_bps(100), 300);
+    }
+
+    #[test]
+    fn matching_emissions() {
+        let table = generate_emissions(&SupplySchedule::ZKC, 0..5);
+        assert!(compare_emissions(&table, &table, 0).is_empty());
+    }
+
+    #[test]
+    fn drifting_emissions() {
+        let expected = generate_emissions(&SupplySchedule::ZKC, 0..5);
+        let mut reported = expected.clone();
+        // Move 0.1% of the emissions of epoch 3 from staking to PoVW.
+        let drift = reported[3].total / U256::from(1_000);
+        reported[3].povw += drift;
+        reported[3].staking -= drift;
+
+        let mismatches = compare_emissions(&expected, &reported, 1);
+        let fields: Vec<_> = mismatches.iter().map(|m| (m.epoch, m.field)).collect();
+        assert_eq!(fields, vec![(3, "PoVW"), (3, "staking")]);
+
+        // A split that does not add up to the total is reported even when within tolerance.
+        reported[3] = expected[3].clone();
+        reported[3].staking += U256::ONE;
+        let mismatches = compare_emissions(&expected, &reported, 1);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].field, "PoVW plus staking");
+        assert_eq!(mismatches[0].reported, reported[3].total + U256::ONE);
+    }
+
+    #[test]
+    fn rounding_within_tolerance() {
+        let expected = generate_emissions(&SupplySchedule::ZKC, 0..5);
+        let reported: Vec<_> = expected
+            .iter()
+            .map(|row| EpochEmissions {
+                total: row.total + U256::from(2),
+                povw: row.povw + U256::ONE,
+                staking: row.staking + U256::ONE,
+                ..row.clone()
+            })
+            .collect();
+        assert!(compare_emissions(&expected, &reported, 1).is_empty());
+        assert_eq!(compare_emissions(&expected, &reported, 0).len(), 15);
+    }
+
+    #[test]
+    fn overflowing_split() {
+        let reported =
+            [EpochEmissions { epoch: 0, total: U256::MAX, povw: U256::MAX, staking: U256::ONE }];
+        let mismatches = compare_emissions(&[], &reported, 0);
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].reported, U256::MAX);
+    }
+}
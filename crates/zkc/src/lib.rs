@@ -4,4 +4,9 @@
 // as found in the LICENSE-BSL file.
 
 pub mod contracts;
+pub mod delegation;
 pub mod deployments;
+pub mod emissions;
+pub mod units;
+pub mod unstake;
+pub mod vote_power;
@@ -0,0 +1,147 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Use of this source code is governed by the Business Source License
+// as found in the LICENSE-BSL file.
+
+//! Formatting and parsing of ZKC amounts, shared by every surface displaying them so that an
+//! amount always renders the same way.
+
+use alloy::primitives::U256;
+use anyhow::{ensure, Context, Result};
+
+/// Number of decimals of the ZKC token.
+pub const ZKC_DECIMALS: u8 = 18;
+
+fn unit() -> U256 {
+    U256::from(10).pow(U256::from(ZKC_DECIMALS))
+}
+
+/// Format an amount of ZKC, given in its smallest denomination, with exactly `precision`
+/// decimals.
+///
+/// Digits beyond the precision are truncated rather than rounded, so the formatted amount never
+/// exceeds the actual amount. Precisions above [ZKC_DECIMALS] are capped to it.
+pub fn format_zkc(amount: U256, precision: u8) -> String {
+    let precision = usize::from(precision.min(ZKC_DECIMALS));
+    let (integer, fraction) = amount.div_rem(unit());
+    if precision == 0 {
+        return integer.to_string();
+    }
+    let fraction = format!("{:0>width$}", fraction.to_string(), width = usize::from(ZKC_DECIMALS));
+    format!("{integer}.{}", &fraction[..precision])
+}
+
+/// Parse an amount of ZKC, such as `1.5`, into its smallest denomination.
+///
+/// Amounts with more than [ZKC_DECIMALS] decimals are rejected rather than truncated.
+pub fn parse_zkc(amount: &str) -> Result<U256> {
+    let trimmed = amount.trim();
+    let (integer, fraction) = trimmed.split_once('.').unwrap_or((trimmed, ""));
+    ensure!(
+        !(integer.is_empty() && fraction.is_empty())
+            && integer.bytes().chain(fraction.bytes()).all(|b| b.is_ascii_digit()),
+        "invalid ZKC amount: {amount}"
+    );
+    ensure!(
+        fraction.len() <= usize::from(ZKC_DECIMALS),
+        "ZKC amount {amount} has more than {ZKC_DECIMALS} decimals"
+    );
+
+    let integer = match integer {
+        "" => U256::ZERO,
+        integer => U256::from_str_radix(integer, 10)
+            .with_context(|| format!("ZKC amount {amount} is too large"))?,
+    };
+    let fraction = format!("{fraction:0<width$}", width = usize::from(ZKC_DECIMALS));
+    let fraction = U256::from_str_radix(&fraction, 10).context("invalid ZKC decimals")?;
+    integer
+        .checked_mul(unit())
+        .and_then(|integer| integer.checked_add(fraction))
+        .with_context(|| format!("ZKC amount {amount} is too large"))
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::utils::format_ether;
+
+    use super::*;
+
+    #[test]
+    fn format_exact_decimals() {
+        let amount = U256::from(1_500_000_000_000_000_000u128);
+        assert_eq!(format_zkc(amount, ZKC_DECIMALS), "1.500000000000000000");
+        assert_eq!(format_zkc(amount, 2), "1.50");
+        assert_eq!(format_zkc(amount, 0), "1");
+        assert_eq!(format_zkc(U256::ZERO, 3), "0.000");
+        assert_eq!(format_zkc(amount, 30), format_zkc(amount, ZKC_DECIMALS));
+    }
+
+    #[test]
+    fn format_truncates() {
+        let amount = U256::from(1_999_999_999_999_999_999u128);
+        assert_eq!(format_zkc(amount, 2), "1.99");
+        assert_eq!(format_zkc(amount, 0), "1");
+
+        // Dust below the precision is dropped rather than rounded up
+        assert_eq!(format_zkc(U256::from(1), 6), "0.000000");
+        assert_eq!(format_zkc(U256::from(1), ZKC_DECIMALS), "0.000000000000000001");
+    }
+
+    #[test]
+    fn format_max() {
+        assert_eq!(
+            format_zkc(U256::MAX, ZKC_DECIMALS),
+            "115792089237316195423570985008687907853269984665640564039457.584007913129639935"
+        );
+        assert_eq!(
+            format_zkc(U256::MAX, 1),
+            "115792089237316195423570985008687907853269984665640564039457.5"
+        );
+    }
+
+    #[test]
+    fn parse() {
+        assert_eq!(parse_zkc("1.5").unwrap(), U256::from(1_500_000_000_000_000_000u128));
+        assert_eq!(parse_zkc(" 2 ").unwrap(), U256::from(2_000_000_000_000_000_000u128));
+        assert_eq!(parse_zkc("3.").unwrap(), U256::from(3_000_000_000_000_000_000u128));
+        assert_eq!(parse_zkc(".25").unwrap(), U256::from(250_000_000_000_000_000u128));
+        assert_eq!(parse_zkc("0.000000000000000001").unwrap(), U256::from(1));
+        assert_eq!(parse_zkc("0").unwrap(), U256::ZERO);
+        assert_eq!(parse_zkc(&format_zkc(U256::MAX, ZKC_DECIMALS)).unwrap(), U256::MAX);
+    }
+
+    #[test]
+    fn parse_rejects_invalid_amounts() {
+        for amount in ["", ".", "-1", "1e18", "1.2.3", "abc", "0x10", "1,5"] {
+            assert!(parse_zkc(amount).is_err(), "{amount}");
+        }
+        let err = parse_zkc("0.0000000000000000001").unwrap_err();
+        assert!(err.to_string().contains("more than 18 decimals"));
+
+        // One unit above the max
+        let err = parse_zkc(
+            "115792089237316195423570985008687907853269984665640564039457.584007913129639936",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("too large"));
+    }
+
+    /// Amounts printed at full precision render exactly as the ether formatting the CLI
+    /// previously used for ZKC amounts.
+    #[test]
+    fn golden_full_precision() {
+        let fixtures = [
+            U256::ZERO,
+            U256::from(1),
+            U256::from(999_999_999_999_999_999u128),
+            U256::from(1_000_000_000_000_000_000u128),
+            U256::from(123_456_789_012_345_678_901_234u128),
+            U256::MAX,
+        ];
+        for amount in fixtures {
+            let formatted = format_zkc(amount, ZKC_DECIMALS);
+            assert_eq!(formatted, format_ether(amount));
+            assert_eq!(parse_zkc(&formatted).unwrap(), amount);
+        }
+    }
+}
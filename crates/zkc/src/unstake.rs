@@ -0,0 +1,119 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Use of this source code is governed by the Business Source License
+// as found in the LICENSE-BSL file.
+
+//! Helpers for estimating the cost of unstaking ZKC.
+
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+};
+use anyhow::{Context, Result};
+
+use crate::contracts::{IRewards, IZKC};
+
+/// Length of the withdrawal period that starts when an unstake is initiated, in seconds.
+pub const WITHDRAWAL_PERIOD_SECS: u64 = 30 * 24 * 60 * 60;
+
+/// Estimated staking rewards of an account for a single epoch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EpochRewardsEstimate {
+    /// Epoch number.
+    pub epoch: U256,
+    /// Timestamp at which the epoch ends.
+    pub end_time: u64,
+    /// Staking emissions for the epoch.
+    pub emissions: U256,
+    /// Share of the staking emissions estimated for the account.
+    pub rewards: U256,
+}
+
+/// Preview of the effect of initiating an unstake.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnstakePreview {
+    /// Timestamp at which the withdrawal period would end.
+    pub cooldown_end: u64,
+    /// Current staking reward power of the account.
+    pub reward_power: U256,
+    /// Current total staking reward power.
+    pub total_reward_power: U256,
+    /// Estimated rewards for each epoch ending before the withdrawal period ends.
+    pub epochs: Vec<EpochRewardsEstimate>,
+}
+
+impl UnstakePreview {
+    /// Total estimated staking rewards forgone over the withdrawal period.
+    pub fn forgone_rewards(&self) -> U256 {
+        self.epochs.iter().map(|epoch| epoch.rewards).sum()
+    }
+}
+
+/// Estimate the staking rewards for an epoch, given the reward power share of an account.
+pub fn estimate_epoch_rewards(
+    emissions: U256,
+    reward_power: U256,
+    total_reward_power: U256,
+) -> U256 {
+    if total_reward_power.is_zero() {
+        return U256::ZERO;
+    }
+    emissions * reward_power / total_reward_power
+}
+
+/// Preview the staking rewards an account would forgo by initiating an unstake at `timestamp`.
+///
+/// Staking rewards are distributed at the end of each epoch based on the reward power at that
+/// time, and initiating an unstake drops the reward power of the account to zero. The estimate
+/// covers every epoch ending before the withdrawal period ends, assuming the current reward power
+/// share of the account holds and using the emissions schedule of the ZKC contract.
+pub async fn preview_unstake(
+    provider: impl Provider + Clone,
+    zkc_address: Address,
+    vezkc_address: Address,
+    account: Address,
+    timestamp: u64,
+) -> Result<UnstakePreview> {
+    let zkc = IZKC::new(zkc_address, provider.clone());
+    let rewards = IRewards::new(vezkc_address, provider);
+
+    let reward_power = rewards
+        .getStakingRewards(account)
+        .call()
+        .await
+        .context("failed to get staking reward power")?;
+    let total_reward_power = rewards
+        .getTotalStakingRewards()
+        .call()
+        .await
+        .context("failed to get total staking reward power")?;
+
+    let cooldown_end = timestamp + WITHDRAWAL_PERIOD_SECS;
+    let mut epoch = zkc.getCurrentEpoch().call().await.context("failed to get current epoch")?;
+    let mut epochs = vec![];
+    loop {
+        let end_time: u64 = zkc
+            .getEpochEndTime(epoch)
+            .call()
+            .await
+            .with_context(|| format!("failed to get end time of epoch {epoch}"))?
+            .try_into()?;
+        if end_time > cooldown_end {
+            break;
+        }
+        let emissions = zkc
+            .getStakingEmissionsForEpoch(epoch)
+            .call()
+            .await
+            .with_context(|| format!("failed to get staking emissions for epoch {epoch}"))?;
+        epochs.push(EpochRewardsEstimate {
+            epoch,
+            end_time,
+            emissions,
+            rewards: estimate_epoch_rewards(emissions, reward_power, total_reward_power),
+        });
+        epoch += U256::ONE;
+    }
+
+    Ok(UnstakePreview { cooldown_end, reward_power, total_reward_power, epochs })
+}
@@ -0,0 +1,219 @@
+// Copyright 2025 RISC Zero, Inc.
+//
+// Use of this source code is governed by the Business Source License
+// as found in the LICENSE-BSL file.
+
+//! Snapshots of veZKC governance vote power, for use in off-chain voting.
+
+use std::collections::BTreeSet;
+
+use alloy::{
+    eips::BlockId,
+    primitives::{keccak256, Address, B256, U256},
+    providers::Provider,
+    rpc::types::Filter,
+    sol_types::{SolEvent, SolValue},
+};
+use anyhow::{ensure, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::contracts::IVotes;
+
+/// Maximum number of blocks to query for events in a single request.
+const LOG_QUERY_BLOCK_RANGE: u64 = 10_000;
+
+/// Vote power of a single address.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VotePower {
+    /// Address holding the vote power.
+    pub address: Address,
+    /// Vote power of the address, including power delegated to it.
+    #[serde(rename = "score", with = "u256_decimal")]
+    pub power: U256,
+}
+
+/// Vote power of every address holding veZKC vote power at a block.
+///
+/// Serializes to the format consumed by Snapshot API strategies, with the entries under `score`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VotePowerSnapshot {
+    /// Block at which the vote power was computed.
+    pub block: u64,
+    /// Root of the Merkle tree committing to the entries, see [merkle_root].
+    pub merkle_root: B256,
+    /// Vote power of each address, sorted by address. Addresses without vote power are omitted.
+    #[serde(rename = "score")]
+    pub powers: Vec<VotePower>,
+}
+
+/// Leaf of the Merkle tree for a single entry,
+/// `keccak256(bytes.concat(keccak256(abi.encode(address, power))))`.
+///
+/// The leaf is double hashed, as in the OpenZeppelin `StandardMerkleTree`, so that it cannot be
+/// mistaken for an inner node of the tree.
+pub fn merkle_leaf(entry: &VotePower) -> B256 {
+    keccak256(keccak256((entry.address, entry.power).abi_encode()))
+}
+
+/// Root of the Merkle tree over the given entries, in order.
+///
+/// Each pair of nodes is hashed in sorted order, matching the OpenZeppelin `MerkleProof` library,
+/// and the last node of a level with an odd number of nodes is carried up unchanged. The root of
+/// an empty list is zero.
+pub fn merkle_root(powers: &[VotePower]) -> B256 {
+    let mut level: Vec<B256> = powers.iter().map(merkle_leaf).collect();
+    if level.is_empty() {
+        return B256::ZERO;
+    }
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [a, b] if a <= b => keccak256([a.as_slice(), b.as_slice()].concat()),
+                [a, b] => keccak256([b.as_slice(), a.as_slice()].concat()),
+                [a] => *a,
+                _ => unreachable!(),
+            })
+            .collect();
+    }
+    level[0]
+}
+
+/// Find the block in which the contract at `address` was deployed, by binary search over the
+/// blocks up to `block` for the first one at which it has code.
+///
+/// Requires a provider serving historical state, as [vote_power_snapshot] does.
+pub async fn deployment_block(
+    provider: &impl Provider,
+    address: Address,
+    block: u64,
+) -> Result<u64> {
+    let has_code = async |number: u64| -> Result<bool> {
+        let code = provider
+            .get_code_at(address)
+            .block_id(BlockId::number(number))
+            .await
+            .with_context(|| format!("failed to get code of {address} at block {number}"))?;
+        Ok(!code.is_empty())
+    };
+
+    ensure!(has_code(block).await?, "no contract at {address} as of block {block}");
+    let (mut low, mut high) = (0, block);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if has_code(mid).await? {
+            high = mid;
+        } else {
+            low = mid + 1;
+        }
+    }
+    Ok(low)
+}
+
+/// Compute the vote power of every address holding veZKC vote power at `block`.
+///
+/// Candidate addresses are collected from the `DelegateVotesChanged` events emitted by veZKC
+/// between `from_block` and `block`, so `from_block` must be no later than the veZKC deployment.
+/// The vote power of each candidate is then read with all calls pinned to `block`.
+pub async fn vote_power_snapshot(
+    provider: impl Provider,
+    vezkc_address: Address,
+    from_block: u64,
+    block: u64,
+) -> Result<VotePowerSnapshot> {
+    ensure!(from_block <= block, "from block {from_block} is after block {block}");
+
+    let mut candidates = BTreeSet::new();
+    let mut start = from_block;
+    while start <= block {
+        let end = block.min(start.saturating_add(LOG_QUERY_BLOCK_RANGE - 1));
+        let filter = Filter::new()
+            .address(vezkc_address)
+            .event_signature(IVotes::DelegateVotesChanged::SIGNATURE_HASH)
+            .from_block(start)
+            .to_block(end);
+        let logs = provider.get_logs(&filter).await.with_context(|| {
+            format!("failed to query vote power changes in blocks {start}-{end}")
+        })?;
+        for log in logs {
+            let event = log
+                .log_decode::<IVotes::DelegateVotesChanged>()
+                .context("failed to decode DelegateVotesChanged event")?;
+            candidates.insert(event.inner.data.delegate);
+        }
+        start = end + 1;
+    }
+    tracing::debug!("Found {} addresses with vote power changes", candidates.len());
+
+    let votes = IVotes::new(vezkc_address, provider);
+    let mut powers = Vec::new();
+    for address in candidates {
+        let power = votes
+            .getVotes(address)
+            .block(BlockId::number(block))
+            .call()
+            .await
+            .with_context(|| format!("failed to get vote power of {address}"))?;
+        if !power.is_zero() {
+            powers.push(VotePower { address, power });
+        }
+    }
+
+    Ok(VotePowerSnapshot { block, merkle_root: merkle_root(&powers), powers })
+}
+
+/// Serialize U256 values as decimal strings, as expected by Snapshot strategies.
+mod u256_decimal {
+    use alloy::primitives::U256;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        value.parse().map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::address;
+
+    use super::*;
+
+    fn entry(address: Address, power: u64) -> VotePower {
+        VotePower { address, power: U256::from(power) }
+    }
+
+    #[test]
+    fn merkle_leaf_is_double_hashed() {
+        let entry = entry(address!("0x1111111111111111111111111111111111111111"), 100);
+        let encoded = [
+            B256::left_padding_from(entry.address.as_slice()).as_slice(),
+            &entry.power.to_be_bytes::<32>(),
+        ]
+        .concat();
+        assert_eq!(merkle_leaf(&entry), keccak256(keccak256(encoded)));
+    }
+
+    #[test]
+    fn merkle_root_hashes_sorted_pairs() {
+        let a = entry(address!("0x1111111111111111111111111111111111111111"), 100);
+        let b = entry(address!("0x2222222222222222222222222222222222222222"), 200);
+        let c = entry(address!("0x3333333333333333333333333333333333333333"), 300);
+        let (leaf_a, leaf_b, leaf_c) = (merkle_leaf(&a), merkle_leaf(&b), merkle_leaf(&c));
+        let hash_pair = |x: B256, y: B256| {
+            let (lo, hi) = if x <= y { (x, y) } else { (y, x) };
+            keccak256([lo.as_slice(), hi.as_slice()].concat())
+        };
+
+        assert_eq!(merkle_root(&[]), B256::ZERO);
+        assert_eq!(merkle_root(std::slice::from_ref(&a)), leaf_a);
+        assert_eq!(merkle_root(&[a.clone(), b.clone()]), hash_pair(leaf_a, leaf_b));
+        assert_eq!(merkle_root(&[b.clone(), a.clone()]), hash_pair(leaf_a, leaf_b));
+        // The odd node is carried up unchanged.
+        assert_eq!(merkle_root(&[a, b, c]), hash_pair(hash_pair(leaf_a, leaf_b), leaf_c));
+    }
+}